@@ -1,27 +1,198 @@
-use std::{cell::RefCell, collections::{HashMap, HashSet}, rc::Rc};
+use std::{cell::RefCell, collections::{HashMap, HashSet, VecDeque}, rc::Rc};
 
 use oxc_ast::{
-    ast::{AssignmentOperator, CallExpression, Expression, GeneralBinaryOperator, PrivateIdentifier, SourceFile, UnaryOperator},
+    ast::{AssignmentExpression, AssignmentOperator, AssignmentTarget, AssignmentTargetMaybeDefault, AssignmentTargetProperty, BindingPattern, BindingPatternKind, CallExpression, CatchClause, Class, ConditionalExpression, Declaration, Directive, DoWhileStatement, ExportDefaultDeclarationKind, ExportSpecifier, Expression, ExpressionStatement, ForStatement, ForStatementLeft, Function, GeneralBinaryOperator, IfStatement, LabeledStatement, LogicalOperator, ModuleExportName, NumericLiteral, ObjectProperty, ObjectPropertyKind, PrivateIdentifier, PropertyKind, SourceFile, Statement, SwitchCase, SwitchStatement, TSModuleDeclaration, TSModuleDeclarationBody, TryStatement, UnaryExpression, UnaryOperator, UpdateExpression, VariableDeclarator, WhileStatement, WithStatement},
     AstKind, GetChildren,
 };
+use oxc_span::GetSpan;
 
 use crate::{define_flags, flag_names_impl, new_rc_cell, opt_rc_cell, rc_cell};
 use crate::compiler::rb_extra::SourceFilePassthrough;
 
 use super::{
-    core::appendIfUnique, diagnostic_information_map_generated::Diagnostics, factory::nodeTests::*, rb_extra::{AstKindExt, SourceFileExt}, rb_unions::{DeclarationNameOrQualifiedName, EscapedText, IsContainerOrEntityNameExpression, PropertyNameLiteralOrPrivateIdentifier, StrText, StringOrNumber}, types::*, utilities::*, utilitiesPublic::*
+    core::{appendIfUnique, getRangesWhere}, diagnostic_information_map_generated::Diagnostics, factory::nodeTests::*, parser::{isExternalModule, isFileProbablyExternalModule}, rb_extra::{AstKindExt, SourceFileExt}, rb_unions::{DeclarationNameOrQualifiedName, EscapedText, IsContainerOrEntityNameExpression, PropertyNameLiteralOrPrivateIdentifier, StrName, StrText, StringOrNumber}, types::*, utilities::*, utilitiesPublic::*
 };
 
+// region: 325
+/** @internal */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ModuleInstanceState {
+    NonInstantiated = 0,
+    Instantiated = 1,
+    ConstEnumOnly = 2,
+}
+// endregion: 330
+
 // region: 332
 pub struct ActiveLabel<'a> {
     pub next: opt_rc_cell!(ActiveLabel<'a>),
     pub name: __String,
-    pub breakTarget: FlowLabel<'a>,
-    pub continueTarget: opt_rc_cell!(FlowLabel<'a>),
+    pub breakTarget: rc_cell!(FlowNode<'a>), // FlowLabel
+    pub continueTarget: opt_rc_cell!(FlowNode<'a>), // FlowLabel
     pub referenced: bool,
 }
 // endregion: 338
 
+// region: 340
+/** @internal */
+pub fn getModuleInstanceState<'a>(node: &'a TSModuleDeclaration<'a>, visited: Option<&mut HashMap<u32, Option<ModuleInstanceState>>>) -> ModuleInstanceState {
+    // ! rb parent pointers are always set on oxc trees, so there is no need to set them here
+    let mut new_visited = HashMap::new();
+    let visited = visited.unwrap_or(&mut new_visited);
+    match &node.body {
+        Some(TSModuleDeclarationBody::TSModuleBlock(block)) => getModuleInstanceStateCached(AstKind::TSModuleBlock(block), visited),
+        // ! rb the body of `namespace A.B {}` is the nested module declaration in oxc
+        Some(TSModuleDeclarationBody::TSModuleDeclaration(module)) => getModuleInstanceStateCached(AstKind::TSModuleDeclaration(module), visited),
+        None => ModuleInstanceState::Instantiated,
+    }
+}
+
+fn getModuleInstanceStateCached<'a>(node: AstKind<'a>, visited: &mut HashMap<u32, Option<ModuleInstanceState>>) -> ModuleInstanceState {
+    let nodeId = node.get_node_id();
+    if let Some(state) = visited.get(&nodeId) {
+        return state.unwrap_or(ModuleInstanceState::NonInstantiated);
+    }
+    visited.insert(nodeId, None);
+    let result = getModuleInstanceStateWorker(node, visited);
+    visited.insert(nodeId, Some(result));
+    result
+}
+
+fn getModuleInstanceStateWorker<'a>(node: AstKind<'a>, visited: &mut HashMap<u32, Option<ModuleInstanceState>>) -> ModuleInstanceState {
+    // A module is uninstantiated if it contains only
+    match node {
+        // 1. interface declarations, type alias declarations
+        AstKind::TSInterfaceDeclaration(_) | AstKind::TSTypeAliasDeclaration(_) => {
+            return ModuleInstanceState::NonInstantiated;
+        }
+        // 2. const enum declarations
+        AstKind::TSEnumDeclaration(decl) => {
+            if decl.r#const {
+                return ModuleInstanceState::ConstEnumOnly;
+            }
+        }
+        // 3. non-exported import declarations
+        // ! rb an exported declaration is wrapped in an ExportNamedDeclaration in oxc rather than having an export modifier
+        AstKind::ImportDeclaration(_) | AstKind::TSImportEqualsDeclaration(_) => {
+            if !matches!(node.parent(), Some(AstKind::ExportNamedDeclaration(_))) {
+                return ModuleInstanceState::NonInstantiated;
+            }
+        }
+        // ! rb the state of an exported declaration is the state of the declaration it wraps
+        AstKind::ExportNamedDeclaration(decl) if decl.declaration.is_some() => {
+            return getModuleInstanceStateCached(decl.declaration.as_ref().unwrap().to_ast_kind(), visited);
+        }
+        // 4. Export alias declarations pointing at only uninstantiated modules or things uninstantiated modules contain
+        AstKind::ExportNamedDeclaration(decl) => {
+            if decl.source.is_none() {
+                let mut state = ModuleInstanceState::NonInstantiated;
+                for specifier in &decl.specifiers {
+                    let specifierState = getModuleInstanceStateForAliasTarget(specifier, visited);
+                    if specifierState > state {
+                        state = specifierState;
+                    }
+                    if state == ModuleInstanceState::Instantiated {
+                        return state;
+                    }
+                }
+                return state;
+            }
+        }
+        // 5. other uninstantiated module declarations.
+        AstKind::TSModuleBlock(block) => {
+            // ! rb prologue directives are expression statements of the module block in typescript
+            if !block.directives.is_empty() {
+                return ModuleInstanceState::Instantiated;
+            }
+            let mut state = ModuleInstanceState::NonInstantiated;
+            for statement in &block.body {
+                match getModuleInstanceStateCached(statement.to_ast_kind(), visited) {
+                    ModuleInstanceState::NonInstantiated => {
+                        // child is non-instantiated - continue searching
+                    }
+                    ModuleInstanceState::ConstEnumOnly => {
+                        // child is const enum only - record state and continue searching
+                        state = ModuleInstanceState::ConstEnumOnly;
+                    }
+                    ModuleInstanceState::Instantiated => {
+                        // child is instantiated - record state and stop
+                        state = ModuleInstanceState::Instantiated;
+                        break;
+                    }
+                }
+            }
+            return state;
+        }
+        AstKind::TSModuleDeclaration(module) => {
+            return getModuleInstanceState(module, Some(visited));
+        }
+        // ! rb skipping jsdoc
+        // case SyntaxKind.Identifier:
+        //     // Only jsdoc typedef definition can exist in jsdoc namespace, and it should
+        //     // be considered the same as type alias
+        //     if ((node as Identifier).flags & NodeFlags.IdentifierIsInJSDocNamespace) {
+        //         return ModuleInstanceState.NonInstantiated;
+        //     }
+        _ => {}
+    }
+    ModuleInstanceState::Instantiated
+}
+
+fn getModuleInstanceStateForAliasTarget<'a>(specifier: &'a ExportSpecifier<'a>, visited: &mut HashMap<u32, Option<ModuleInstanceState>>) -> ModuleInstanceState {
+    // ! rb `specifier.propertyName || specifier.name` is the local name in oxc
+    let name = match &specifier.local {
+        ModuleExportName::IdentifierName(name) => name.name.as_str(),
+        ModuleExportName::IdentifierReference(name) => name.name.as_str(),
+        ModuleExportName::StringLiteral(_) => return ModuleInstanceState::Instantiated, // Skip for invalid syntax like this: export { "x" }
+    };
+    let mut p = AstKind::ExportSpecifier(specifier).parent();
+    while let Some(parent) = p {
+        let statements = match parent {
+            AstKind::TSModuleBlock(block) => Some(&block.body),
+            AstKind::SourceFile(file) => Some(&file.body),
+            _ => rb_block_statements(&parent),
+        };
+        if let Some(statements) = statements {
+            let mut found: Option<ModuleInstanceState> = None;
+            for statement in statements {
+                let statement = statement.to_ast_kind();
+                if rb_node_has_name(&statement, name) {
+                    let state = getModuleInstanceStateCached(statement, visited);
+                    if found.map_or(true, |found| state > found) {
+                        found = Some(state);
+                    }
+                    if found == Some(ModuleInstanceState::Instantiated) {
+                        return ModuleInstanceState::Instantiated;
+                    }
+                    if matches!(statement, AstKind::TSImportEqualsDeclaration(_)) {
+                        // Treat re-exports of import aliases as instantiated,
+                        // since they're ambiguous. This is consistent with
+                        // `export import x = mod.x` being treated as instantiated:
+                        //   import x = mod.x;
+                        //   export { x };
+                        found = Some(ModuleInstanceState::Instantiated);
+                    }
+                }
+            }
+            if let Some(found) = found {
+                return found;
+            }
+        }
+        p = parent.parent();
+    }
+    ModuleInstanceState::Instantiated // Couldn't locate, assume could refer to a value
+}
+// endregion: 473
+
+// ! rb nodeHasName, an exported declaration is wrapped in an ExportNamedDeclaration in oxc
+fn rb_node_has_name(statement: &AstKind, name: &str) -> bool {
+    match *statement {
+        AstKind::ExportNamedDeclaration(decl) => decl.declaration.as_ref().is_some_and(|declaration| rb_node_has_name(&declaration.to_ast_kind(), name)),
+        AstKind::VariableDeclarationList(list) => list.declarations.iter().any(|d| matches!(&d.id.kind, BindingPatternKind::BindingIdentifier(id) if id.name.as_str() == name)),
+        _ => matches!(getNameOfDeclaration(*statement).map(|declarationName| declarationName.to_ast_kind()), Some(AstKind::BindingIdentifier(id)) if id.name.as_str() == name),
+    }
+}
+
 // region: 475
 // @internal
 define_flags!(ContainerFlags {
@@ -79,14 +250,15 @@ pub struct Binder<'a> {
 
     // state used by control flow analysis
     pub currentFlow: opt_rc_cell!(FlowNode<'a>),
-    pub currentBreakTarget: opt_rc_cell!(FlowLabel<'a>),
-    pub currentContinueTarget: opt_rc_cell!(FlowLabel<'a>),
-    pub currentReturnTarget: opt_rc_cell!(FlowLabel<'a>),
-    pub currentTrueTarget: opt_rc_cell!(FlowLabel<'a>),
-    pub currentFalseTarget: opt_rc_cell!(FlowLabel<'a>),
-    pub currentExceptionTarget: opt_rc_cell!(FlowLabel<'a>),
+    // ! rb labels are kept as FlowNode so that they can be used as antecedents of other labels
+    pub currentBreakTarget: opt_rc_cell!(FlowNode<'a>),
+    pub currentContinueTarget: opt_rc_cell!(FlowNode<'a>),
+    pub currentReturnTarget: opt_rc_cell!(FlowNode<'a>),
+    pub currentTrueTarget: opt_rc_cell!(FlowNode<'a>),
+    pub currentFalseTarget: opt_rc_cell!(FlowNode<'a>),
+    pub currentExceptionTarget: opt_rc_cell!(FlowNode<'a>),
     pub preSwitchCaseFlow: opt_rc_cell!(FlowNode<'a>),
-    pub activeLabelList: Option<ActiveLabel<'a>>,
+    pub activeLabelList: opt_rc_cell!(ActiveLabel<'a>),
     pub hasExplicitReturn: bool,
     pub hasFlowEffects: bool,
 
@@ -107,8 +279,8 @@ pub struct Binder<'a> {
     pub symbol: Option<Symbol<'a>>,
     pub classifiableNames: opt_rc_cell!(HashSet<__String>),
 
-    pub unreachableFlow: FlowNode<'a>,
-    pub reportedUnreachableFlow: FlowNode<'a>,
+    pub unreachableFlow: rc_cell!(FlowNode<'a>),
+    pub reportedUnreachableFlow: rc_cell!(FlowNode<'a>),
     // pub bindBinaryExpressionFlow: FlowNode<'a>,
 }
 
@@ -143,8 +315,8 @@ impl<'a> Default for Binder<'a> {
             symbolCount: 0,
             symbol: None,
             classifiableNames: None,
-            unreachableFlow: FlowNode::Unreachable(FlowUnreachable { flags: FlowFlags(0), id: 0 }),
-            reportedUnreachableFlow: FlowNode::Unreachable(FlowUnreachable { flags: FlowFlags(0), id: 0 }),
+            unreachableFlow: new_rc_cell!(FlowNode::Unreachable(FlowUnreachable { flags: FlowFlags::Unreachable, id: 0 })),
+            reportedUnreachableFlow: new_rc_cell!(FlowNode::Unreachable(FlowUnreachable { flags: FlowFlags::Unreachable, id: 0 })),
             // bindBinaryExpressionFlow: createBindBinaryExpressionFlow(),
         }
     }
@@ -158,11 +330,20 @@ impl<'a> Binder<'a> {
      */
     fn createDiagnosticForNode(&self, node: &AstKind<'a>, message: DiagnosticMessage, args: DiagnosticArguments) -> DiagnosticWithLocation { createDiagnosticForNodeInSourceFile(getSourceFileOfNode(Some(node)).or(self.file), node, message, args) }
 
+    fn bindInStrictMode(&self, file: &'a SourceFile<'a>, opts: &'a CompilerOptions) -> bool {
+        if getStrictOptionValue(opts, StrictOptionName::AlwaysStrict) && !file.source_type.is_typescript_definition() {
+            // bind in strict mode source files with alwaysStrict option
+            true
+        } else {
+            file.externalModuleIndicator()
+        }
+    }
+
     fn bindSourceFile(&mut self, f: &'a SourceFile<'a>, opts: &'a CompilerOptions) {
         self.file = Some(f);
         self.options = Some(opts);
         self.languageVersion = Some(getEmitScriptTarget(opts));
-        self.inStrictMode = true; // todo(RB): bindInStrictMode(&self.file.as_ref().unwrap(), &opts);
+        self.inStrictMode = self.bindInStrictMode(f, opts);
         self.classifiableNames = Some(new_rc_cell!(HashSet::new()));
         self.symbolCount = 0;
 
@@ -226,11 +407,11 @@ impl<'a> Binder<'a> {
         }
 
         if (symbolFlags & (SymbolFlags::Class | SymbolFlags::Enum | SymbolFlags::Module | SymbolFlags::Variable)).0 != 0 && symbol.borrow().exports.is_none() {
-            symbol.borrow_mut().exports = Some(HashMap::new());
+            symbol.borrow_mut().exports = Some(new_rc_cell!(HashMap::new()));
         }
 
         if (symbolFlags & (SymbolFlags::Class | SymbolFlags::Interface | SymbolFlags::TypeLiteral | SymbolFlags::ObjectLiteral)).0 != 0 && symbol.borrow().members.is_none() {
-            symbol.borrow_mut().members = Some(HashMap::new());
+            symbol.borrow_mut().members = Some(new_rc_cell!(HashMap::new()));
         }
 
         // On merge of const enum module with class or function, reset const enum only flag (namespaces will already recalculate)
@@ -381,7 +562,7 @@ impl<'a> Binder<'a> {
         let isComputedName = isComputedName.unwrap_or(false);
         debug_assert!(isComputedName || !hasDynamicName(&node));
 
-        let mut isDefaultExport = hasSyntacticModifier(&node, ModifierFlags::Default);
+        let mut isDefaultExport = hasSyntacticModifier(&node, ModifierFlags::Default) || rb_export_wrapper_modifier_flags(&node).intersects(ModifierFlags::Default);
         if let AstKind::ExportSpecifier(export_specifier) = &node {
             if moduleExportNameIsDefault(&export_specifier.local) {
                 isDefaultExport = true;
@@ -425,37 +606,38 @@ impl<'a> Binder<'a> {
             // just add this node into the declarations list of the symbol.
             let name = name.unwrap();
             
-            if includes.contains(SymbolFlags::Classifiable) {
+            if includes.intersects(SymbolFlags::Classifiable) {
                 self.classifiableNames.as_ref().unwrap().borrow_mut().insert(name.to_string());
             }
 
-            if let Some(existing_symbol) = symbolTable.borrow().get(&name.to_string()) {
+            let existing_symbol = symbolTable.borrow().get(&name.to_string()).cloned();
+            if let Some(existing_symbol) = existing_symbol {
                 if isReplaceableByMethod.unwrap_or(false) && !existing_symbol.borrow().isReplaceableByMethod.unwrap_or(false) {
                     // A symbol already exists, so don't add this as a declaration
                     return existing_symbol.clone();
-                } else if existing_symbol.borrow().flags.contains(excludes) {
+                } else if existing_symbol.borrow().flags.intersects(excludes) {
                     if existing_symbol.borrow().isReplaceableByMethod.unwrap_or(false) {
                         // Javascript constructor-declared symbols can be discarded in favor of
                         // prototype symbols like methods
                         let new_symbol = self.createSymbol(SymbolFlags::None, name.clone().to_string());
                         symbolTable.borrow_mut().insert(name.to_string(), new_symbol.clone());
                         new_symbol
-                    } else if !(includes.contains(SymbolFlags::Variable) && existing_symbol.borrow().flags.contains(SymbolFlags::Assignment)) {
+                    } else if !(includes.intersects(SymbolFlags::Variable) && existing_symbol.borrow().flags.intersects(SymbolFlags::Assignment)) {
                         // Assignment declarations are allowed to merge with variables, no matter what other flags they have
                         if isNamedDeclaration(&node) {
-                            node.set_parent(Some(NamedDeclaration::from_ast_kind(&node).unwrap().name().unwrap().to_ast_kind()));
+                            NamedDeclaration::from_ast_kind(&node).unwrap().name().unwrap().to_ast_kind().set_parent(Some(node));
                         }
 
                         // Report errors every position with duplicate declaration
                         // Report errors on previous encountered declarations
-                        let mut message = if existing_symbol.borrow().flags.contains(SymbolFlags::BlockScopedVariable) {
+                        let mut message = if existing_symbol.borrow().flags.intersects(SymbolFlags::BlockScopedVariable) {
                             Diagnostics::Cannot_redeclare_block_scoped_variable_0()
                         } else {
                             Diagnostics::Duplicate_identifier_0()
                         };
                         let mut messageNeedsName = true;
 
-                        if existing_symbol.borrow().flags.contains(SymbolFlags::Enum) || includes.contains(SymbolFlags::Enum) {
+                        if existing_symbol.borrow().flags.intersects(SymbolFlags::Enum) || includes.intersects(SymbolFlags::Enum) {
                             message = Diagnostics::Enum_declarations_can_only_merge_with_namespace_or_other_enum_declarations();
                             messageNeedsName = false;
                         }
@@ -498,7 +680,7 @@ impl<'a> Binder<'a> {
 
                         let declarationName = getNameOfDeclaration(node).map(|name| name.to_ast_kind()).unwrap_or(node);
 
-                        for (index, declaration) in existing_symbol.borrow().declarations.as_ref().unwrap().iter().enumerate() {
+                        for (index, declaration) in existing_symbol.borrow().declarations.iter().flatten().enumerate() {
                             let decl = getNameOfDeclaration(declaration.to_ast_kind()).map(|name| name.to_ast_kind()).unwrap_or(declaration.to_ast_kind());
                             let mut diag = if messageNeedsName {
                                 self.createDiagnosticForNode(&decl, message.clone(), vec![StringOrNumber::String(self.getDisplayName(&declaration.to_ast_kind()))])
@@ -567,125 +749,168 @@ impl<'a> Binder<'a> {
     }
     // endregion: 896
 
+    // region: 898
+    fn declareModuleMember(&mut self, node: AstKind<'a>, symbolFlags: SymbolFlags, symbolExcludes: SymbolFlags) -> rc_cell!(Symbol<'a>) {
+        let exportModifierFlags = getCombinedModifierFlags(&node) | rb_export_wrapper_modifier_flags(&node);
+        let hasExportModifier = exportModifierFlags.intersects(ModifierFlags::Export); // || jsdocTreatAsExported(node);
+        let container = self.container.unwrap().to_ast_kind();
+        let containerLocals = HasLocals::from_ast_kind(&container).and_then(|container| container.locals());
+        // ! rb module declarations are not bound yet, so a namespace container may not have a symbol to export from
+        let containerSymbol = container.symbol();
+        if symbolFlags.intersects(SymbolFlags::Alias) {
+            if matches!(node, AstKind::ExportSpecifier(_)) || (matches!(node, AstKind::TSImportEqualsDeclaration(_)) && hasExportModifier) {
+                if let Some(containerSymbol) = containerSymbol {
+                    let exports = containerSymbol.borrow().exports.clone().unwrap();
+                    return self.declareSymbol(exports, Some(containerSymbol), node, symbolFlags, symbolExcludes, None, None);
+                }
+            }
+            self.declareSymbol(containerLocals.unwrap(), /*parent*/ None, node, symbolFlags, symbolExcludes, None, None)
+        } else {
+            // if (isJSDocTypeAlias(node)) Debug.assert(isInJSFile(node)); // We shouldn't add symbols for JSDoc nodes if not in a JS file.
+
+            // Exported module members are given 2 symbols: A local symbol that is classified with an ExportValue flag,
+            // and an associated export symbol with all the correct flags set on it. There are 2 main reasons:
+            //
+            //   1. We treat locals and exports of the same name as mutually exclusive within a container.
+            //      That means the binder will issue a Duplicate Identifier error if you mix locals and exports
+            //      with the same name in the same container.
+            //      TODO: Make this a more specific error and decouple it from the exclusion logic.
+            //   2. When we checkIdentifier in the checker, we set its resolved symbol to the local symbol,
+            //      but return the export symbol (by calling getExportSymbolOfValueSymbolIfExported). That way
+            //      when the emitter comes back to it, it knows not to qualify the name if it was found in a containing scope.
+
+            // NOTE: Nested ambient modules always should go to to 'locals' table to prevent their automatic merge
+            //       during global merging in the checker. Why? The only case when ambient module is permitted inside another module is module augmentation
+            //       and this case is specially handled. Module augmentations should only be merged with original module definition
+            //       and should never be merged directly with other augmentation, and the latter case would be possible if automatic merge is allowed.
+            if !isAmbientModule(&node) && (hasExportModifier || container.flags().intersects(NodeFlags::ExportContext)) {
+                if let Some(containerSymbol) = containerSymbol {
+                    let exports = containerSymbol.borrow().exports.clone().unwrap();
+                    if containerLocals.is_none() || (exportModifierFlags.intersects(ModifierFlags::Default) && self.getDeclarationName(&node).is_none()) {
+                        return self.declareSymbol(exports, Some(containerSymbol), node, symbolFlags, symbolExcludes, None, None); // No local symbol for an unnamed default!
+                    }
+                    let exportKind = if symbolFlags.intersects(SymbolFlags::Value) { SymbolFlags::ExportValue } else { SymbolFlags::None };
+                    let local = self.declareSymbol(containerLocals.unwrap(), /*parent*/ None, node, exportKind, symbolExcludes, None, None);
+                    let exportSymbol = self.declareSymbol(exports, Some(containerSymbol), node, symbolFlags, symbolExcludes, None, None);
+                    local.borrow_mut().exportSymbol = Some(exportSymbol);
+                    node.set_localSymbol(Some(local.clone()));
+                    return local;
+                }
+            }
+            self.declareSymbol(containerLocals.unwrap(), /*parent*/ None, node, symbolFlags, symbolExcludes, None, None)
+        }
+    }
+    // endregion: 944
+
     // region: 960
     // All container nodes are kept on a linked list in declaration order. This list is used by
     // the getLocalNameOfContainer function in the type checker to validate that the local name
     // used for a container is unique.
     fn bindContainer(&mut self, node: &AstKind<'a>, container_flags: ContainerFlags) {
-        // todo(RB): continue conversion from here
-        // // Before we recurse into a node's children, we first save the existing parent, container
-        // // and block-container.  Then after we pop out of processing the children, we restore
-        // // these saved values.
-        // let save_container = self.container.clone();
-        // let save_this_parent_container = self.thisParentContainer.clone();
-        // let saved_block_scope_container = self.blockScopeContainer.clone();
-
-        // // Depending on what kind of node this is, we may have to adjust the current container
-        // // and block-container.   If the current node is a container, then it is automatically
-        // // considered the current block-container as well.  Also, for containers that we know
-        // // may contain locals, we eagerly initialize the .locals field. We do this because
-        // // it's highly likely that the .locals will be needed to place some child in (for example,
-        // // a parameter, or variable declaration).
-        // //
-        // // However, we do not proactively create the .locals for block-containers because it's
-        // // totally normal and common for block-containers to never actually have a block-scoped
-        // // variable in them.  We don't want to end up allocating an object for every 'block' we
-        // // run into when most of them won't be necessary.
-        // //
-        // // Finally, if this is a block-container, then we clear out any existing .locals object
-        // // it may contain within it.  This happens in incremental scenarios.  Because we can be
-        // // reusing a node from a previous compilation, that node may have had 'locals' created
-        // // for it.  We must clear this so we don't accidentally move any stale data forward from
-        // // a previous compilation.
-        // if container_flags.contains(ContainerFlags::IsContainer) {
-        //     if !matches!(node, AstKind::ArrowFunction(_)) {
-        //         self.thisParentContainer = self.container.clone();
-        //     }
-        //     self.container = Some(node.clone());
-        //     self.blockScopeContainer = Some(node.clone());
-        //     if container_flags.contains(ContainerFlags::HasLocals) {
-        //         // node.locals = createSymbolTable();
-        //         // addToContainerChain(node);
-        //     }
-        // } else if container_flags.contains(ContainerFlags::IsBlockScopedContainer) {
-        //     self.blockScopeContainer = Some(node.clone());
-        //     if container_flags.contains(ContainerFlags::HasLocals) {
-        //         // node.locals = None;
-        //     }
-        // }
-
-        // if container_flags.contains(ContainerFlags::IsControlFlowContainer) {
-        //     let save_current_flow = self.currentFlow.clone();
-        //     let save_break_target = self.currentBreakTarget.clone();
-        //     let save_continue_target = self.currentContinueTarget.clone();
-        //     let save_return_target = self.currentReturnTarget.clone();
-        //     let save_exception_target = self.currentExceptionTarget.clone();
-        //     let save_active_label_list = self.activeLabelList.clone();
-        //     let save_has_explicit_return = self.hasExplicitReturn;
-
-        //     let is_immediately_invoked = (container_flags.contains(ContainerFlags::IsFunctionExpression) &&
-        //         // !hasSyntacticModifier(node, ModifierFlags::Async) &&
-        //         // !(node as FunctionLikeDeclaration).asteriskToken &&
-        //         // !!getImmediatelyInvokedFunctionExpression(node)
-        //         false)
-        //         || matches!(node, AstKind::ClassStaticBlock(_));
-
-        //     // A non-async, non-generator IIFE is considered part of the containing control flow. Return statements behave
-        //     // similarly to break statements that exit to a label just past the statement body.
-        //     if !is_immediately_invoked {
-        //         self.currentFlow = Some(FlowNode::new(FlowFlags::Start));
-        //         if container_flags.intersects(ContainerFlags::IsFunctionExpression | ContainerFlags::IsObjectLiteralOrClassExpressionMethodOrAccessor) {
-        //             // currentFlow.node = node;
-        //         }
-        //     }
-
-        //     // We create a return control flow graph for IIFEs and constructors. For constructors
-        //     // we use the return control flow graph in strict property initialization checks.
-        //     self.currentReturnTarget = if is_immediately_invoked || matches!(node, AstKind::Constructor(_)) || (self.isInJSFile(node) && (matches!(node, AstKind::FunctionDeclaration(_)) || matches!(node, AstKind::FunctionExpression(_)))) { Some(self.createBranchLabel()) } else { None };
-
-        //     self.currentExceptionTarget = None;
-        //     self.currentBreakTarget = None;
-        //     self.currentContinueTarget = None;
-        //     self.activeLabelList = None;
-        //     self.hasExplicitReturn = false;
-
-        //     self.bindChildren(node);
-
-        //     // Reset all reachability check related flags on node (for incremental scenarios)
-        //     // node.flags &= !NodeFlags::ReachabilityAndEmitFlags;
-
-        //     if let Some(current_flow) = &self.currentFlow {
-        //         if !current_flow.flags.contains(FlowFlags::Unreachable) && container_flags.contains(ContainerFlags::IsFunctionLike) {
-        //             // node.flags |= NodeFlags::HasImplicitReturn;
-        //             // if self.hasExplicitReturn {
-        //             //     node.flags |= NodeFlags::HasExplicitReturn;
-        //             // }
-        //             // node.endFlowNode = self.currentFlow.clone();
-        //         }
-        //     }
-
-        //     if matches!(node, AstKind::SourceFile(_)) {
-        //         // node.flags |= self.emitFlags;
-        //         // node.endFlowNode = self.currentFlow.clone();
-        //     }
+        // Before we recurse into a node's children, we first save the existing parent, container
+        // and block-container.  Then after we pop out of processing the children, we restore
+        // these saved values.
+        let save_container = self.container.clone();
+        let save_this_parent_container = self.thisParentContainer.clone();
+        let saved_block_scope_container = self.blockScopeContainer.clone();
+
+        // Depending on what kind of node this is, we may have to adjust the current container
+        // and block-container.   If the current node is a container, then it is automatically
+        // considered the current block-container as well.  Also, for containers that we know
+        // may contain locals, we eagerly initialize the .locals field. We do this because
+        // it's highly likely that the .locals will be needed to place some child in (for example,
+        // a parameter, or variable declaration).
+        //
+        // However, we do not proactively create the .locals for block-containers because it's
+        // totally normal and common for block-containers to never actually have a block-scoped
+        // variable in them.  We don't want to end up allocating an object for every 'block' we
+        // run into when most of them won't be necessary.
+        //
+        // Finally, if this is a block-container, then we clear out any existing .locals object
+        // it may contain within it.  This happens in incremental scenarios.  Because we can be
+        // reusing a node from a previous compilation, that node may have had 'locals' created
+        // for it.  We must clear this so we don't accidentally move any stale data forward from
+        // a previous compilation.
+        if container_flags.contains(ContainerFlags::IsContainer) {
+            if !matches!(node, AstKind::ArrowFunctionExpression(_)) {
+                self.thisParentContainer = self.container.clone();
+            }
+            self.container = IsContainerOrEntityNameExpression::from_ast_kind(node);
+            self.blockScopeContainer = IsBlockScopedContainer::from_ast_kind(node);
+            if container_flags.contains(ContainerFlags::HasLocals) {
+                let node = HasLocals::from_ast_kind(node).unwrap();
+                node.set_locals(Some(new_rc_cell!(createSymbolTable(None))));
+                self.addToContainerChain(node);
+            }
+        } else if container_flags.contains(ContainerFlags::IsBlockScopedContainer) {
+            self.blockScopeContainer = IsBlockScopedContainer::from_ast_kind(node);
+            if container_flags.contains(ContainerFlags::HasLocals) {
+                HasLocals::from_ast_kind(node).unwrap().set_locals(None);
+            }
+        }
 
-        //     if let Some(current_return_target) = &self.currentReturnTarget {
-        //         self.addAntecedent(current_return_target, self.currentFlow.as_ref().unwrap());
-        //         self.currentFlow = Some(self.finishFlowLabel(current_return_target));
-        //         if matches!(node, AstKind::Constructor(_)) || matches!(node, AstKind::ClassStaticBlock(_)) || (self.isInJSFile(node) && (matches!(node, AstKind::FunctionDeclaration(_)) || matches!(node, AstKind::FunctionExpression(_)))) {
-        //             // node.returnFlowNode = self.currentFlow.clone();
-        //         }
-        //     }
+        if container_flags.contains(ContainerFlags::IsControlFlowContainer) {
+            let save_current_flow = self.currentFlow.clone();
+            let save_break_target = self.currentBreakTarget.clone();
+            let save_continue_target = self.currentContinueTarget.clone();
+            let save_return_target = self.currentReturnTarget.clone();
+            let save_exception_target = self.currentExceptionTarget.clone();
+            let save_active_label_list = self.activeLabelList.clone();
+            let save_has_explicit_return = self.hasExplicitReturn;
+
+            let is_immediately_invoked = (container_flags.contains(ContainerFlags::IsFunctionExpression) &&
+                // ! rb `async` and the asterisk token are flags on the function in oxc, not modifiers
+                !matches!(node, AstKind::Function(func) if func.r#async || func.generator) &&
+                !matches!(node, AstKind::ArrowFunctionExpression(arrow) if arrow.r#async) &&
+                getImmediatelyInvokedFunctionExpression(node).is_some())
+                || matches!(node, AstKind::StaticBlock(_));
+
+            // A non-async, non-generator IIFE is considered part of the containing control flow. Return statements behave
+            // similarly to break statements that exit to a label just past the statement body.
+            if !is_immediately_invoked {
+                let start_node = if container_flags.intersects(ContainerFlags::IsFunctionExpression | ContainerFlags::IsObjectLiteralOrClassExpressionMethodOrAccessor) { Some(*node) } else { None };
+                self.currentFlow = Some(new_rc_cell!(FlowNode::Start(FlowStart { flags: FlowFlags::Start, id: 0, node: start_node })));
+            }
+
+            // We create a return control flow graph for IIFEs and constructors. For constructors
+            // we use the return control flow graph in strict property initialization checks.
+            self.currentReturnTarget = if is_immediately_invoked || isConstructorDeclaration(node) || (isInJSFile(node) && matches!(node, AstKind::Function(_))) { Some(self.createBranchLabel()) } else { None };
+            self.currentExceptionTarget = None;
+            self.currentBreakTarget = None;
+            self.currentContinueTarget = None;
+            self.activeLabelList = None;
+            self.hasExplicitReturn = false;
+            self.bindChildren(node);
+            // Reset all reachability check related flags on node (for incremental scenarios)
+            node.set_flags(node.flags() & !NodeFlags::ReachabilityAndEmitFlags);
+            if !self.currentFlow.as_ref().unwrap().borrow().get_flags().intersects(FlowFlags::Unreachable) && container_flags.contains(ContainerFlags::IsFunctionLike) && rb_function_body_is_present(node) {
+                node.set_flags(node.flags() | NodeFlags::HasImplicitReturn);
+                if self.hasExplicitReturn {
+                    node.set_flags(node.flags() | NodeFlags::HasExplicitReturn);
+                }
+                node.set_endFlowNode(self.currentFlow.clone());
+            }
+            if matches!(node, AstKind::SourceFile(_)) {
+                // node.flags |= self.emitFlags;
+                node.set_endFlowNode(self.currentFlow.clone());
+            }
 
-        //     if !is_immediately_invoked {
-        //         self.currentFlow = save_current_flow;
-        //     }
-        //     self.currentBreakTarget = save_break_target;
-        //     self.currentContinueTarget = save_continue_target;
-        //     self.currentReturnTarget = save_return_target;
-        //     self.currentExceptionTarget = save_exception_target;
-        //     self.activeLabelList = save_active_label_list;
-        //     self.hasExplicitReturn = save_has_explicit_return;
+            if let Some(current_return_target) = self.currentReturnTarget.clone() {
+                self.addAntecedent(&current_return_target, self.currentFlow.clone().unwrap());
+                self.currentFlow = Some(self.finishFlowLabel(&current_return_target));
+                if isConstructorDeclaration(node) || matches!(node, AstKind::StaticBlock(_)) || (isInJSFile(node) && matches!(node, AstKind::Function(_))) {
+                    node.set_returnFlowNode(self.currentFlow.clone());
+                }
+            }
+            if !is_immediately_invoked {
+                self.currentFlow = save_current_flow;
+            }
+            self.currentBreakTarget = save_break_target;
+            self.currentContinueTarget = save_continue_target;
+            self.currentReturnTarget = save_return_target;
+            self.currentExceptionTarget = save_exception_target;
+            self.activeLabelList = save_active_label_list;
+            self.hasExplicitReturn = save_has_explicit_return;
         // } else if container_flags.contains(ContainerFlags::IsInterface) {
         //     self.seenThisKeyword = false;
         //     self.bindChildren(node);
@@ -695,19 +920,28 @@ impl<'a> Binder<'a> {
         //     // } else {
         //     //     node.flags & !NodeFlags::ContainsThis
         //     // };
-        // } else {
-        self.bindChildren(node);
-        // }
+        } else {
+            self.bindChildren(node);
+        }
 
-        // self.container = save_container;
-        // self.thisParentContainer = save_this_parent_container;
-        // self.blockScopeContainer = saved_block_scope_container;
+        self.container = save_container;
+        self.thisParentContainer = save_this_parent_container;
+        self.blockScopeContainer = saved_block_scope_container;
     }
 
-    // fn bindEachFunctionsFirst(&mut self, nodes: Option<&[AstKind<'a>]>) {
-    //     self.bindEach(nodes, |n| if matches!(n, AstKind::FunctionDeclaration(_)) { self.bind(Some(n)) } else { None });
-    //     self.bindEach(nodes, |n| if !matches!(n, AstKind::FunctionDeclaration(_)) { self.bind(Some(n)) } else { None });
-    // }
+    // ! rb takes the children of the statement list's node, prologue directives are not part of the statement list in oxc
+    fn bindEachFunctionsFirst(&mut self, nodes: &[AstKind<'a>]) {
+        for node in nodes {
+            if rb_is_function_declaration(node) {
+                self.bind(Some(*node));
+            }
+        }
+        for node in nodes {
+            if !rb_is_function_declaration(node) {
+                self.bind(Some(*node));
+            }
+        }
+    }
 
     fn bindEach<F>(&mut self, nodes: Option<&[AstKind<'a>]>, bind_function: F)
     where
@@ -733,116 +967,141 @@ impl<'a> Binder<'a> {
         // Most nodes aren't valid in an assignment pattern, so we clear the value here
         // and set it before we descend into nodes that could actually be part of an assignment pattern.
         self.inAssignmentPattern = false;
-        // todo(RB): continue conversion from here
-        // if self.checkUnreachable(node) {
-        //     self.bindEachChild(node);
-        //     self.bindJSDoc(node);
-        //     self.inAssignmentPattern = save_in_assignment_pattern;
-        //     return;
-        // }
-        // if node.is_statement() && (!self.options.as_ref().unwrap().allowUnreachableCode || node.is_return_statement()) {
-        //     node.set_flow_node(self.currentFlow.clone());
-        // }
-        match node {
-            //     AstKind::WhileStatement(while_stmt) => {
-            //         self.bindWhileStatement(while_stmt);
-            //     }
-            //     AstKind::DoWhileStatement(do_stmt) => {
-            //         self.bindDoStatement(do_stmt);
-            //     }
-            //     AstKind::ForStatement(for_stmt) => {
-            //         self.bindForStatement(for_stmt);
-            //     }
-            //     AstKind::ForInStatement(for_in) | AstKind::ForOfStatement(for_of) => {
-            //         self.bindForInOrForOfStatement(node);
-            //     }
-            //     AstKind::IfStatement(if_stmt) => {
-            //         self.bindIfStatement(if_stmt);
-            //     }
-            //     AstKind::ReturnStatement(_) | AstKind::ThrowStatement(_) => {
-            //         self.bindReturnOrThrow(node);
-            //     }
-            //     AstKind::BreakStatement(_) | AstKind::ContinueStatement(_) => {
-            //         self.bindBreakOrContinueStatement(node);
-            //     }
-            //     AstKind::TryStatement(try_stmt) => {
-            //         self.bindTryStatement(try_stmt);
-            //     }
-            //     AstKind::SwitchStatement(switch_stmt) => {
-            //         self.bindSwitchStatement(switch_stmt);
-            //     }
-            //     AstKind::BlockStatement(block) => {
-            //         self.bindCaseBlock(block);
-            //     }
-            //     AstKind::SwitchCase(case) => {
-            //         self.bindCaseClause(case);
-            //     }
-            //     AstKind::ExpressionStatement(expr_stmt) => {
-            //         self.bindExpressionStatement(expr_stmt);
-            //     }
-            //     AstKind::LabeledStatement(labeled) => {
-            //         self.bindLabeledStatement(labeled);
-            //     }
-            //     AstKind::UnaryExpression(unary) if unary.prefix => {
-            //         self.bindPrefixUnaryExpressionFlow(unary);
-            //     }
-            //     AstKind::UnaryExpression(unary) if !unary.prefix => {
-            //         self.bindPostfixUnaryExpressionFlow(unary);
-            //     }
-            //     AstKind::BinaryExpression(binary) => {
-            //         if self.isDestructuringAssignment(binary) {
-            //             // Carry over whether we are in an assignment pattern to
-            //             // binary expressions that could actually be an initializer
-            //             self.inAssignmentPattern = save_in_assignment_pattern;
-            //             self.bindDestructuringAssignmentFlow(binary);
-            //             return;
-            //         }
-            //         self.bindBinaryExpressionFlow(binary);
-            //     }
-            //     AstKind::UnaryExpression(unary) if unary.operator == UnaryOperator::Delete => {
-            //         self.bindDeleteExpressionFlow(unary);
-            //     }
-            //     AstKind::ConditionalExpression(cond) => {
-            //         self.bindConditionalExpressionFlow(cond);
-            //     }
-            //     AstKind::VariableDeclarator(var_decl) => {
-            //         self.bindVariableDeclarationFlow(var_decl);
-            //     }
-            //     AstKind::MemberExpression(_) | AstKind::OptionalMemberExpression(_) => {
-            //         self.bindAccessExpressionFlow(node);
-            //     }
-            //     AstKind::CallExpression(call) => {
-            //         self.bindCallExpressionFlow(call);
-            //     }
-            //     AstKind::TSNonNullExpression(non_null) => {
-            //         self.bindNonNullExpressionFlow(non_null);
-            //     }
-            //     AstKind::TSTypeAliasDeclaration(_) | AstKind::TSCallSignatureDeclaration(_) | AstKind::TSEnumDeclaration(_) => {
-            //         self.bindJSDocTypeAlias(node);
-            //     }
-            //     AstKind::TSImportType(import) => {
-            //         self.bindJSDocImportTag(import);
-            //     }
-            //     // In source files and blocks, bind functions first to match hoisting that occurs at runtime
-            //     AstKind::SourceFile(source_file) => {
-            //         self.bindEachFunctionsFirst(&source_file.body);
-            //         self.bind(Some(source_file.to_ast_kind()));
-            //     }
-            //     AstKind::BlockStatement(_) | AstKind::TSModuleBlock(_) => {
-            //         self.bindEachFunctionsFirst(node.get_statements());
-            //     }
-            //     AstKind::ArrayPattern(binding) => {
-            //         self.bindBindingElementFlow(binding);
-            //     }
-            //     AstKind::Parameter(param) => {
-            //         self.bindParameterFlow(param);
-            //     }
-            //     AstKind::ObjectExpression(_) | AstKind::ArrayExpression(_) | AstKind::Property(_) | AstKind::SpreadElement(_) => {
-            //         // Carry over whether we are in an assignment pattern of Object and Array literals
-            //         // as well as their children that are valid assignment targets.
-            //         self.inAssignmentPattern = save_in_assignment_pattern;
-            //         self.bindEachChild(node);
-            //     }
+        if self.checkUnreachable(node) {
+            self.bindEachChild(node);
+            // self.bindJSDoc(node);
+            self.inAssignmentPattern = save_in_assignment_pattern;
+            return;
+        }
+        // FirstStatement..LastStatement
+        if matches!(
+            node,
+            AstKind::VariableDeclarationList(_)
+                | AstKind::EmptyStatement(_)
+                | AstKind::ExpressionStatement(_)
+                | AstKind::IfStatement(_)
+                | AstKind::DoWhileStatement(_)
+                | AstKind::WhileStatement(_)
+                | AstKind::ForStatement(_)
+                | AstKind::ForInStatement(_)
+                | AstKind::ForOfStatement(_)
+                | AstKind::ContinueStatement(_)
+                | AstKind::BreakStatement(_)
+                | AstKind::ReturnStatement(_)
+                | AstKind::WithStatement(_)
+                | AstKind::SwitchStatement(_)
+                | AstKind::LabeledStatement(_)
+                | AstKind::ThrowStatement(_)
+                | AstKind::TryStatement(_)
+                | AstKind::DebuggerStatement(_)
+        ) && (self.options.unwrap().allowUnreachableCode != Some(true) || matches!(node, AstKind::ReturnStatement(_))) {
+            node.set_flowNode(self.currentFlow.clone());
+        }
+        match *node {
+            AstKind::WhileStatement(stmt) => {
+                self.bindWhileStatement(stmt);
+            }
+            AstKind::DoWhileStatement(stmt) => {
+                self.bindDoStatement(stmt);
+            }
+            AstKind::ForStatement(stmt) => {
+                self.bindForStatement(stmt);
+            }
+            AstKind::ForInStatement(_) | AstKind::ForOfStatement(_) => {
+                self.bindForInOrForOfStatement(node);
+            }
+            AstKind::IfStatement(stmt) => {
+                self.bindIfStatement(stmt);
+            }
+            AstKind::ReturnStatement(_) | AstKind::ThrowStatement(_) => {
+                self.bindReturnOrThrow(node);
+            }
+            AstKind::BreakStatement(_) | AstKind::ContinueStatement(_) => {
+                self.bindBreakOrContinueStatement(node);
+            }
+            AstKind::TryStatement(stmt) => {
+                self.bindTryStatement(stmt);
+            }
+            AstKind::SwitchStatement(stmt) => {
+                self.bindSwitchStatement(stmt);
+            }
+            // ! rb oxc has no CaseBlock, the clauses are bound by bindSwitchStatement
+            AstKind::SwitchCase(clause) => {
+                self.bindCaseClause(clause);
+            }
+            AstKind::ExpressionStatement(stmt) => {
+                self.bindExpressionStatement(stmt);
+            }
+            AstKind::LabeledStatement(stmt) => {
+                self.bindLabeledStatement(stmt);
+            }
+            // PrefixUnaryExpression
+            AstKind::UnaryExpression(expr) if !matches!(expr.operator, UnaryOperator::Delete | UnaryOperator::Typeof | UnaryOperator::Void) => {
+                self.bindPrefixUnaryExpressionFlow(node);
+            }
+            AstKind::UpdateExpression(expr) if expr.prefix => {
+                self.bindPrefixUnaryExpressionFlow(node);
+            }
+            // end PrefixUnaryExpression
+            AstKind::UpdateExpression(_) => {
+                self.bindPostfixUnaryExpressionFlow(node);
+            }
+            // BinaryExpression
+            AstKind::AssignmentExpression(expr) if expr.operator == AssignmentOperator::Assign && matches!(expr.left, AssignmentTarget::ArrayAssignmentTarget(_) | AssignmentTarget::ObjectAssignmentTarget(_)) => {
+                // ! rb isDestructuringAssignment looks for literal expressions, oxc parses the left side as a target pattern
+                // Carry over whether we are in an assignment pattern to
+                // binary expressions that could actually be an initializer
+                self.inAssignmentPattern = save_in_assignment_pattern;
+                self.bindDestructuringAssignmentFlow(expr);
+                return;
+            }
+            AstKind::GeneralBinaryExpression(_) | AstKind::AssignmentExpression(_) | AstKind::LogicalExpression(_) | AstKind::PrivateInExpression(_) | AstKind::SequenceExpression(_) => {
+                // end BinaryExpression
+                self.bindBinaryExpressionFlow(node);
+            }
+            AstKind::UnaryExpression(expr) if expr.operator == UnaryOperator::Delete => {
+                self.bindDeleteExpressionFlow(node, expr);
+            }
+            AstKind::ConditionalExpression(expr) => {
+                self.bindConditionalExpressionFlow(expr);
+            }
+            AstKind::VariableDeclarator(decl) => {
+                self.bindVariableDeclarationFlow(node, decl);
+            }
+            // PropertyAccessExpression
+            AstKind::StaticMemberExpression(_) | AstKind::PrivateFieldExpression(_) |
+            // end PropertyAccessExpression
+            AstKind::ElementAccessExpression(_) => {
+                self.bindAccessExpressionFlow(node);
+            }
+            AstKind::CallExpression(call) => {
+                self.bindCallExpressionFlow(node, call);
+            }
+            AstKind::TSNonNullExpression(_) => {
+                self.bindNonNullExpressionFlow(node);
+            }
+            // AstKind::TSTypeAliasDeclaration(_) | AstKind::TSCallSignatureDeclaration(_) | AstKind::TSEnumDeclaration(_) => {
+            //     self.bindJSDocTypeAlias(node);
+            // }
+            // AstKind::TSImportType(import) => {
+            //     self.bindJSDocImportTag(import);
+            // }
+            // In source files and blocks, bind functions first to match hoisting that occurs at runtime
+            // ! rb oxc has no end of file token, and function bodies and static blocks are not a Block
+            AstKind::SourceFile(_) | AstKind::BlockStatement(_) | AstKind::FunctionBody(_) | AstKind::StaticBlock(_) | AstKind::TSModuleBlock(_) => {
+                self.bindEachFunctionsFirst(&node.get_children());
+            }
+            // ! rb BindingElement and Parameter initializers are both an AssignmentPattern in oxc
+            AstKind::AssignmentPattern(pattern) => {
+                self.bindInitializer(Some(pattern.right.to_ast_kind()));
+                self.bind(Some(pattern.left.to_ast_kind()));
+            }
+            AstKind::ObjectExpression(_) | AstKind::ArrayExpression(_) | AstKind::ObjectProperty(_) | AstKind::SpreadElement(_) => {
+                // Carry over whether we are in an assignment pattern of Object and Array literals
+                // as well as their children that are valid assignment targets.
+                self.inAssignmentPattern = save_in_assignment_pattern;
+                self.bindEachChild(node);
+            }
             _ => {
                 self.bindEachChild(node);
             }
@@ -851,41 +1110,43 @@ impl<'a> Binder<'a> {
         self.inAssignmentPattern = save_in_assignment_pattern;
     }
 
-    fn isNarrowingExpression(&mut self, expr: &'a Expression<'a>) -> bool {
-        match expr {
-            Expression::Identifier(_) | Expression::ThisExpression(_) => true,
+    // ! rb takes an AstKindExpression rather than an Expression: flow conditions are also built for nodes, such as
+    // assignment targets, that oxc does not store as an Expression
+    fn isNarrowingExpression(&mut self, expr: &AstKindExpression<'a>) -> bool {
+        match *expr {
+            AstKindExpression::IdentifierReference(_) | AstKindExpression::ThisExpression(_) => true,
 
             // PropertyAccessExpression
-            Expression::StaticMemberExpression(_) | Expression::PrivateFieldExpression(_) | 
+            AstKindExpression::StaticMemberExpression(_) | AstKindExpression::PrivateFieldExpression(_) | 
             // end PropertyAccessExpression
-            Expression::ElementAccessExpression(_) => self.containsNarrowableReference(expr),
+            AstKindExpression::ElementAccessExpression(_) => self.containsNarrowableReference(expr),
 
-            Expression::CallExpression(call) => self.hasNarrowableArgument(call),
+            AstKindExpression::CallExpression(call) => self.hasNarrowableArgument(call),
 
-            Expression::ParenthesizedExpression(paren) => {
+            AstKindExpression::ParenthesizedExpression(paren) => {
                 // ! rb skipping jsdoc
                 // if isJSDocTypeAssertion(expr) {
                 //     return false;
                 // }
                 // fallthrough to NonNullExpression case
-                self.isNarrowingExpression(&paren.expression)
+                self.isNarrowingExpression(&AstKindExpression::from_expression(&paren.expression))
             }
 
-            Expression::TSNonNullExpression(non_null) => self.isNarrowingExpression(&non_null.expression),
+            AstKindExpression::TSNonNullExpression(non_null) => self.isNarrowingExpression(&AstKindExpression::from_expression(&non_null.expression)),
 
             // BinaryExpression
-            Expression::GeneralBinaryExpression(_) | Expression::AssignmentExpression(_) | Expression::LogicalExpression(_) | Expression::PrivateInExpression(_) | Expression::SequenceExpression(_) => {
+            AstKindExpression::GeneralBinaryExpression(_) | AstKindExpression::AssignmentExpression(_) | AstKindExpression::LogicalExpression(_) | AstKindExpression::PrivateInExpression(_) | AstKindExpression::SequenceExpression(_) => {
                 // end BinaryExpression
                 self.isNarrowingBinaryExpression(&BinaryExpression::from_ast_kind(&expr.to_ast_kind()).unwrap())
             }
 
             // PrefixUnaryExpression
             // TypeOfExpression
-            Expression::UnaryExpression(unary) => {
+            AstKindExpression::UnaryExpression(unary) => {
                 if unary.operator == UnaryOperator::LogicalNot {
-                    self.isNarrowingExpression(&unary.argument)
+                    self.isNarrowingExpression(&AstKindExpression::from_expression(&unary.argument))
                 } else if unary.operator == UnaryOperator::Delete {
-                    self.isNarrowingExpression(&unary.argument)
+                    self.isNarrowingExpression(&AstKindExpression::from_expression(&unary.argument))
                 } else {
                     false
                 }
@@ -915,16 +1176,11 @@ impl<'a> Binder<'a> {
         }
     }
 
-    fn containsNarrowableReference(&mut self, expr: &'a Expression<'a>) -> bool {
-        if self.isNarrowableReference(&AstKindExpression::from_expression(expr)) {
+    fn containsNarrowableReference(&mut self, expr: &AstKindExpression<'a>) -> bool {
+        if self.isNarrowableReference(expr) {
             true
         } else if let Some(is_optional_chain) = isOptionalChain(expr.to_ast_kind()) {
-            let expression = match is_optional_chain {
-                isOptionalChainResult::PropertyAccessExpression(n) => &n.object(),
-                isOptionalChainResult::ElementAccessExpression(n) => &n.object,
-                isOptionalChainResult::CallExpression(n) => &n.callee,
-            };
-            self.containsNarrowableReference(expression)
+            self.containsNarrowableReference(&AstKindExpression::from_expression(is_optional_chain.expression()))
         } else {
             false
         }
@@ -933,18 +1189,18 @@ impl<'a> Binder<'a> {
     fn hasNarrowableArgument(&mut self, expr: &'a CallExpression<'a>) -> bool {
         for argument in &expr.arguments {
             if let Some(argument) = argument.as_expression() {
-                if self.containsNarrowableReference(&argument) {
+                if self.containsNarrowableReference(&AstKindExpression::from_expression(argument)) {
                     return true;
                 }
             }
         }
 
         if let Expression::StaticMemberExpression(prop_access) = &expr.callee {
-            if self.containsNarrowableReference(&prop_access.object) {
+            if self.containsNarrowableReference(&AstKindExpression::from_expression(&prop_access.object)) {
                 return true;
             }
         } else if let Expression::PrivateFieldExpression(prop_access) = &expr.callee {
-            if self.containsNarrowableReference(&prop_access.object) {
+            if self.containsNarrowableReference(&AstKindExpression::from_expression(&prop_access.object)) {
                 return true;
             }
         }
@@ -960,18 +1216,18 @@ impl<'a> Binder<'a> {
         match expr {
             BinaryExpression::AssignmentExpression(assignment) if matches!(assignment.operator, AssignmentOperator::Assign | AssignmentOperator::LogicalOr | AssignmentOperator::LogicalAnd | AssignmentOperator::LogicalNullish) => {
                 // ! rb I'm not sure this unwrap is correct
-                self.containsNarrowableReference(&assignment.left.get_expression().unwrap())
+                self.containsNarrowableReference(&AstKindExpression::from_expression(&assignment.left.get_expression().unwrap()))
             }
             BinaryExpression::GeneralBinaryExpression(binary) if matches!(binary.operator, GeneralBinaryOperator::Equality | GeneralBinaryOperator::Inequality | GeneralBinaryOperator::StrictEquality | GeneralBinaryOperator::StrictInequality) => {
                 self.isNarrowableOperand(&binary.left)
                     || self.isNarrowableOperand(&binary.right)
                     || self.isNarrowingTypeofOperands(&binary.right, &binary.left)
                     || self.isNarrowingTypeofOperands(&binary.left, &binary.right)
-                    || (isBooleanLiteral(&binary.right.to_ast_kind()) && self.isNarrowingExpression(&binary.left) || isBooleanLiteral(&binary.left.to_ast_kind()) && self.isNarrowingExpression(&binary.right))
+                    || (isBooleanLiteral(&binary.right.to_ast_kind()) && self.isNarrowingExpression(&AstKindExpression::from_expression(&binary.left)) || isBooleanLiteral(&binary.left.to_ast_kind()) && self.isNarrowingExpression(&AstKindExpression::from_expression(&binary.right)))
             }
             BinaryExpression::GeneralBinaryExpression(binary) if binary.operator == GeneralBinaryOperator::Instanceof => self.isNarrowableOperand(&binary.left),
-            BinaryExpression::GeneralBinaryExpression(binary) if binary.operator == GeneralBinaryOperator::In => self.isNarrowingExpression(&binary.right),
-            BinaryExpression::SequenceExpression(expr) => self.isNarrowingExpression(&expr.expressions.last().unwrap()),
+            BinaryExpression::GeneralBinaryExpression(binary) if binary.operator == GeneralBinaryOperator::In => self.isNarrowingExpression(&AstKindExpression::from_expression(&binary.right)),
+            BinaryExpression::SequenceExpression(expr) => self.isNarrowingExpression(&AstKindExpression::from_expression(expr.expressions.last().unwrap())),
             _ => false,
         }
     }
@@ -984,98 +1240,929 @@ impl<'a> Binder<'a> {
                 self.isNarrowableOperand(&assignment.left.get_expression().unwrap())
             }
             Expression::SequenceExpression(seq) => self.isNarrowableOperand(&seq.expressions.last().unwrap()),
-            _ => self.containsNarrowableReference(expr),
+            _ => self.containsNarrowableReference(&AstKindExpression::from_expression(expr)),
         }
     }
     // endregion: 1339
 
-    // region: 2741
-    fn bind(&mut self, node: Option<AstKind<'a>>) {
-        if node.is_none() {
+    // region: 1341
+    fn createBranchLabel(&self) -> rc_cell!(FlowNode<'a>) { new_rc_cell!(FlowNode::Label(FlowLabel { flags: FlowFlags::BranchLabel, id: 0, antecedent: None })) }
+
+    fn createLoopLabel(&self) -> rc_cell!(FlowNode<'a>) { new_rc_cell!(FlowNode::Label(FlowLabel { flags: FlowFlags::LoopLabel, id: 0, antecedent: None })) }
+
+    fn createReduceLabel(&self, target: rc_cell!(FlowNode<'a>), antecedents: Vec<rc_cell!(FlowNode<'a>)>, antecedent: rc_cell!(FlowNode<'a>)) -> rc_cell!(FlowNode<'a>) {
+        new_rc_cell!(FlowNode::ReduceLabel(FlowReduceLabel { flags: FlowFlags::ReduceLabel, id: 0, node: FlowReduceLabelData { target, antecedents }, antecedent }))
+    }
+
+    fn setFlowNodeReferenced(&self, flow: &rc_cell!(FlowNode<'a>)) {
+        // On first reference we set the Referenced flag, thereafter we set the Shared flag
+        let flags = flow.borrow().get_flags();
+        flow.borrow_mut().set_flags(flags | if flags.intersects(FlowFlags::Referenced) { FlowFlags::Shared } else { FlowFlags::Referenced });
+    }
+
+    fn addAntecedent(&self, label: &rc_cell!(FlowNode<'a>), antecedent: rc_cell!(FlowNode<'a>)) {
+        if antecedent.borrow().get_flags().intersects(FlowFlags::Unreachable) {
             return;
         }
-        let node = node.unwrap();
-        // TEST.with(|t| t.borrow_mut().test(self.parent.clone()));
-        node.set_parent(self.parent.clone());
-        // if tracing { node.tracingPath = self.file.path; }
-        let save_in_strict_mode = self.inStrictMode;
+        {
+            let mut label = label.borrow_mut();
+            let FlowNode::Label(label) = &mut *label else { panic!("Expected FlowLabel") };
+            let antecedents = label.antecedent.get_or_insert_with(Vec::new);
+            if antecedents.iter().any(|a| Rc::ptr_eq(a, &antecedent)) {
+                return;
+            }
+            antecedents.push(antecedent.clone());
+        }
+        self.setFlowNodeReferenced(&antecedent);
+    }
 
-        // Even though in the AST the jsdoc @typedef node belongs to the current node,
-        // its symbol might be in the same scope with the current node's symbol. Consider:
-        //
-        //     /** @typedef {string | number} MyType */
-        //     function foo();
-        //
-        // Here the current node is "foo", which is a container, but the scope of "MyType" should
-        // not be inside "foo". Therefore we always bind @typedef before bind the parent node,
-        // and skip binding this tag later when binding all the other jsdoc tags.
+    fn createFlowCondition(&mut self, flags: FlowFlags, antecedent: rc_cell!(FlowNode<'a>), expression: Option<AstKind<'a>>) -> rc_cell!(FlowNode<'a>) {
+        if antecedent.borrow().get_flags().intersects(FlowFlags::Unreachable) {
+            return antecedent;
+        }
+        let Some(expression) = expression else {
+            return if flags.intersects(FlowFlags::TrueCondition) { antecedent } else { self.unreachableFlow.clone() };
+        };
+        if (matches!(expression, AstKind::BooleanLiteral(literal) if literal.value) && flags.intersects(FlowFlags::FalseCondition) || matches!(expression, AstKind::BooleanLiteral(literal) if !literal.value) && flags.intersects(FlowFlags::TrueCondition))
+            && !isExpressionOfOptionalChainRoot(&expression)
+            && !expression.parent().is_some_and(|parent| isNullishCoalesce(&parent))
+        {
+            return self.unreachableFlow.clone();
+        }
+        if !AstKindExpression::from_ast_kind(&expression).is_some_and(|expr| self.isNarrowingExpression(&expr)) {
+            return antecedent;
+        }
+        self.setFlowNodeReferenced(&antecedent);
+        new_rc_cell!(FlowNode::Condition(FlowCondition { flags, id: 0, node: expression, antecedent }))
+    }
 
-        // First we bind declaration nodes to a symbol if possible. We'll both create a symbol
-        // and then potentially add the symbol to an appropriate symbol table. Possible
-        // destination symbol tables are:
-        //
-        //  1) The 'exports' table of the current container's symbol.
-        //  2) The 'members' table of the current container's symbol.
-        //  3) The 'locals' table of the current container.
-        //
-        // However, not all symbols will end up in any of these tables. 'Anonymous' symbols
-        // (like TypeLiterals for example) will not be put in any table.
-        self.bindWorker(&node);
+    fn createFlowSwitchClause(&self, antecedent: rc_cell!(FlowNode<'a>), switchStatement: &'a SwitchStatement<'a>, clauseStart: usize, clauseEnd: usize) -> rc_cell!(FlowNode<'a>) {
+        self.setFlowNodeReferenced(&antecedent);
+        new_rc_cell!(FlowNode::SwitchClause(FlowSwitchClause { flags: FlowFlags::SwitchClause, id: 0, node: FlowSwitchClauseData { switchStatement, clauseStart, clauseEnd }, antecedent }))
+    }
 
-        // Then we recurse into the children of the node to bind them as well. For certain
-        // symbols we do specialized work when we recurse. For example, we'll keep track of
-        // the current 'container' node when it changes. This helps us know which symbol table
-        // a local should go into for example. Since terminal nodes are known not to have
-        // children, as an optimization we don't process those.
-        // if (node.kind > SyntaxKind.LastToken) {
-        let save_parent = self.parent.clone();
-        self.parent = Some(node.clone());
-        let container_flags = getContainerFlags(&node);
-        if container_flags == ContainerFlags::None {
-            self.bindChildren(&node);
+    fn createFlowMutation(&mut self, flags: FlowFlags, antecedent: rc_cell!(FlowNode<'a>), node: AstKind<'a>) -> rc_cell!(FlowNode<'a>) {
+        self.setFlowNodeReferenced(&antecedent);
+        self.hasFlowEffects = true;
+        let result = new_rc_cell!(if flags.intersects(FlowFlags::Assignment) {
+            FlowNode::Assignment(FlowAssignment { flags, id: 0, node, antecedent })
         } else {
-            self.bindContainer(&node, container_flags);
+            let node = match node {
+                AstKind::CallExpression(call) => FlowArrayMutationNode::CallExpression(call),
+                _ => FlowArrayMutationNode::BinaryExpression(BinaryExpression::from_ast_kind(&node).unwrap()),
+            };
+            FlowNode::ArrayMutation(FlowArrayMutation { flags, id: 0, node, antecedent })
+        });
+        if let Some(current_exception_target) = self.currentExceptionTarget.clone() {
+            self.addAntecedent(&current_exception_target, result.clone());
         }
-        self.parent = save_parent;
-        // } else {
-        //     if node.kind == SyntaxKind::EndOfFileToken { self.parent = Some(node); }
-        //     self.bindJSDoc(&node);
-        //     self.parent = save_parent;
-        // }
-        self.inStrictMode = save_in_strict_mode;
+        result
     }
-    // endregion: 2794
 
-    // region: 2836
-    fn bindWorker(&mut self, node: &AstKind<'a>) {
-        match node {
-            /* Strict mode checks */
-            // Identifier
-            AstKind::IdentifierName(_) |
-            AstKind::IdentifierReference(_) |
-            AstKind::BindingIdentifier(_) |
-            AstKind::JSXIdentifier(_) |
-            // end Identifier
-                // ! rb skipping jsdoc
-                // for typedef type names with namespaces, bind the new jsdoc type symbol here
-                // because it requires all containing namespaces to be in effect, namely the
-                // current "blockScopeContainer" needs to be set to its immediate namespace parent.
-                // if (node.flags & NodeFlags.IdentifierIsInJSDocNamespace) {
-                //     let parentNode = node.parent;
-                //     while (parentNode && !isJSDocTypeAlias(parentNode)) {
-                //         parentNode = parentNode.parent;
-                //     }
-                //     bindBlockScopedDeclaration(parentNode as Declaration, SymbolFlags.TypeAlias, SymbolFlags.TypeAliasExcludes);
-                //     break;
-                // }
-                // falls through
-            AstKind::ThisExpression(_) => {
+    fn createFlowCall(&mut self, antecedent: rc_cell!(FlowNode<'a>), node: &'a CallExpression<'a>) -> rc_cell!(FlowNode<'a>) {
+        self.setFlowNodeReferenced(&antecedent);
+        self.hasFlowEffects = true;
+        new_rc_cell!(FlowNode::Call(FlowCall { flags: FlowFlags::Call, id: 0, node, antecedent }))
+    }
+
+    fn finishFlowLabel(&self, flow: &rc_cell!(FlowNode<'a>)) -> rc_cell!(FlowNode<'a>) {
+        match rb_label_antecedents(flow) {
+            None => self.unreachableFlow.clone(),
+            Some(antecedents) if antecedents.len() == 1 => antecedents[0].clone(),
+            Some(_) => flow.clone(),
+        }
+    }
+
+    fn isStatementCondition(&self, node: &AstKind<'a>) -> bool {
+        let Some(parent) = node.parent() else { return false };
+        match parent {
+            AstKind::IfStatement(stmt) => stmt.test.span() == node.span(),
+            AstKind::WhileStatement(stmt) => stmt.test.span() == node.span(),
+            AstKind::DoWhileStatement(stmt) => stmt.test.span() == node.span(),
+            AstKind::ForStatement(stmt) => stmt.test.as_ref().is_some_and(|test| test.span() == node.span()),
+            AstKind::ConditionalExpression(expr) => expr.test.span() == node.span(),
+            _ => false,
+        }
+    }
+
+    fn isLogicalExpression(&self, node: &AstKind<'a>) -> bool {
+        let mut node = *node;
+        loop {
+            match node {
+                AstKind::ParenthesizedExpression(paren) => node = paren.expression.to_ast_kind(),
+                AstKind::UnaryExpression(unary) if unary.operator == UnaryOperator::LogicalNot => node = unary.argument.to_ast_kind(),
+                _ => return matches!(node, AstKind::LogicalExpression(_)),
+            }
+        }
+    }
+
+    fn isLogicalAssignmentExpression(&self, node: &AstKind<'a>) -> bool { matches!(skipParentheses(*node, None), AstKind::AssignmentExpression(expr) if matches!(expr.operator, AssignmentOperator::LogicalAnd | AssignmentOperator::LogicalOr | AssignmentOperator::LogicalNullish)) }
+
+    fn isTopLevelLogicalExpression(&self, node: &AstKind<'a>) -> bool {
+        let mut node = *node;
+        while let Some(parent) = node.parent() {
+            // ! rb oxc wraps the outermost link of an optional chain in a ChainExpression, which typescript does not have
+            if !(matches!(parent, AstKind::ParenthesizedExpression(_) | AstKind::ChainExpression(_)) || matches!(parent, AstKind::UnaryExpression(unary) if unary.operator == UnaryOperator::LogicalNot)) {
+                break;
+            }
+            node = parent;
+        }
+        !self.isStatementCondition(&node) && !node.parent().is_some_and(|parent| self.isLogicalExpression(&parent)) && !node.parent().is_some_and(|parent| isOptionalChain(parent).is_some_and(|chain| chain.expression().span() == node.span()))
+    }
+
+    fn doWithConditionalBranches<F: FnOnce(&mut Self, Option<AstKind<'a>>)>(&mut self, action: F, value: Option<AstKind<'a>>, trueTarget: rc_cell!(FlowNode<'a>), falseTarget: rc_cell!(FlowNode<'a>)) {
+        let savedTrueTarget = self.currentTrueTarget.clone();
+        let savedFalseTarget = self.currentFalseTarget.clone();
+        self.currentTrueTarget = Some(trueTarget);
+        self.currentFalseTarget = Some(falseTarget);
+        action(self, value);
+        self.currentTrueTarget = savedTrueTarget;
+        self.currentFalseTarget = savedFalseTarget;
+    }
+
+    fn bindCondition(&mut self, node: Option<AstKind<'a>>, trueTarget: &rc_cell!(FlowNode<'a>), falseTarget: &rc_cell!(FlowNode<'a>)) {
+        self.doWithConditionalBranches(Self::bind, node, trueTarget.clone(), falseTarget.clone());
+        // ! rb the outermost link of an optional chain is the expression of a ChainExpression in oxc
+        let chain = node.map(|node| if let AstKind::ChainExpression(chain) = node { chain.expression.to_ast_kind() } else { node });
+        if node.map_or(true, |node| !self.isLogicalAssignmentExpression(&node) && !self.isLogicalExpression(&node)) && !chain.is_some_and(|chain| isOptionalChain(chain).is_some() && isOutermostOptionalChain(&chain)) {
+            let trueCondition = self.createFlowCondition(FlowFlags::TrueCondition, self.currentFlow.clone().unwrap(), node);
+            self.addAntecedent(trueTarget, trueCondition);
+            let falseCondition = self.createFlowCondition(FlowFlags::FalseCondition, self.currentFlow.clone().unwrap(), node);
+            self.addAntecedent(falseTarget, falseCondition);
+        }
+    }
+
+    fn bindIterativeStatement(&mut self, node: AstKind<'a>, breakTarget: rc_cell!(FlowNode<'a>), continueTarget: rc_cell!(FlowNode<'a>)) {
+        let saveBreakTarget = self.currentBreakTarget.clone();
+        let saveContinueTarget = self.currentContinueTarget.clone();
+        self.currentBreakTarget = Some(breakTarget);
+        self.currentContinueTarget = Some(continueTarget);
+        self.bind(Some(node));
+        self.currentBreakTarget = saveBreakTarget;
+        self.currentContinueTarget = saveContinueTarget;
+    }
+
+    fn setContinueTarget(&self, node: AstKind<'a>, target: rc_cell!(FlowNode<'a>)) -> rc_cell!(FlowNode<'a>) {
+        let mut label = self.activeLabelList.clone();
+        let mut node = node;
+        while let Some(active_label) = label {
+            let Some(parent @ AstKind::LabeledStatement(_)) = node.parent() else { break };
+            active_label.borrow_mut().continueTarget = Some(target.clone());
+            label = active_label.borrow().next.clone();
+            node = parent;
+        }
+        target
+    }
+
+    fn bindWhileStatement(&mut self, node: &'a WhileStatement<'a>) {
+        let preWhileLabel = self.setContinueTarget(AstKind::WhileStatement(node), self.createLoopLabel());
+        let preBodyLabel = self.createBranchLabel();
+        let postWhileLabel = self.createBranchLabel();
+        self.addAntecedent(&preWhileLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(preWhileLabel.clone());
+        self.bindCondition(Some(node.test.to_ast_kind()), &preBodyLabel, &postWhileLabel);
+        self.currentFlow = Some(self.finishFlowLabel(&preBodyLabel));
+        self.bindIterativeStatement(node.body.to_ast_kind(), postWhileLabel.clone(), preWhileLabel.clone());
+        self.addAntecedent(&preWhileLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&postWhileLabel));
+    }
+
+    fn bindDoStatement(&mut self, node: &'a DoWhileStatement<'a>) {
+        let preDoLabel = self.createLoopLabel();
+        let preConditionLabel = self.setContinueTarget(AstKind::DoWhileStatement(node), self.createBranchLabel());
+        let postDoLabel = self.createBranchLabel();
+        self.addAntecedent(&preDoLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(preDoLabel.clone());
+        self.bindIterativeStatement(node.body.to_ast_kind(), postDoLabel.clone(), preConditionLabel.clone());
+        self.addAntecedent(&preConditionLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&preConditionLabel));
+        self.bindCondition(Some(node.test.to_ast_kind()), &preDoLabel, &postDoLabel);
+        self.currentFlow = Some(self.finishFlowLabel(&postDoLabel));
+    }
+
+    fn bindForStatement(&mut self, node: &'a ForStatement<'a>) {
+        let preLoopLabel = self.setContinueTarget(AstKind::ForStatement(node), self.createLoopLabel());
+        let preBodyLabel = self.createBranchLabel();
+        let preIncrementorLabel = self.createBranchLabel();
+        let postLoopLabel = self.createBranchLabel();
+        self.bind(node.init.as_ref().map(|init| init.to_ast_kind()));
+        self.addAntecedent(&preLoopLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(preLoopLabel.clone());
+        self.bindCondition(node.test.as_ref().map(|test| test.to_ast_kind()), &preBodyLabel, &postLoopLabel);
+        self.currentFlow = Some(self.finishFlowLabel(&preBodyLabel));
+        self.bindIterativeStatement(node.body.to_ast_kind(), postLoopLabel.clone(), preIncrementorLabel.clone());
+        self.addAntecedent(&preIncrementorLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&preIncrementorLabel));
+        self.bind(node.update.as_ref().map(|update| update.to_ast_kind()));
+        self.addAntecedent(&preLoopLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&postLoopLabel));
+    }
+
+    fn bindForInOrForOfStatement(&mut self, node: &AstKind<'a>) {
+        let (initializer, expression, statement) = match *node {
+            AstKind::ForInStatement(stmt) => (&stmt.left, &stmt.right, &stmt.body),
+            AstKind::ForOfStatement(stmt) => (&stmt.left, &stmt.right, &stmt.body),
+            _ => panic!("Expected ForInStatement or ForOfStatement, got {:?}", node),
+        };
+        let preLoopLabel = self.setContinueTarget(*node, self.createLoopLabel());
+        let postLoopLabel = self.createBranchLabel();
+        self.bind(Some(expression.to_ast_kind()));
+        self.addAntecedent(&preLoopLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(preLoopLabel.clone());
+        // ! rb `for await` is a flag on the statement in oxc, there is no await modifier to bind
+        // if (node.kind === SyntaxKind.ForOfStatement) {
+        //     bind(node.awaitModifier);
+        // }
+        self.addAntecedent(&postLoopLabel, self.currentFlow.clone().unwrap());
+        self.bind(Some(initializer.to_ast_kind()));
+        if !matches!(initializer, ForStatementLeft::VariableDeclaration(_)) {
+            self.bindAssignmentTargetFlow(&initializer.to_ast_kind());
+        }
+        self.bindIterativeStatement(statement.to_ast_kind(), postLoopLabel.clone(), preLoopLabel.clone());
+        self.addAntecedent(&preLoopLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&postLoopLabel));
+    }
+
+    fn bindIfStatement(&mut self, node: &'a IfStatement<'a>) {
+        let thenLabel = self.createBranchLabel();
+        let elseLabel = self.createBranchLabel();
+        let postIfLabel = self.createBranchLabel();
+        self.bindCondition(Some(node.test.to_ast_kind()), &thenLabel, &elseLabel);
+        self.currentFlow = Some(self.finishFlowLabel(&thenLabel));
+        self.bind(Some(node.consequent.to_ast_kind()));
+        self.addAntecedent(&postIfLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&elseLabel));
+        self.bind(node.alternate.as_ref().map(|alternate| alternate.to_ast_kind()));
+        self.addAntecedent(&postIfLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&postIfLabel));
+    }
+
+    fn bindReturnOrThrow(&mut self, node: &AstKind<'a>) {
+        match *node {
+            AstKind::ReturnStatement(stmt) => {
+                self.bind(stmt.argument.as_ref().map(|argument| argument.to_ast_kind()));
+                self.hasExplicitReturn = true;
+                if let Some(current_return_target) = self.currentReturnTarget.clone() {
+                    self.addAntecedent(&current_return_target, self.currentFlow.clone().unwrap());
+                }
+            }
+            AstKind::ThrowStatement(stmt) => {
+                self.bind(Some(stmt.argument.to_ast_kind()));
+            }
+            _ => panic!("Expected ReturnStatement or ThrowStatement, got {:?}", node),
+        }
+        self.currentFlow = Some(self.unreachableFlow.clone());
+        self.hasFlowEffects = true;
+    }
+
+    fn findActiveLabel(&self, name: &str) -> opt_rc_cell!(ActiveLabel<'a>) {
+        let mut label = self.activeLabelList.clone();
+        while let Some(active_label) = label {
+            if active_label.borrow().name == name {
+                return Some(active_label);
+            }
+            label = active_label.borrow().next.clone();
+        }
+        None
+    }
+
+    fn bindBreakOrContinueFlow(&mut self, node: &AstKind<'a>, breakTarget: opt_rc_cell!(FlowNode<'a>), continueTarget: opt_rc_cell!(FlowNode<'a>)) {
+        let flowLabel = if matches!(node, AstKind::BreakStatement(_)) { breakTarget } else { continueTarget };
+        if let Some(flowLabel) = flowLabel {
+            self.addAntecedent(&flowLabel, self.currentFlow.clone().unwrap());
+            self.currentFlow = Some(self.unreachableFlow.clone());
+            self.hasFlowEffects = true;
+        }
+    }
+
+    fn bindBreakOrContinueStatement(&mut self, node: &AstKind<'a>) {
+        let label = match *node {
+            AstKind::BreakStatement(stmt) => stmt.label.as_ref(),
+            AstKind::ContinueStatement(stmt) => stmt.label.as_ref(),
+            _ => panic!("Expected BreakStatement or ContinueStatement, got {:?}", node),
+        };
+        self.bind(label.map(|label| AstKind::LabelIdentifier(label)));
+        if let Some(label) = label {
+            if let Some(activeLabel) = self.findActiveLabel(&label.name) {
+                activeLabel.borrow_mut().referenced = true;
+                let breakTarget = activeLabel.borrow().breakTarget.clone();
+                let continueTarget = activeLabel.borrow().continueTarget.clone();
+                self.bindBreakOrContinueFlow(node, Some(breakTarget), continueTarget);
+            }
+        } else {
+            self.bindBreakOrContinueFlow(node, self.currentBreakTarget.clone(), self.currentContinueTarget.clone());
+        }
+    }
+
+    fn bindTryStatement(&mut self, node: &'a TryStatement<'a>) {
+        // We conservatively assume that *any* code in the try block can cause an exception, but we only need
+        // to track code that causes mutations (because only mutations widen the possible control flow type of
+        // a variable). The exceptionLabel is the target label for control flows that result from exceptions.
+        // We add all mutation flow nodes as antecedents of this label such that we can analyze them as possible
+        // antecedents of the start of catch or finally blocks. Furthermore, we add the current control flow to
+        // represent exceptions that occur before any mutations.
+        let saveReturnTarget = self.currentReturnTarget.clone();
+        let saveExceptionTarget = self.currentExceptionTarget.clone();
+        let normalExitLabel = self.createBranchLabel();
+        let returnLabel = self.createBranchLabel();
+        let mut exceptionLabel = self.createBranchLabel();
+        if node.finalizer.is_some() {
+            self.currentReturnTarget = Some(returnLabel.clone());
+        }
+        self.addAntecedent(&exceptionLabel, self.currentFlow.clone().unwrap());
+        self.currentExceptionTarget = Some(exceptionLabel.clone());
+        self.bind(Some(AstKind::BlockStatement(&node.block)));
+        self.addAntecedent(&normalExitLabel, self.currentFlow.clone().unwrap());
+        if let Some(handler) = &node.handler {
+            // Start of catch clause is the target of exceptions from try block.
+            self.currentFlow = Some(self.finishFlowLabel(&exceptionLabel));
+            // The currentExceptionTarget now represents control flows from exceptions in the catch clause.
+            // Effectively, in a try-catch-finally, if an exception occurs in the try block, the catch block
+            // acts like a second try block.
+            exceptionLabel = self.createBranchLabel();
+            self.addAntecedent(&exceptionLabel, self.currentFlow.clone().unwrap());
+            self.currentExceptionTarget = Some(exceptionLabel.clone());
+            self.bind(Some(AstKind::CatchClause(handler)));
+            self.addAntecedent(&normalExitLabel, self.currentFlow.clone().unwrap());
+        }
+        self.currentReturnTarget = saveReturnTarget;
+        self.currentExceptionTarget = saveExceptionTarget;
+        if let Some(finalizer) = &node.finalizer {
+            // Possible ways control can reach the finally block:
+            // 1) Normal completion of try block of a try-finally or try-catch-finally
+            // 2) Normal completion of catch block (following exception in try block) of a try-catch-finally
+            // 3) Return in try or catch block of a try-finally or try-catch-finally
+            // 4) Exception in try block of a try-finally
+            // 5) Exception in catch block of a try-catch-finally
+            // When analyzing a control flow graph that starts inside a finally block we want to consider all
+            // five possibilities above. However, when analyzing a control flow graph that starts outside (past)
+            // the finally block, we only want to consider the first two (if we're past a finally block then it
+            // must have completed normally). Likewise, when analyzing a control flow graph from return statements
+            // in try or catch blocks in an IIFE, we only want to consider the third. To make this possible, we
+            // inject a ReduceLabel node into the control flow graph. This node contains an alternate reduced
+            // set of antecedents for the pre-finally label. As control flow analysis passes by a ReduceLabel
+            // node, the pre-finally label is temporarily switched to the reduced antecedent set.
+            let finallyLabel = self.createBranchLabel();
+            let antecedent: Vec<_> = [&normalExitLabel, &exceptionLabel, &returnLabel].into_iter().flat_map(|label| rb_label_antecedents(label).unwrap_or_default()).collect();
+            if let FlowNode::Label(label) = &mut *finallyLabel.borrow_mut() {
+                label.antecedent = if antecedent.is_empty() { None } else { Some(antecedent) };
+            }
+            self.currentFlow = Some(finallyLabel.clone());
+            self.bind(Some(AstKind::BlockStatement(finalizer)));
+            if self.currentFlow.as_ref().unwrap().borrow().get_flags().intersects(FlowFlags::Unreachable) {
+                // If the end of the finally block is unreachable, the end of the entire try statement is unreachable.
+                self.currentFlow = Some(self.unreachableFlow.clone());
+            } else {
+                // If we have an IIFE return target and return statements in the try or catch blocks, add a control
+                // flow that goes back through the finally block and back through only the return statements.
+                if let (Some(current_return_target), Some(return_antecedents)) = (self.currentReturnTarget.clone(), rb_label_antecedents(&returnLabel)) {
+                    let reduceLabel = self.createReduceLabel(finallyLabel.clone(), return_antecedents, self.currentFlow.clone().unwrap());
+                    self.addAntecedent(&current_return_target, reduceLabel);
+                }
+                // If we have an outer exception target (i.e. a containing try-finally or try-catch-finally), add a
+                // control flow that goes back through the finally block and back through each possible exception source.
+                if let (Some(current_exception_target), Some(exception_antecedents)) = (self.currentExceptionTarget.clone(), rb_label_antecedents(&exceptionLabel)) {
+                    let reduceLabel = self.createReduceLabel(finallyLabel.clone(), exception_antecedents, self.currentFlow.clone().unwrap());
+                    self.addAntecedent(&current_exception_target, reduceLabel);
+                }
+                // If the end of the finally block is reachable, but the end of the try and catch blocks are not,
+                // convert the current flow to unreachable. For example, 'return f() finally { ... }' should not
+                // result in an unreachable flow node.
+                self.currentFlow = Some(match rb_label_antecedents(&normalExitLabel) {
+                    Some(normal_exit_antecedents) => self.createReduceLabel(finallyLabel.clone(), normal_exit_antecedents, self.currentFlow.clone().unwrap()),
+                    None => self.unreachableFlow.clone(),
+                });
+            }
+        } else {
+            self.currentFlow = Some(self.finishFlowLabel(&normalExitLabel));
+        }
+    }
+
+    fn bindSwitchStatement(&mut self, node: &'a SwitchStatement<'a>) {
+        let postSwitchLabel = self.createBranchLabel();
+        self.bind(Some(node.discriminant.to_ast_kind()));
+        let saveBreakTarget = self.currentBreakTarget.clone();
+        let savePreSwitchCaseFlow = self.preSwitchCaseFlow.clone();
+        self.currentBreakTarget = Some(postSwitchLabel.clone());
+        self.preSwitchCaseFlow = self.currentFlow.clone();
+        self.bindCaseBlock(node);
+        self.addAntecedent(&postSwitchLabel, self.currentFlow.clone().unwrap());
+        let hasDefault = node.cases.iter().any(|clause| clause.test.is_none());
+        // We mark a switch statement as possibly exhaustive if it has no default clause and if all
+        // case clauses have unreachable end points (e.g. they all return). Note, we no longer need
+        // this property in control flow analysis, it's there only for backwards compatibility.
+        AstKind::SwitchStatement(node).set_possiblyExhaustive(!hasDefault && rb_label_antecedents(&postSwitchLabel).is_none());
+        if !hasDefault {
+            let switchClause = self.createFlowSwitchClause(self.preSwitchCaseFlow.clone().unwrap(), node, 0, 0);
+            self.addAntecedent(&postSwitchLabel, switchClause);
+        }
+        self.currentBreakTarget = saveBreakTarget;
+        self.preSwitchCaseFlow = savePreSwitchCaseFlow;
+        self.currentFlow = Some(self.finishFlowLabel(&postSwitchLabel));
+    }
+
+    // ! rb oxc has no CaseBlock node, the clauses are read off the switch statement
+    fn bindCaseBlock(&mut self, node: &'a SwitchStatement<'a>) {
+        let clauses = &node.cases;
+        let isNarrowingSwitch = matches!(node.discriminant, Expression::BooleanLiteral(ref literal) if literal.value) || self.isNarrowingExpression(&AstKindExpression::from_expression(&node.discriminant));
+        let mut fallthroughFlow = self.unreachableFlow.clone();
+        let mut i = 0;
+        while i < clauses.len() {
+            let clauseStart = i;
+            while clauses[i].consequent.is_empty() && i + 1 < clauses.len() {
+                if Rc::ptr_eq(&fallthroughFlow, &self.unreachableFlow) {
+                    self.currentFlow = self.preSwitchCaseFlow.clone();
+                }
+                self.bind(Some(AstKind::SwitchCase(&clauses[i])));
+                i += 1;
+            }
+            let preCaseLabel = self.createBranchLabel();
+            let preSwitchCaseFlow = self.preSwitchCaseFlow.clone().unwrap();
+            let caseAntecedent = if isNarrowingSwitch { self.createFlowSwitchClause(preSwitchCaseFlow, node, clauseStart, i + 1) } else { preSwitchCaseFlow };
+            self.addAntecedent(&preCaseLabel, caseAntecedent);
+            self.addAntecedent(&preCaseLabel, fallthroughFlow.clone());
+            self.currentFlow = Some(self.finishFlowLabel(&preCaseLabel));
+            let clause = &clauses[i];
+            self.bind(Some(AstKind::SwitchCase(clause)));
+            fallthroughFlow = self.currentFlow.clone().unwrap();
+            if !fallthroughFlow.borrow().get_flags().intersects(FlowFlags::Unreachable) && i != clauses.len() - 1 && self.options.unwrap().noFallthroughCasesInSwitch == Some(true) {
+                AstKind::SwitchCase(clause).set_fallthroughFlowNode(self.currentFlow.clone());
+            }
+            i += 1;
+        }
+    }
+
+    fn bindCaseClause(&mut self, node: &'a SwitchCase<'a>) {
+        let saveCurrentFlow = self.currentFlow.clone();
+        self.currentFlow = self.preSwitchCaseFlow.clone();
+        self.bind(node.test.as_ref().map(|test| test.to_ast_kind()));
+        self.currentFlow = saveCurrentFlow;
+        for statement in &node.consequent {
+            self.bind(Some(statement.to_ast_kind()));
+        }
+    }
+
+    fn bindExpressionStatement(&mut self, node: &'a ExpressionStatement<'a>) {
+        self.bind(Some(node.expression.to_ast_kind()));
+        self.maybeBindExpressionFlowIfCall(&node.expression);
+    }
+
+    fn maybeBindExpressionFlowIfCall(&mut self, node: &'a Expression<'a>) {
+        // A top level or comma expression call expression with a dotted function name and at least one argument
+        // is potentially an assertion and is therefore included in the control flow.
+        if let Expression::CallExpression(call) = node {
+            if !matches!(call.callee, Expression::Super(_)) && isDottedName(&call.callee) {
+                self.currentFlow = Some(self.createFlowCall(self.currentFlow.clone().unwrap(), call));
+            }
+        }
+    }
+
+    fn bindLabeledStatement(&mut self, node: &'a LabeledStatement<'a>) {
+        let postStatementLabel = self.createBranchLabel();
+        self.activeLabelList = Some(new_rc_cell!(ActiveLabel {
+            next: self.activeLabelList.clone(),
+            name: node.label.name.to_string(),
+            breakTarget: postStatementLabel.clone(),
+            continueTarget: None,
+            referenced: false,
+        }));
+        self.bind(Some(AstKind::LabelIdentifier(&node.label)));
+        self.bind(Some(node.body.to_ast_kind()));
+        if !self.activeLabelList.as_ref().unwrap().borrow().referenced && self.options.unwrap().allowUnusedLabels != Some(true) {
+            self.errorOrSuggestionOnNode(unusedLabelIsError(self.options.unwrap()), &AstKind::LabelIdentifier(&node.label), Diagnostics::Unused_label());
+        }
+        let next = self.activeLabelList.as_ref().unwrap().borrow().next.clone();
+        self.activeLabelList = next;
+        self.addAntecedent(&postStatementLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&postStatementLabel));
+    }
+
+    fn bindDestructuringTargetFlow(&mut self, node: &'a AssignmentTargetMaybeDefault<'a>) {
+        // ! rb a target with a default value is its own node in oxc, rather than an `=` binary expression
+        match node {
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => self.bindAssignmentTargetFlow(&target.binding.to_ast_kind()),
+            _ => self.bindAssignmentTargetFlow(&node.to_ast_kind()),
+        }
+    }
+
+    fn bindAssignmentTargetFlow(&mut self, node: &AstKind<'a>) {
+        if AstKindExpression::from_ast_kind(node).is_some_and(|expr| self.isNarrowableReference(&expr)) {
+            self.currentFlow = Some(self.createFlowMutation(FlowFlags::Assignment, self.currentFlow.clone().unwrap(), *node));
+            return;
+        }
+        match *node {
+            AstKind::ArrayAssignmentTarget(target) => {
+                for element in target.elements.iter().flatten() {
+                    self.bindDestructuringTargetFlow(element);
+                }
+                if let Some(rest) = &target.rest {
+                    self.bindAssignmentTargetFlow(&rest.target.to_ast_kind());
+                }
+            }
+            AstKind::ObjectAssignmentTarget(target) => {
+                for property in &target.properties {
+                    match property {
+                        AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(property) => self.bindAssignmentTargetFlow(&AstKind::IdentifierReference(&property.binding)),
+                        AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) => self.bindDestructuringTargetFlow(&property.binding),
+                    }
+                }
+                if let Some(rest) = &target.rest {
+                    self.bindAssignmentTargetFlow(&rest.target.to_ast_kind());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn bindLogicalLikeExpression(&mut self, node: AstKind<'a>, trueTarget: &rc_cell!(FlowNode<'a>), falseTarget: &rc_cell!(FlowNode<'a>)) {
+        let (left, right, isAnd, isLogicalAssignment) = match node {
+            AstKind::LogicalExpression(expr) => (expr.left.to_ast_kind(), expr.right.to_ast_kind(), expr.operator == LogicalOperator::And, false),
+            AstKind::AssignmentExpression(expr) => (expr.left.to_ast_kind(), expr.right.to_ast_kind(), expr.operator == AssignmentOperator::LogicalAnd, true),
+            _ => panic!("Expected LogicalExpression or AssignmentExpression, got {:?}", node),
+        };
+        let preRightLabel = self.createBranchLabel();
+        if isAnd {
+            self.bindCondition(Some(left), &preRightLabel, falseTarget);
+        } else {
+            self.bindCondition(Some(left), trueTarget, &preRightLabel);
+        }
+        self.currentFlow = Some(self.finishFlowLabel(&preRightLabel));
+        // bind(node.operatorToken);
+
+        if isLogicalAssignment {
+            self.doWithConditionalBranches(Self::bind, Some(right), trueTarget.clone(), falseTarget.clone());
+            self.bindAssignmentTargetFlow(&left);
+
+            let trueCondition = self.createFlowCondition(FlowFlags::TrueCondition, self.currentFlow.clone().unwrap(), Some(node));
+            self.addAntecedent(trueTarget, trueCondition);
+            let falseCondition = self.createFlowCondition(FlowFlags::FalseCondition, self.currentFlow.clone().unwrap(), Some(node));
+            self.addAntecedent(falseTarget, falseCondition);
+        } else {
+            self.bindCondition(Some(right), trueTarget, falseTarget);
+        }
+    }
+
+    fn bindPrefixUnaryExpressionFlow(&mut self, node: &AstKind<'a>) {
+        if matches!(node, AstKind::UnaryExpression(expr) if expr.operator == UnaryOperator::LogicalNot) {
+            let saveTrueTarget = self.currentTrueTarget.clone();
+            self.currentTrueTarget = self.currentFalseTarget.clone();
+            self.currentFalseTarget = saveTrueTarget.clone();
+            self.bindEachChild(node);
+            self.currentFalseTarget = self.currentTrueTarget.clone();
+            self.currentTrueTarget = saveTrueTarget;
+        } else {
+            self.bindEachChild(node);
+            if let AstKind::UpdateExpression(expr) = *node {
+                self.bindAssignmentTargetFlow(&expr.argument.to_ast_kind());
+            }
+        }
+    }
+
+    fn bindPostfixUnaryExpressionFlow(&mut self, node: &AstKind<'a>) {
+        self.bindEachChild(node);
+        if let AstKind::UpdateExpression(expr) = *node {
+            self.bindAssignmentTargetFlow(&expr.argument.to_ast_kind());
+        }
+    }
+
+    fn bindDestructuringAssignmentFlow(&mut self, node: &'a AssignmentExpression<'a>) {
+        if self.inAssignmentPattern {
+            self.inAssignmentPattern = false;
+            // bind(node.operatorToken);
+            self.bind(Some(node.right.to_ast_kind()));
+            self.inAssignmentPattern = true;
+            self.bind(Some(node.left.to_ast_kind()));
+        } else {
+            self.inAssignmentPattern = true;
+            self.bind(Some(node.left.to_ast_kind()));
+            self.inAssignmentPattern = false;
+            // bind(node.operatorToken);
+            self.bind(Some(node.right.to_ast_kind()));
+        }
+        self.bindAssignmentTargetFlow(&node.left.to_ast_kind());
+    }
+
+    // ! rb TypeScript walks binary expressions with an explicit work stack to avoid deep recursion,
+    // this binds them recursively like the rest of the binder
+    fn bindBinaryExpressionFlow(&mut self, node: &AstKind<'a>) {
+        let isLogical = matches!(node, AstKind::LogicalExpression(_)) || matches!(node, AstKind::AssignmentExpression(expr) if matches!(expr.operator, AssignmentOperator::LogicalAnd | AssignmentOperator::LogicalOr | AssignmentOperator::LogicalNullish));
+        if isLogical {
+            if self.isTopLevelLogicalExpression(node) {
+                // If the binary operator is not nested in another &&, || or ??, or one of the operands is a
+                // logical expression, this creates its own set of flow labels
+                let postExpressionLabel = self.createBranchLabel();
+                let saveCurrentFlow = self.currentFlow.clone();
+                let saveHasFlowEffects = self.hasFlowEffects;
+                self.hasFlowEffects = false;
+                self.bindLogicalLikeExpression(*node, &postExpressionLabel, &postExpressionLabel);
+                self.currentFlow = if self.hasFlowEffects { Some(self.finishFlowLabel(&postExpressionLabel)) } else { saveCurrentFlow };
+                self.hasFlowEffects = self.hasFlowEffects || saveHasFlowEffects;
+            } else {
+                let trueTarget = self.currentTrueTarget.clone().unwrap();
+                let falseTarget = self.currentFalseTarget.clone().unwrap();
+                self.bindLogicalLikeExpression(*node, &trueTarget, &falseTarget);
+            }
+            return;
+        }
+        match *node {
+            // CommaToken
+            AstKind::SequenceExpression(expr) => {
+                for expression in &expr.expressions {
+                    self.bind(Some(expression.to_ast_kind()));
+                    self.maybeBindExpressionFlowIfCall(expression);
+                }
+            }
+            AstKind::AssignmentExpression(expr) => {
+                self.bindEachChild(node);
+                // ! rb nested defaults are AssignmentTargetWithDefault in oxc, so an assignment expression is never itself an assignment target
+                self.bindAssignmentTargetFlow(&expr.left.to_ast_kind());
+                if expr.operator == AssignmentOperator::Assign {
+                    if let AstKind::ElementAccessExpression(elementAccess) = expr.left.to_ast_kind() {
+                        if self.isNarrowableOperand(&elementAccess.object) {
+                            self.currentFlow = Some(self.createFlowMutation(FlowFlags::ArrayMutation, self.currentFlow.clone().unwrap(), *node));
+                        }
+                    }
+                }
+            }
+            _ => {
+                self.bindEachChild(node);
+            }
+        }
+    }
+
+    fn bindDeleteExpressionFlow(&mut self, node: &AstKind<'a>, expr: &'a UnaryExpression<'a>) {
+        self.bindEachChild(node);
+        // PropertyAccessExpression
+        if matches!(expr.argument, Expression::StaticMemberExpression(_) | Expression::PrivateFieldExpression(_)) {
+            self.bindAssignmentTargetFlow(&expr.argument.to_ast_kind());
+        }
+    }
+
+    fn bindConditionalExpressionFlow(&mut self, node: &'a ConditionalExpression<'a>) {
+        let trueLabel = self.createBranchLabel();
+        let falseLabel = self.createBranchLabel();
+        let postExpressionLabel = self.createBranchLabel();
+        let saveCurrentFlow = self.currentFlow.clone();
+        let saveHasFlowEffects = self.hasFlowEffects;
+        self.hasFlowEffects = false;
+        self.bindCondition(Some(node.test.to_ast_kind()), &trueLabel, &falseLabel);
+        self.currentFlow = Some(self.finishFlowLabel(&trueLabel));
+        // bind(node.questionToken);
+        self.bind(Some(node.consequent.to_ast_kind()));
+        self.addAntecedent(&postExpressionLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = Some(self.finishFlowLabel(&falseLabel));
+        // bind(node.colonToken);
+        self.bind(Some(node.alternate.to_ast_kind()));
+        self.addAntecedent(&postExpressionLabel, self.currentFlow.clone().unwrap());
+        self.currentFlow = if self.hasFlowEffects { Some(self.finishFlowLabel(&postExpressionLabel)) } else { saveCurrentFlow };
+        self.hasFlowEffects = self.hasFlowEffects || saveHasFlowEffects;
+    }
+
+    fn bindInitializedVariableFlow(&mut self, node: AstKind<'a>, name: &'a BindingPattern<'a>) {
+        match &name.kind {
+            BindingPatternKind::ObjectPattern(pattern) => {
+                for property in &pattern.properties {
+                    self.bindInitializedVariableFlow(AstKind::BindingProperty(property), &property.value);
+                }
+                if let Some(rest) = &pattern.rest {
+                    self.bindInitializedVariableFlow(AstKind::BindingRestElement(rest), &rest.argument);
+                }
+            }
+            BindingPatternKind::ArrayPattern(pattern) => {
+                for element in pattern.elements.iter().flatten() {
+                    self.bindInitializedVariableFlow(element.to_ast_kind(), element);
+                }
+                if let Some(rest) = &pattern.rest {
+                    self.bindInitializedVariableFlow(AstKind::BindingRestElement(rest), &rest.argument);
+                }
+            }
+            // ! rb an initializer inside a binding pattern wraps the name in oxc
+            BindingPatternKind::AssignmentPattern(pattern) => {
+                self.bindInitializedVariableFlow(node, &pattern.left);
+            }
+            BindingPatternKind::BindingIdentifier(_) => {
+                self.currentFlow = Some(self.createFlowMutation(FlowFlags::Assignment, self.currentFlow.clone().unwrap(), node));
+            }
+        }
+    }
+
+    fn bindVariableDeclarationFlow(&mut self, node: &AstKind<'a>, decl: &'a VariableDeclarator<'a>) {
+        self.bindEachChild(node);
+        if decl.init.is_some() || node.parent().and_then(|parent| parent.parent()).is_some_and(|grandparent| matches!(grandparent, AstKind::ForInStatement(_) | AstKind::ForOfStatement(_))) {
+            self.bindInitializedVariableFlow(*node, &decl.id);
+        }
+    }
+
+    fn bindInitializer(&mut self, node: Option<AstKind<'a>>) {
+        let Some(node) = node else {
+            return;
+        };
+        let entryFlow = self.currentFlow.clone().unwrap();
+        self.bind(Some(node));
+        let currentFlow = self.currentFlow.clone().unwrap();
+        if Rc::ptr_eq(&entryFlow, &self.unreachableFlow) || Rc::ptr_eq(&entryFlow, &currentFlow) {
+            return;
+        }
+        let exitFlow = self.createBranchLabel();
+        self.addAntecedent(&exitFlow, entryFlow);
+        self.addAntecedent(&exitFlow, currentFlow);
+        self.currentFlow = Some(self.finishFlowLabel(&exitFlow));
+    }
+
+    fn bindOptionalExpression(&mut self, node: AstKind<'a>, trueTarget: &rc_cell!(FlowNode<'a>), falseTarget: &rc_cell!(FlowNode<'a>)) {
+        self.doWithConditionalBranches(Self::bind, Some(node), trueTarget.clone(), falseTarget.clone());
+        if isOptionalChain(node).is_none() || isOutermostOptionalChain(&node) {
+            let trueCondition = self.createFlowCondition(FlowFlags::TrueCondition, self.currentFlow.clone().unwrap(), Some(node));
+            self.addAntecedent(trueTarget, trueCondition);
+            let falseCondition = self.createFlowCondition(FlowFlags::FalseCondition, self.currentFlow.clone().unwrap(), Some(node));
+            self.addAntecedent(falseTarget, falseCondition);
+        }
+    }
+
+    // ! rb oxc has no question dot token, the rest of the chain is every child but the chained expression
+    fn bindOptionalChainRest(&mut self, node: Option<AstKind<'a>>) {
+        let node = node.unwrap();
+        let expression = isOptionalChain(node).unwrap().expression().span();
+        for child in node.get_children() {
+            if child.span() != expression {
+                self.bind(Some(child));
+            }
+        }
+    }
+
+    fn bindOptionalChain(&mut self, node: AstKind<'a>, trueTarget: &rc_cell!(FlowNode<'a>), falseTarget: &rc_cell!(FlowNode<'a>)) {
+        // For an optional chain, we emulate the behavior of a logical expression:
+        //
+        //   a?.b         -> a && a.b
+        //   a?.b.c       -> a && a.b.c
+        //   a?.b?.c      -> a && a.b && a.b.c
+        //   a?.[x = 1]   -> a && a[x = 1]
+        //
+        // To do this we descend through the chain until we reach the root of a chain (the expression with a `?.`)
+        // and build it's CFA graph as if it were the first condition (`a && ...`). Then we bind the rest
+        // of the node as part of the "true" branch, and continue to do so as we ascend back up to the outermost
+        // chain node. We then treat the entire node as the right side of the expression.
+        let preChainLabel = if isOptionalChainRoot(&node) { Some(self.createBranchLabel()) } else { None };
+        let expression = isOptionalChain(node).unwrap().expression().to_ast_kind();
+        self.bindOptionalExpression(expression, preChainLabel.as_ref().unwrap_or(trueTarget), falseTarget);
+        if let Some(preChainLabel) = &preChainLabel {
+            self.currentFlow = Some(self.finishFlowLabel(preChainLabel));
+        }
+        self.doWithConditionalBranches(Self::bindOptionalChainRest, Some(node), trueTarget.clone(), falseTarget.clone());
+        if isOutermostOptionalChain(&node) {
+            let trueCondition = self.createFlowCondition(FlowFlags::TrueCondition, self.currentFlow.clone().unwrap(), Some(node));
+            self.addAntecedent(trueTarget, trueCondition);
+            let falseCondition = self.createFlowCondition(FlowFlags::FalseCondition, self.currentFlow.clone().unwrap(), Some(node));
+            self.addAntecedent(falseTarget, falseCondition);
+        }
+    }
+
+    fn bindOptionalChainFlow(&mut self, node: &AstKind<'a>) {
+        if self.isTopLevelLogicalExpression(node) {
+            let postExpressionLabel = self.createBranchLabel();
+            let saveCurrentFlow = self.currentFlow.clone();
+            let saveHasFlowEffects = self.hasFlowEffects;
+            self.bindOptionalChain(*node, &postExpressionLabel, &postExpressionLabel);
+            self.currentFlow = if self.hasFlowEffects { Some(self.finishFlowLabel(&postExpressionLabel)) } else { saveCurrentFlow };
+            self.hasFlowEffects = self.hasFlowEffects || saveHasFlowEffects;
+        } else {
+            let trueTarget = self.currentTrueTarget.clone().unwrap();
+            let falseTarget = self.currentFalseTarget.clone().unwrap();
+            self.bindOptionalChain(*node, &trueTarget, &falseTarget);
+        }
+    }
+
+    fn bindNonNullExpressionFlow(&mut self, node: &AstKind<'a>) {
+        if isOptionalChain(*node).is_some() {
+            self.bindOptionalChainFlow(node);
+        } else {
+            self.bindEachChild(node);
+        }
+    }
+
+    fn bindAccessExpressionFlow(&mut self, node: &AstKind<'a>) {
+        if isOptionalChain(*node).is_some() {
+            self.bindOptionalChainFlow(node);
+        } else {
+            self.bindEachChild(node);
+        }
+    }
+
+    fn bindCallExpressionFlow(&mut self, node: &AstKind<'a>, call: &'a CallExpression<'a>) {
+        if isOptionalChain(*node).is_some() {
+            self.bindOptionalChainFlow(node);
+        } else {
+            // If the target of the call expression is a function expression or arrow function we have
+            // an immediately invoked function expression (IIFE). Initialize the flowNode property to
+            // the current control flow (which includes evaluation of the IIFE arguments).
+            let expr = skipParentheses(call.callee.to_ast_kind(), None);
+            if matches!(expr, AstKind::Function(_) | AstKind::ArrowFunctionExpression(_)) {
+                let (callee, rest): (Vec<_>, Vec<_>) = node.get_children().into_iter().partition(|child| child.span() == call.callee.span());
+                for child in rest.into_iter().chain(callee) {
+                    self.bind(Some(child));
+                }
+            } else {
+                self.bindEachChild(node);
+                if matches!(call.callee, Expression::Super(_)) {
+                    self.currentFlow = Some(self.createFlowCall(self.currentFlow.clone().unwrap(), call));
+                }
+            }
+        }
+        if let Expression::StaticMemberExpression(propertyAccess) = &call.callee {
+            if self.isNarrowableOperand(&propertyAccess.object) && isPushOrUnshiftIdentifier(&propertyAccess.property) {
+                self.currentFlow = Some(self.createFlowMutation(FlowFlags::ArrayMutation, self.currentFlow.clone().unwrap(), *node));
+            }
+        }
+    }
+    // endregion: 2206
+
+    // region: 2741
+    fn bind(&mut self, node: Option<AstKind<'a>>) {
+        if node.is_none() {
+            return;
+        }
+        let node = node.unwrap();
+        // TEST.with(|t| t.borrow_mut().test(self.parent.clone()));
+        node.set_parent(self.parent.clone());
+        // if tracing { node.tracingPath = self.file.path; }
+        let save_in_strict_mode = self.inStrictMode;
+
+        // Even though in the AST the jsdoc @typedef node belongs to the current node,
+        // its symbol might be in the same scope with the current node's symbol. Consider:
+        //
+        //     /** @typedef {string | number} MyType */
+        //     function foo();
+        //
+        // Here the current node is "foo", which is a container, but the scope of "MyType" should
+        // not be inside "foo". Therefore we always bind @typedef before bind the parent node,
+        // and skip binding this tag later when binding all the other jsdoc tags.
+
+        // First we bind declaration nodes to a symbol if possible. We'll both create a symbol
+        // and then potentially add the symbol to an appropriate symbol table. Possible
+        // destination symbol tables are:
+        //
+        //  1) The 'exports' table of the current container's symbol.
+        //  2) The 'members' table of the current container's symbol.
+        //  3) The 'locals' table of the current container.
+        //
+        // However, not all symbols will end up in any of these tables. 'Anonymous' symbols
+        // (like TypeLiterals for example) will not be put in any table.
+        self.bindWorker(&node);
+
+        // Then we recurse into the children of the node to bind them as well. For certain
+        // symbols we do specialized work when we recurse. For example, we'll keep track of
+        // the current 'container' node when it changes. This helps us know which symbol table
+        // a local should go into for example. Since terminal nodes are known not to have
+        // children, as an optimization we don't process those.
+        // if (node.kind > SyntaxKind.LastToken) {
+        let save_parent = self.parent.clone();
+        self.parent = Some(node.clone());
+        let container_flags = getContainerFlags(&node);
+        if container_flags == ContainerFlags::None {
+            self.bindChildren(&node);
+        } else {
+            self.bindContainer(&node, container_flags);
+        }
+        self.parent = save_parent;
+        // } else {
+        //     if node.kind == SyntaxKind::EndOfFileToken { self.parent = Some(node); }
+        //     self.bindJSDoc(&node);
+        //     self.parent = save_parent;
+        // }
+        self.inStrictMode = save_in_strict_mode;
+    }
+    // endregion: 2794
+
+    // region: 2836
+    fn bindWorker(&mut self, node: &AstKind<'a>) {
+        match node {
+            /* Strict mode checks */
+            // Identifier
+            AstKind::IdentifierName(_) |
+            AstKind::IdentifierReference(_) |
+            AstKind::BindingIdentifier(_) |
+            AstKind::JSXIdentifier(_) |
+            // end Identifier
+                // ! rb skipping jsdoc
+                // for typedef type names with namespaces, bind the new jsdoc type symbol here
+                // because it requires all containing namespaces to be in effect, namely the
+                // current "blockScopeContainer" needs to be set to its immediate namespace parent.
+                // if (node.flags & NodeFlags.IdentifierIsInJSDocNamespace) {
+                //     let parentNode = node.parent;
+                //     while (parentNode && !isJSDocTypeAlias(parentNode)) {
+                //         parentNode = parentNode.parent;
+                //     }
+                //     bindBlockScopedDeclaration(parentNode as Declaration, SymbolFlags.TypeAlias, SymbolFlags.TypeAliasExcludes);
+                //     break;
+                // }
+                // falls through
+            AstKind::ThisExpression(_) => {
                 // TODO: Why use `isExpression` here? both Identifier and ThisKeyword are expressions.
                 // if (currentFlow && (isExpression(node) || parent.kind === SyntaxKind.ShorthandPropertyAssignment)) {
                 node.set_flowNode(self.currentFlow.clone());
                 // }
                 // TODO: a `ThisExpression` is not an Identifier, this cast is unsound
-                // return checkContextualIdentifier(node as Identifier); // todo(RB): checkContextualIdentifier()
+                return self.checkContextualIdentifier(node);
             }
             AstKind::TSQualifiedName(_) => {
                 if self.currentFlow.is_some() && isPartOfTypeQuery(node) {
@@ -1103,7 +2190,7 @@ impl<'a> Binder<'a> {
                 //     bindSpecialPropertyDeclaration(expr);
                 // }
                 if isInJSFile(node) &&
-                    self.file.unwrap().source_type.is_script() && // file.commonJsModuleIndicator &&
+                    self.file.unwrap().commonJsModuleIndicator().is_some() &&
                     isModuleExportsAccessExpression(&expr.to_ast_kind()) &&
                     self.blockScopeContainer.is_some() &&
                     lookupSymbolForName(&self.blockScopeContainer.unwrap().to_ast_kind(), "module").is_none() {
@@ -1111,63 +2198,68 @@ impl<'a> Binder<'a> {
                     self.declareSymbol(self.file.unwrap().locals().unwrap(), /*parent*/ None, expr.object().to_ast_kind(), SymbolFlags::FunctionScopedVariable | SymbolFlags::ModuleExports, SymbolFlags::FunctionScopedVariableExcludes, None, None);
                 }
             }
-            // todo(RB): continue conversion from here
-            AstKind::GeneralBinaryExpression(expr) => {
-                // const specialKind = getAssignmentDeclarationKind(node as BinaryExpression);
-                // switch (specialKind) {
-                //     case AssignmentDeclarationKind.ExportsProperty:
-                //         bindExportsPropertyAssignment(node as BindableStaticPropertyAssignmentExpression);
-                //         break;
-                //     case AssignmentDeclarationKind.ModuleExports:
-                //         bindModuleExportsAssignment(node as BindablePropertyAssignmentExpression);
-                //         break;
-                //     case AssignmentDeclarationKind.PrototypeProperty:
-                //         bindPrototypePropertyAssignment((node as BindableStaticPropertyAssignmentExpression).left, node);
-                //         break;
-                //     case AssignmentDeclarationKind.Prototype:
-                //         bindPrototypeAssignment(node as BindableStaticPropertyAssignmentExpression);
-                //         break;
-                //     case AssignmentDeclarationKind.ThisProperty:
-                //         bindThisPropertyAssignment(node as BindablePropertyAssignmentExpression);
-                //         break;
-                //     case AssignmentDeclarationKind.Property:
-                //         const expression = ((node as BinaryExpression).left as AccessExpression).expression;
-                //         if (isInJSFile(node) && isIdentifier(expression)) {
-                //             const symbol = lookupSymbolForName(blockScopeContainer, expression.escapedText);
-                //             if (isThisInitializedDeclaration(symbol?.valueDeclaration)) {
-                //                 bindThisPropertyAssignment(node as BindablePropertyAssignmentExpression);
-                //                 break;
-                //             }
-                //         }
-                //         bindSpecialPropertyAssignment(node as BindablePropertyAssignmentExpression);
-                //         break;
-                //     case AssignmentDeclarationKind.None:
-                //         // Nothing to do
-                //         break;
-                //     default:
-                //         Debug.fail("Unknown binary expression special property assignment kind");
-                // }
-                // return checkStrictModeBinaryExpression(node as BinaryExpression);
+            // ! rb only an AssignmentExpression can be an assignment declaration, so the other binary expressions are only checked
+            AstKind::GeneralBinaryExpression(_) | AstKind::LogicalExpression(_) | AstKind::PrivateInExpression(_) | AstKind::SequenceExpression(_) => {
+                return self.checkStrictModeBinaryExpression(BinaryExpression::from_ast_kind(node).unwrap());
+            }
+            // ! rb assignment declarations are always AssignmentExpression in oxc, not a general binary expression
+            AstKind::AssignmentExpression(expr) => {
+                let specialKind = getAssignmentDeclarationKind(node);
+                match specialKind {
+                    AssignmentDeclarationKind::ExportsProperty => {
+                        self.bindExportsPropertyAssignment(expr);
+                    }
+                    AssignmentDeclarationKind::ModuleExports => {
+                        self.bindModuleExportsAssignment(expr);
+                    }
+                    AssignmentDeclarationKind::PrototypeProperty => {
+                        self.bindPrototypePropertyAssignment(AccessExpression::from_ast_kind(&expr.left.to_ast_kind()).unwrap(), *node);
+                    }
+                    AssignmentDeclarationKind::Prototype => {
+                        self.bindPrototypeAssignment(expr);
+                    }
+                    AssignmentDeclarationKind::ThisProperty => {
+                        self.bindThisPropertyAssignment(expr);
+                    }
+                    AssignmentDeclarationKind::Property => {
+                        let expression = AccessExpression::from_ast_kind(&expr.left.to_ast_kind()).unwrap().object().to_ast_kind();
+                        let symbol = if isInJSFile(node) { Identifier::from_ast_kind(&expression).and_then(|expression| lookupSymbolForName(&self.blockScopeContainer.unwrap().to_ast_kind(), expression.str_name())) } else { None };
+                        let valueDeclaration = symbol.as_ref().and_then(|symbol| symbol.borrow().valueDeclaration.clone()).map(|declaration| declaration.to_ast_kind());
+                        if isThisInitializedDeclaration(valueDeclaration.as_ref()) {
+                            self.bindThisPropertyAssignment(expr);
+                        } else {
+                            self.bindSpecialPropertyAssignment(expr);
+                        }
+                    }
+                    AssignmentDeclarationKind::None => {
+                        // Nothing to do
+                    }
+                    _ => panic!("Unknown binary expression special property assignment kind"),
+                }
+                return self.checkStrictModeBinaryExpression(BinaryExpression::AssignmentExpression(expr));
             }
             AstKind::CatchClause(catch) => {
-                // return checkStrictModeCatchClause(node as CatchClause);
+                return self.checkStrictModeCatchClause(catch);
             }
-            AstKind::UnaryExpression(expr) => {
-                // if delete expression
-                // return checkStrictModeDeleteExpression(node as DeleteExpression);
+            AstKind::UnaryExpression(expr) if expr.operator == UnaryOperator::Delete => {
+                return self.checkStrictModeDeleteExpression(expr);
+            }
+            // ! rb legacy octal literals are reported by the scanner in TypeScript
+            AstKind::NumericLiteral(literal) => {
+                return self.checkStrictModeNumericLiteral(literal);
             }
             AstKind::UpdateExpression(expr) => {
                 if !expr.prefix {
-                // return checkStrictModePostfixUnaryExpression(node);
-                } else{
-                // return checkStrictModePrefixUnaryExpression(node);
+                    return self.checkStrictModePostfixUnaryExpression(expr);
+                } else {
+                    return self.checkStrictModePrefixUnaryExpression(expr);
                 }
             }
             AstKind::WithStatement(stmt) => {
-                // return checkStrictModeWithStatement(node as WithStatement);
+                return self.checkStrictModeWithStatement(stmt);
             }
             AstKind::LabeledStatement(stmt) => {
-                // return checkStrictModeLabeledStatement(node as LabeledStatement);
+                return self.checkStrictModeLabeledStatement(stmt);
             }
             AstKind::TSThisType(this) => {
                 // seenThisKeyword = true;
@@ -1179,10 +2271,16 @@ impl<'a> Binder<'a> {
                 // return bindTypeParameter(node as TypeParameterDeclaration);
             }
             AstKind::FormalParameter(param) => {
+                // ! rb only the strict mode check of bindParameter
+                if self.inStrictMode && !node.flags().intersects(NodeFlags::Ambient) {
+                    // It is a SyntaxError if the identifier eval or arguments appears within a FormalParameterList of a
+                    // strict mode FunctionDeclaration or FunctionExpression (13.1)
+                    self.checkStrictModeEvalOrArguments(*node, Some(param.pattern.to_ast_kind()));
+                }
                 // return bindParameter(node as ParameterDeclaration);
             }
-            AstKind::VariableDeclarationList(decl) => {
-                // return bindVariableDeclarationOrBindingElement(node as VariableDeclaration);
+            AstKind::VariableDeclarator(decl) => {
+                return self.bindVariableDeclarationOrBindingElement(decl);
             }
             // BindingElement
             AstKind::BindingProperty(_) |
@@ -1216,8 +2314,14 @@ impl<'a> Binder<'a> {
                 // name.
                 // return bindPropertyOrMethodOrAccessor(node as Declaration, SymbolFlags.Method | ((node as MethodDeclaration).questionToken ? SymbolFlags.Optional : SymbolFlags.None), isObjectLiteralMethod(node) ? SymbolFlags.PropertyExcludes : SymbolFlags.MethodExcludes);
             }
-            AstKind::Function(func) => {
-                // return bindFunctionDeclaration(node as FunctionDeclaration);
+            // ! rb methods are a Function under a MethodDefinition or an ObjectProperty in oxc, they are bound as methods
+            AstKind::Function(_) if matches!(node.parent(), Some(AstKind::MethodDefinition(_))) || matches!(node.parent(), Some(AstKind::ObjectProperty(property)) if property.method || property.kind != PropertyKind::Init) => {}
+            AstKind::Function(func) if func.is_declaration() => {
+                return self.bindFunctionDeclaration(func);
+            }
+            // FunctionExpression
+            AstKind::Function(_) => {
+                self.bindFunctionExpression(*node);
             }
             // case SyntaxKind.Constructor:
             //     return declareSymbolAndAddToSymbolTable(node as Declaration, SymbolFlags.Constructor, /*symbolExcludes:*/ SymbolFlags.None);
@@ -1243,8 +2347,8 @@ impl<'a> Binder<'a> {
             AstKind::ObjectExpression(obj) => {
                 // return bindObjectLiteralExpression(node as ObjectLiteralExpression);
             }
-            AstKind::ArrowFunctionExpression(arrow) => {
-                // return bindFunctionExpression(node as FunctionExpression | ArrowFunction);
+            AstKind::ArrowFunctionExpression(_) => {
+                self.bindFunctionExpression(*node);
             }
             AstKind::CallExpression(call) => {
                 // const assignmentKind = getAssignmentDeclarationKind(node as CallExpression);
@@ -1260,14 +2364,14 @@ impl<'a> Binder<'a> {
                 //     default:
                 //         return Debug.fail("Unknown call expression assignment declaration kind");
                 // }
-                // if (isInJSFile(node)) {
-                //     bindCallExpression(node as CallExpression);
-                // }
+                if isInJSFile(node) {
+                    self.bindCallExpression(call);
+                }
             }
             AstKind::Class(class) => {
                 // All classes are automatically in strict mode in ES6.
                 self.inStrictMode = true;
-                // return bindClassLikeDeclaration(node as ClassLikeDeclaration);
+                return self.bindClassLikeDeclaration(class);
             }
             AstKind::TSInterfaceDeclaration(iface) => {
                 // return bindBlockScopedDeclaration(node as Declaration, SymbolFlags.Interface, SymbolFlags.InterfaceExcludes);
@@ -1306,8 +2410,8 @@ impl<'a> Binder<'a> {
                 // return bindExportAssignment(node as ExportAssignment);
             }
             AstKind::SourceFile(source_file) => {
-                // updateStrictModeStatementList((node as SourceFile).statements);
-                // return bindSourceFileIfExternalModule();
+                self.updateStrictModeStatementList(&source_file.directives);
+                return self.bindSourceFileIfExternalModule();
             }
             AstKind::BlockStatement(block) => {
                 // if (!isFunctionLikeOrClassStaticBlockDeclaration(node.parent)) {
@@ -1315,36 +2419,395 @@ impl<'a> Binder<'a> {
                 // }
                 // falls through
             }
+            // ! rb function bodies are a FunctionBody in oxc rather than a Block under a function-like declaration
+            AstKind::FunctionBody(body) => {
+                return self.updateStrictModeStatementList(&body.directives);
+            }
             AstKind::TSModuleBlock(module_block) => {
-                // return updateStrictModeStatementList((node as Block | ModuleBlock).statements);
+                return self.updateStrictModeStatementList(&module_block.directives);
+            }
+            // ! skipping jsdoc
+            // case SyntaxKind.JSDocParameterTag:
+            //     if (node.parent.kind === SyntaxKind.JSDocSignature) {
+            //         return bindParameter(node as JSDocParameterTag);
+            //     }
+            //     if (node.parent.kind !== SyntaxKind.JSDocTypeLiteral) {
+            //         break;
+            //     }
+            //     // falls through
+            // case SyntaxKind.JSDocPropertyTag:
+            //     const propTag = node as JSDocPropertyLikeTag;
+            //     const flags = propTag.isBracketed || propTag.typeExpression && propTag.typeExpression.type.kind === SyntaxKind.JSDocOptionalType ?
+            //         SymbolFlags.Property | SymbolFlags.Optional :
+            //         SymbolFlags.Property;
+            //     return declareSymbolAndAddToSymbolTable(propTag, flags, SymbolFlags.PropertyExcludes);
+            // case SyntaxKind.JSDocTypedefTag:
+            // case SyntaxKind.JSDocCallbackTag:
+            // case SyntaxKind.JSDocEnumTag:
+            //     return (delayedTypeAliases || (delayedTypeAliases = [])).push(node as JSDocTypedefTag | JSDocCallbackTag | JSDocEnumTag);
+            // case SyntaxKind.JSDocOverloadTag:
+            //     return bind((node as JSDocOverloadTag).typeExpression);
+            // case SyntaxKind.JSDocImportTag:
+            //     return (jsDocImports || (jsDocImports = [])).push(node as JSDocImportTag);
+            _ => {}
+        }
+    }
+    // endregion: 2082
+
+    // region: 2209
+    fn addToContainerChain(&mut self, next: HasLocals<'a>) {
+        if let Some(lastContainer) = self.lastContainer {
+            lastContainer.set_nextContainer(Some(next));
+        }
+
+        self.lastContainer = Some(next);
+    }
+
+    fn declareSymbolAndAddToSymbolTable(&mut self, node: AstKind<'a>, symbolFlags: SymbolFlags, symbolExcludes: SymbolFlags) -> opt_rc_cell!(Symbol<'a>) {
+        let container = self.container.unwrap().to_ast_kind();
+        match container {
+            // Modules, source files, and classes need specialized handling for how their
+            // members are declared (for example, a member of a class will go into a specific
+            // symbol table depending on if it is static or not). We defer to specialized
+            // handlers to take care of declaring these child members.
+            AstKind::TSModuleDeclaration(_) => Some(self.declareModuleMember(node, symbolFlags, symbolExcludes)),
+            AstKind::SourceFile(_) => Some(self.declareSourceFileMember(node, symbolFlags, symbolExcludes)),
+            // ClassExpression, ClassDeclaration
+            AstKind::Class(_) => Some(self.declareClassMember(node, symbolFlags, symbolExcludes)),
+            AstKind::TSEnumDeclaration(_) => {
+                let containerSymbol = container.symbol()?;
+                let exports = containerSymbol.borrow().exports.clone().unwrap();
+                Some(self.declareSymbol(exports, Some(containerSymbol), node, symbolFlags, symbolExcludes, None, None))
+            }
+            AstKind::TSTypeLiteral(_) |
+            // case SyntaxKind.JSDocTypeLiteral:
+            AstKind::ObjectExpression(_) |
+            AstKind::TSInterfaceDeclaration(_) |
+            // JsxAttributes
+            AstKind::JSXAttribute(_) => {
+                // Interface/Object-types always have their children added to the 'members' of
+                // their container. They are only accessible through an instance of their
+                // container, and are never in scope otherwise (even inside the body of the
+                // object / type / interface declaring them). An exception is type parameters,
+                // which are in scope without qualification (similar to 'locals').
+                let containerSymbol = container.symbol()?;
+                let members = containerSymbol.borrow().members.clone().unwrap();
+                Some(self.declareSymbol(members, Some(containerSymbol), node, symbolFlags, symbolExcludes, None, None))
+            }
+            AstKind::TSFunctionType(_) |
+            AstKind::TSConstructorType(_) |
+            AstKind::TSCallSignatureDeclaration(_) |
+            AstKind::TSConstructSignatureDeclaration(_) |
+            // case SyntaxKind.JSDocSignature:
+            AstKind::TSIndexSignature(_) |
+            AstKind::TSMethodSignature(_) |
+            AstKind::MethodDefinition(_) |
+            AstKind::Function(_) |
+            AstKind::ArrowFunctionExpression(_) |
+            // case SyntaxKind.JSDocFunctionType:
+            AstKind::StaticBlock(_) |
+            AstKind::TSTypeAliasDeclaration(_) |
+            AstKind::TSMappedType(_) => {
+                // All the children of these container types are never visible through another
+                // symbol (i.e. through another symbol's 'exports' or 'members').  Instead,
+                // they're only accessed 'lexically' (i.e. from code that exists underneath
+                // their container in the tree). To accomplish this, we simply add their declared
+                // symbol to the 'locals' of the container.  These symbols can then be found as
+                // the type checker walks up the containers, checking them for matching names.
+                let locals = HasLocals::from_ast_kind(&container).unwrap().locals().unwrap();
+                Some(self.declareSymbol(locals, /*parent*/ None, node, symbolFlags, symbolExcludes, None, None))
+            }
+            _ => None,
+        }
+    }
+
+    fn declareClassMember(&mut self, node: AstKind<'a>, symbolFlags: SymbolFlags, symbolExcludes: SymbolFlags) -> rc_cell!(Symbol<'a>) {
+        let containerSymbol = self.container.unwrap().to_ast_kind().symbol().unwrap();
+        let symbolTable = if isStatic(&node) { containerSymbol.borrow().exports.clone().unwrap() } else { containerSymbol.borrow().members.clone().unwrap() };
+        self.declareSymbol(symbolTable, Some(containerSymbol), node, symbolFlags, symbolExcludes, None, None)
+    }
+
+    fn declareSourceFileMember(&mut self, node: AstKind<'a>, symbolFlags: SymbolFlags, symbolExcludes: SymbolFlags) -> rc_cell!(Symbol<'a>) {
+        if isExternalModule(self.file.unwrap()) {
+            self.declareModuleMember(node, symbolFlags, symbolExcludes)
+        } else {
+            self.declareSymbol(self.file.unwrap().locals().unwrap(), /*parent*/ None, node, symbolFlags, symbolExcludes, None, None)
+        }
+    }
+    // endregion: 2284
+
+    // region: 2378
+    // ! rb prologue directives are not part of the statement list in oxc, so the directives are passed instead
+    fn updateStrictModeStatementList(&mut self, statements: &[Directive<'a>]) {
+        if !self.inStrictMode {
+            for statement in statements {
+                // if (!isPrologueDirective(statement)) {
+                //     return;
+                // }
+
+                if self.isUseStrictPrologueDirective(statement) {
+                    self.inStrictMode = true;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Should be called only on prologue directives (isPrologueDirective(node) should be true)
+    fn isUseStrictPrologueDirective(&self, node: &Directive<'a>) -> bool {
+        // ! rb the directive is the raw text between the quotes
+        let nodeText = node.directive.as_str();
+
+        // Note: the node text must be exactly "use strict" or 'use strict'.  It is not ok for the
+        // string to contain unicode escapes (as per ES5).
+        nodeText == "use strict"
+    }
+    // endregion: 2397
+
+    // region: 2454
+    fn bindAnonymousDeclaration(&mut self, node: AstKind<'a>, symbolFlags: SymbolFlags, name: __String) -> rc_cell!(Symbol<'a>) {
+        let symbol = self.createSymbol(symbolFlags, name);
+        if symbolFlags.intersects(SymbolFlags::EnumMember | SymbolFlags::ClassMember) {
+            symbol.borrow_mut().parent = self.container.unwrap().to_ast_kind().symbol();
+        }
+        self.addDeclarationToSymbol(symbol.clone(), AstKindDeclaration::from_ast_kind(&node).unwrap(), symbolFlags);
+        symbol
+    }
+
+    fn bindBlockScopedDeclaration(&mut self, node: AstKind<'a>, symbolFlags: SymbolFlags, symbolExcludes: SymbolFlags) {
+        match self.blockScopeContainer.unwrap().to_ast_kind() {
+            AstKind::TSModuleDeclaration(_) => {
+                self.declareModuleMember(node, symbolFlags, symbolExcludes);
+            }
+            AstKind::SourceFile(file) if isExternalOrCommonJsModule(file) => {
+                self.declareModuleMember(node, symbolFlags, symbolExcludes);
+            }
+            blockScopeContainer => {
+                let blockScopeContainer = HasLocals::from_ast_kind(&blockScopeContainer).unwrap();
+                if blockScopeContainer.locals().is_none() {
+                    blockScopeContainer.set_locals(Some(new_rc_cell!(createSymbolTable(None))));
+                    self.addToContainerChain(blockScopeContainer);
+                }
+                self.declareSymbol(blockScopeContainer.locals().unwrap(), /*parent*/ None, node, symbolFlags, symbolExcludes, None, None);
+            }
+        }
+    }
+    // endregion: 2498
+
+    // region: 2500
+    // The binder visits every node in the syntax tree so it is a convenient place to perform a single localized
+    // check for reserved words used as identifiers in strict mode code, as well as `yield` or `await` in
+    // [Yield] or [Await] contexts, respectively.
+    fn checkContextualIdentifier(&mut self, node: &AstKind<'a>) {
+        let file = self.file.unwrap();
+        // Report error only if there are no parse errors in file
+        if file.parseDiagnostics().borrow().is_empty() &&
+            !node.flags().intersects(NodeFlags::Ambient) &&
+            !node.flags().intersects(NodeFlags::JSDoc) &&
+            // ! rb names in property positions are IdentifierName in oxc
+            !matches!(node, AstKind::IdentifierName(_)) {
+            let Some(identifier) = Identifier::from_ast_kind(node) else {
+                return;
+            };
+            // strict mode identifiers
+            // const originalKeywordKind = identifierToKeywordKind(node);
+            // if (originalKeywordKind === undefined) {
+            //     return;
+            // }
+
+            if self.inStrictMode && rb_is_future_reserved_word(identifier.str_name()) {
+                let diagnostic = self.createDiagnosticForNode(node, self.getStrictModeIdentifierMessage(node), vec![StringOrNumber::String(declarationNameToString(DeclarationNameOrQualifiedName::from_ast_kind(node)))]);
+                file.bindDiagnostics().borrow_mut().push(diagnostic);
+            } else if identifier.str_name() == "await" {
+                if isExternalModule(file) && isInTopLevelContext(node) {
+                    let diagnostic = self.createDiagnosticForNode(node, Diagnostics::Identifier_expected_0_is_a_reserved_word_at_the_top_level_of_a_module(), vec![StringOrNumber::String(declarationNameToString(DeclarationNameOrQualifiedName::from_ast_kind(node)))]);
+                    file.bindDiagnostics().borrow_mut().push(diagnostic);
+                } else if node.flags().intersects(NodeFlags::AwaitContext) {
+                    let diagnostic = self.createDiagnosticForNode(node, Diagnostics::Identifier_expected_0_is_a_reserved_word_that_cannot_be_used_here(), vec![StringOrNumber::String(declarationNameToString(DeclarationNameOrQualifiedName::from_ast_kind(node)))]);
+                    file.bindDiagnostics().borrow_mut().push(diagnostic);
+                }
+            } else if identifier.str_name() == "yield" && node.flags().intersects(NodeFlags::YieldContext) {
+                let diagnostic = self.createDiagnosticForNode(node, Diagnostics::Identifier_expected_0_is_a_reserved_word_that_cannot_be_used_here(), vec![StringOrNumber::String(declarationNameToString(DeclarationNameOrQualifiedName::from_ast_kind(node)))]);
+                file.bindDiagnostics().borrow_mut().push(diagnostic);
+            }
+        }
+    }
+
+    fn getStrictModeIdentifierMessage(&self, node: &AstKind<'a>) -> DiagnosticMessage {
+        // Provide specialized messages to help the user understand why we think they're in
+        // strict mode.
+        if getContainingClass(*node).is_some() {
+            return Diagnostics::Identifier_expected_0_is_a_reserved_word_in_strict_mode_Class_definitions_are_automatically_in_strict_mode();
+        }
+
+        if self.file.unwrap().externalModuleIndicator() {
+            return Diagnostics::Identifier_expected_0_is_a_reserved_word_in_strict_mode_Modules_are_automatically_in_strict_mode();
+        }
+
+        Diagnostics::Identifier_expected_0_is_a_reserved_word_in_strict_mode()
+    }
+
+    fn checkStrictModeBinaryExpression(&mut self, node: BinaryExpression<'a>) {
+        // ! rb only an AssignmentExpression has an assignment operator in oxc, and its left side is an assignment target
+        // rather than an expression
+        let BinaryExpression::AssignmentExpression(expr) = node else {
+            return;
+        };
+        if self.inStrictMode && isLeftHandSideExpression(&expr.left.to_ast_kind()) && isAssignmentOperator(node.operator()) {
+            // ECMA 262 (Annex C) The identifier eval or arguments may not appear as the LeftHandSideExpression of an
+            // Assignment operator(11.13) or of a PostfixExpression(11.3)
+            self.checkStrictModeEvalOrArguments(expr.to_ast_kind(), Some(expr.left.to_ast_kind()));
+        }
+    }
+
+    fn checkStrictModeCatchClause(&mut self, node: &'a CatchClause<'a>) {
+        // It is a SyntaxError if a TryStatement with a Catch occurs within strict code and the Identifier of the
+        // Catch production is eval or arguments
+        if self.inStrictMode {
+            if let Some(param) = &node.param {
+                self.checkStrictModeEvalOrArguments(node.to_ast_kind(), Some(param.pattern.to_ast_kind()));
+            }
+        }
+    }
+
+    fn checkStrictModeDeleteExpression(&mut self, node: &'a UnaryExpression<'a>) {
+        // Grammar checking
+        if self.inStrictMode && isIdentifier(&node.argument.to_ast_kind()) {
+            // When a delete operator occurs within strict mode code, a SyntaxError is thrown if its
+            // UnaryExpression is a direct reference to a variable, function argument, or function name
+            let file = self.file.unwrap();
+            let span = getErrorSpanForNode(file, &node.argument.to_ast_kind());
+            file.bindDiagnostics().borrow_mut().push(createFileDiagnostic(file, span.start, span.length, Diagnostics::delete_cannot_be_called_on_an_identifier_in_strict_mode(), vec![]));
+        }
+    }
+
+    fn isEvalOrArgumentsIdentifier(&self, node: &AstKind<'a>) -> bool { Identifier::from_ast_kind(node).is_some_and(|identifier| matches!(identifier.str_name(), "eval" | "arguments")) }
+
+    fn checkStrictModeEvalOrArguments(&mut self, contextNode: AstKind<'a>, name: Option<AstKind<'a>>) {
+        if let Some(name) = name {
+            if let Some(identifier) = Identifier::from_ast_kind(&name) {
+                if self.isEvalOrArgumentsIdentifier(&name) {
+                    // We check first if the name is inside class declaration or class expression; if so give explicit message
+                    // otherwise report generic error message.
+                    let file = self.file.unwrap();
+                    let span = getErrorSpanForNode(file, &name);
+                    let diagnostic = createFileDiagnostic(file, span.start, span.length, self.getStrictModeEvalOrArgumentsMessage(&contextNode), vec![StringOrNumber::String(identifier.str_name().to_string())]);
+                    file.bindDiagnostics().borrow_mut().push(diagnostic);
+                }
+            }
+        }
+    }
+
+    fn getStrictModeEvalOrArgumentsMessage(&self, node: &AstKind<'a>) -> DiagnosticMessage {
+        // Provide specialized messages to help the user understand why we think they're in
+        // strict mode.
+        if getContainingClass(*node).is_some() {
+            return Diagnostics::Code_contained_in_a_class_is_evaluated_in_JavaScript_s_strict_mode_which_does_not_allow_this_use_of_0_For_more_information_see_https_colon_slash_slashdeveloper_mozilla_org_slashen_US_slashdocs_slashWeb_slashJavaScript_slashReference_slashStrict_mode();
+        }
+
+        if self.file.unwrap().externalModuleIndicator() {
+            return Diagnostics::Invalid_use_of_0_Modules_are_automatically_in_strict_mode();
+        }
+
+        Diagnostics::Invalid_use_of_0_in_strict_mode()
+    }
+
+    fn checkStrictModeFunctionName(&mut self, node: AstKind<'a>) {
+        if self.inStrictMode && !node.flags().intersects(NodeFlags::Ambient) {
+            // It is a SyntaxError if the identifier eval or arguments appears within a FormalParameterList of a
+            // strict mode FunctionDeclaration or FunctionExpression (13.1)
+            let name = match node {
+                AstKind::Function(func) => func.id.as_ref().map(|id| id.to_ast_kind()),
+                _ => None,
+            };
+            self.checkStrictModeEvalOrArguments(node, name);
+        }
+    }
+
+    fn getStrictModeBlockScopeFunctionDeclarationMessage(&self, node: &AstKind<'a>) -> DiagnosticMessage {
+        // Provide specialized messages to help the user understand why we think they're in
+        // strict mode.
+        if getContainingClass(*node).is_some() {
+            return Diagnostics::Function_declarations_are_not_allowed_inside_blocks_in_strict_mode_when_targeting_ES5_Class_definitions_are_automatically_in_strict_mode();
+        }
+
+        if self.file.unwrap().externalModuleIndicator() {
+            return Diagnostics::Function_declarations_are_not_allowed_inside_blocks_in_strict_mode_when_targeting_ES5_Modules_are_automatically_in_strict_mode();
+        }
+
+        Diagnostics::Function_declarations_are_not_allowed_inside_blocks_in_strict_mode_when_targeting_ES5()
+    }
+
+    fn checkStrictModeFunctionDeclaration(&mut self, node: &'a Function<'a>) {
+        if self.languageVersion.unwrap() < ScriptTarget::ES2015 {
+            // Report error if function is not top level function declaration
+            let blockScopeContainer = self.blockScopeContainer.unwrap().to_ast_kind();
+            if !matches!(blockScopeContainer, AstKind::SourceFile(_) | AstKind::TSModuleDeclaration(_)) &&
+                // !isFunctionLikeOrClassStaticBlockDeclaration(blockScopeContainer)
+                !(isFunctionLike(Some(&blockScopeContainer)) || matches!(blockScopeContainer, AstKind::StaticBlock(_))) {
+                // We check first if the name is inside class declaration or class expression; if so give explicit message
+                // otherwise report generic error message.
+                let file = self.file.unwrap();
+                let errorSpan = getErrorSpanForNode(file, &node.to_ast_kind());
+                let diagnostic = createFileDiagnostic(file, errorSpan.start, errorSpan.length, self.getStrictModeBlockScopeFunctionDeclarationMessage(&node.to_ast_kind()), vec![]);
+                file.bindDiagnostics().borrow_mut().push(diagnostic);
+            }
+        }
+    }
+
+    // ! rb TypeScript reports legacy octal literals in the scanner, oxc accepts them so strict mode code reports them here
+    fn checkStrictModeNumericLiteral(&mut self, node: &'a NumericLiteral<'a>) {
+        if self.inStrictMode && rb_is_legacy_octal_literal(node) {
+            let literal = format!("0o{:o}", node.value as u64);
+            let diagnostic = self.createDiagnosticForNode(&node.to_ast_kind(), Diagnostics::Octal_literals_are_not_allowed_Use_the_syntax_0(), vec![StringOrNumber::String(literal)]);
+            self.file.unwrap().bindDiagnostics().borrow_mut().push(diagnostic);
+        }
+    }
+
+    fn checkStrictModePostfixUnaryExpression(&mut self, node: &'a UpdateExpression<'a>) {
+        // Grammar checking
+        // The identifier eval or arguments may not appear as the LeftHandSideExpression of an
+        // Assignment operator(11.13) or of a PostfixExpression(11.3) or as the UnaryExpression
+        // operated upon by a Prefix Increment(11.4.4) or a Prefix Decrement(11.4.5) operator.
+        if self.inStrictMode {
+            self.checkStrictModeEvalOrArguments(node.to_ast_kind(), Some(node.argument.to_ast_kind()));
+        }
+    }
+
+    fn checkStrictModePrefixUnaryExpression(&mut self, node: &'a UpdateExpression<'a>) {
+        // Grammar checking
+        if self.inStrictMode {
+            // ! rb UpdateExpression is always ++ or --
+            // if (node.operator === SyntaxKind.PlusPlusToken || node.operator === SyntaxKind.MinusMinusToken) {
+            self.checkStrictModeEvalOrArguments(node.to_ast_kind(), Some(node.argument.to_ast_kind()));
+            // }
+        }
+    }
+
+    fn checkStrictModeWithStatement(&mut self, node: &'a WithStatement<'a>) {
+        // Grammar checking for withStatement
+        if self.inStrictMode {
+            self.errorOnFirstToken(&node.to_ast_kind(), Diagnostics::with_statements_are_not_allowed_in_strict_mode(), vec![]);
+        }
+    }
+
+    fn checkStrictModeLabeledStatement(&mut self, node: &'a LabeledStatement<'a>) {
+        // Grammar checking for labeledStatement
+        if self.inStrictMode && getEmitScriptTarget(self.options.unwrap()) >= ScriptTarget::ES2015 {
+            let statement = node.body.to_ast_kind();
+            // isDeclarationStatement(node.statement) || isVariableStatement(node.statement)
+            if DeclarationStatement::from_ast_kind(&statement).is_some() || matches!(statement, AstKind::VariableDeclarationList(_)) {
+                self.errorOnFirstToken(&AstKind::LabelIdentifier(&node.label), Diagnostics::A_label_is_not_allowed_here(), vec![]);
             }
-            // ! skipping jsdoc
-            // case SyntaxKind.JSDocParameterTag:
-            //     if (node.parent.kind === SyntaxKind.JSDocSignature) {
-            //         return bindParameter(node as JSDocParameterTag);
-            //     }
-            //     if (node.parent.kind !== SyntaxKind.JSDocTypeLiteral) {
-            //         break;
-            //     }
-            //     // falls through
-            // case SyntaxKind.JSDocPropertyTag:
-            //     const propTag = node as JSDocPropertyLikeTag;
-            //     const flags = propTag.isBracketed || propTag.typeExpression && propTag.typeExpression.type.kind === SyntaxKind.JSDocOptionalType ?
-            //         SymbolFlags.Property | SymbolFlags.Optional :
-            //         SymbolFlags.Property;
-            //     return declareSymbolAndAddToSymbolTable(propTag, flags, SymbolFlags.PropertyExcludes);
-            // case SyntaxKind.JSDocTypedefTag:
-            // case SyntaxKind.JSDocCallbackTag:
-            // case SyntaxKind.JSDocEnumTag:
-            //     return (delayedTypeAliases || (delayedTypeAliases = [])).push(node as JSDocTypedefTag | JSDocCallbackTag | JSDocEnumTag);
-            // case SyntaxKind.JSDocOverloadTag:
-            //     return bind((node as JSDocOverloadTag).typeExpression);
-            // case SyntaxKind.JSDocImportTag:
-            //     return (jsDocImports || (jsDocImports = [])).push(node as JSDocImportTag);
-            _ => {}
         }
     }
-    // endregion: 2082
+
+    fn errorOnFirstToken(&self, node: &AstKind<'a>, message: DiagnosticMessage, args: DiagnosticArguments) {
+        let file = self.file.unwrap();
+        let span = getSpanOfTokenAtPosition(file, node.span().start);
+        file.bindDiagnostics().borrow_mut().push(createFileDiagnostic(file, span.start, span.length, message, args));
+    }
+    // endregion: 2577
 
     // region: 2579
     // The binder visits every node, so this is a good place to check for
@@ -1362,7 +2825,789 @@ impl<'a> Binder<'a> {
         }
     }
     // endregion: 2588
+
+    // region: 2615
+    fn errorOrSuggestionOnNode(&self, isError: bool, node: &AstKind<'a>, message: DiagnosticMessage) { self.errorOrSuggestionOnRange(isError, node, node, message); }
+
+    fn errorOrSuggestionOnRange(&self, isError: bool, startNode: &AstKind<'a>, endNode: &AstKind<'a>, message: DiagnosticMessage) {
+        // ! rb oxc spans start at the token, there is no leading trivia to skip
+        let pos = startNode.span().start;
+        self.addErrorOrSuggestionDiagnostic(isError, pos, endNode.span().end, message);
+    }
+
+    fn addErrorOrSuggestionDiagnostic(&self, isError: bool, pos: u32, end: u32, message: DiagnosticMessage) {
+        let file = self.file.unwrap();
+        let mut diag = createFileDiagnostic(file, pos, end - pos, message, vec![]);
+        if isError {
+            file.bindDiagnostics().borrow_mut().push(diag);
+        } else {
+            diag.category = DiagnosticCategory::Suggestion;
+            file.bindSuggestionDiagnostics().borrow_mut().push(diag);
+        }
+    }
+    // endregion: 2632
+
+    // region: 3070
+    fn bindSourceFileIfExternalModule(&mut self) {
+        let file = self.file.unwrap();
+        // setExportContextFlag(file);
+        if isExternalModule(file) {
+            self.bindSourceFileAsExternalModule();
+        } else if isJsonSourceFile(file) {
+            self.bindSourceFileAsExternalModule();
+            // Create symbol equivalent for the module.exports = {}
+            let originalSymbol = file.to_ast_kind().symbol();
+            let exports = originalSymbol.as_ref().unwrap().borrow().exports.clone().unwrap();
+            self.declareSymbol(exports, originalSymbol.clone(), file.to_ast_kind(), SymbolFlags::Property, SymbolFlags::All, None, None);
+            file.to_ast_kind().set_symbol(originalSymbol);
+        }
+    }
+
+    fn bindSourceFileAsExternalModule(&mut self) {
+        let file = self.file.unwrap();
+        self.bindAnonymousDeclaration(file.to_ast_kind(), SymbolFlags::ValueModule, format!("\"{}\"", removeFileExtension(&file.filepath().to_string_lossy())));
+    }
+    // endregion: 3085
+
+    // region: 3203
+    fn setCommonJsModuleIndicator(&mut self, node: AstKind<'a>) -> bool {
+        let file = self.file.unwrap();
+        // ! rb externalModuleIndicator is a bool here, TypeScript only bails out when it is a node (an import or
+        // export), not when it is `true` because the file was forced to be a module
+        if file.externalModuleIndicator() && isFileProbablyExternalModule(file) {
+            return false;
+        }
+        if file.commonJsModuleIndicator().is_none() {
+            file.set_commonJsModuleIndicator(Some(node));
+            if !file.externalModuleIndicator() {
+                self.bindSourceFileAsExternalModule();
+            }
+        }
+        true
+    }
+    // endregion: 3214
+
+    // region: 3228
+    fn bindExportsPropertyAssignment(&mut self, node: &'a AssignmentExpression<'a>) {
+        // When we create a property via 'exports.foo = bar', the 'exports.foo' property access
+        // expression is the declaration
+        if !self.setCommonJsModuleIndicator(node.to_ast_kind()) {
+            return;
+        }
+        let left = AccessExpression::from_ast_kind(&node.left.to_ast_kind()).unwrap();
+        let symbol = self.forEachIdentifierInEntityName(left.object().to_ast_kind(), /*parent*/ None, &mut |this, id, symbol, _| {
+            if let Some(symbol) = &symbol {
+                this.addDeclarationToSymbol(symbol.clone(), AstKindDeclaration::from_ast_kind(&id).unwrap(), SymbolFlags::Module | SymbolFlags::Assignment);
+            }
+            symbol
+        });
+        if let Some(symbol) = symbol {
+            let isAlias = isAliasableExpression(&node.right.to_ast_kind()) && (isExportsIdentifier(&left.object().to_ast_kind()) || isModuleExportsAccessExpression(&left.object().to_ast_kind()));
+            let flags = if isAlias { SymbolFlags::Alias } else { SymbolFlags::Property | SymbolFlags::ExportValue };
+            node.left.to_ast_kind().set_parent(Some(node.to_ast_kind()));
+            let exports = symbol.borrow().exports.clone().unwrap();
+            self.declareSymbol(exports, Some(symbol), node.left.to_ast_kind(), flags, SymbolFlags::None, None, None);
+        }
+    }
+
+    fn bindModuleExportsAssignment(&mut self, node: &'a AssignmentExpression<'a>) {
+        // A common practice in node modules is to set 'export = module.exports = {}', this ensures that 'exports'
+        // is still pointing to 'module.exports'.
+        // We do not want to consider this as 'export=' since a module can have only one of these.
+        // Similarly we do not want to treat 'module.exports = exports' as an 'export='.
+        if !self.setCommonJsModuleIndicator(node.to_ast_kind()) {
+            return;
+        }
+        let file = self.file.unwrap();
+        let assignedExpression = getRightMostAssignedExpression(node.right.to_ast_kind());
+        if isEmptyObjectLiteral(&assignedExpression) || matches!(self.container.unwrap().to_ast_kind(), AstKind::SourceFile(_)) && isExportsOrModuleExportsOrAlias(file, &assignedExpression) {
+            return;
+        }
+
+        if let AstKind::ObjectExpression(object) = assignedExpression {
+            if object.properties.iter().all(|property| matches!(property, ObjectPropertyKind::ObjectProperty(property) if property.shorthand)) {
+                for property in &object.properties {
+                    if let ObjectPropertyKind::ObjectProperty(property) = property {
+                        self.bindExportAssignedObjectMemberAlias(property);
+                    }
+                }
+                return;
+            }
+        }
+
+        // 'module.exports = expr' assignment
+        let flags = if exportAssignmentIsAlias(&node.to_ast_kind()) {
+            SymbolFlags::Alias // An export= with an EntityNameExpression or a ClassExpression exports all meanings of that identifier or class
+        } else {
+            SymbolFlags::Property | SymbolFlags::ExportValue | SymbolFlags::ValueModule
+        };
+        let fileSymbol = file.to_ast_kind().symbol().unwrap();
+        let exports = fileSymbol.borrow().exports.clone().unwrap();
+        let symbol = self.declareSymbol(exports, Some(fileSymbol), node.to_ast_kind(), flags | SymbolFlags::Assignment, SymbolFlags::None, None, None);
+        setValueDeclaration(symbol, AstKindDeclaration::AssignmentExpression(node));
+    }
+
+    fn bindExportAssignedObjectMemberAlias(&mut self, node: &'a ObjectProperty<'a>) {
+        let fileSymbol = self.file.unwrap().to_ast_kind().symbol().unwrap();
+        let exports = fileSymbol.borrow().exports.clone().unwrap();
+        self.declareSymbol(exports, Some(fileSymbol), node.to_ast_kind(), SymbolFlags::Alias | SymbolFlags::Assignment, SymbolFlags::None, None, None);
+    }
+    // endregion: 3276
+
+    // region: 3278
+    fn bindThisPropertyAssignment(&mut self, node: &'a AssignmentExpression<'a>) {
+        debug_assert!(isInJSFile(&node.to_ast_kind()));
+        // private identifiers *must* be declared (even in JS files)
+        let hasPrivateIdentifier = matches!(node.left, AssignmentTarget::PrivateFieldExpression(_));
+        if hasPrivateIdentifier {
+            return;
+        }
+        let thisContainer = getThisContainer(&node.to_ast_kind(), /*includeArrowFunctions*/ false, /*includeClassComputedPropertyName*/ false);
+        match thisContainer {
+            // ! rb object literal methods are functions under an ObjectProperty in oxc, and object literals are not bound yet
+            AstKind::Function(_) if matches!(thisContainer.parent(), Some(AstKind::ObjectProperty(property)) if property.method || property.kind != PropertyKind::Init) => {}
+            // FunctionDeclaration, FunctionExpression
+            AstKind::Function(_) => {
+                let mut constructorSymbol = thisContainer.symbol();
+                // For `f.prototype.m = function() { this.x = 0; }`, `this.x = 0` should modify `f`'s members, not the function expression.
+                if let Some(AstKind::AssignmentExpression(parent)) = thisContainer.parent() {
+                    if parent.operator == AssignmentOperator::Assign {
+                        let l = parent.left.to_ast_kind();
+                        if isBindableStaticAccessExpression(&l, None) && isPrototypeAccess(&AccessExpression::from_ast_kind(&l).unwrap().object().to_ast_kind()) {
+                            let classPrototype = AccessExpression::from_ast_kind(&AccessExpression::from_ast_kind(&l).unwrap().object().to_ast_kind()).unwrap();
+                            constructorSymbol = self.lookupSymbolForPropertyAccess(&classPrototype.object().to_ast_kind(), self.thisParentContainer.map(|container| container.to_ast_kind()));
+                        }
+                    }
+                }
+
+                if let Some(constructorSymbol) = constructorSymbol {
+                    let valueDeclaration = constructorSymbol.borrow().valueDeclaration.clone();
+                    let Some(valueDeclaration) = valueDeclaration else {
+                        return;
+                    };
+                    // Declare a 'member' if the container is an ES5 class or ES6 constructor
+                    if constructorSymbol.borrow().members.is_none() {
+                        constructorSymbol.borrow_mut().members = Some(new_rc_cell!(createSymbolTable(None)));
+                    }
+                    let members = constructorSymbol.borrow().members.clone().unwrap();
+                    // It's acceptable for multiple 'this' assignments of the same identifier to occur
+                    if hasDynamicName(&node.to_ast_kind()) {
+                        self.bindDynamicallyNamedThisPropertyAssignment(node.to_ast_kind(), constructorSymbol.clone(), members);
+                    } else {
+                        self.declareSymbol(members, Some(constructorSymbol.clone()), node.to_ast_kind(), SymbolFlags::Property | SymbolFlags::Assignment, SymbolFlags::PropertyExcludes & !SymbolFlags::Property, None, None);
+                    }
+                    self.addDeclarationToSymbol(constructorSymbol, valueDeclaration, SymbolFlags::Class);
+                }
+            }
+            // Constructor, PropertyDeclaration, MethodDeclaration, GetAccessor, SetAccessor
+            AstKind::MethodDefinition(_) |
+            AstKind::PropertyDefinition(_) |
+            AstKind::AccessorProperty(_) |
+            AstKind::StaticBlock(_) => {
+                // this.foo assignment in a JavaScript class
+                // Bind this property to the containing class
+                let containingClass = getContainingClass(thisContainer).unwrap();
+                let classSymbol = containingClass.to_ast_kind().symbol().unwrap();
+                let symbolTable = if isStatic(&thisContainer) { classSymbol.borrow().exports.clone().unwrap() } else { classSymbol.borrow().members.clone().unwrap() };
+                if hasDynamicName(&node.to_ast_kind()) {
+                    self.bindDynamicallyNamedThisPropertyAssignment(node.to_ast_kind(), classSymbol, symbolTable);
+                } else {
+                    self.declareSymbol(symbolTable, Some(classSymbol), node.to_ast_kind(), SymbolFlags::Property, SymbolFlags::None, /*isReplaceableByMethod*/ Some(true), None);
+                }
+            }
+            AstKind::SourceFile(file) => {
+                // this.property = assignment in a source file -- declare symbol in exports for a module, in locals for a script
+                if hasDynamicName(&node.to_ast_kind()) {
+                    return;
+                } else if file.commonJsModuleIndicator().is_some() {
+                    let fileSymbol = thisContainer.symbol().unwrap();
+                    let exports = fileSymbol.borrow().exports.clone().unwrap();
+                    self.declareSymbol(exports, Some(fileSymbol), node.to_ast_kind(), SymbolFlags::Property | SymbolFlags::ExportValue, SymbolFlags::None, None, None);
+                } else {
+                    self.declareSymbolAndAddToSymbolTable(node.to_ast_kind(), SymbolFlags::FunctionScopedVariable, SymbolFlags::FunctionScopedVariableExcludes);
+                }
+            }
+            // Namespaces are not allowed in javascript files, so do nothing here
+            AstKind::TSModuleDeclaration(_) => {}
+            _ => panic!("Unexpected this container {:?}", thisContainer),
+        }
+    }
+
+    fn bindDynamicallyNamedThisPropertyAssignment(&mut self, node: AstKind<'a>, symbol: rc_cell!(Symbol<'a>), symbolTable: rc_cell!(SymbolTable<'a>)) {
+        self.declareSymbol(symbolTable, Some(symbol.clone()), node, SymbolFlags::Property, SymbolFlags::None, /*isReplaceableByMethod*/ Some(true), /*isComputedName*/ Some(true));
+        self.addLateBoundAssignmentDeclarationToSymbol(node, Some(symbol));
+    }
+
+    fn addLateBoundAssignmentDeclarationToSymbol(&self, node: AstKind<'a>, symbol: opt_rc_cell!(Symbol<'a>)) {
+        if let Some(symbol) = symbol {
+            symbol.borrow_mut().assignmentDeclarationMembers.get_or_insert_with(HashMap::new).insert(node.get_node_id(), AstKindDeclaration::from_ast_kind(&node).unwrap());
+        }
+    }
+    // endregion: 3360
+
+    // region: 3372
+    fn bindPrototypeAssignment(&mut self, node: &'a AssignmentExpression<'a>) {
+        node.left.to_ast_kind().set_parent(Some(node.to_ast_kind()));
+        node.right.to_ast_kind().set_parent(Some(node.to_ast_kind()));
+        let left = AccessExpression::from_ast_kind(&node.left.to_ast_kind()).unwrap();
+        self.bindPropertyAssignment(left.object().to_ast_kind(), left, /*isPrototypeProperty*/ false, /*containerIsClass*/ true);
+    }
+
+    // fn bindObjectDefinePrototypeProperty(&mut self, node: BindableObjectDefinePropertyCall) { ... }
+
+    /**
+     * For `x.prototype.y = z`, declare a member `y` on `x` if `x` is a function or class, or not declared.
+     * Note that jsdoc preceding an ExpressionStatement like `x.prototype.y;` is also treated as a declaration.
+     */
+    fn bindPrototypePropertyAssignment(&mut self, lhs: AccessExpression<'a>, parent: AstKind<'a>) {
+        // Look up the function in the local scope, since prototype assignments should
+        // follow the function declaration
+        let classPrototype = AccessExpression::from_ast_kind(&lhs.object().to_ast_kind()).unwrap();
+        let constructorFunction = classPrototype.object().to_ast_kind();
+
+        // Fix up parent pointers since we're going to use these nodes before we bind into them
+        constructorFunction.set_parent(Some(classPrototype.to_ast_kind()));
+        classPrototype.to_ast_kind().set_parent(Some(lhs.to_ast_kind()));
+        lhs.to_ast_kind().set_parent(Some(parent));
+
+        self.bindPropertyAssignment(constructorFunction, lhs, /*isPrototypeProperty*/ true, /*containerIsClass*/ true);
+    }
+    // endregion: 3398
+
+    // region: 3400
+    fn bindSpecialPropertyAssignment(&mut self, node: &'a AssignmentExpression<'a>) {
+        let left = AccessExpression::from_ast_kind(&node.left.to_ast_kind()).unwrap();
+        // Class declarations in Typescript do not allow property declarations
+        let parentSymbol = self.lookupSymbolForPropertyAccess(&left.object().to_ast_kind(), self.blockScopeContainer.map(|container| container.to_ast_kind())).or_else(|| self.lookupSymbolForPropertyAccess(&left.object().to_ast_kind(), self.container.map(|container| container.to_ast_kind())));
+        if !isInJSFile(&node.to_ast_kind()) && !isFunctionSymbol(parentSymbol.as_ref()) {
+            return;
+        }
+        let rootExpr = getLeftmostAccessExpression(&node.left.to_ast_kind());
+        if let Some(rootExpr) = Identifier::from_ast_kind(&rootExpr) {
+            if lookupSymbolForName(&self.container.unwrap().to_ast_kind(), rootExpr.str_name()).as_ref().is_some_and(|symbol| symbol.borrow().flags.intersects(SymbolFlags::Alias)) {
+                return;
+            }
+        }
+        // Fix up parent pointers since we're going to use these nodes before we bind into them
+        node.left.to_ast_kind().set_parent(Some(node.to_ast_kind()));
+        node.right.to_ast_kind().set_parent(Some(node.to_ast_kind()));
+        if isIdentifier(&left.object().to_ast_kind()) && matches!(self.container.unwrap().to_ast_kind(), AstKind::SourceFile(_)) && isExportsOrModuleExportsOrAlias(self.file.unwrap(), &left.object().to_ast_kind()) {
+            // This can be an alias for the 'exports' or 'module.exports' names, e.g.
+            //    var util = module.exports;
+            //    util.property = function ...
+            self.bindExportsPropertyAssignment(node);
+        } else if hasDynamicName(&node.to_ast_kind()) {
+            self.bindAnonymousDeclaration(node.to_ast_kind(), SymbolFlags::Property | SymbolFlags::Assignment, InternalSymbolName::Computed.as_str().to_string());
+            let isToplevel = self.isTopLevelNamespaceAssignment(&left);
+            let sym = self.bindPotentiallyMissingNamespaces(parentSymbol, left.object().to_ast_kind(), isToplevel, /*isPrototypeProperty*/ false, /*containerIsClass*/ false);
+            self.addLateBoundAssignmentDeclarationToSymbol(node.to_ast_kind(), sym);
+        } else {
+            self.bindStaticPropertyAssignment(left);
+        }
+    }
+
+    /**
+     * For nodes like `x.y = z`, declare a member 'y' on 'x' if x is a function (or IIFE) or class or {}, or not declared.
+     * Also works for expression statements preceded by JSDoc, like / ** @type number * / x.y;
+     */
+    fn bindStaticPropertyAssignment(&mut self, node: AccessExpression<'a>) {
+        debug_assert!(!isIdentifier(&node.to_ast_kind()));
+        node.object().to_ast_kind().set_parent(Some(node.to_ast_kind()));
+        self.bindPropertyAssignment(node.object().to_ast_kind(), node, /*isPrototypeProperty*/ false, /*containerIsClass*/ false);
+    }
+
+    fn bindPotentiallyMissingNamespaces(&mut self, namespaceSymbol: opt_rc_cell!(Symbol<'a>), entityName: AstKind<'a>, isToplevel: bool, isPrototypeProperty: bool, containerIsClass: bool) -> opt_rc_cell!(Symbol<'a>) {
+        let mut namespaceSymbol = namespaceSymbol;
+        if namespaceSymbol.as_ref().is_some_and(|symbol| symbol.borrow().flags.intersects(SymbolFlags::Alias)) {
+            return namespaceSymbol;
+        }
+        if isToplevel && !isPrototypeProperty {
+            // make symbols or add declarations for intermediate containers
+            let flags = SymbolFlags::Module | SymbolFlags::Assignment;
+            let excludeFlags = SymbolFlags::ValueModuleExcludes & !SymbolFlags::Assignment;
+            namespaceSymbol = self.forEachIdentifierInEntityName(entityName, namespaceSymbol, &mut |this, id, symbol, parent| {
+                if let Some(symbol) = symbol {
+                    this.addDeclarationToSymbol(symbol.clone(), AstKindDeclaration::from_ast_kind(&id).unwrap(), flags);
+                    Some(symbol)
+                } else {
+                    let table = match &parent {
+                        Some(parent) => parent.borrow().exports.clone().unwrap(),
+                        None => {
+                            let file = this.file.unwrap();
+                            if file.jsGlobalAugmentations().is_none() {
+                                file.set_jsGlobalAugmentations(Some(new_rc_cell!(createSymbolTable(None))));
+                            }
+                            file.jsGlobalAugmentations().unwrap()
+                        }
+                    };
+                    Some(this.declareSymbol(table, parent, id, flags, excludeFlags, None, None))
+                }
+            });
+        }
+        if containerIsClass {
+            if let Some(namespaceSymbol) = &namespaceSymbol {
+                let valueDeclaration = namespaceSymbol.borrow().valueDeclaration.clone();
+                if let Some(valueDeclaration) = valueDeclaration {
+                    self.addDeclarationToSymbol(namespaceSymbol.clone(), valueDeclaration, SymbolFlags::Class);
+                }
+            }
+        }
+        namespaceSymbol
+    }
+
+    fn bindPotentiallyNewExpandoMemberToNamespace(&mut self, declaration: AstKind<'a>, namespaceSymbol: opt_rc_cell!(Symbol<'a>), isPrototypeProperty: bool) {
+        let Some(namespaceSymbol) = namespaceSymbol else {
+            return;
+        };
+        if !self.isExpandoSymbol(&namespaceSymbol) {
+            return;
+        }
+
+        // Set up the members collection if it doesn't exist already
+        let symbolTable = if isPrototypeProperty {
+            namespaceSymbol.borrow_mut().members.get_or_insert_with(|| new_rc_cell!(createSymbolTable(None))).clone()
+        } else {
+            namespaceSymbol.borrow_mut().exports.get_or_insert_with(|| new_rc_cell!(createSymbolTable(None))).clone()
+        };
+
+        let mut includes = SymbolFlags::None;
+        let mut excludes = SymbolFlags::None;
+        // Method-like
+        if isFunctionLike(getAssignedExpandoInitializer(Some(&declaration)).as_ref()) {
+            includes = SymbolFlags::Method;
+            excludes = SymbolFlags::MethodExcludes;
+        }
+        // Maybe accessor-like
+        // else if (isCallExpression(declaration) && isBindableObjectDefinePropertyCall(declaration)) {
+        //     if (some(declaration.arguments[2].properties, p => {
+        //         const id = getNameOfDeclaration(p);
+        //         return !!id && isIdentifier(id) && idText(id) === "set";
+        //     })) {
+        //         // We mix in `SymbolFLags.Property` so in the checker `getTypeOfVariableParameterOrProperty` is used for this
+        //         // symbol, instead of `getTypeOfAccessor` (which will assert as there is no real accessor declaration)
+        //         includes |= SymbolFlags.SetAccessor | SymbolFlags.Property;
+        //         excludes |= SymbolFlags.SetAccessorExcludes;
+        //     }
+        //     if (some(declaration.arguments[2].properties, p => {
+        //         const id = getNameOfDeclaration(p);
+        //         return !!id && isIdentifier(id) && idText(id) === "get";
+        //     })) {
+        //         includes |= SymbolFlags.GetAccessor | SymbolFlags.Property;
+        //         excludes |= SymbolFlags.GetAccessorExcludes;
+        //     }
+        // }
+
+        if includes == SymbolFlags::None {
+            includes = SymbolFlags::Property;
+            excludes = SymbolFlags::PropertyExcludes;
+        }
+
+        self.declareSymbol(symbolTable, Some(namespaceSymbol), declaration, includes | SymbolFlags::Assignment, excludes & !SymbolFlags::Assignment, None, None);
+    }
+
+    fn isTopLevelNamespaceAssignment(&self, propertyAccess: &AccessExpression<'a>) -> bool {
+        let parent = propertyAccess.to_ast_kind().parent().unwrap();
+        if isBinaryExpression(&parent) {
+            matches!(self.getParentOfBinaryExpression(parent).parent(), Some(AstKind::SourceFile(_)))
+        } else {
+            matches!(parent.parent(), Some(AstKind::SourceFile(_)))
+        }
+    }
+
+    fn bindPropertyAssignment(&mut self, name: AstKind<'a>, propertyAccess: AccessExpression<'a>, isPrototypeProperty: bool, containerIsClass: bool) {
+        let namespaceSymbol = self.lookupSymbolForPropertyAccess(&name, self.blockScopeContainer.map(|container| container.to_ast_kind())).or_else(|| self.lookupSymbolForPropertyAccess(&name, self.container.map(|container| container.to_ast_kind())));
+        let isToplevel = self.isTopLevelNamespaceAssignment(&propertyAccess);
+        let namespaceSymbol = self.bindPotentiallyMissingNamespaces(namespaceSymbol, propertyAccess.object().to_ast_kind(), isToplevel, isPrototypeProperty, containerIsClass);
+        self.bindPotentiallyNewExpandoMemberToNamespace(propertyAccess.to_ast_kind(), namespaceSymbol, isPrototypeProperty);
+    }
+
+    /**
+     * Javascript expando values are:
+     * - Functions
+     * - classes
+     * - namespaces
+     * - variables initialized with function expressions
+     * -                       with class expressions
+     * -                       with empty object literals
+     * -                       with non-empty object literals if assigned to the prototype property
+     */
+    fn isExpandoSymbol(&self, symbol: &rc_cell!(Symbol<'a>)) -> bool {
+        if symbol.borrow().flags.intersects(SymbolFlags::Function | SymbolFlags::Class | SymbolFlags::NamespaceModule) {
+            return true;
+        }
+        let valueDeclaration = symbol.borrow().valueDeclaration.clone();
+        let Some(node) = valueDeclaration.map(|declaration| declaration.to_ast_kind()) else {
+            return false;
+        };
+        // if (node && isCallExpression(node)) {
+        //     return !!getAssignedExpandoInitializer(node);
+        // }
+        let init = match node {
+            AstKind::VariableDeclarator(decl) => decl.init.as_ref().map(|init| init.to_ast_kind()),
+            _ if isBinaryExpression(&node) => Some(BinaryExpression::from_ast_kind(&node).unwrap().right().to_ast_kind()),
+            _ if isPropertyAccessExpression(&node) && node.parent().is_some_and(|parent| isBinaryExpression(&parent)) => Some(BinaryExpression::from_ast_kind(&node.parent().unwrap()).unwrap().right().to_ast_kind()),
+            _ => None,
+        };
+        let Some(init) = init.map(getRightMostAssignedExpression) else {
+            return false;
+        };
+        let isPrototypeAssignment = isPrototypeAccess(&match node {
+            AstKind::VariableDeclarator(decl) => decl.id.to_ast_kind(),
+            _ if isBinaryExpression(&node) => BinaryExpression::from_ast_kind(&node).unwrap().left().to_ast_kind(),
+            _ => node,
+        });
+        let init = match init {
+            AstKind::LogicalExpression(expr) if matches!(expr.operator, LogicalOperator::Or | LogicalOperator::Coalesce) => expr.right.to_ast_kind(),
+            _ => init,
+        };
+        getExpandoInitializer(&init, isPrototypeAssignment).is_some()
+    }
+
+    fn getParentOfBinaryExpression(&self, expr: AstKind<'a>) -> AstKind<'a> {
+        let mut expr = expr;
+        while let Some(parent) = expr.parent().filter(|parent| isBinaryExpression(parent)) {
+            expr = parent;
+        }
+        expr.parent().unwrap()
+    }
+
+    fn lookupSymbolForPropertyAccess(&self, node: &AstKind<'a>, lookupContainer: Option<AstKind<'a>>) -> opt_rc_cell!(Symbol<'a>) {
+        let lookupContainer = lookupContainer.or_else(|| self.container.map(|container| container.to_ast_kind()))?;
+        if let Some(identifier) = Identifier::from_ast_kind(node) {
+            return lookupSymbolForName(&lookupContainer, identifier.str_name());
+        }
+        let access = AccessExpression::from_ast_kind(node).unwrap();
+        let symbol = self.lookupSymbolForPropertyAccess(&access.object().to_ast_kind(), None)?;
+        let exports = symbol.borrow().exports.clone()?;
+        let name = getElementOrPropertyAccessName(access)?;
+        let result = exports.borrow().get(&name).cloned();
+        result
+    }
+
+    fn forEachIdentifierInEntityName(&mut self, e: AstKind<'a>, parent: opt_rc_cell!(Symbol<'a>), action: &mut impl FnMut(&mut Self, AstKind<'a>, opt_rc_cell!(Symbol<'a>), opt_rc_cell!(Symbol<'a>)) -> opt_rc_cell!(Symbol<'a>)) -> opt_rc_cell!(Symbol<'a>) {
+        if isExportsOrModuleExportsOrAlias(self.file.unwrap(), &e) {
+            self.file.unwrap().to_ast_kind().symbol()
+        } else if isIdentifier(&e) {
+            let symbol = self.lookupSymbolForPropertyAccess(&e, None);
+            action(self, e, symbol, parent)
+        } else {
+            let access = AccessExpression::from_ast_kind(&e).unwrap();
+            let s = self.forEachIdentifierInEntityName(access.object().to_ast_kind(), parent, action);
+            let name = getNameOrArgument(&access);
+            // unreachable
+            if matches!(name, AstKind::PrivateIdentifier(_)) {
+                panic!("unexpected PrivateIdentifier");
+            }
+            let symbol = s.as_ref().and_then(|s| s.borrow().exports.clone()).and_then(|exports| getElementOrPropertyAccessName(access).and_then(|name| exports.borrow().get(&name).cloned()));
+            action(self, name, symbol, s)
+        }
+    }
+    // endregion: 3490
+
+    // region: 3492
+    fn bindCallExpression(&mut self, node: &'a CallExpression<'a>) {
+        // We're only inspecting call expressions to detect CommonJS modules, so we can skip
+        // this check if we've already seen the module indicator
+        if self.file.unwrap().commonJsModuleIndicator().is_none() && isRequireCall(&node.to_ast_kind(), /*requireStringLiteralLikeArgument*/ false) {
+            self.setCommonJsModuleIndicator(node.to_ast_kind());
+        }
+    }
+    // endregion: 3499
+
+    // region: 3501
+    fn bindClassLikeDeclaration(&mut self, node: &'a Class<'a>) {
+        if node.is_declaration() {
+            self.bindBlockScopedDeclaration(node.to_ast_kind(), SymbolFlags::Class, SymbolFlags::ClassExcludes);
+        } else {
+            let bindingName = node.id.as_ref().map_or(InternalSymbolName::Class.as_str().to_string(), |id| id.name.to_string());
+            self.bindAnonymousDeclaration(node.to_ast_kind(), SymbolFlags::Class, bindingName);
+            // Add name of class expression into the map for semantic classifier
+            if let Some(id) = &node.id {
+                self.classifiableNames.as_ref().unwrap().borrow_mut().insert(id.name.to_string());
+            }
+        }
+
+        let symbol = node.to_ast_kind().symbol().unwrap();
+
+        // TypeScript 1.0 spec (April 2014): 8.4
+        // Every class automatically contains a static property member named 'prototype', the
+        // type of which is an instantiation of the class type with type Any supplied as a type
+        // argument for each type parameter. It is an error to explicitly declare a static
+        // property member with the name 'prototype'.
+        //
+        // Note: we check for this here because this class may be merging into a module.  The
+        // module might have an exported variable called 'prototype'.  We can't allow that as
+        // that would clash with the built-in 'prototype' for the class.
+        let prototypeSymbol = self.createSymbol(SymbolFlags::Property | SymbolFlags::Prototype, "prototype".to_string());
+        let exports = symbol.borrow().exports.clone().unwrap();
+        let symbolExport = exports.borrow().get(&prototypeSymbol.borrow().escapedName).cloned();
+        if let Some(symbolExport) = symbolExport {
+            // if (node.name) {
+            //     setParent(node.name, node);
+            // }
+            let declaration = symbolExport.borrow().declarations.as_ref().unwrap()[0].to_ast_kind();
+            let diagnostic = self.createDiagnosticForNode(&declaration, Diagnostics::Duplicate_identifier_0(), vec![StringOrNumber::String(unescapeLeadingUnderscores(&prototypeSymbol.borrow().escapedName))]);
+            self.file.unwrap().bindDiagnostics().borrow_mut().push(diagnostic);
+        }
+        exports.borrow_mut().insert(prototypeSymbol.borrow().escapedName.clone(), prototypeSymbol.clone());
+        prototypeSymbol.borrow_mut().parent = Some(symbol);
+    }
+    // endregion: 3537
+
+    // region: 3543
+    fn bindVariableDeclarationOrBindingElement(&mut self, node: &'a VariableDeclarator<'a>) {
+        if self.inStrictMode {
+            self.checkStrictModeEvalOrArguments(node.to_ast_kind(), Some(node.id.to_ast_kind()));
+        }
+
+        // !isBindingPattern(node.name)
+        if matches!(node.id.kind, BindingPatternKind::BindingIdentifier(_)) {
+            // ! rb require aliases are not bound yet
+            // const possibleVariableDecl = node.kind === SyntaxKind.VariableDeclaration ? node : node.parent.parent;
+            // if (
+            //     isInJSFile(node) &&
+            //     isVariableDeclarationInitializedToBareOrAccessedRequire(possibleVariableDecl) &&
+            //     !getJSDocTypeTag(node) &&
+            //     !(getCombinedModifierFlags(node) & ModifierFlags.Export)
+            // ) {
+            //     declareSymbolAndAddToSymbolTable(node as Declaration, SymbolFlags.Alias, SymbolFlags.AliasExcludes);
+            // }
+            // else
+            if isBlockOrCatchScoped(&node.to_ast_kind()) {
+                self.bindBlockScopedDeclaration(node.to_ast_kind(), SymbolFlags::BlockScopedVariable, SymbolFlags::BlockScopedVariableExcludes);
+            }
+            // else if (isPartOfParameterDeclaration(node)) {
+            //     // It is safe to walk up parent chain to find whether the node is a destructuring parameter declaration
+            //     // because its parent chain has already been set up, since parents are set before descending into children.
+            //     //
+            //     // If node is a binding element in parameter declaration, we need to use ParameterExcludes.
+            //     // Using ParameterExcludes flag allows the compiler to report an error on duplicate identifiers in Parameter Declaration
+            //     // For example:
+            //     //      function foo([a,a]) {} // Duplicate Identifier error
+            //     //      function bar(a,a) {}   // Duplicate Identifier error, parameter declaration in this case is handled in bindParameter
+            //     //                             // which correctly set excluded symbols
+            //     declareSymbolAndAddToSymbolTable(node as Declaration, SymbolFlags.FunctionScopedVariable, SymbolFlags.ParameterExcludes);
+            // }
+            else {
+                self.declareSymbolAndAddToSymbolTable(node.to_ast_kind(), SymbolFlags::FunctionScopedVariable, SymbolFlags::FunctionScopedVariableExcludes);
+            }
+        }
+    }
+    // endregion: 3572
+
+    // region: 3574
+    fn bindFunctionDeclaration(&mut self, node: &'a Function<'a>) {
+        // if (!file.isDeclarationFile && !(node.flags & NodeFlags.Ambient)) {
+        //     if (isAsyncFunction(node)) {
+        //         emitFlags |= NodeFlags.HasAsyncFunctions;
+        //     }
+        // }
+
+        self.checkStrictModeFunctionName(node.to_ast_kind());
+        if self.inStrictMode {
+            self.checkStrictModeFunctionDeclaration(node);
+            self.bindBlockScopedDeclaration(node.to_ast_kind(), SymbolFlags::Function, SymbolFlags::FunctionExcludes);
+        } else {
+            self.declareSymbolAndAddToSymbolTable(node.to_ast_kind(), SymbolFlags::Function, SymbolFlags::FunctionExcludes);
+        }
+    }
+
+    fn bindFunctionExpression(&mut self, node: AstKind<'a>) -> rc_cell!(Symbol<'a>) {
+        // if (!file.isDeclarationFile && !(node.flags & NodeFlags.Ambient)) {
+        //     if (isAsyncFunction(node)) {
+        //         emitFlags |= NodeFlags.HasAsyncFunctions;
+        //     }
+        // }
+        if self.currentFlow.is_some() {
+            node.set_flowNode(self.currentFlow.clone());
+        }
+        self.checkStrictModeFunctionName(node);
+        let bindingName = match node {
+            AstKind::Function(func) => func.id.as_ref().map(|id| id.name.to_string()),
+            _ => None,
+        };
+        self.bindAnonymousDeclaration(node, SymbolFlags::Function, bindingName.unwrap_or_else(|| InternalSymbolName::Function.as_str().to_string()))
+    }
+    // endregion: 3600
+
+    // region: 3602
+    fn shouldReportErrorOnModuleDeclaration(&self, node: &'a TSModuleDeclaration<'a>) -> bool {
+        let instanceState = getModuleInstanceState(node, None);
+        instanceState == ModuleInstanceState::Instantiated || (instanceState == ModuleInstanceState::ConstEnumOnly && shouldPreserveConstEnums(self.options.unwrap()))
+    }
+
+    fn checkUnreachable(&mut self, node: &AstKind<'a>) -> bool {
+        let Some(current_flow) = self.currentFlow.clone() else {
+            return false;
+        };
+        if !current_flow.borrow().get_flags().intersects(FlowFlags::Unreachable) {
+            return false;
+        }
+        if Rc::ptr_eq(&current_flow, &self.unreachableFlow) {
+            let reportError =
+                // report error on all statements except empty ones
+                (isStatementButNotDeclaration(node) && !matches!(node, AstKind::EmptyStatement(_))) ||
+                // report error on class declarations
+                matches!(node, AstKind::Class(class) if class.is_declaration()) ||
+                // report errors on enums with preserved emit
+                isEnumDeclarationWithPreserveConstEnum(node, self.options.unwrap()) ||
+                // report error on instantiated modules
+                matches!(*node, AstKind::TSModuleDeclaration(module) if self.shouldReportErrorOnModuleDeclaration(module));
+                // ! rb an exported declaration is wrapped in an ExportNamedDeclaration, which is not a statement here, so the
+                // error is reported on the wrapped declaration alone rather than on the range of statements that follows it
+
+            if reportError {
+                self.currentFlow = Some(self.reportedUnreachableFlow.clone());
+
+                let options = self.options.unwrap();
+                if options.allowUnreachableCode != Some(true) {
+                    // unreachable code is reported if
+                    // - user has explicitly asked about it AND
+                    // - statement is in not ambient context (statements in ambient context is already an error
+                    //   so we should not report extras) AND
+                    //   - node is not variable statement OR
+                    //   - node is block scoped variable statement OR
+                    //   - node is not block scoped variable statement and at least one variable declaration has initializer
+                    //   Rationale: we don't want to report errors on non-initialized var's since they are hoisted
+                    //   On the other side we do want to report errors on non-initialized 'lets' because of TDZ
+                    let isError = unreachableCodeIsError(options)
+                        && !node.flags().intersects(NodeFlags::Ambient)
+                        && match *node {
+                            AstKind::VariableDeclarationList(list) => getCombinedNodeFlags(node).intersects(NodeFlags::BlockScoped) || list.declarations.iter().any(|d| d.init.is_some()),
+                            _ => true,
+                        };
+
+                    eachUnreachableRange(node, |start, end| self.errorOrSuggestionOnRange(isError, &start, &end, Diagnostics::Unreachable_code_detected()));
+                }
+            }
+        }
+        true
+    }
+    // endregion: 3639
+}
+
+// ! rb `label.antecedent` of a FlowLabel held as a FlowNode
+fn rb_label_antecedents<'a>(label: &rc_cell!(FlowNode<'a>)) -> Option<Vec<rc_cell!(FlowNode<'a>)>> {
+    match &*label.borrow() {
+        FlowNode::Label(label) => label.antecedent.clone(),
+        _ => panic!("Expected FlowLabel"),
+    }
+}
+
+// ! rb `nodeIsPresent((node as FunctionLikeDeclaration | ClassStaticBlockDeclaration).body)`
+// ! rb `export` and `export default` are not modifiers in oxc, the declaration is wrapped in an
+// ExportNamedDeclaration or ExportDefaultDeclaration which carries them instead
+fn rb_export_wrapper_modifier_flags(node: &AstKind) -> ModifierFlags {
+    let declaration = if matches!(node, AstKind::VariableDeclarator(_)) { node.parent() } else { Some(*node) };
+    match declaration.and_then(|declaration| declaration.parent()) {
+        Some(parent @ (AstKind::ExportNamedDeclaration(_) | AstKind::ExportDefaultDeclaration(_))) => getSyntacticModifierFlags(&parent),
+        _ => ModifierFlags::None,
+    }
+}
+
+// ! rb keywords are not scanned for identifiers in oxc, these are the FirstFutureReservedWord..LastFutureReservedWord kinds
+fn rb_is_future_reserved_word(name: &str) -> bool { matches!(name, "implements" | "interface" | "let" | "package" | "private" | "protected" | "public" | "static" | "yield") }
+
+// ! rb TokenFlags.Octal, a leading zero followed only by octal digits
+fn rb_is_legacy_octal_literal(node: &NumericLiteral) -> bool {
+    let raw = node.raw.map_or("", |raw| raw.as_str());
+    raw.len() > 1 && raw.starts_with('0') && raw[1..].bytes().all(|b| matches!(b, b'0'..=b'7'))
+}
+
+fn rb_function_body_is_present(node: &AstKind) -> bool {
+    match node {
+        AstKind::Function(func) => func.body.is_some(),
+        AstKind::MethodDefinition(method) => method.value.body.is_some(),
+        AstKind::ArrowFunctionExpression(_) | AstKind::StaticBlock(_) => true,
+        _ => false,
+    }
+}
+
+// region: 3826
+fn eachUnreachableRange<'a>(node: &AstKind<'a>, mut cb: impl FnMut(AstKind<'a>, AstKind<'a>)) {
+    let statements = if isExecutableStatement(node) { node.parent().and_then(|parent| rb_block_statements(&parent)) } else { None };
+    if let Some(statements) = statements {
+        // sliceAfter(statements, node)
+        let slice: Vec<_> = statements.iter().map(|statement| statement.to_ast_kind()).skip_while(|statement| statement.span() != node.span()).collect();
+        getRangesWhere(&slice, isExecutableStatement, |start, afterEnd| cb(slice[start], slice[afterEnd - 1]));
+    } else {
+        cb(*node, *node);
+    }
+}
+
+// As opposed to a pure declaration like an `interface`
+fn isExecutableStatement(s: &AstKind) -> bool {
+    // Don't remove statements that can validly be used before they appear.
+    !matches!(s, AstKind::Function(func) if func.is_declaration()) && !isPurelyTypeDeclaration(s) && !matches!(s, AstKind::TSEnumDeclaration(_)) &&
+        // `var x;` may declare a variable used above
+        !matches!(s, AstKind::VariableDeclarationList(list) if !getCombinedNodeFlags(s).intersects(NodeFlags::Let | NodeFlags::Const) && list.declarations.iter().any(|d| d.init.is_none()))
+}
+
+fn isPurelyTypeDeclaration(s: &AstKind) -> bool {
+    match s {
+        AstKind::TSInterfaceDeclaration(_) | AstKind::TSTypeAliasDeclaration(_) => true,
+        AstKind::TSModuleDeclaration(module) => getModuleInstanceState(module, None) != ModuleInstanceState::Instantiated,
+        AstKind::TSEnumDeclaration(_) => hasSyntacticModifier(s, ModifierFlags::Const),
+        _ => false,
+    }
+}
+
+fn isEnumDeclarationWithPreserveConstEnum(node: &AstKind, options: &CompilerOptions) -> bool {
+    matches!(node, AstKind::TSEnumDeclaration(decl) if !decl.r#const || shouldPreserveConstEnums(options))
+}
+// endregion: 3863
+
+// ! rb exported function declarations are wrapped in their export declaration in oxc
+fn rb_is_function_declaration(node: &AstKind) -> bool {
+    match node {
+        AstKind::Function(func) => func.is_declaration(),
+        AstKind::ExportNamedDeclaration(decl) => matches!(decl.declaration, Some(Declaration::FunctionDeclaration(_))),
+        AstKind::ExportDefaultDeclaration(decl) => matches!(decl.declaration, ExportDefaultDeclarationKind::FunctionDeclaration(_)),
+        _ => false,
+    }
+}
+
+// ! rb `isBlock(node.parent) ? node.parent.statements : undefined`, function bodies and static blocks are blocks in TypeScript
+fn rb_block_statements<'a>(node: &AstKind<'a>) -> Option<&'a oxc_allocator::Vec<'a, Statement<'a>>> {
+    match *node {
+        AstKind::BlockStatement(block) => Some(&block.body),
+        AstKind::FunctionBody(body) => Some(&body.statements),
+        AstKind::StaticBlock(block) => Some(&block.body),
+        _ => None,
+    }
+}
+
+// region: 3866
+/** @internal */
+pub fn isExportsOrModuleExportsOrAlias<'a>(sourceFile: &'a SourceFile<'a>, node: &AstKind<'a>) -> bool {
+    let mut i = 0;
+    let mut q = VecDeque::new();
+    q.push_back(*node);
+    while !q.is_empty() && i < 100 {
+        i += 1;
+        let node = q.pop_front().unwrap();
+        if isExportsIdentifier(&node) || isModuleExportsAccessExpression(&node) {
+            return true;
+        } else if let Some(identifier) = Identifier::from_ast_kind(&node) {
+            let symbol = lookupSymbolForName(&AstKind::SourceFile(sourceFile), identifier.str_name());
+            let valueDeclaration = symbol.as_ref().and_then(|symbol| symbol.borrow().valueDeclaration.clone());
+            if let Some(AstKindDeclaration::VariableDeclarator(declaration)) = valueDeclaration {
+                if let Some(init) = &declaration.init {
+                    q.push_back(init.to_ast_kind());
+                    // isAssignmentExpression(init, /*excludeCompoundAssignment*/ true)
+                    if let Expression::AssignmentExpression(init) = init {
+                        if init.operator == AssignmentOperator::Assign {
+                            q.push_back(init.left.to_ast_kind());
+                            q.push_back(init.right.to_ast_kind());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
 }
+// endregion: 3886
 
 // region: 3888
 /** @internal */
@@ -1455,7 +3700,7 @@ pub fn getContainerFlags(node: &AstKind) -> ContainerFlags {
 // endregion: 3968
 
 // region: 3969
-pub fn lookupSymbolForName<'a>(container: &'a AstKind<'a>, name: &str) -> opt_rc_cell!(Symbol<'a>) {
+pub fn lookupSymbolForName<'a>(container: &AstKind<'a>, name: &str) -> opt_rc_cell!(Symbol<'a>) {
     if let Some(container_with_locals) = HasLocals::from_ast_kind(container) {
         if let Some(locals) = &container_with_locals.locals() {
             if let Some(local) = locals.borrow().get(name) {
@@ -1479,7 +3724,7 @@ pub fn lookupSymbolForName<'a>(container: &'a AstKind<'a>, name: &str) -> opt_rc
     if canHaveSymbol(container) {
         if let Some(symbol) = &container.symbol() {
             if let Some(exports) = &symbol.borrow().exports {
-                return exports.get(name).cloned();
+                return exports.borrow().get(name).cloned();
             }
         }
     }
@@ -1487,3 +3732,63 @@ pub fn lookupSymbolForName<'a>(container: &'a AstKind<'a>, name: &str) -> opt_rc
     None
 }
 // endregion: 3982
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::bindSourceFile;
+    use crate::compiler::rb_vfs_host::VirtualFileSystemHost;
+    use crate::compiler::types::{diagnosticCategoryName, CompilerOptions, Diagnostic};
+
+    const UNREACHABLE_CODE: &str = "function f() {\n    return;\n    foo();\n    bar();\n}\n";
+    const UNUSED_LABEL: &str = "a: for (;;) { break; }\n";
+
+    // (code, category, start, length) of each diagnostic
+    type Summary = Vec<(i32, String, Option<u32>, Option<u32>)>;
+
+    /// Binds `text` with `options`, giving the summaries of the bind diagnostics and of the bind suggestion diagnostics.
+    fn bind(text: &str, options: CompilerOptions) -> (Summary, Summary) {
+        let mut host = VirtualFileSystemHost::new("/proj".to_string(), Rc::new(CompilerOptions::default()), true);
+        let owned = host.parseSourceFile("/proj/index.ts".to_string(), text.to_string());
+        let file = owned.borrow();
+        bindSourceFile(&file, &options);
+        let summarize = |diagnostics: &Vec<Diagnostic>| -> Summary { diagnostics.iter().map(|d| (d.code, diagnosticCategoryName(&d.category, false), d.start, d.length)).collect() };
+        // bound to a local so that the borrows of the diagnostics end before `file` is dropped
+        let reported = (summarize(&file.bindDiagnostics().borrow()), summarize(&file.bindSuggestionDiagnostics().borrow()));
+        reported
+    }
+
+    #[test]
+    fn reports_unreachable_code_as_an_error_only_when_disallowed() {
+        // the range runs from the first unreachable statement to the end of the block
+        let (errors, suggestions) = bind(UNREACHABLE_CODE, CompilerOptions { allowUnreachableCode: Some(false), ..Default::default() });
+        assert_eq!(errors, vec![(7027, "Error".to_string(), Some(31), Some(17))]);
+        assert_eq!(suggestions, vec![]);
+
+        let (errors, suggestions) = bind(UNREACHABLE_CODE, CompilerOptions::default());
+        assert_eq!(errors, vec![]);
+        assert_eq!(suggestions, vec![(7027, "Suggestion".to_string(), Some(31), Some(17))]);
+
+        let (errors, suggestions) = bind(UNREACHABLE_CODE, CompilerOptions { allowUnreachableCode: Some(true), ..Default::default() });
+        assert_eq!((errors, suggestions), (vec![], vec![]));
+    }
+
+    #[test]
+    fn reports_unused_labels_as_an_error_only_when_disallowed() {
+        let (errors, suggestions) = bind(UNUSED_LABEL, CompilerOptions { allowUnusedLabels: Some(false), ..Default::default() });
+        assert_eq!(errors, vec![(7028, "Error".to_string(), Some(0), Some(1))]);
+        assert_eq!(suggestions, vec![]);
+
+        let (errors, suggestions) = bind(UNUSED_LABEL, CompilerOptions::default());
+        assert_eq!(errors, vec![]);
+        assert_eq!(suggestions, vec![(7028, "Suggestion".to_string(), Some(0), Some(1))]);
+
+        let (errors, suggestions) = bind(UNUSED_LABEL, CompilerOptions { allowUnusedLabels: Some(true), ..Default::default() });
+        assert_eq!((errors, suggestions), (vec![], vec![]));
+
+        // a label that is broken out of is used
+        let (errors, suggestions) = bind("a: for (;;) { break a; }\n", CompilerOptions { allowUnusedLabels: Some(false), ..Default::default() });
+        assert_eq!((errors, suggestions), (vec![], vec![]));
+    }
+}
@@ -9,7 +9,7 @@ use oxc_ast::ast::ExportSpecifier;
 use oxc_ast::ast::SourceFile;
 use oxc_ast::ast::TSTypeParameter;
 use oxc_ast::{
-    ast::{Argument, Expression, JSXAttribute, ObjectExpression},
+    ast::{Argument, Expression, JSXAttribute, ObjectExpression, ObjectProperty, ObjectPropertyKind, PropertyKey, Statement, UnaryOperator},
     AstKind,
 };
 use std::cell::RefCell;
@@ -20,10 +20,29 @@ use std::rc::Rc;
 use std::sync::atomic::AtomicU32;
 
 use super::binder::bindSourceFile;
+use super::core::pushIfUnique;
+use super::diagnostic_information_map_generated::Diagnostics;
+use super::rb_extra::AstKindExt;
+use super::rb_extra::SourceFileExt;
+use super::rb_extra::SourceFilePassthrough;
 use super::rb_unions::StringLiteralOrIdentifier;
+use super::rb_unions::StringOrNumber;
 use super::utilities::addRelatedInfo;
+use super::utilities::compareDiagnostics;
+use super::utilities::compareDiagnosticsSkipRelatedInformation;
+use super::utilities::createCompilerDiagnostic;
 use super::utilities::createDiagnosticForNode;
 use super::utilities::createSymbolTable;
+use super::utilities::getExpandoInitializer;
+use super::utilities::getNameOfExpando;
+use super::utilities::getSourceFileOfNode;
+use super::utilities::isExternalOrCommonJsModule;
+use super::utilities::isJsonSourceFile;
+use super::utilities::isPlainJsFile;
+use super::utilities::setValueDeclaration;
+use super::utilitiesPublic::escapeLeadingUnderscores;
+use super::utilitiesPublic::getNameOfDeclaration;
+use super::utilitiesPublic::unescapeLeadingUnderscores;
 // region: 1141
 static nextSymbolId: AtomicU32 = AtomicU32::new(1);
 static nextNodeId: AtomicU32 = AtomicU32::new(1);
@@ -112,13 +131,68 @@ pub struct DuplicateInfoForSymbol<'a> {
 }
 #[derive(Debug)]
 pub struct DuplicateInfoForFiles<'a> {
-    pub firstFile: SourceFile<'a>,
-    pub secondFile: SourceFile<'a>,
+    pub firstFile: &'a SourceFile<'a>,
+    pub secondFile: &'a SourceFile<'a>,
     /** Key is symbol name. */
     pub conflictingSymbols: HashMap<String, DuplicateInfoForSymbol<'a>>,
 }
 // endregion: 2207
 
+// region: 2441
+fn getExcludedSymbolFlags(flags: SymbolFlags) -> SymbolFlags {
+    let mut result = SymbolFlags::None;
+    if flags.intersects(SymbolFlags::BlockScopedVariable) {
+        result |= SymbolFlags::BlockScopedVariableExcludes;
+    }
+    if flags.intersects(SymbolFlags::FunctionScopedVariable) {
+        result |= SymbolFlags::FunctionScopedVariableExcludes;
+    }
+    if flags.intersects(SymbolFlags::Property) {
+        result |= SymbolFlags::PropertyExcludes;
+    }
+    if flags.intersects(SymbolFlags::EnumMember) {
+        result |= SymbolFlags::EnumMemberExcludes;
+    }
+    if flags.intersects(SymbolFlags::Function) {
+        result |= SymbolFlags::FunctionExcludes;
+    }
+    if flags.intersects(SymbolFlags::Class) {
+        result |= SymbolFlags::ClassExcludes;
+    }
+    if flags.intersects(SymbolFlags::Interface) {
+        result |= SymbolFlags::InterfaceExcludes;
+    }
+    if flags.intersects(SymbolFlags::RegularEnum) {
+        result |= SymbolFlags::RegularEnumExcludes;
+    }
+    if flags.intersects(SymbolFlags::ConstEnum) {
+        result |= SymbolFlags::ConstEnumExcludes;
+    }
+    if flags.intersects(SymbolFlags::ValueModule) {
+        result |= SymbolFlags::ValueModuleExcludes;
+    }
+    if flags.intersects(SymbolFlags::Method) {
+        result |= SymbolFlags::MethodExcludes;
+    }
+    if flags.intersects(SymbolFlags::GetAccessor) {
+        result |= SymbolFlags::GetAccessorExcludes;
+    }
+    if flags.intersects(SymbolFlags::SetAccessor) {
+        result |= SymbolFlags::SetAccessorExcludes;
+    }
+    if flags.intersects(SymbolFlags::TypeParameter) {
+        result |= SymbolFlags::TypeParameterExcludes;
+    }
+    if flags.intersects(SymbolFlags::TypeAlias) {
+        result |= SymbolFlags::TypeAliasExcludes;
+    }
+    if flags.intersects(SymbolFlags::Alias) {
+        result |= SymbolFlags::AliasExcludes;
+    }
+    result
+}
+// endregion: 2460
+
 #[derive(Debug)]
 pub struct TypeChecker<'a> {
     host: Rc<UnsafeCell<dyn TypeCheckerHost<'a>>>,
@@ -139,6 +213,14 @@ pub struct TypeChecker<'a> {
 
     compilerOptions: Rc<CompilerOptions>,
     // endregion: 1499
+
+    // #region: 1531
+    globals: rc_cell!(SymbolTable<'a>),
+    // endregion: 1532
+
+    // #region: 1539
+    globalThisSymbol: rc_cell!(Symbol<'a>),
+    // endregion: 1540
     seenIntrinsicNames: HashSet<String>,
 
     anyType: Rc<dyn IntrinsicType<'a> + 'a>,
@@ -164,15 +246,35 @@ pub struct TypeChecker<'a> {
     regularFalseType: Rc<dyn FreshableIntrinsicType<'a> + 'a>,
     trueType: Rc<dyn FreshableIntrinsicType<'a> + 'a>,
     regularTrueType: Rc<dyn FreshableIntrinsicType<'a> + 'a>,
+    booleanType: Rc<dyn Type<'a> + 'a>,
+    emptyObjectType: Rc<dyn ObjectType<'a> + 'a>,
 
     // #region: 2208
     /* Key is "/path/to/a.ts|/path/to/b.ts". */
     amalgamatedDuplicates: Option<HashMap<String, DuplicateInfoForFiles<'a>>>,
     // endregion: 2209
+
+    // #region: 2244
+    // ! rb keyed by mergeId instead of an array indexed by it
+    mergedSymbols: HashMap<usize, rc_cell!(Symbol<'a>)>,
+    // endregion: 2245
+
+    // ! rb SymbolLinks.type, keyed by symbol id
+    symbolTypes: HashMap<SymbolId, Rc<dyn Type<'a> + 'a>>,
+
+    // #region: 2326
+    diagnostics: Vec<Diagnostic<'a>>,
+    // endregion: 2327
 }
 
 impl<'a> TypeChecker<'a> {
     pub fn new(host: Rc<UnsafeCell<dyn TypeCheckerHost<'a>>>) -> Rc<RefCell<Self>> {
+        let globals = new_rc_cell!(createSymbolTable(None));
+        let globalThisSymbol = new_rc_cell!(Symbol::new(SymbolFlags::Module | SymbolFlags::Transient, "globalThis"));
+        globalThisSymbol.borrow_mut().exports = Some(globals.clone());
+        globalThisSymbol.borrow_mut().declarations = Some(vec![]);
+        globals.borrow_mut().insert(globalThisSymbol.borrow().escapedName.clone(), globalThisSymbol.clone());
+
         let checker = new_rc_cell!(Self {
             host: host.clone(),
             typeCount: 0,
@@ -190,6 +292,9 @@ impl<'a> TypeChecker<'a> {
 
             compilerOptions: unsafe { &*host.as_ref().get() }.getCompilerOptions(),
 
+            globals,
+            globalThisSymbol,
+
             seenIntrinsicNames: HashSet::new(),
 
             // Initialize with empty types that will be properly set in init_intrinsic_types
@@ -216,8 +321,15 @@ impl<'a> TypeChecker<'a> {
             regularFalseType: Rc::new(TypeObject::new(TypeFlags::Any)),
             trueType: Rc::new(TypeObject::new(TypeFlags::Any)),
             regularTrueType: Rc::new(TypeObject::new(TypeFlags::Any)),
+            booleanType: Rc::new(TypeObject::new(TypeFlags::Any)),
+            emptyObjectType: Rc::new(TypeObject::new(TypeFlags::Any)),
 
             amalgamatedDuplicates: None,
+            mergedSymbols: HashMap::new(),
+
+            symbolTypes: HashMap::new(),
+
+            diagnostics: vec![],
         });
 
         checker.borrow_mut().init_intrinsic_types();
@@ -265,8 +377,285 @@ impl<'a> TypeChecker<'a> {
         self.trueType = Rc::new(true_type);
         self.regularTrueType = Rc::new(regular_true_type);
         // endregion: 2068
+
+        // region: 2074
+        // ! rb union types are not ported yet, so boolean only has the flags getUnionType gives the union of false and true
+        self.booleanType = Rc::new(self.createType(TypeFlags::Boolean | TypeFlags::Union));
+        // endregion: 2074
+
+        // region: 2081
+        self.emptyObjectType = Rc::new(self.createAnonymousType(None, createSymbolTable(None), vec![]));
+        // endregion: 2081
+    }
+
+    // region: 2271
+    fn createError(&self, location: Option<AstKind<'a>>, message: DiagnosticMessage, args: DiagnosticArguments) -> Diagnostic<'a> {
+        match location {
+            Some(location) => createDiagnosticForNode(&location, message, args),
+            None => createCompilerDiagnostic(message, args),
+        }
+    }
+
+    fn error(&mut self, location: Option<AstKind<'a>>, message: DiagnosticMessage, args: DiagnosticArguments) -> Diagnostic<'a> {
+        let diagnostic = self.createError(location, message, args);
+        self.diagnostics.push(diagnostic.clone());
+        diagnostic
+    }
+
+    // ! rb returns the index of the diagnostic in the collection so that callers can add related information to it
+    fn lookupOrIssueError(&mut self, location: Option<AstKind<'a>>, message: DiagnosticMessage, args: DiagnosticArguments) -> usize {
+        let diagnostic = self.createError(location, message, args);
+        if let Some(existing) = self.diagnostics.iter().position(|existing| compareDiagnosticsSkipRelatedInformation(existing, &diagnostic).is_eq()) {
+            existing
+        } else {
+            self.diagnostics.push(diagnostic);
+            self.diagnostics.len() - 1
+        }
+    }
+    // endregion: 2356
+
+    // region: 2434
+    fn createSymbol(&mut self, flags: SymbolFlags, name: __String) -> rc_cell!(Symbol<'a>) {
+        self.symbolCount += 1;
+        // symbol.links = new SymbolLinks() as TransientSymbolLinks;
+        // symbol.links.checkFlags = checkFlags || CheckFlags.None;
+        new_rc_cell!(Symbol::new(flags | SymbolFlags::Transient, &name))
+    }
+
+    fn recordMergedSymbol(&mut self, target: rc_cell!(Symbol<'a>), source: rc_cell!(Symbol<'a>)) {
+        if source.borrow().mergeId == 0 {
+            source.borrow_mut().mergeId = nextMergeId.fetch_add(1, std::sync::atomic::Ordering::Relaxed) as usize;
+        }
+        let mergeId = source.borrow().mergeId;
+        self.mergedSymbols.insert(mergeId, target);
+    }
+
+    fn cloneSymbol(&mut self, symbol: rc_cell!(Symbol<'a>)) -> rc_cell!(Symbol<'a>) {
+        let (flags, escapedName) = {
+            let symbol = symbol.borrow();
+            (symbol.flags, symbol.escapedName.clone())
+        };
+        let result = self.createSymbol(flags, escapedName);
+        {
+            let symbol = symbol.borrow();
+            let mut result = result.borrow_mut();
+            result.declarations = Some(symbol.declarations.clone().unwrap_or_default());
+            result.parent = symbol.parent.clone();
+            if symbol.valueDeclaration.is_some() {
+                result.valueDeclaration = symbol.valueDeclaration;
+            }
+            if symbol.constEnumOnlyModule.unwrap_or(false) {
+                result.constEnumOnlyModule = Some(true);
+            }
+            if let Some(members) = &symbol.members {
+                result.members = Some(new_rc_cell!(members.borrow().clone()));
+            }
+            if let Some(exports) = &symbol.exports {
+                result.exports = Some(new_rc_cell!(exports.borrow().clone()));
+            }
+        }
+        self.recordMergedSymbol(result.clone(), symbol);
+        result
+    }
+
+    /**
+     * Note: if target is transient, then it is mutable, and mergeSymbol with both mutate and return it.
+     * If target is not transient, mergeSymbol will produce a transient clone, mutate that and return it.
+     */
+    fn mergeSymbol(&mut self, target: rc_cell!(Symbol<'a>), source: rc_cell!(Symbol<'a>), unidirectional: Option<bool>) -> rc_cell!(Symbol<'a>) {
+        let unidirectional = unidirectional.unwrap_or(false);
+        let mut target = target;
+        let sourceFlags = source.borrow().flags;
+        let targetFlags = target.borrow().flags;
+        if !targetFlags.intersects(getExcludedSymbolFlags(sourceFlags)) || (sourceFlags | targetFlags).intersects(SymbolFlags::Assignment) {
+            if Rc::ptr_eq(&source, &target) {
+                // This can happen when an export assigned namespace exports something also erroneously exported at the top level
+                // See `declarationFileNoCrashOnExtraExportModifier` for an example
+                return target;
+            }
+            if !targetFlags.intersects(SymbolFlags::Transient) {
+                // ! rb aliases are not resolved yet, so every target is its own resolved symbol
+                let resolvedTarget = target.clone(); // resolveSymbol(target)
+                                                     // if (resolvedTarget === unknownSymbol) {
+                                                     //     return source;
+                                                     // }
+                let resolvedTargetFlags = resolvedTarget.borrow().flags;
+                if !resolvedTargetFlags.intersects(getExcludedSymbolFlags(sourceFlags)) || (sourceFlags | resolvedTargetFlags).intersects(SymbolFlags::Assignment) {
+                    target = self.cloneSymbol(resolvedTarget);
+                } else {
+                    self.reportMergeSymbolError(target.clone(), source.clone());
+                    return source;
+                }
+            }
+            // Javascript static-property-assignment declarations always merge, even though they are also values
+            if sourceFlags.intersects(SymbolFlags::ValueModule) && target.borrow().flags.intersects(SymbolFlags::ValueModule) && target.borrow().constEnumOnlyModule.unwrap_or(false) && !source.borrow().constEnumOnlyModule.unwrap_or(false) {
+                // reset flag when merging instantiated module into value module that has only const enums
+                target.borrow_mut().constEnumOnlyModule = Some(false);
+            }
+            target.borrow_mut().flags |= sourceFlags;
+            let valueDeclaration = source.borrow().valueDeclaration;
+            if let Some(valueDeclaration) = valueDeclaration {
+                setValueDeclaration(target.clone(), valueDeclaration);
+            }
+            let declarations = source.borrow().declarations.clone();
+            if let Some(declarations) = declarations {
+                target.borrow_mut().declarations.get_or_insert_with(Vec::new).extend(declarations);
+            }
+            let members = source.borrow().members.clone();
+            if let Some(members) = members {
+                let targetMembers = target.borrow_mut().members.get_or_insert_with(|| new_rc_cell!(createSymbolTable(None))).clone();
+                self.mergeSymbolTable(targetMembers, members, Some(unidirectional), None);
+            }
+            let exports = source.borrow().exports.clone();
+            if let Some(exports) = exports {
+                let targetExports = target.borrow_mut().exports.get_or_insert_with(|| new_rc_cell!(createSymbolTable(None))).clone();
+                self.mergeSymbolTable(targetExports, exports, Some(unidirectional), Some(target.clone()));
+            }
+            if !unidirectional {
+                self.recordMergedSymbol(target.clone(), source);
+            }
+        } else if targetFlags.intersects(SymbolFlags::NamespaceModule) {
+            // Do not report an error when merging `var globalThis` with the built-in `globalThis`,
+            // as we will already report a "Declaration name conflicts..." error, and this error
+            // won't make much sense.
+            if !Rc::ptr_eq(&target, &self.globalThisSymbol) {
+                let location = source.borrow().declarations.as_ref().and_then(|declarations| declarations.first()).and_then(|declaration| getNameOfDeclaration(declaration.to_ast_kind())).map(|name| name.to_ast_kind());
+                // ! rb symbolToString is not converted, the unescaped name is what it prints for these symbols
+                let symbolName = unescapeLeadingUnderscores(&target.borrow().escapedName);
+                self.error(location, Diagnostics::Cannot_augment_module_0_with_value_exports_because_it_resolves_to_a_non_module_entity(), vec![StringOrNumber::String(symbolName)]);
+            }
+        } else {
+            // error
+            self.reportMergeSymbolError(target.clone(), source);
+        }
+        target
+    }
+
+    fn reportMergeSymbolError(&mut self, target: rc_cell!(Symbol<'a>), source: rc_cell!(Symbol<'a>)) {
+        let isEitherEnum = target.borrow().flags.intersects(SymbolFlags::Enum) || source.borrow().flags.intersects(SymbolFlags::Enum);
+        let isEitherBlockScoped = target.borrow().flags.intersects(SymbolFlags::BlockScopedVariable) || source.borrow().flags.intersects(SymbolFlags::BlockScopedVariable);
+        let message = if isEitherEnum {
+            Diagnostics::Enum_declarations_can_only_merge_with_namespace_or_other_enum_declarations()
+        } else if isEitherBlockScoped {
+            Diagnostics::Cannot_redeclare_block_scoped_variable_0()
+        } else {
+            Diagnostics::Duplicate_identifier_0()
+        };
+        let sourceSymbolFile = source.borrow().declarations.as_ref().and_then(|declarations| declarations.first()).and_then(|declaration| getSourceFileOfNode(Some(&declaration.to_ast_kind())));
+        let targetSymbolFile = target.borrow().declarations.as_ref().and_then(|declarations| declarations.first()).and_then(|declaration| getSourceFileOfNode(Some(&declaration.to_ast_kind())));
+
+        let isSourcePlainJs = isPlainJsFile(sourceSymbolFile, self.compilerOptions.checkJs);
+        let isTargetPlainJs = isPlainJsFile(targetSymbolFile, self.compilerOptions.checkJs);
+        // ! rb symbolToString is not converted, the unescaped name is what it prints for these symbols
+        let symbolName = unescapeLeadingUnderscores(&source.borrow().escapedName);
+
+        // Collect top-level duplicate identifier errors into one mapping, so we can then merge their diagnostics if there are a bunch
+        match (sourceSymbolFile, targetSymbolFile) {
+            (Some(sourceSymbolFile), Some(targetSymbolFile)) if self.amalgamatedDuplicates.is_some() && !isEitherEnum && !std::ptr::eq(sourceSymbolFile, targetSymbolFile) => {
+                // ! rb comparePaths is not converted, the file paths are compared directly
+                let firstFile = if sourceSymbolFile.filepath() < targetSymbolFile.filepath() { sourceSymbolFile } else { targetSymbolFile };
+                let secondFile = if std::ptr::eq(firstFile, sourceSymbolFile) { targetSymbolFile } else { sourceSymbolFile };
+                let key = format!("{}|{}", firstFile.filepath().display(), secondFile.filepath().display());
+                let filesDuplicates = self.amalgamatedDuplicates.as_mut().unwrap().entry(key).or_insert_with(|| DuplicateInfoForFiles { firstFile, secondFile, conflictingSymbols: HashMap::new() });
+                let conflictingSymbolInfo = filesDuplicates.conflictingSymbols.entry(symbolName).or_insert_with(|| DuplicateInfoForSymbol { isBlockScoped: isEitherBlockScoped, firstFileLocations: vec![], secondFileLocations: vec![] });
+                if !isSourcePlainJs {
+                    addDuplicateLocations(&mut conflictingSymbolInfo.firstFileLocations, &source.borrow());
+                }
+                if !isTargetPlainJs {
+                    addDuplicateLocations(&mut conflictingSymbolInfo.secondFileLocations, &target.borrow());
+                }
+            }
+            _ => {
+                if !isSourcePlainJs {
+                    self.addDuplicateDeclarationErrorsForSymbols(&source, message.clone(), &symbolName, &target);
+                }
+                if !isTargetPlainJs {
+                    self.addDuplicateDeclarationErrorsForSymbols(&target, message, &symbolName, &source);
+                }
+            }
+        }
+
+        fn addDuplicateLocations<'a>(locs: &mut Vec<AstKindDeclaration<'a>>, symbol: &Symbol<'a>) {
+            for decl in symbol.declarations.iter().flatten() {
+                pushIfUnique(locs, *decl, None::<fn(&AstKindDeclaration<'a>, &AstKindDeclaration<'a>) -> bool>);
+            }
+        }
+    }
+
+    fn addDuplicateDeclarationErrorsForSymbols(&mut self, target: &rc_cell!(Symbol<'a>), message: DiagnosticMessage, symbolName: &str, source: &rc_cell!(Symbol<'a>)) {
+        let declarations = target.borrow().declarations.clone().unwrap_or_default();
+        let relatedNodes = source.borrow().declarations.clone();
+        for node in declarations {
+            self.addDuplicateDeclarationError(node, message.clone(), symbolName, relatedNodes.as_deref());
+        }
+    }
+
+    fn addDuplicateDeclarationError(&mut self, node: AstKindDeclaration<'a>, message: DiagnosticMessage, symbolName: &str, relatedNodes: Option<&[AstKindDeclaration<'a>]>) {
+        let getErrorNode = |node: AstKindDeclaration<'a>| {
+            let node = node.to_ast_kind();
+            if getExpandoInitializer(&node, false).is_some() { getNameOfExpando(&node) } else { getNameOfDeclaration(node).map(|name| name.to_ast_kind()) }.unwrap_or(node)
+        };
+        let errorNode = getErrorNode(node);
+        let err = self.lookupOrIssueError(Some(errorNode), message, vec![StringOrNumber::String(symbolName.to_string())]);
+        for relatedNode in relatedNodes.unwrap_or_default() {
+            let adjustedNode = getErrorNode(*relatedNode);
+            if adjustedNode.get_node_id() == errorNode.get_node_id() {
+                continue;
+            }
+            let leadingMessage = createDiagnosticForNode(&adjustedNode, Diagnostics::_0_was_also_declared_here(), vec![StringOrNumber::String(symbolName.to_string())]);
+            let followOnMessage = createDiagnosticForNode(&adjustedNode, Diagnostics::and_here(), vec![]);
+            let err = &mut self.diagnostics[err];
+            let relatedInformation = err.relatedInformation.as_deref().unwrap_or_default();
+            if relatedInformation.len() >= 5 || relatedInformation.iter().any(|r| compareDiagnostics(r, &followOnMessage).is_eq() || compareDiagnostics(r, &leadingMessage).is_eq()) {
+                continue;
+            }
+            let relatedInfo = if relatedInformation.is_empty() { leadingMessage } else { followOnMessage };
+            addRelatedInfo(err, vec![relatedInfo]);
+        }
     }
 
+    fn mergeSymbolTable(&mut self, target: rc_cell!(SymbolTable<'a>), source: rc_cell!(SymbolTable<'a>), unidirectional: Option<bool>, mergedParent: opt_rc_cell!(Symbol<'a>)) {
+        let sourceSymbols: Vec<_> = source.borrow().iter().map(|(id, sourceSymbol)| (id.clone(), sourceSymbol.clone())).collect();
+        for (id, sourceSymbol) in sourceSymbols {
+            let targetSymbol = target.borrow().get(&id).cloned();
+            let merged = match targetSymbol {
+                Some(targetSymbol) => self.mergeSymbol(targetSymbol, sourceSymbol, unidirectional),
+                None => self.getMergedSymbol(sourceSymbol),
+            };
+            if let Some(mergedParent) = &mergedParent {
+                merged.borrow_mut().parent = Some(mergedParent.clone());
+            }
+            target.borrow_mut().insert(id, merged);
+        }
+    }
+    // endregion: 2725
+
+    // region: 4137
+    fn getMergedSymbol(&self, symbol: rc_cell!(Symbol<'a>)) -> rc_cell!(Symbol<'a>) {
+        let mergeId = symbol.borrow().mergeId;
+        if mergeId != 0 {
+            if let Some(merged) = self.mergedSymbols.get(&mergeId) {
+                return merged.clone();
+            }
+        }
+        symbol
+    }
+    // endregion: 4143
+
+    // region: 5072
+    // An external module with an 'export =' declaration resolves to the target of the 'export =' declaration,
+    // and an external module with no 'export =' declaration resolves to the module itself.
+    fn resolveExternalModuleSymbol(&self, moduleSymbol: opt_rc_cell!(Symbol<'a>)) -> opt_rc_cell!(Symbol<'a>) {
+        let moduleSymbol = moduleSymbol?;
+        let Some(exports) = moduleSymbol.borrow().exports.clone() else {
+            return None;
+        };
+        // todo(RB): resolveSymbol and getCommonJsExportEquals, which only differ for aliases and CommonJS modules
+        let exportEquals = exports.borrow().get(InternalSymbolName::ExportEquals.as_str()).cloned();
+        Some(exportEquals.map(|exportEquals| self.getMergedSymbol(exportEquals)).unwrap_or(moduleSymbol))
+    }
+    // endregion: 5082
+
     // region: 5337
     fn createType(&mut self, flags: TypeFlags) -> TypeObject<'a> {
         let mut result: TypeObject<'a> = TypeObject::new(flags);
@@ -308,6 +697,22 @@ impl<'a> TypeChecker<'a> {
     }
     // endregion: 5381
 
+    // region: 5424
+    // ! rb SymbolTable does not keep insertion order, so the properties are passed in declaration order alongside the members.
+    // Call and construct signatures and index infos are not ported yet.
+    fn createAnonymousType(&mut self, symbol: opt_rc_cell!(Symbol<'a>), members: SymbolTable<'a>, properties: Vec<rc_cell!(Symbol<'a>)>) -> TypeObject<'a> {
+        let mut type_ = self.createObjectType(ObjectFlags::Anonymous, symbol);
+        // setStructuredTypeMembers
+        let resolved = type_.object_props.as_mut().unwrap();
+        resolved.members = Some(members);
+        resolved.properties = Some(properties);
+        resolved.callSignatures = Some(vec![]);
+        resolved.constructSignatures = Some(vec![]);
+        resolved.indexInfos = Some(vec![]);
+        type_
+    }
+    // endregion: 5433
+
     // region: 11394
 
     // Return the inferred type for a variable, parameter, or property declaration
@@ -460,6 +865,202 @@ impl<'a> TypeChecker<'a> {
     }
     // endregion: 11531
 
+    // region: 11855
+    fn getTypeOfVariableOrParameterOrProperty(&mut self, symbol: &rc_cell!(Symbol<'a>)) -> Rc<dyn Type<'a> + 'a> {
+        let id = getSymbolId(symbol.clone());
+        if let Some(type_) = self.symbolTypes.get(&id) {
+            return type_.clone();
+        }
+        // todo(RB): pushTypeResolution / popTypeResolution, to report circular references
+        let type_ = self.getTypeOfVariableOrParameterOrPropertyWorker(symbol);
+        self.symbolTypes.insert(id, type_.clone());
+        type_
+    }
+
+    fn getTypeOfVariableOrParameterOrPropertyWorker(&mut self, symbol: &rc_cell!(Symbol<'a>)) -> Rc<dyn Type<'a> + 'a> {
+        // Handle prototype property
+        // if (symbol.flags & SymbolFlags.Prototype) {
+        //     return getTypeOfPrototypeProperty(symbol);
+        // }
+        // CommonsJS require and module both have type any.
+        // if (symbol === requireSymbol) {
+        //     return anyType;
+        // }
+        // if (symbol.flags & SymbolFlags.ModuleExports && symbol.valueDeclaration) { ... }
+        let declaration = symbol.borrow().valueDeclaration.expect("symbol should have a value declaration").to_ast_kind();
+        // Handle export default expressions
+        if let Some(declaration) = declaration.as_source_file() {
+            if isJsonSourceFile(declaration) {
+                let Some(Statement::ExpressionStatement(statement)) = declaration.body.first() else {
+                    return self.emptyObjectType.clone();
+                };
+                let type_ = self.checkExpression(&statement.expression);
+                let type_ = self.getWidenedLiteralType(type_);
+                return self.getWidenedType(type_);
+            }
+        }
+
+        // todo(RB): continue conversion from here
+
+        self.errorType.clone()
+    }
+    // endregion: 11905
+
+    // region: 12343
+    fn getTypeOfSymbol(&mut self, symbol: &rc_cell!(Symbol<'a>)) -> Rc<dyn Type<'a> + 'a> {
+        // const checkFlags = getCheckFlags(symbol);
+        // if (checkFlags & CheckFlags.DeferredType) {
+        //     return getTypeOfSymbolWithDeferredType(symbol);
+        // }
+        // if (checkFlags & CheckFlags.Instantiated) {
+        //     return getTypeOfInstantiatedSymbol(symbol);
+        // }
+        // if (checkFlags & CheckFlags.Mapped) {
+        //     return getTypeOfMappedSymbol(symbol as MappedSymbol);
+        // }
+        // if (checkFlags & CheckFlags.ReverseMapped) {
+        //     return getTypeOfReverseMappedSymbol(symbol as ReverseMappedSymbol);
+        // }
+        if symbol.borrow().flags.intersects(SymbolFlags::Variable | SymbolFlags::Property) {
+            return self.getTypeOfVariableOrParameterOrProperty(symbol);
+        }
+        // if (symbol.flags & (SymbolFlags.Function | SymbolFlags.Method | SymbolFlags.Class | SymbolFlags.Enum | SymbolFlags.ValueModule)) {
+        //     return getTypeOfFuncClassEnumModule(symbol);
+        // }
+        // if (symbol.flags & SymbolFlags.EnumMember) {
+        //     return getTypeOfEnumMember(symbol);
+        // }
+        // if (symbol.flags & SymbolFlags.Accessor) {
+        //     return getTypeOfAccessors(symbol);
+        // }
+        // if (symbol.flags & SymbolFlags.Alias) {
+        //     return getTypeOfAlias(symbol);
+        // }
+        self.errorType.clone()
+    }
+    // endregion: 12372
+
+    // region: 24075
+    // ! rb the fresh true and false types are the only fresh literal types so far; enum, string, number and bigint
+    // literal types are not ported yet, and neither are the unions that would be mapped over
+    fn getWidenedLiteralType(&self, type_: Rc<dyn Type<'a> + 'a>) -> Rc<dyn Type<'a> + 'a> {
+        let isFreshLiteralType = std::ptr::addr_eq(Rc::as_ptr(&type_), Rc::as_ptr(&self.falseType)) || std::ptr::addr_eq(Rc::as_ptr(&type_), Rc::as_ptr(&self.trueType));
+        if type_.getFlags().intersects(TypeFlags::BooleanLiteral) && isFreshLiteralType {
+            return self.booleanType.clone();
+        }
+        type_
+    }
+    // endregion: 24083
+
+    // region: 24836
+    // ! rb the widening null type of non-strict mode is the only type that requires widening so far, as object literals
+    // are created with their properties already widened (see checkObjectLiteral)
+    fn getWidenedType(&self, type_: Rc<dyn Type<'a> + 'a>) -> Rc<dyn Type<'a> + 'a> {
+        let isWideningNullType = std::ptr::addr_eq(Rc::as_ptr(&type_), Rc::as_ptr(&self.nullWideningType)) && !Rc::ptr_eq(&self.nullWideningType, &self.nullType);
+        if isWideningNullType {
+            return self.anyType.clone();
+        }
+        type_
+    }
+    // endregion: 24839
+
+    // region: 31957
+    // ! rb only object literals made of property assignments, the ones a JSON file can contain, are checked so far
+    fn checkObjectLiteral(&mut self, node: &'a ObjectExpression<'a>) -> Rc<dyn Type<'a> + 'a> {
+        let mut propertiesTable = createSymbolTable(None);
+        let mut propertiesArray = vec![];
+        for memberDecl in &node.properties {
+            let ObjectPropertyKind::ObjectProperty(memberDecl) = memberDecl else {
+                continue;
+            };
+            // ! rb object literal members are not bound yet, so the property is named after its key
+            let name = match &memberDecl.key {
+                PropertyKey::StringLiteral(key) => escapeLeadingUnderscores(key.value.as_str()),
+                PropertyKey::StaticIdentifier(key) => escapeLeadingUnderscores(key.name.as_str()),
+                _ => continue,
+            };
+            // ! rb getWidenedTypeOfObjectLiteral needs the members and object flags of a type, which `Type` does not
+            // expose yet, so each property is widened here and the literal is created as its own widened type
+            let type_ = self.checkPropertyAssignment(memberDecl);
+            let type_ = self.getWidenedType(type_);
+            let prop = self.createSymbol(SymbolFlags::Property, name.clone());
+            let declaration = AstKindDeclaration::ObjectProperty(&**memberDecl);
+            prop.borrow_mut().declarations = Some(vec![declaration]);
+            prop.borrow_mut().valueDeclaration = Some(declaration);
+            self.symbolTypes.insert(getSymbolId(prop.clone()), type_);
+            propertiesTable.insert(name, prop.clone());
+            propertiesArray.push(prop);
+        }
+
+        // createObjectLiteralType
+        let mut result = self.createAnonymousType(AstKind::ObjectExpression(node).symbol(), propertiesTable, propertiesArray);
+        result.object_flags = Some(result.get_object_flags() | ObjectFlags::ObjectLiteral | ObjectFlags::ContainsObjectOrArrayLiteral);
+        Rc::new(result)
+    }
+    // endregion: 32227
+
+    // region: 40135
+    fn checkPropertyAssignment(&mut self, node: &'a ObjectProperty<'a>) -> Rc<dyn Type<'a> + 'a> {
+        // Do not use hasDynamicName here, because that returns false for well known symbols.
+        // We want to perform checkComputedPropertyName for all computed properties, including
+        // well known symbols.
+        // if (node.name.kind === SyntaxKind.ComputedPropertyName) {
+        //     checkComputedPropertyName(node.name);
+        // }
+        self.checkExpressionForMutableLocation(&node.value)
+    }
+    // endregion: 40145
+
+    // region: 40310
+    // ! rb there are no contextual types yet, so literals are widened as they are without one
+    fn checkExpressionForMutableLocation(&mut self, node: &'a Expression<'a>) -> Rc<dyn Type<'a> + 'a> {
+        let type_ = self.checkExpression(node);
+        // isConstContext(node) || isCommonJsExportedExpression(node) ? getRegularTypeOfLiteralType(type) :
+        self.getWidenedLiteralType(type_)
+    }
+    // endregion: 40320
+
+    // region: 40453
+    fn checkExpression(&mut self, node: &'a Expression<'a>) -> Rc<dyn Type<'a> + 'a> {
+        // tracing?.push(tracing.Phase.Check, "checkExpression", { kind: node.kind, pos: node.pos, end: node.end, path: (node as TracingNode).tracingPath });
+        let saveCurrentNode = self.currentNode;
+        self.currentNode = Some(node.to_ast_kind());
+        self.instantiationCount = 0;
+        let uninstantiatedType = self.checkExpressionWorker(node);
+        // const type = instantiateTypeWithSingleGenericCallSignature(node, uninstantiatedType, checkMode);
+        // if (isConstEnumObjectType(type)) {
+        //     checkConstEnumAccess(node, type);
+        // }
+        self.currentNode = saveCurrentNode;
+        // tracing?.pop();
+        uninstantiatedType
+    }
+
+    // todo(RB): only the expressions a JSON file can contain are checked so far
+    fn checkExpressionWorker(&mut self, node: &'a Expression<'a>) -> Rc<dyn Type<'a> + 'a> {
+        match node {
+            // ! rb string and number literal types are not ported yet, so literals are given their base types,
+            // which is what they widen to outside of const contexts
+            Expression::StringLiteral(_) => self.stringType.clone(),
+            Expression::NumericLiteral(_) => self.numberType.clone(),
+            Expression::UnaryExpression(node) if node.operator == UnaryOperator::UnaryNegation && matches!(node.argument, Expression::NumericLiteral(_)) => self.numberType.clone(),
+            Expression::BooleanLiteral(node) => {
+                if node.value {
+                    self.trueType.clone()
+                } else {
+                    self.falseType.clone()
+                }
+            }
+            Expression::NullLiteral(_) => self.nullWideningType.clone(),
+            Expression::ObjectExpression(node) => self.checkObjectLiteral(node),
+            // ! rb there is no global Array type to instantiate without the lib files, so array literals are any
+            Expression::ArrayExpression(_) => self.anyType.clone(),
+            Expression::ParenthesizedExpression(node) => self.checkExpression(&node.expression),
+            _ => self.errorType.clone(),
+        }
+    }
+    // endregion: 40590
+
     // region: 49169
     fn getTypeOfNode(&self, node: &AstKind) -> &dyn Type<'a> {
         if let Some(source_file) = node.as_source_file() {
@@ -552,7 +1153,76 @@ impl<'a> TypeChecker<'a> {
         for file in unsafe { &*self.host.as_ref().get() }.getSourceFiles().borrow().iter() {
             bindSourceFile(&file.borrow(), &self.compilerOptions);
         }
+
+        self.amalgamatedDuplicates = Some(HashMap::new());
+
+        // Initialize global symbol table
+        // let augmentations: (readonly (StringLiteral | Identifier)[])[] | undefined;
+        for file in unsafe { &*self.host.as_ref().get() }.getSourceFiles().borrow().iter() {
+            let file = file.borrow();
+            // if (file.redirectInfo) {
+            //     continue;
+            // }
+            if !isExternalOrCommonJsModule(&file) {
+                // It is an error for a non-external-module (i.e. script) to declare its own `globalThis`.
+                let fileGlobalThisSymbol = file.locals().unwrap().borrow().get("globalThis").cloned();
+                if let Some(fileGlobalThisSymbol) = fileGlobalThisSymbol {
+                    for declaration in fileGlobalThisSymbol.borrow().declarations.iter().flatten() {
+                        self.diagnostics.push(createDiagnosticForNode(&declaration.to_ast_kind(), Diagnostics::Declaration_name_conflicts_with_built_in_global_identifier_0(), vec![StringOrNumber::String("globalThis".to_string())]));
+                    }
+                }
+                self.mergeSymbolTable(self.globals.clone(), file.locals().unwrap(), None, None);
+            }
+            if let Some(jsGlobalAugmentations) = file.jsGlobalAugmentations() {
+                self.mergeSymbolTable(self.globals.clone(), jsGlobalAugmentations, None, None);
+            }
+            // if (file.patternAmbientModules && file.patternAmbientModules.length) {
+            //     patternAmbientModules = concatenate(patternAmbientModules, file.patternAmbientModules);
+            // }
+            // if (file.moduleAugmentations.length) {
+            //     (augmentations || (augmentations = [])).push(file.moduleAugmentations);
+            // }
+            let globalExports = AstKind::SourceFile(&file).symbol().and_then(|symbol| symbol.borrow().globalExports.clone());
+            if let Some(source) = globalExports {
+                // Merge in UMD exports with first-in-wins semantics (see #9771)
+                for (id, sourceSymbol) in source.borrow().iter() {
+                    if !self.globals.borrow().contains_key(id) {
+                        self.globals.borrow_mut().insert(id.clone(), sourceSymbol.clone());
+                    }
+                }
+            }
+        }
+
         // todo(RB): continue conversion from here
+
+        // ! rb the map is sorted by file pair and symbol name because HashMap does not keep insertion order
+        let mut amalgamatedDuplicates: Vec<_> = self.amalgamatedDuplicates.take().unwrap().into_iter().collect();
+        amalgamatedDuplicates.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (_, DuplicateInfoForFiles { firstFile, secondFile, conflictingSymbols }) in amalgamatedDuplicates {
+            let mut conflictingSymbols: Vec<_> = conflictingSymbols.into_iter().collect();
+            conflictingSymbols.sort_by(|(a, _), (b, _)| a.cmp(b));
+            // If not many things conflict, issue individual errors
+            if conflictingSymbols.len() < 8 {
+                for (symbolName, DuplicateInfoForSymbol { isBlockScoped, firstFileLocations, secondFileLocations }) in conflictingSymbols {
+                    let message = if isBlockScoped { Diagnostics::Cannot_redeclare_block_scoped_variable_0() } else { Diagnostics::Duplicate_identifier_0() };
+                    for node in &firstFileLocations {
+                        self.addDuplicateDeclarationError(*node, message.clone(), &symbolName, Some(&secondFileLocations));
+                    }
+                    for node in &secondFileLocations {
+                        self.addDuplicateDeclarationError(*node, message.clone(), &symbolName, Some(&firstFileLocations));
+                    }
+                }
+            } else {
+                // Otherwise issue top-level error since the files appear very identical in terms of what they contain
+                let list = conflictingSymbols.iter().map(|(symbolName, _)| symbolName.as_str()).collect::<Vec<_>>().join(", ");
+                let mut diagnostic = createDiagnosticForNode(&AstKind::SourceFile(firstFile), Diagnostics::Definitions_of_the_following_identifiers_conflict_with_those_in_another_file_colon_0(), vec![StringOrNumber::String(list.clone())]);
+                addRelatedInfo(&mut diagnostic, vec![createDiagnosticForNode(&AstKind::SourceFile(secondFile), Diagnostics::Conflicts_are_in_this_file(), vec![])]);
+                self.diagnostics.push(diagnostic);
+                let mut diagnostic = createDiagnosticForNode(&AstKind::SourceFile(secondFile), Diagnostics::Definitions_of_the_following_identifiers_conflict_with_those_in_another_file_colon_0(), vec![StringOrNumber::String(list)]);
+                addRelatedInfo(&mut diagnostic, vec![createDiagnosticForNode(&AstKind::SourceFile(firstFile), Diagnostics::Conflicts_are_in_this_file(), vec![])]);
+                self.diagnostics.push(diagnostic);
+            }
+        }
     }
     // endregion: 50479
 }
@@ -685,8 +1355,16 @@ impl<'a> TypeCheckerTrait<'a> for TypeChecker<'a> {
     fn isSymbolAccessible(&self, symbol: Symbol, enclosingDeclaration: Option<AstKind>, meaning: SymbolFlags, shouldComputeAliasToMarkVisible: bool) -> SymbolAccessibilityResult { todo!() }
     fn tryFindAmbientModule(&self, moduleName: &str) -> Option<Symbol<'a>> { todo!() }
     fn getSymbolWalker(&self, accept: Option<fn(Symbol<'a>) -> bool>) -> SymbolWalker { todo!() }
-    fn getDiagnostics(&self, sourceFile: Option<&SourceFile<'a>>, cancellationToken: Option<CancellationToken>, nodesToCheck: Option<Vec<AstKind>>) -> Vec<Diagnostic> { todo!() }
-    fn getGlobalDiagnostics(&self) -> Vec<Diagnostic> { todo!() }
+    fn getDiagnostics(&self, sourceFile: Option<&SourceFile<'a>>, cancellationToken: Option<CancellationToken>, nodesToCheck: Option<Vec<AstKind>>) -> Vec<Diagnostic> {
+        // ! rb checkSourceFile is not ported, as the statement and expression checks it runs are not converted yet. The semantic
+        // diagnostics are only the ones initializeTypeChecker reports while merging the files' globals, so executeCommandLine
+        // does not treat a program without diagnostics as checked
+        match sourceFile {
+            Some(sourceFile) => self.diagnostics.iter().filter(|d| d.file.map_or(false, |file| file.filepath() == sourceFile.filepath())).cloned().collect(),
+            None => self.diagnostics.clone(),
+        }
+    }
+    fn getGlobalDiagnostics(&self) -> Vec<Diagnostic> { self.diagnostics.iter().filter(|d| d.file.is_none()).cloned().collect() }
     fn getEmitResolver(&self, sourceFile: Option<&SourceFile<'a>>, cancellationToken: Option<CancellationToken>, forceDts: Option<bool>) -> EmitResolver { todo!() }
     fn requiresAddingImplicitUndefined(&self, parameter: Argument<'a>, enclosingDeclaration: Option<AstKind>) -> bool { todo!() }
     fn getNodeCount(&self) -> usize { todo!() }
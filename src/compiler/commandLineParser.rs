@@ -0,0 +1,1626 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use serde_json::Value;
+
+use super::core::getSpellingSuggestion;
+use super::diagnostic_information_map_generated::Diagnostics;
+use super::moduleNameResolver::nodeNextJsonConfigResolver;
+use super::path::*;
+use super::scanner::computeLineStarts;
+use super::types::*;
+use super::utilities::{changeExtension, createCompilerDiagnostic, createDetachedDiagnostic, getRegularExpressionsForWildcards, getSupportedExtensions, getSupportedExtensionsWithJsonIfResolveJsonModule, hasZeroOrOneAsteriskCharacter, matchesWildcardPattern, WildcardMatcherUsage};
+
+// region: 258
+/** The enum types of compiler options, as they are spelled in tsconfig.json and on the command line */
+trait CommandLineOptionMap: Copy + PartialEq + 'static {
+    const entries: &'static [(&'static str, Self)];
+
+    fn keys() -> Vec<&'static str> { Self::entries.iter().map(|(key, _)| *key).collect() }
+
+    fn fromKey(key: &str) -> Option<Self> { Self::entries.iter().find(|(k, _)| *k == key).map(|(_, value)| *value) }
+
+    fn toKey(self) -> &'static str { Self::entries.iter().find(|(_, value)| *value == self).map(|(key, _)| *key).unwrap() }
+}
+
+impl CommandLineOptionMap for ScriptTarget {
+    const entries: &'static [(&'static str, Self)] = &[
+        ("es3", ScriptTarget::ES3),
+        ("es5", ScriptTarget::ES5),
+        ("es6", ScriptTarget::ES2015),
+        ("es2015", ScriptTarget::ES2015),
+        ("es2016", ScriptTarget::ES2016),
+        ("es2017", ScriptTarget::ES2017),
+        ("es2018", ScriptTarget::ES2018),
+        ("es2019", ScriptTarget::ES2019),
+        ("es2020", ScriptTarget::ES2020),
+        ("es2021", ScriptTarget::ES2021),
+        ("es2022", ScriptTarget::ES2022),
+        ("es2023", ScriptTarget::ES2023),
+        ("esnext", ScriptTarget::ESNext),
+    ];
+}
+
+impl CommandLineOptionMap for ModuleKind {
+    const entries: &'static [(&'static str, Self)] = &[
+        ("none", ModuleKind::None),
+        ("commonjs", ModuleKind::CommonJS),
+        ("amd", ModuleKind::AMD),
+        ("system", ModuleKind::System),
+        ("umd", ModuleKind::UMD),
+        ("es6", ModuleKind::ES2015),
+        ("es2015", ModuleKind::ES2015),
+        ("es2020", ModuleKind::ES2020),
+        ("es2022", ModuleKind::ES2022),
+        ("esnext", ModuleKind::ESNext),
+        ("node16", ModuleKind::Node16),
+        ("nodenext", ModuleKind::NodeNext),
+        ("preserve", ModuleKind::Preserve),
+    ];
+}
+
+impl CommandLineOptionMap for ModuleResolutionKind {
+    const entries: &'static [(&'static str, Self)] =
+        &[("node10", ModuleResolutionKind::Node10), ("node", ModuleResolutionKind::Node10), ("classic", ModuleResolutionKind::Classic), ("node16", ModuleResolutionKind::Node16), ("nodenext", ModuleResolutionKind::NodeNext), ("bundler", ModuleResolutionKind::Bundler)];
+}
+
+impl CommandLineOptionMap for ModuleDetectionKind {
+    const entries: &'static [(&'static str, Self)] = &[("auto", ModuleDetectionKind::Auto), ("legacy", ModuleDetectionKind::Legacy), ("force", ModuleDetectionKind::Force)];
+}
+
+impl CommandLineOptionMap for JsxEmit {
+    const entries: &'static [(&'static str, Self)] = &[("preserve", JsxEmit::Preserve), ("react-native", JsxEmit::ReactNative), ("react-jsx", JsxEmit::ReactJSX), ("react-jsxdev", JsxEmit::ReactJSXDev), ("react", JsxEmit::React)];
+}
+
+impl CommandLineOptionMap for NewLineKind {
+    const entries: &'static [(&'static str, Self)] = &[("crlf", NewLineKind::CarriageReturnLineFeed), ("lf", NewLineKind::LineFeed)];
+}
+
+impl CommandLineOptionMap for ImportsNotUsedAsValues {
+    const entries: &'static [(&'static str, Self)] = &[("remove", ImportsNotUsedAsValues::Remove), ("preserve", ImportsNotUsedAsValues::Preserve), ("error", ImportsNotUsedAsValues::Error)];
+}
+
+macro_rules! optionName {
+    ($field:ident) => {
+        stringify!($field)
+    };
+    ($field:ident, $name:literal) => {
+        $name
+    };
+}
+
+macro_rules! optionType {
+    (Map, $map:ty) => {
+        CommandLineOptionType::Map(<$map as CommandLineOptionMap>::keys())
+    };
+    ($kind:ident) => {
+        CommandLineOptionType::$kind
+    };
+}
+
+macro_rules! fromOptionValue {
+    (Map, $map:ty, $value:expr) => {
+        <$map as CommandLineOptionMap>::fromKey($value.as_str().unwrap()).unwrap()
+    };
+    (String, $value:expr) => {
+        $value.as_str().unwrap().to_string()
+    };
+    (Number, $value:expr) => {
+        $value.as_f64().unwrap() as i32
+    };
+    (Boolean, $value:expr) => {
+        $value.as_bool().unwrap()
+    };
+    (Object, $value:expr) => {
+        serde_json::from_value($value.clone()).unwrap_or_default()
+    };
+    (List, $value:expr) => {
+        $value.as_array().unwrap().iter().filter_map(|element| element.as_str()).map(|element| element.to_string()).collect()
+    };
+}
+
+macro_rules! toOptionValue {
+    (Map, $map:ty, $value:expr) => {
+        Value::String(<$map as CommandLineOptionMap>::toKey(*$value).to_string())
+    };
+    (String, $value:expr) => {
+        Value::String($value.clone())
+    };
+    (Number, $value:expr) => {
+        Value::from(*$value)
+    };
+    (Boolean, $value:expr) => {
+        Value::Bool(*$value)
+    };
+    (Object, $value:expr) => {
+        serde_json::to_value($value).unwrap()
+    };
+    (List, $value:expr) => {
+        Value::from($value.clone())
+    };
+}
+
+/**
+ * Declares the compiler options that can be set in tsconfig.json or on the command line. Every entry names the
+ * `CompilerOptions` field it is stored in, and the accessors below are generated from the same list so the
+ * declarations and the options struct can't drift apart.
+ */
+macro_rules! compilerOptionDeclarations {
+    ($($field:ident $(as $name:literal)? $(short $shortName:literal)?: $kind:ident $(<$map:ty>)? $([$($flag:ident),*])?;)*) => {
+        lazy_static! {
+            /** @internal */
+            pub static ref optionDeclarations: Vec<CommandLineOption> = vec![$(CommandLineOption {
+                name: optionName!($field $(, $name)?),
+                type_: optionType!($kind $(, $map)?),
+                shortName: None $(.or(Some($shortName)))?,
+                $($($flag: true,)*)?
+                ..Default::default()
+            },)*];
+        }
+
+        fn setCompilerOptionValue(options: &mut CompilerOptions, name: &str, value: Option<&Value>) {
+            $(if name == optionName!($field $(, $name)?) {
+                options.$field = value.map(|value| fromOptionValue!($kind $(, $map)?, value));
+                return;
+            })*
+            unreachable!("'{name}' is not a declared compiler option");
+        }
+
+        fn getCompilerOptionValue(options: &CompilerOptions, name: &str) -> Option<Value> {
+            $(if name == optionName!($field $(, $name)?) {
+                return options.$field.as_ref().map(|value| toOptionValue!($kind $(, $map)?, value));
+            })*
+            unreachable!("'{name}' is not a declared compiler option");
+        }
+
+        /** Copies every option that is set in `source` over the ones in `target` */
+        fn extendCompilerOptions(target: &mut CompilerOptions, source: &CompilerOptions) {
+            $(if source.$field.is_some() {
+                target.$field = source.$field.clone();
+            })*
+            if source.configFilePath.is_some() {
+                target.configFilePath = source.configFilePath.clone();
+            }
+            if source.pathsBasePath.is_some() {
+                target.pathsBasePath = source.pathsBasePath.clone();
+            }
+        }
+    };
+}
+
+compilerOptionDeclarations! {
+    // CommandLine only options
+    help short "h": Boolean [isCommandLineOnly];
+    watch short "w": Boolean [isCommandLineOnly];
+    preserveWatchOutput: Boolean;
+    listFiles: Boolean;
+    explainFiles: Boolean;
+    listEmittedFiles: Boolean;
+    pretty: Boolean;
+    traceResolution: Boolean;
+    diagnostics: Boolean;
+    extendedDiagnostics: Boolean;
+    generateCpuProfile: String [isFilePath];
+    generateTrace: String [isFilePath];
+    incremental short "i": Boolean;
+    declaration short "d": Boolean;
+    declarationMap: Boolean;
+    emitDeclarationOnly: Boolean;
+    sourceMap: Boolean;
+    inlineSourceMap: Boolean;
+    noCheck: Boolean;
+    noEmit: Boolean;
+    assumeChangesOnlyAffectDirectDependencies: Boolean;
+    locale: String;
+    all: Boolean [isCommandLineOnly];
+    version short "v": Boolean [isCommandLineOnly];
+    init: Boolean [isCommandLineOnly];
+    project short "p": String [isFilePath, isCommandLineOnly];
+    build short "b": Boolean [isCommandLineOnly];
+    showConfig: Boolean [isCommandLineOnly];
+    listFilesOnly: Boolean [isCommandLineOnly];
+
+    // Basic
+    target short "t": Map<ScriptTarget>;
+    module short "m": Map<ModuleKind>;
+    lib: List;
+    allowJs: Boolean;
+    checkJs: Boolean;
+    jsx: Map<JsxEmit>;
+    outFile: String [isFilePath];
+    outDir: String [isFilePath];
+    rootDir: String [isFilePath];
+    composite: Boolean;
+    tsBuildInfoFile: String [isFilePath];
+    removeComments: Boolean;
+    importHelpers: Boolean;
+    importsNotUsedAsValues: Map<ImportsNotUsedAsValues>;
+    downlevelIteration: Boolean;
+    isolatedModules: Boolean;
+    verbatimModuleSyntax: Boolean;
+    isolatedDeclarations: Boolean;
+
+    // Strict Type Checks
+    strict: Boolean;
+    noImplicitAny: Boolean;
+    strictNullChecks: Boolean;
+    strictFunctionTypes: Boolean;
+    strictBindCallApply: Boolean;
+    strictPropertyInitialization: Boolean;
+    strictBuiltinIteratorReturn: Boolean;
+    noImplicitThis: Boolean;
+    useUnknownInCatchVariables: Boolean;
+    alwaysStrict: Boolean;
+
+    // Additional Checks
+    noUnusedLocals: Boolean;
+    noUnusedParameters: Boolean;
+    exactOptionalPropertyTypes: Boolean;
+    noImplicitReturns: Boolean;
+    noFallthroughCasesInSwitch: Boolean;
+    noUncheckedIndexedAccess: Boolean;
+    noImplicitOverride: Boolean;
+    noPropertyAccessFromIndexSignature: Boolean;
+
+    // Module Resolution
+    moduleResolution: Map<ModuleResolutionKind>;
+    baseUrl: String [isFilePath];
+    paths: Object [isTSConfigOnly];
+    rootDirs: List [isFilePath];
+    typeRoots: List [isFilePath];
+    types: List;
+    allowSyntheticDefaultImports: Boolean;
+    esModuleInterop: Boolean;
+    preserveSymlinks: Boolean;
+    allowUmdGlobalAccess: Boolean;
+    moduleSuffixes: List [listPreserveFalsyValues];
+    allowImportingTsExtensions: Boolean;
+    resolvePackageJsonExports: Boolean;
+    resolvePackageJsonImports: Boolean;
+    customConditions: List;
+    noUncheckedSideEffectImports: Boolean;
+
+    // Source Maps
+    sourceRoot: String;
+    mapRoot: String;
+    inlineSources: Boolean;
+
+    // Experimental
+    experimentalDecorators: Boolean;
+    emitDecoratorMetadata: Boolean;
+
+    // Advanced
+    jsxFactory: String;
+    jsxFragmentFactory: String;
+    jsxImportSource: String;
+    resolveJsonModule: Boolean;
+    allowArbitraryExtensions: Boolean;
+    reactNamespace: String;
+    skipDefaultLibCheck: Boolean;
+    charset: String;
+    emitBom as "emitBOM": Boolean;
+    newLine: Map<NewLineKind>;
+    noErrorTruncation: Boolean;
+    noLib: Boolean;
+    noResolve: Boolean;
+    stripInternal: Boolean;
+    disableSizeLimit: Boolean;
+    disableSourceOfProjectReferenceRedirect: Boolean;
+    disableSolutionSearching: Boolean;
+    disableReferencedProjectLoad: Boolean;
+    noImplicitUseStrict: Boolean;
+    noEmitHelpers: Boolean;
+    noEmitOnError: Boolean;
+    preserveConstEnums: Boolean;
+    declarationDir: String [isFilePath];
+    skipLibCheck: Boolean;
+    allowUnusedLabels: Boolean;
+    allowUnreachableCode: Boolean;
+    suppressExcessPropertyErrors: Boolean;
+    suppressImplicitAnyIndexErrors: Boolean;
+    forceConsistentCasingInFileNames: Boolean;
+    maxNodeModuleJsDepth: Number;
+    noStrictGenericChecks: Boolean;
+    useDefineForClassFields: Boolean;
+    preserveValueImports: Boolean;
+    keyofStringsOnly: Boolean;
+    ignoreDeprecations: String;
+    moduleDetection: Map<ModuleDetectionKind>;
+}
+// endregion: 1672
+
+// region: 1715
+/** @internal */
+#[derive(Debug)]
+pub struct OptionsNameMap {
+    pub optionsNameMap: HashMap<String, &'static CommandLineOption>,
+    pub shortOptionNames: HashMap<&'static str, String>,
+}
+
+lazy_static! {
+    static ref optionsNameMapCache: OptionsNameMap = createOptionNameMap(&optionDeclarations);
+}
+
+/** @internal */
+pub fn createOptionNameMap(optionDeclarations: &'static [CommandLineOption]) -> OptionsNameMap {
+    let mut optionsNameMap = HashMap::new();
+    let mut shortOptionNames = HashMap::new();
+    for option in optionDeclarations {
+        optionsNameMap.insert(option.name.to_lowercase(), option);
+        if let Some(shortName) = option.shortName {
+            shortOptionNames.insert(shortName, option.name.to_string());
+        }
+    }
+
+    OptionsNameMap { optionsNameMap, shortOptionNames }
+}
+
+/** @internal */
+pub fn getOptionsNameMap() -> &'static OptionsNameMap { &optionsNameMapCache }
+
+fn createUnknownOptionError(unknownOption: &str, unknownOptionErrorText: Option<&str>) -> Diagnostic<'static> {
+    let errorText = unknownOptionErrorText.unwrap_or(unknownOption).to_string();
+    match getSpellingSuggestion(unknownOption, optionDeclarations.iter(), |option| Some(option.name)) {
+        Some(possibleOption) => createCompilerDiagnostic(Diagnostics::Unknown_compiler_option_0_Did_you_mean_1(), vec![errorText.into(), possibleOption.name.to_string().into()]),
+        None => createCompilerDiagnostic(Diagnostics::Unknown_compiler_option_0(), vec![errorText.into()]),
+    }
+}
+// endregion: 1784
+
+// region: 1786
+/** @internal */
+pub fn parseCommandLine(commandLine: &[String]) -> ParsedCommandLine {
+    let mut options = CompilerOptions::default();
+    let mut fileNames = vec![];
+    let mut errors = vec![];
+
+    let mut i = 0;
+    while i < commandLine.len() {
+        let s = &commandLine[i];
+        i += 1;
+        if s.starts_with('-') {
+            let inputOptionName = if s.starts_with("--") { &s[2..] } else { &s[1..] };
+            match getOptionDeclarationFromName(inputOptionName, /*allowShort*/ true) {
+                Some(opt) => i = parseOptionValue(commandLine, i, opt, &mut options, &mut errors),
+                None => errors.push(createUnknownOptionError(inputOptionName, Some(s))),
+            }
+        } else {
+            fileNames.push(s.clone());
+        }
+    }
+
+    ParsedCommandLine { options, fileNames, errors, ..Default::default() }
+}
+
+fn parseOptionValue(args: &[String], mut i: usize, opt: &CommandLineOption, options: &mut CompilerOptions, errors: &mut Vec<Diagnostic<'static>>) -> usize {
+    let optValue = args.get(i).map(|arg| arg.as_str());
+    if opt.isTSConfigOnly {
+        if optValue == Some("null") {
+            setCompilerOptionValue(options, opt.name, None);
+            i += 1;
+        } else if opt.type_ == CommandLineOptionType::Boolean {
+            if optValue == Some("false") {
+                setCompilerOptionValue(options, opt.name, Some(&Value::Bool(false)));
+                i += 1;
+            } else {
+                if optValue == Some("true") {
+                    i += 1;
+                }
+                errors.push(createCompilerDiagnostic(Diagnostics::Option_0_can_only_be_specified_in_tsconfig_json_file_or_set_to_false_or_null_on_command_line(), vec![opt.name.to_string().into()]));
+            }
+        } else {
+            errors.push(createCompilerDiagnostic(Diagnostics::Option_0_can_only_be_specified_in_tsconfig_json_file_or_set_to_null_on_command_line(), vec![opt.name.to_string().into()]));
+            if optValue.is_some_and(|optValue| !optValue.is_empty() && !optValue.starts_with('-')) {
+                i += 1;
+            }
+        }
+        return i;
+    }
+
+    // Check to see if no argument was provided (e.g. "--locale" is the last command-line argument).
+    if optValue.map_or(true, |optValue| optValue.is_empty()) && opt.type_ != CommandLineOptionType::Boolean {
+        errors.push(createCompilerDiagnostic(Diagnostics::Compiler_option_0_expects_an_argument(), vec![opt.name.to_string().into(), getCompilerOptionValueTypeString(opt).to_string().into()]));
+    }
+
+    if optValue == Some("null") {
+        setCompilerOptionValue(options, opt.name, None);
+        return i + 1;
+    }
+
+    match &opt.type_ {
+        CommandLineOptionType::Number => {
+            let value = optValue.and_then(|optValue| optValue.parse::<i32>().ok()).map(Value::from);
+            setCompilerOptionValue(options, opt.name, value.as_ref());
+            i += 1;
+        }
+        CommandLineOptionType::Boolean => {
+            // boolean flag has optional value true, false, others
+            setCompilerOptionValue(options, opt.name, Some(&Value::Bool(optValue != Some("false"))));
+            // consume next argument as boolean flag value
+            if optValue == Some("false") || optValue == Some("true") {
+                i += 1;
+            }
+        }
+        CommandLineOptionType::String => {
+            let value = Value::String(optValue.unwrap_or("").to_string());
+            setCompilerOptionValue(options, opt.name, Some(&value));
+            i += 1;
+        }
+        CommandLineOptionType::List => {
+            let result = parseListTypeOption(optValue.unwrap_or(""));
+            let consumed = result.is_some();
+            setCompilerOptionValue(options, opt.name, Some(&Value::from(result.unwrap_or_default())));
+            if consumed {
+                i += 1;
+            }
+        }
+        // If not a primitive, the possible types are specified in what is effectively a map of options.
+        CommandLineOptionType::Map(_) => {
+            let value = parseCustomTypeOption(opt, optValue.unwrap_or(""), errors);
+            setCompilerOptionValue(options, opt.name, value.as_ref());
+            i += 1;
+        }
+        CommandLineOptionType::Object => unreachable!("object options are tsconfig only"),
+    }
+
+    i
+}
+
+fn parseCustomTypeOption(opt: &CommandLineOption, value: &str, errors: &mut Vec<Diagnostic<'static>>) -> Option<Value> { convertJsonOptionOfCustomType(opt, value.trim(), errors) }
+
+fn parseListTypeOption(value: &str) -> Option<Vec<String>> {
+    let value = value.trim();
+    if value.starts_with('-') {
+        return None;
+    }
+    if value.is_empty() {
+        return Some(vec![]);
+    }
+    Some(value.split(',').map(|element| element.to_string()).collect())
+}
+// endregion: 1868
+
+// region: 1900
+/** @internal */
+pub fn getOptionFromName(optionName: &str, allowShort: Option<bool>) -> Option<&'static CommandLineOption> { getOptionDeclarationFromName(optionName, allowShort.unwrap_or(false)) }
+
+fn getOptionDeclarationFromName(optionName: &str, allowShort: bool) -> Option<&'static CommandLineOption> {
+    let mut optionName = optionName.to_lowercase();
+    let OptionsNameMap { optionsNameMap, shortOptionNames } = getOptionsNameMap();
+    // Try to translate short option names to their full equivalents.
+    if allowShort {
+        if let Some(short) = shortOptionNames.get(optionName.as_str()) {
+            optionName = short.to_lowercase();
+        }
+    }
+    optionsNameMap.get(&optionName).copied()
+}
+// endregion: 1913
+
+// region: 2021
+/**
+ * Reads the config file, reports errors if any and exits if the config file cannot be found
+ */
+pub fn getParsedCommandLineOfConfigFile(configFileName: &str, optionsToExtend: Option<&CompilerOptions>, host: &dyn ParseConfigHost) -> Result<ParsedCommandLine, Diagnostic<'static>> {
+    let configFileText = tryReadFile(configFileName, |fileName| host.readFile(fileName))?;
+    let cwd = host.getCurrentDirectory().unwrap_or_default();
+    let configFileName = getNormalizedAbsolutePath(configFileName, Some(&cwd));
+    let sourceFile = parseJsonConfigFileText(&configFileName, &configFileText);
+    let errors = sourceFile.parseDiagnostics.clone();
+
+    Ok(parseJsonConfigFileContentWorker(sourceFile.json.clone(), Some(&sourceFile), host, &getDirectoryPath(&configFileName), optionsToExtend, Some(&configFileName), vec![], errors))
+}
+
+/**
+ * Read tsconfig.json file
+ * @param fileName The path to the config file
+ */
+pub fn readConfigFile(fileName: &str, readFile: impl Fn(&str) -> Option<String>) -> Result<Value, Diagnostic<'static>> {
+    let text = tryReadFile(fileName, readFile)?;
+    parseConfigFileTextToJson(fileName, &text)
+}
+
+/**
+ * Parse the text of the tsconfig.json file
+ * @param fileName The path to the config file
+ * @param jsonText The text of the config file
+ */
+pub fn parseConfigFileTextToJson(fileName: &str, jsonText: &str) -> Result<Value, Diagnostic<'static>> {
+    let sourceFile = parseJsonConfigFileText(fileName, jsonText);
+    match sourceFile.parseDiagnostics.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(sourceFile.json),
+    }
+}
+
+/**
+ * Read tsconfig.json file
+ * @param fileName The path to the config file
+ */
+pub fn readJsonConfigFile(fileName: &str, readFile: impl Fn(&str) -> Option<String>) -> Result<TsConfigSourceFile, Diagnostic<'static>> {
+    let text = tryReadFile(fileName, readFile)?;
+    Ok(parseJsonConfigFileText(fileName, &text))
+}
+
+/**
+ * A parsed config file. serde keeps no positions, so the span of each value is recorded separately, keyed by the
+ * JSON pointer (`/compilerOptions/paths/@~1*`) that `Value::pointer` takes to reach it.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct TsConfigSourceFile {
+    pub fileName: String,
+    pub text: String,
+    /** The contents of the file, or an empty object when it could not be parsed */
+    pub json: Value,
+    pub parseDiagnostics: Vec<Diagnostic<'static>>,
+    locations: HashMap<String, JsonValueLocation>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct JsonValueLocation {
+    /** The span of the property name, for the members of an object */
+    name: Option<(u32, u32)>,
+    value: (u32, u32),
+}
+
+impl TsConfigSourceFile {
+    /**
+     * Points `diagnostic` at the value that `pointer` refers to, or at its property name when `onKey` is set.
+     * Diagnostics about values that are not in this file, like those inherited from an extended config, are left global.
+     */
+    pub fn locateDiagnostic(&self, pointer: &str, onKey: bool, mut diagnostic: Diagnostic<'static>) -> Diagnostic<'static> {
+        let Some(location) = self.locations.get(pointer) else {
+            return diagnostic;
+        };
+        let (start, length) = if onKey { location.name.unwrap_or(location.value) } else { location.value };
+        diagnostic.fileName = Some(self.fileName.clone());
+        diagnostic.start = Some(start);
+        diagnostic.length = Some(length);
+        diagnostic
+    }
+}
+
+/** Locates `diagnostic` in `sourceFile` if there is one, see `TsConfigSourceFile::locateDiagnostic`. */
+fn locateConfigDiagnostic(sourceFile: Option<&TsConfigSourceFile>, pointer: &str, onKey: bool, diagnostic: Diagnostic<'static>) -> Diagnostic<'static> {
+    match sourceFile {
+        Some(sourceFile) => sourceFile.locateDiagnostic(pointer, onKey, diagnostic),
+        None => diagnostic,
+    }
+}
+
+/** Appends `token` to `pointer` as one more reference token, escaped as in RFC 6901. */
+fn jsonPointer(pointer: &str, token: &str) -> String { format!("{pointer}/{}", token.replace('~', "~0").replace('/', "~1")) }
+
+/**
+ * Parses the text of a config file. Syntax errors are reported at their position with the diagnostics TypeScript's
+ * JSON parser reports, and leave the file with an empty object as its contents.
+ */
+pub fn parseJsonConfigFileText(fileName: &str, text: &str) -> TsConfigSourceFile {
+    let bom = if text.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+    let jsonText = convertJsoncToJson(text);
+    let mut locations = HashMap::new();
+    locateJsonValues(jsonText.as_bytes(), skipJsonWhitespace(jsonText.as_bytes(), 0), "", bom as u32, &mut locations);
+
+    let (json, parseDiagnostics) = match serde_json::from_str::<Value>(&jsonText) {
+        Ok(config @ Value::Object(_)) => (config, vec![]),
+        Ok(_) => {
+            let error = createCompilerDiagnostic(Diagnostics::The_root_value_of_a_0_file_must_be_an_object(), vec![getBaseFileName(fileName).into()]);
+            let error = match locations.get("") {
+                Some(JsonValueLocation { value: (start, length), .. }) => Diagnostic { fileName: Some(fileName.to_string()), start: Some(*start), length: Some(*length), ..error },
+                None => error,
+            };
+            (Value::Object(Default::default()), vec![error])
+        }
+        Err(error) => {
+            // serde reports one-based lines and columns; the converted text keeps every offset of the original after its BOM
+            let lineStarts = computeLineStarts(&jsonText);
+            let line = error.line().saturating_sub(1).min(lineStarts.len().saturating_sub(1));
+            let start = (lineStarts.get(line).copied().unwrap_or(0) + error.column().saturating_sub(1)).min(jsonText.len());
+            let (message, args) = getJsonSyntaxErrorMessage(&error);
+            let length = if start < jsonText.len() { 1 } else { 0 };
+            (Value::Object(Default::default()), vec![createDetachedDiagnostic(fileName, text, start as u32 + bom as u32, length, message, args)])
+        }
+    };
+    TsConfigSourceFile { fileName: fileName.to_string(), text: text.to_string(), json, parseDiagnostics, locations }
+}
+
+/** Maps a serde syntax error to the diagnostic the TypeScript scanner and parser report for the same mistake. */
+fn getJsonSyntaxErrorMessage(error: &serde_json::Error) -> (DiagnosticMessage, DiagnosticArguments) {
+    if error.is_eof() {
+        return (Diagnostics::Unexpected_end_of_text(), vec![]);
+    }
+    let message = error.to_string();
+    let message = message.split(" at line ").next().unwrap_or_default();
+    match message {
+        "expected `:`" => (Diagnostics::_0_expected(), vec![":".to_string().into()]),
+        "expected `,` or `}`" | "expected `,` or `]`" => (Diagnostics::_0_expected(), vec![",".to_string().into()]),
+        "key must be a string" => (Diagnostics::String_literal_with_double_quotes_expected(), vec![]),
+        "trailing characters" | "trailing comma" => (Diagnostics::Unexpected_token(), vec![]),
+        "invalid number" | "number out of range" => (Diagnostics::Digit_expected(), vec![]),
+        _ if message.starts_with("control character") => (Diagnostics::Unterminated_string_literal(), vec![]),
+        _ if message.starts_with("invalid escape") || message.starts_with("invalid unicode") || message.starts_with("lone leading surrogate") || message.starts_with("unexpected end of hex escape") => (Diagnostics::Invalid_character(), vec![]),
+        _ => (Diagnostics::Expression_expected(), vec![]),
+    }
+}
+
+fn skipJsonWhitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/**
+ * Records the span of the value starting at `pos` and of everything nested in it, and returns where the value ends.
+ * Malformed text stops the walk; serde reports the error itself.
+ */
+fn locateJsonValues(bytes: &[u8], pos: usize, pointer: &str, offset: u32, locations: &mut HashMap<String, JsonValueLocation>) -> Option<usize> {
+    let end = locateJsonValue(bytes, pos, pointer, offset, locations)?;
+    let span = (pos as u32 + offset, (end - pos) as u32);
+    locations.entry(pointer.to_string()).and_modify(|location| location.value = span).or_insert(JsonValueLocation { name: None, value: span });
+    Some(end)
+}
+
+fn locateJsonValue(bytes: &[u8], pos: usize, pointer: &str, offset: u32, locations: &mut HashMap<String, JsonValueLocation>) -> Option<usize> {
+    match bytes.get(pos)? {
+        b'{' => {
+            let mut pos = skipJsonWhitespace(bytes, pos + 1);
+            if bytes.get(pos) == Some(&b'}') {
+                return Some(pos + 1);
+            }
+            loop {
+                let nameEnd = locateJsonString(bytes, pos)?;
+                let name: String = serde_json::from_slice(&bytes[pos..nameEnd]).ok()?;
+                let memberPointer = jsonPointer(pointer, &name);
+                locations.insert(memberPointer.clone(), JsonValueLocation { name: Some((pos as u32 + offset, (nameEnd - pos) as u32)), value: (pos as u32 + offset, 0) });
+                pos = skipJsonWhitespace(bytes, nameEnd);
+                if bytes.get(pos) != Some(&b':') {
+                    return None;
+                }
+                pos = skipJsonWhitespace(bytes, pos + 1);
+                pos = skipJsonWhitespace(bytes, locateJsonValues(bytes, pos, &memberPointer, offset, locations)?);
+                match bytes.get(pos)? {
+                    b',' => pos = skipJsonWhitespace(bytes, pos + 1),
+                    b'}' => return Some(pos + 1),
+                    _ => return None,
+                }
+            }
+        }
+        b'[' => {
+            let mut pos = skipJsonWhitespace(bytes, pos + 1);
+            if bytes.get(pos) == Some(&b']') {
+                return Some(pos + 1);
+            }
+            let mut index = 0;
+            loop {
+                pos = skipJsonWhitespace(bytes, locateJsonValues(bytes, pos, &jsonPointer(pointer, &index.to_string()), offset, locations)?);
+                index += 1;
+                match bytes.get(pos)? {
+                    b',' => pos = skipJsonWhitespace(bytes, pos + 1),
+                    b']' => return Some(pos + 1),
+                    _ => return None,
+                }
+            }
+        }
+        b'"' => locateJsonString(bytes, pos),
+        _ => {
+            let end = bytes[pos..].iter().position(|&b| b.is_ascii_whitespace() || matches!(b, b',' | b']' | b'}')).map_or(bytes.len(), |length| pos + length);
+            if end == pos {
+                None
+            } else {
+                Some(end)
+            }
+        }
+    }
+}
+
+fn locateJsonString(bytes: &[u8], pos: usize) -> Option<usize> {
+    if bytes.get(pos) != Some(&b'"') {
+        return None;
+    }
+    let mut pos = pos + 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => pos += 2,
+            b'"' => return Some(pos + 1),
+            _ => pos += 1,
+        }
+    }
+    None
+}
+
+/**
+ * tsconfig.json files are JSON with comments: line and block comments and trailing commas are allowed.
+ * They are blanked out (keeping line breaks so error positions stay meaningful) before handing the text to serde.
+ */
+fn convertJsoncToJson(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut bytes = text.as_bytes().to_vec();
+    let blank = |bytes: &mut [u8], pos: usize| {
+        if bytes[pos] != b'\n' && bytes[pos] != b'\r' {
+            bytes[pos] = b' ';
+        }
+    };
+
+    // Comments first, so that a comment between a trailing comma and the closing bracket doesn't hide it
+    let mut pos = 0;
+    let mut inString = false;
+    while pos < bytes.len() {
+        let ch = bytes[pos];
+        if inString {
+            if ch == b'\\' {
+                pos += 1;
+            } else if ch == b'"' {
+                inString = false;
+            }
+            pos += 1;
+            continue;
+        }
+        match (ch, bytes.get(pos + 1)) {
+            (b'"', _) => inString = true,
+            (b'/', Some(b'/')) => {
+                while pos < bytes.len() && bytes[pos] != b'\n' {
+                    blank(&mut bytes, pos);
+                    pos += 1;
+                }
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                let end = text[pos + 2..].find("*/").map_or(bytes.len(), |end| pos + 2 + end + 2);
+                while pos < end {
+                    blank(&mut bytes, pos);
+                    pos += 1;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+
+    let mut pos = 0;
+    let mut inString = false;
+    while pos < bytes.len() {
+        let ch = bytes[pos];
+        if inString {
+            if ch == b'\\' {
+                pos += 1;
+            } else if ch == b'"' {
+                inString = false;
+            }
+        } else if ch == b'"' {
+            inString = true;
+        } else if ch == b',' {
+            let next = bytes[pos + 1..].iter().find(|b| !b.is_ascii_whitespace());
+            if matches!(next, Some(b'}') | Some(b']')) {
+                bytes[pos] = b' ';
+            }
+        }
+        pos += 1;
+    }
+
+    // Only ASCII bytes outside of strings were replaced, so the text is still valid UTF-8
+    String::from_utf8(bytes).unwrap()
+}
+
+fn tryReadFile(fileName: &str, readFile: impl Fn(&str) -> Option<String>) -> Result<String, Diagnostic<'static>> {
+    match readFile(fileName) {
+        Some(text) => Ok(text),
+        None => Err(createCompilerDiagnostic(Diagnostics::Cannot_read_file_0(), vec![fileName.to_string().into()])),
+    }
+}
+// endregion: 2106
+
+// region: 2611
+/** @internal */
+pub fn convertToOptionsWithAbsolutePaths(options: &CompilerOptions, toAbsolutePath: impl Fn(&str) -> String) -> CompilerOptions {
+    let mut result = options.clone();
+    for option in optionDeclarations.iter().filter(|option| option.isFilePath) {
+        let value = match getCompilerOptionValue(options, option.name) {
+            Some(Value::String(value)) => Value::String(toAbsolutePath(&value)),
+            Some(Value::Array(values)) => Value::Array(values.iter().map(|value| Value::String(toAbsolutePath(value.as_str().unwrap()))).collect()),
+            _ => continue,
+        };
+        setCompilerOptionValue(&mut result, option.name, Some(&value));
+    }
+    if let Some(configFilePath) = &options.configFilePath {
+        result.configFilePath = Some(toAbsolutePath(configFilePath));
+    }
+    result
+}
+// endregion: 2638
+
+// region: 2905
+const defaultIncludeSpec: &str = "**/*";
+
+/**
+ * Parse the contents of a config file from json or json source file (tsconfig.json).
+ * @param json The contents of the config file to parse
+ * @param host Instance of ParseConfigHost used to enumerate files in folder.
+ * @param basePath A root directory to resolve relative path entries in the config
+ *    file to. e.g. outDir
+ * @param resolutionStack Only present for backwards-compatibility. Should be empty.
+ */
+fn parseJsonConfigFileContentWorker(json: Value, sourceFile: Option<&TsConfigSourceFile>, host: &dyn ParseConfigHost, basePath: &str, existingOptions: Option<&CompilerOptions>, configFileName: Option<&str>, resolutionStack: Vec<String>, mut errors: Vec<Diagnostic<'static>>) -> ParsedCommandLine {
+    let parsedConfig = parseConfig(json, sourceFile, host, basePath, configFileName, &resolutionStack, &mut errors);
+    let raw = parsedConfig.raw;
+    let mut options = parsedConfig.options.unwrap_or_default();
+    if let Some(existingOptions) = existingOptions {
+        extendCompilerOptions(&mut options, existingOptions);
+    }
+    let basePathForFileNames = normalizePath(&match configFileName {
+        Some(configFileName) => directoryOfCombinedPath(configFileName, basePath),
+        None => basePath.to_string(),
+    });
+    verifyPathsBaseUrl(&options, sourceFile, &mut errors);
+    let configFileSpecs = getConfigFileSpecs(&raw, sourceFile, &options, configFileName, &mut errors);
+
+    let fileNames = getFileNamesFromConfigSpecs(&configFileSpecs, &basePathForFileNames, &options, host);
+    if shouldReportNoInputFiles(&fileNames, canJsonReportNoInputFiles(&raw), &resolutionStack) {
+        errors.push(getErrorForNoInputFiles(&configFileSpecs, configFileName));
+    }
+
+    let compileOnSave = Some(raw.get("compileOnSave").and_then(|compileOnSave| compileOnSave.as_bool()).unwrap_or(false));
+    ParsedCommandLine { options, fileNames, raw: Some(raw), errors, compileOnSave }
+}
+
+fn getConfigFileSpecs(raw: &Value, sourceFile: Option<&TsConfigSourceFile>, options: &CompilerOptions, configFileName: Option<&str>, errors: &mut Vec<Diagnostic<'static>>) -> ConfigFileSpecs {
+    let referencesOfRaw = getPropFromRaw(raw, sourceFile, "references", |element| element.is_object(), "object", errors);
+    let filesSpecs = toPropValue(getSpecsFromRaw(raw, sourceFile, "files", errors));
+    if let Some(filesSpecs) = &filesSpecs {
+        let hasZeroOrNoReferences = match &referencesOfRaw {
+            PropOfRaw::NoProp => true,
+            PropOfRaw::Array(references) => references.is_empty(),
+            PropOfRaw::NotArray => false,
+        };
+        let hasExtends = raw.get("extends").is_some();
+        if filesSpecs.is_empty() && hasZeroOrNoReferences && !hasExtends {
+            errors.push(locateConfigDiagnostic(sourceFile, "/files", false, createCompilerDiagnostic(Diagnostics::The_files_list_in_config_file_0_is_empty(), vec![configFileName.unwrap_or("tsconfig.json").to_string().into()])));
+        }
+    }
+
+    let includeOfRaw = getSpecsFromRaw(raw, sourceFile, "include", errors);
+    let mut includeSpecs = toPropValue(includeOfRaw.clone());
+
+    let excludeOfRaw = getSpecsFromRaw(raw, sourceFile, "exclude", errors);
+    let mut isDefaultIncludeSpec = false;
+    let mut excludeSpecs = toPropValue(excludeOfRaw.clone());
+    if matches!(excludeOfRaw, PropOfRaw::NoProp) {
+        let outDirs: Vec<Value> = [&options.outDir, &options.declarationDir].into_iter().flatten().map(|dir| Value::String(dir.clone())).collect();
+        if !outDirs.is_empty() {
+            excludeSpecs = Some(outDirs);
+        }
+    }
+
+    if filesSpecs.is_none() && matches!(includeOfRaw, PropOfRaw::NoProp) {
+        includeSpecs = Some(vec![Value::String(defaultIncludeSpec.to_string())]);
+        isDefaultIncludeSpec = true;
+    }
+
+    let validatedFilesSpec = filesSpecs.as_ref().map(|specs| specs.iter().filter_map(|spec| spec.as_str()).map(|spec| spec.to_string()).collect());
+    let validatedIncludeSpecs = includeSpecs.as_ref().map(|specs| validateSpecs(specs, sourceFile, "/include", errors, /*disallowTrailingRecursion*/ true));
+    let validatedExcludeSpecs = excludeSpecs.as_ref().map(|specs| validateSpecs(specs, sourceFile, "/exclude", errors, /*disallowTrailingRecursion*/ false));
+
+    ConfigFileSpecs { filesSpecs, includeSpecs, excludeSpecs, validatedFilesSpec, validatedIncludeSpecs, validatedExcludeSpecs, isDefaultIncludeSpec }
+}
+
+#[derive(Debug, Clone)]
+enum PropOfRaw {
+    Array(Vec<Value>),
+    NotArray,
+    NoProp,
+}
+
+fn toPropValue(specResult: PropOfRaw) -> Option<Vec<Value>> {
+    match specResult {
+        PropOfRaw::Array(specs) => Some(specs),
+        _ => None,
+    }
+}
+
+fn getSpecsFromRaw(raw: &Value, sourceFile: Option<&TsConfigSourceFile>, prop: &str, errors: &mut Vec<Diagnostic<'static>>) -> PropOfRaw { getPropFromRaw(raw, sourceFile, prop, |element| element.is_string(), "string", errors) }
+
+fn getPropFromRaw(raw: &Value, sourceFile: Option<&TsConfigSourceFile>, prop: &str, validateElement: impl Fn(&Value) -> bool, elementTypeName: &str, errors: &mut Vec<Diagnostic<'static>>) -> PropOfRaw {
+    let pointer = jsonPointer("", prop);
+    match raw.get(prop) {
+        None | Some(Value::Null) => PropOfRaw::NoProp,
+        Some(Value::Array(result)) => {
+            if let Some(index) = result.iter().position(|element| !validateElement(element)) {
+                let error = createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec![prop.to_string().into(), elementTypeName.to_string().into()]);
+                errors.push(locateConfigDiagnostic(sourceFile, &jsonPointer(&pointer, &index.to_string()), false, error));
+            }
+            PropOfRaw::Array(result.clone())
+        }
+        Some(_) => {
+            let error = createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec![prop.to_string().into(), "Array".to_string().into()]);
+            errors.push(locateConfigDiagnostic(sourceFile, &pointer, false, error));
+            PropOfRaw::NotArray
+        }
+    }
+}
+
+fn getErrorForNoInputFiles(configFileSpecs: &ConfigFileSpecs, configFileName: Option<&str>) -> Diagnostic<'static> {
+    let stringify = |specs: &Option<Vec<Value>>| serde_json::to_string(specs.as_deref().unwrap_or(&[])).unwrap();
+    createCompilerDiagnostic(
+        Diagnostics::No_inputs_were_found_in_config_file_0_Specified_include_paths_were_1_and_exclude_paths_were_2(),
+        vec![configFileName.unwrap_or("tsconfig.json").to_string().into(), stringify(&configFileSpecs.includeSpecs).into(), stringify(&configFileSpecs.excludeSpecs).into()],
+    )
+}
+
+fn shouldReportNoInputFiles(fileNames: &[String], canJsonReportNoInutFiles: bool, resolutionStack: &[String]) -> bool { fileNames.is_empty() && canJsonReportNoInutFiles && resolutionStack.is_empty() }
+
+/** @internal */
+pub fn canJsonReportNoInputFiles(raw: &Value) -> bool { raw.get("files").is_none() && raw.get("references").is_none() }
+// endregion: 3153
+
+// region: 3155
+/** @internal */
+#[derive(Debug, Clone, Default)]
+pub struct ParsedTsconfig {
+    pub raw: Value,
+    pub options: Option<CompilerOptions>,
+    // watchOptions?: WatchOptions;
+    // typeAcquisition?: TypeAcquisition;
+    /**
+     * Note that the case of the config path has not yet been normalized, as no files have been imported into the project yet
+     */
+    pub extendedConfigPath: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct ExtendsResult {
+    options: CompilerOptions,
+    include: Option<Vec<Value>>,
+    exclude: Option<Vec<Value>>,
+    files: Option<Vec<Value>>,
+    compileOnSave: Option<bool>,
+}
+
+/**
+ * This *just* extracts options/include/exclude/files out of a config file.
+ * It does *not* resolve the included files.
+ */
+fn parseConfig(json: Value, sourceFile: Option<&TsConfigSourceFile>, host: &dyn ParseConfigHost, basePath: &str, configFileName: Option<&str>, resolutionStack: &[String], errors: &mut Vec<Diagnostic<'static>>) -> ParsedTsconfig {
+    let basePath = normalizeSlashes(basePath);
+    let resolvedPath = getNormalizedAbsolutePath(configFileName.unwrap_or(""), Some(&basePath));
+
+    if resolutionStack.contains(&resolvedPath) {
+        let cycle: Vec<&str> = resolutionStack.iter().map(|path| path.as_str()).chain([resolvedPath.as_str()]).collect();
+        errors.push(createCompilerDiagnostic(Diagnostics::Circularity_detected_while_resolving_configuration_colon_0(), vec![cycle.join(" -> ").into()]));
+        return ParsedTsconfig { raw: json, ..Default::default() };
+    }
+
+    let mut ownConfig = parseOwnConfigOfJson(json, sourceFile, host, &basePath, configFileName, errors);
+
+    if let Some(options) = &mut ownConfig.options {
+        if options.paths.is_some() {
+            // If we end up needing to resolve relative paths from 'paths' relative to
+            // the config file location, we'll need to know where that config file was.
+            // Since 'paths' can be inherited from an extended config in another directory,
+            // we wouldn't know which directory to use unless we store it here.
+            options.pathsBasePath = Some(basePath.clone());
+        }
+    }
+
+    if let Some(extendedConfigPaths) = ownConfig.extendedConfigPath.clone() {
+        // copy the resolution stack so it is never reused between branches in potential diamond-problem scenarios.
+        let resolutionStack: Vec<String> = resolutionStack.iter().cloned().chain([resolvedPath]).collect();
+        let mut result = ExtendsResult::default();
+        for extendedConfigPath in &extendedConfigPaths {
+            applyExtendedConfig(&mut result, extendedConfigPath, &ownConfig.raw, host, &basePath, &resolutionStack, errors);
+        }
+
+        let raw = ownConfig.raw.as_object_mut().unwrap();
+        if let Some(include) = result.include {
+            raw.insert("include".to_string(), Value::Array(include));
+        }
+        if let Some(exclude) = result.exclude {
+            raw.insert("exclude".to_string(), Value::Array(exclude));
+        }
+        if let Some(files) = result.files {
+            raw.insert("files".to_string(), Value::Array(files));
+        }
+        if raw.get("compileOnSave").is_none() && result.compileOnSave == Some(true) {
+            raw.insert("compileOnSave".to_string(), Value::Bool(true));
+        }
+        let mut options = result.options;
+        extendCompilerOptions(&mut options, ownConfig.options.as_ref().unwrap());
+        ownConfig.options = Some(options);
+    }
+
+    ownConfig
+}
+
+fn applyExtendedConfig(result: &mut ExtendsResult, extendedConfigPath: &str, ownRaw: &Value, host: &dyn ParseConfigHost, basePath: &str, resolutionStack: &[String], errors: &mut Vec<Diagnostic<'static>>) {
+    let Some(extendedConfig) = getExtendedConfig(extendedConfigPath, host, resolutionStack, errors) else {
+        return;
+    };
+    let Some(extendedOptions) = &extendedConfig.options else {
+        return;
+    };
+
+    let extendsRaw = &extendedConfig.raw;
+    let relativeDifference = convertToRelativePath(&getDirectoryPath(extendedConfigPath), basePath, |fileName| if host.useCaseSensitiveFileNames() == Some(false) { fileName.to_lowercase() } else { fileName.to_string() });
+    let setPropertyInResultIfNotUndefined = |propertyName: &str| -> Option<Vec<Value>> {
+        if ownRaw.get(propertyName).is_some_and(|value| !value.is_null()) {
+            return None; // No need to calculate if already set in own config
+        }
+        let paths = extendsRaw.get(propertyName)?.as_array()?;
+        Some(
+            paths
+                .iter()
+                .map(|path| match path.as_str() {
+                    Some(path) if !isRootedDiskPath(path) => Value::String(combinePaths(&relativeDifference, &[Some(path)])),
+                    _ => path.clone(),
+                })
+                .collect(),
+        )
+    };
+    if let Some(include) = setPropertyInResultIfNotUndefined("include") {
+        result.include = Some(include);
+    }
+    if let Some(exclude) = setPropertyInResultIfNotUndefined("exclude") {
+        result.exclude = Some(exclude);
+    }
+    if let Some(files) = setPropertyInResultIfNotUndefined("files") {
+        result.files = Some(files);
+    }
+    if let Some(compileOnSave) = extendsRaw.get("compileOnSave").and_then(|compileOnSave| compileOnSave.as_bool()) {
+        result.compileOnSave = Some(compileOnSave);
+    }
+    extendCompilerOptions(&mut result.options, extendedOptions);
+}
+
+fn parseOwnConfigOfJson(json: Value, sourceFile: Option<&TsConfigSourceFile>, host: &dyn ParseConfigHost, basePath: &str, configFileName: Option<&str>, errors: &mut Vec<Diagnostic<'static>>) -> ParsedTsconfig {
+    if json.get("excludes").is_some() {
+        errors.push(locateConfigDiagnostic(sourceFile, "/excludes", true, createCompilerDiagnostic(Diagnostics::Unknown_option_excludes_Did_you_mean_exclude(), vec![])));
+    }
+
+    let options = convertCompilerOptionsFromJsonWorker(json.get("compilerOptions"), sourceFile, basePath, errors, configFileName);
+    let mut json = json;
+    let compileOnSave = convertCompileOnSaveOptionFromJson(&json, sourceFile, errors);
+    json.as_object_mut().unwrap().insert("compileOnSave".to_string(), Value::Bool(compileOnSave));
+    let extendedConfigPath = match json.get("extends") {
+        Some(Value::Null) | None => None,
+        Some(extends) => Some(getExtendsConfigPathOrArray(extends, sourceFile, host, basePath, configFileName, errors)),
+    };
+    ParsedTsconfig { raw: json, options: Some(options), extendedConfigPath }
+}
+
+fn getExtendsConfigPathOrArray(value: &Value, sourceFile: Option<&TsConfigSourceFile>, host: &dyn ParseConfigHost, basePath: &str, configFileName: Option<&str>, errors: &mut Vec<Diagnostic<'static>>) -> Vec<String> {
+    let newBase = match configFileName {
+        Some(configFileName) => directoryOfCombinedPath(configFileName, basePath),
+        None => basePath.to_string(),
+    };
+    match value {
+        Value::String(extendedConfig) => getExtendsConfigPath(extendedConfig, sourceFile, "/extends", host, &newBase, errors).into_iter().collect(),
+        Value::Array(extendedConfigs) => {
+            let mut extendedConfigPath = vec![];
+            for (index, fileName) in extendedConfigs.iter().enumerate() {
+                let pointer = jsonPointer("/extends", &index.to_string());
+                match fileName {
+                    Value::String(fileName) => extendedConfigPath.extend(getExtendsConfigPath(fileName, sourceFile, &pointer, host, &newBase, errors)),
+                    _ => errors.push(locateConfigDiagnostic(sourceFile, &pointer, false, createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec!["extends".to_string().into(), "string".to_string().into()]))),
+                }
+            }
+            extendedConfigPath
+        }
+        _ => {
+            errors.push(locateConfigDiagnostic(sourceFile, "/extends", false, createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec!["extends".to_string().into(), "string or Array".to_string().into()])));
+            vec![]
+        }
+    }
+}
+
+fn directoryOfCombinedPath(fileName: &str, basePath: &str) -> String {
+    // Use the `getNormalizedAbsolutePath` function to avoid canonicalizing the path, as it must remain noncanonical
+    // until consistent casing errors are reported
+    getDirectoryPath(&getNormalizedAbsolutePath(fileName, Some(basePath)))
+}
+
+fn getExtendsConfigPath(extendedConfig: &str, sourceFile: Option<&TsConfigSourceFile>, pointer: &str, host: &dyn ParseConfigHost, basePath: &str, errors: &mut Vec<Diagnostic<'static>>) -> Option<String> {
+    let extendedConfig = normalizeSlashes(extendedConfig);
+    if isRootedDiskPath(&extendedConfig) || extendedConfig.starts_with("./") || extendedConfig.starts_with("../") {
+        let mut extendedConfigPath = getNormalizedAbsolutePath(&extendedConfig, Some(basePath));
+        if !host.fileExists(&extendedConfigPath) && !extendedConfigPath.ends_with(Extension::Json.as_str()) {
+            extendedConfigPath = format!("{extendedConfigPath}.json");
+            if !host.fileExists(&extendedConfigPath) {
+                errors.push(locateConfigDiagnostic(sourceFile, pointer, false, createCompilerDiagnostic(Diagnostics::File_0_not_found(), vec![extendedConfig.into()])));
+                return None;
+            }
+        }
+        return Some(extendedConfigPath);
+    }
+    // If the path isn't a rooted or relative path, resolve like a module
+    let resolved = nodeNextJsonConfigResolver(&extendedConfig, &combinePaths(basePath, &[Some("tsconfig.json")]), host);
+    if let Some(resolvedModule) = resolved.resolvedModule {
+        return Some(resolvedModule.resolvedFileName);
+    }
+    let error = if extendedConfig.is_empty() { createCompilerDiagnostic(Diagnostics::Compiler_option_0_cannot_be_given_an_empty_string(), vec!["extends".to_string().into()]) } else { createCompilerDiagnostic(Diagnostics::File_0_not_found(), vec![extendedConfig.into()]) };
+    errors.push(locateConfigDiagnostic(sourceFile, pointer, false, error));
+    None
+}
+
+fn getExtendedConfig(extendedConfigPath: &str, host: &dyn ParseConfigHost, resolutionStack: &[String], errors: &mut Vec<Diagnostic<'static>>) -> Option<ParsedTsconfig> {
+    match readJsonConfigFile(extendedConfigPath, |path| host.readFile(path)) {
+        Ok(extendedResult) => {
+            errors.extend(extendedResult.parseDiagnostics.iter().cloned());
+            Some(parseConfig(extendedResult.json.clone(), Some(&extendedResult), host, &getDirectoryPath(extendedConfigPath), Some(&getBaseFileName(extendedConfigPath)), resolutionStack, errors))
+        }
+        Err(error) => {
+            errors.push(error);
+            None
+        }
+    }
+}
+
+fn convertCompileOnSaveOptionFromJson(jsonOption: &Value, sourceFile: Option<&TsConfigSourceFile>, errors: &mut Vec<Diagnostic<'static>>) -> bool {
+    match jsonOption.get("compileOnSave") {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(result)) => *result,
+        Some(_) => {
+            errors.push(locateConfigDiagnostic(sourceFile, "/compileOnSave", false, createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec!["compileOnSave".to_string().into(), "boolean".to_string().into()])));
+            false
+        }
+    }
+}
+
+fn getDefaultCompilerOptions(configFileName: Option<&str>) -> CompilerOptions {
+    if configFileName.is_some_and(|configFileName| getBaseFileName(configFileName) == "jsconfig.json") {
+        CompilerOptions { allowJs: Some(true), maxNodeModuleJsDepth: Some(2), allowSyntheticDefaultImports: Some(true), skipLibCheck: Some(true), noEmit: Some(true), ..Default::default() }
+    } else {
+        CompilerOptions::default()
+    }
+}
+
+fn convertCompilerOptionsFromJsonWorker(jsonOptions: Option<&Value>, sourceFile: Option<&TsConfigSourceFile>, basePath: &str, errors: &mut Vec<Diagnostic<'static>>, configFileName: Option<&str>) -> CompilerOptions {
+    let mut options = getDefaultCompilerOptions(configFileName);
+    convertOptionsFromJson(jsonOptions, sourceFile, basePath, &mut options, errors);
+    if let Some(configFileName) = configFileName {
+        options.configFilePath = Some(normalizeSlashes(configFileName));
+    }
+    options
+}
+
+fn convertOptionsFromJson(jsonOptions: Option<&Value>, sourceFile: Option<&TsConfigSourceFile>, basePath: &str, defaultOptions: &mut CompilerOptions, errors: &mut Vec<Diagnostic<'static>>) {
+    let jsonOptions = match jsonOptions {
+        None | Some(Value::Null) => return,
+        Some(Value::Object(jsonOptions)) => jsonOptions,
+        Some(_) => {
+            errors.push(locateConfigDiagnostic(sourceFile, "/compilerOptions", false, createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec!["compilerOptions".to_string().into(), "object".to_string().into()])));
+            return;
+        }
+    };
+
+    let optionsNameMap: HashMap<&str, &CommandLineOption> = optionDeclarations.iter().map(|option| (option.name, option)).collect();
+    for (id, value) in jsonOptions {
+        let pointer = jsonPointer("/compilerOptions", id);
+        match optionsNameMap.get(id.as_str()) {
+            Some(opt) => {
+                let value = convertJsonOption(opt, value, basePath, sourceFile, &pointer, errors);
+                setCompilerOptionValue(defaultOptions, opt.name, value.as_ref());
+            }
+            None => errors.push(locateConfigDiagnostic(sourceFile, &pointer, true, createUnknownOptionError(id, None))),
+        }
+    }
+}
+
+/** @internal */
+pub fn convertJsonOption(opt: &CommandLineOption, value: &Value, basePath: &str, sourceFile: Option<&TsConfigSourceFile>, pointer: &str, errors: &mut Vec<Diagnostic<'static>>) -> Option<Value> {
+    if opt.isCommandLineOnly {
+        errors.push(locateConfigDiagnostic(sourceFile, pointer, true, createCompilerDiagnostic(Diagnostics::Option_0_can_only_be_specified_on_command_line(), vec![opt.name.to_string().into()])));
+        return None;
+    }
+    if !isCompilerOptionsValue(opt, value) {
+        errors.push(locateConfigDiagnostic(sourceFile, pointer, false, createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec![opt.name.to_string().into(), getCompilerOptionValueTypeString(opt).to_string().into()])));
+        return None;
+    }
+    match (&opt.type_, value) {
+        (_, Value::Null) => None,
+        (CommandLineOptionType::List, Value::Array(values)) => Some(convertJsonOptionOfListType(opt, values, basePath, sourceFile, pointer, errors)),
+        (CommandLineOptionType::Map(_), Value::String(value)) => {
+            let mut customTypeErrors = vec![];
+            let result = convertJsonOptionOfCustomType(opt, value, &mut customTypeErrors);
+            errors.extend(customTypeErrors.into_iter().map(|error| locateConfigDiagnostic(sourceFile, pointer, false, error)));
+            result
+        }
+        (CommandLineOptionType::Object, Value::Object(_)) if opt.name == "paths" => Some(verifyPathsOption(value, sourceFile, pointer, errors)),
+        _ => Some(normalizeNonListOptionValue(opt, basePath, value.clone())),
+    }
+}
+
+fn normalizeNonListOptionValue(option: &CommandLineOption, basePath: &str, value: Value) -> Value {
+    match value {
+        Value::String(value) if option.isFilePath => {
+            let value = getNormalizedAbsolutePath(&value, Some(basePath));
+            Value::String(if value.is_empty() { ".".to_string() } else { value })
+        }
+        value => value,
+    }
+}
+
+fn convertJsonOptionOfCustomType(opt: &CommandLineOption, value: &str, errors: &mut Vec<Diagnostic<'static>>) -> Option<Value> {
+    let CommandLineOptionType::Map(keys) = &opt.type_ else {
+        unreachable!("'{}' is not an enum option", opt.name);
+    };
+    let key = value.to_lowercase();
+    if keys.contains(&key.as_str()) {
+        Some(Value::String(key))
+    } else {
+        errors.push(createDiagnosticForInvalidCustomType(opt));
+        None
+    }
+}
+
+fn createDiagnosticForInvalidCustomType(opt: &CommandLineOption) -> Diagnostic<'static> {
+    let CommandLineOptionType::Map(keys) = &opt.type_ else {
+        unreachable!("'{}' is not an enum option", opt.name);
+    };
+    let stringNames: Vec<String> = keys.iter().map(|key| format!("'{key}'")).collect();
+    createCompilerDiagnostic(Diagnostics::Argument_for_0_option_must_be_colon_1(), vec![format!("--{}", opt.name).into(), stringNames.join(", ").into()])
+}
+
+fn convertJsonOptionOfListType(option: &CommandLineOption, values: &[Value], basePath: &str, sourceFile: Option<&TsConfigSourceFile>, pointer: &str, errors: &mut Vec<Diagnostic<'static>>) -> Value {
+    let mut result = vec![];
+    for (index, value) in values.iter().enumerate() {
+        let Some(element) = value.as_str() else {
+            let error = createCompilerDiagnostic(Diagnostics::Compiler_option_0_requires_a_value_of_type_1(), vec![option.name.to_string().into(), "string".to_string().into()]);
+            errors.push(locateConfigDiagnostic(sourceFile, &jsonPointer(pointer, &index.to_string()), false, error));
+            continue;
+        };
+        if element.is_empty() && !option.listPreserveFalsyValues {
+            continue;
+        }
+        result.push(Value::String(if option.isFilePath { getNormalizedAbsolutePath(element, Some(basePath)) } else { element.to_string() }));
+    }
+    Value::Array(result)
+}
+
+fn isCompilerOptionsValue(option: &CommandLineOption, value: &Value) -> bool {
+    match (&option.type_, value) {
+        (_, Value::Null) => true,
+        (CommandLineOptionType::List, value) => value.is_array(),
+        (CommandLineOptionType::String | CommandLineOptionType::Map(_), value) => value.is_string(),
+        (CommandLineOptionType::Number, value) => value.is_number(),
+        (CommandLineOptionType::Boolean, value) => value.is_boolean(),
+        (CommandLineOptionType::Object, value) => value.is_object(),
+    }
+}
+
+fn getCompilerOptionValueTypeString(option: &CommandLineOption) -> &'static str {
+    match option.type_ {
+        CommandLineOptionType::List => "Array",
+        CommandLineOptionType::String | CommandLineOptionType::Map(_) => "string",
+        CommandLineOptionType::Number => "number",
+        CommandLineOptionType::Boolean => "boolean",
+        CommandLineOptionType::Object => "object",
+    }
+}
+
+/**
+ * Reports the malformed patterns and substitutions of `paths` like verifyCompilerOptions in program.ts does, and returns
+ * the mappings without the substitutions that are not strings, which `CompilerOptions::paths` cannot hold.
+ */
+fn verifyPathsOption(paths: &Value, sourceFile: Option<&TsConfigSourceFile>, pointer: &str, errors: &mut Vec<Diagnostic<'static>>) -> Value {
+    let mut result = serde_json::Map::new();
+    for (key, substitutions) in paths.as_object().into_iter().flatten() {
+        let keyPointer = jsonPointer(pointer, key);
+        if !hasZeroOrOneAsteriskCharacter(key) {
+            errors.push(locateConfigDiagnostic(sourceFile, &keyPointer, true, createCompilerDiagnostic(Diagnostics::Pattern_0_can_have_at_most_one_asterisk_character(), vec![key.clone().into()])));
+        }
+        let Value::Array(substitutions) = substitutions else {
+            errors.push(locateConfigDiagnostic(sourceFile, &keyPointer, false, createCompilerDiagnostic(Diagnostics::Substitutions_for_pattern_0_should_be_an_array(), vec![key.clone().into()])));
+            continue;
+        };
+        if substitutions.is_empty() {
+            errors.push(locateConfigDiagnostic(sourceFile, &keyPointer, false, createCompilerDiagnostic(Diagnostics::Substitutions_for_pattern_0_shouldn_t_be_an_empty_array(), vec![key.clone().into()])));
+        }
+        let mut validSubstitutions = vec![];
+        for (index, subst) in substitutions.iter().enumerate() {
+            let substPointer = jsonPointer(&keyPointer, &index.to_string());
+            match subst {
+                Value::String(subst) => {
+                    if !hasZeroOrOneAsteriskCharacter(subst) {
+                        errors.push(locateConfigDiagnostic(sourceFile, &substPointer, false, createCompilerDiagnostic(Diagnostics::Substitution_0_in_pattern_1_can_have_at_most_one_asterisk_character(), vec![subst.clone().into(), key.clone().into()])));
+                    }
+                    validSubstitutions.push(Value::String(subst.clone()));
+                }
+                _ => {
+                    let typeOfSubst = match subst {
+                        Value::Number(_) => "number",
+                        Value::Bool(_) => "boolean",
+                        _ => "object",
+                    };
+                    let error = createCompilerDiagnostic(Diagnostics::Substitution_0_for_pattern_1_has_incorrect_type_expected_string_got_2(), vec![subst.to_string().into(), key.clone().into(), typeOfSubst.to_string().into()]);
+                    errors.push(locateConfigDiagnostic(sourceFile, &substPointer, false, error));
+                }
+            }
+        }
+        result.insert(key.clone(), Value::Array(validSubstitutions));
+    }
+    Value::Object(result)
+}
+
+/**
+ * `paths` substitutions are resolved against `baseUrl` when they are not relative, so they must be relative without one.
+ * Checked once the options of the extended configs are merged in, as `baseUrl` may come from any of them.
+ */
+fn verifyPathsBaseUrl(options: &CompilerOptions, sourceFile: Option<&TsConfigSourceFile>, errors: &mut Vec<Diagnostic<'static>>) {
+    let (Some(paths), None) = (&options.paths, &options.baseUrl) else {
+        return;
+    };
+    let mut keys: Vec<&String> = paths.keys().collect();
+    keys.sort();
+    for key in keys {
+        for (index, subst) in paths[key].iter().enumerate() {
+            if !pathIsRelative(subst) && !pathIsAbsolute(subst) {
+                let pointer = jsonPointer(&jsonPointer("/compilerOptions/paths", key), &index.to_string());
+                errors.push(locateConfigDiagnostic(sourceFile, &pointer, false, createCompilerDiagnostic(Diagnostics::Non_relative_paths_are_not_allowed_when_baseUrl_is_not_set_Did_you_forget_a_leading_slash(), vec![])));
+            }
+        }
+    }
+}
+// endregion: 3584
+
+// region: 3683
+/**
+ * Gets the file names from the provided config file specs that contain, files, include, exclude and
+ * other properties needed to resolve the file names
+ * @param configFileSpecs The config file specs extracted with file names to include, wildcards to include/exclude and other details
+ * @param basePath The base path for any relative file specifications.
+ * @param options Compiler options.
+ * @param host The host used to resolve files and directories.
+ *
+ * @internal
+ */
+pub fn getFileNamesFromConfigSpecs(configFileSpecs: &ConfigFileSpecs, basePath: &str, options: &CompilerOptions, host: &dyn ParseConfigHost) -> Vec<String> {
+    let basePath = normalizePath(basePath);
+    let useCaseSensitiveFileNames = host.useCaseSensitiveFileNames() != Some(false);
+    let keyMapper = |fileName: &str| if useCaseSensitiveFileNames { fileName.to_string() } else { fileName.to_lowercase() };
+
+    // Literal file names (provided via the "files" array in tsconfig.json) are stored in a
+    // file map with a possibly case insensitive key. We use this map later when when including
+    // wildcard paths.
+    let mut literalFileMap = OrderedFileMap::default();
+
+    // Wildcard paths (provided via the "includes" array in tsconfig.json) are stored in a
+    // file map with a possibly case insensitive key. We use this map to store paths matched
+    // via wildcard, and to handle extension priority.
+    let mut wildcardFileMap = OrderedFileMap::default();
+
+    // Wildcard paths of json files (provided via the "includes" array in tsconfig.json) are stored in a
+    // file map with a possibly case insensitive key. We use this map to store paths matched
+    // via wildcard of *.json kind
+    let mut wildCardJsonFileMap = OrderedFileMap::default();
+
+    // Rather than re-query this for each file and filespec, we query the supported extensions
+    // once and store it on the expansion context.
+    let supportedExtensions = getSupportedExtensions(Some(options));
+    let supportedExtensionsWithJsonIfResolveJsonModule = getSupportedExtensionsWithJsonIfResolveJsonModule(Some(options), supportedExtensions);
+
+    // Literal files are always included verbatim. An "include" or "exclude" specification cannot
+    // remove a literal file.
+    if let Some(validatedFilesSpec) = &configFileSpecs.validatedFilesSpec {
+        for fileName in validatedFilesSpec {
+            let file = getNormalizedAbsolutePath(fileName, Some(&basePath));
+            literalFileMap.set(keyMapper(&file), file);
+        }
+    }
+
+    let mut jsonOnlyIncludePatterns: Option<Vec<Vec<String>>> = None;
+    if let Some(validatedIncludeSpecs) = configFileSpecs.validatedIncludeSpecs.as_ref().filter(|specs| !specs.is_empty()) {
+        let extensions: Vec<&str> = supportedExtensionsWithJsonIfResolveJsonModule.iter().flat_map(|group| group.iter()).map(|extension| extension.as_str()).collect();
+        for file in host.readDirectory(&basePath, &extensions, configFileSpecs.validatedExcludeSpecs.as_deref(), validatedIncludeSpecs, /*depth*/ None) {
+            if fileExtensionIs(&file, Extension::Json.as_str()) {
+                // Valid only if *.json specified
+                let patterns = jsonOnlyIncludePatterns.get_or_insert_with(|| {
+                    let includes: Vec<String> = validatedIncludeSpecs.iter().filter(|s| s.ends_with(Extension::Json.as_str())).cloned().collect();
+                    getRegularExpressionsForWildcards(Some(includes.as_slice()), &basePath, WildcardMatcherUsage::Files).unwrap_or_default()
+                });
+                if patterns.iter().any(|pattern| matchesWildcardPattern(pattern, &file, WildcardMatcherUsage::Files, useCaseSensitiveFileNames)) {
+                    let key = keyMapper(&file);
+                    if !literalFileMap.has(&key) && !wildCardJsonFileMap.has(&key) {
+                        wildCardJsonFileMap.set(key, file);
+                    }
+                }
+                continue;
+            }
+            // If we have already included a literal or wildcard path with a
+            // higher priority extension, we should skip this file.
+            //
+            // This handles cases where we may encounter both <file>.ts and
+            // <file>.d.ts (or <file>.js if "allowJs" is enabled) in the same
+            // directory when they are compilation outputs.
+            if hasFileWithHigherPriorityExtension(&file, &literalFileMap, &wildcardFileMap, supportedExtensions, &keyMapper) {
+                continue;
+            }
+
+            // We may have included a wildcard path with a lower priority
+            // extension due to the user-defined order of entries in the
+            // "include" array. If there is a lower priority extension in the
+            // same directory, we should remove it.
+            removeWildcardFilesWithLowerPriorityExtension(&file, &mut wildcardFileMap, supportedExtensions, &keyMapper);
+
+            let key = keyMapper(&file);
+            if !literalFileMap.has(&key) && !wildcardFileMap.has(&key) {
+                wildcardFileMap.set(key, file);
+            }
+        }
+    }
+
+    literalFileMap.into_values().chain(wildcardFileMap.into_values()).chain(wildCardJsonFileMap.into_values()).collect()
+}
+
+/** A map from file keys to file names that, like a JavaScript `Map`, iterates in insertion order */
+#[derive(Default)]
+struct OrderedFileMap {
+    keys: Vec<String>,
+    values: HashMap<String, String>,
+}
+
+impl OrderedFileMap {
+    fn has(&self, key: &str) -> bool { self.values.contains_key(key) }
+
+    fn set(&mut self, key: String, value: String) {
+        if self.values.insert(key.clone(), value).is_none() {
+            self.keys.push(key);
+        }
+    }
+
+    fn delete(&mut self, key: &str) {
+        if self.values.remove(key).is_some() {
+            self.keys.retain(|k| k != key);
+        }
+    }
+
+    fn into_values(self) -> impl Iterator<Item = String> {
+        let OrderedFileMap { keys, mut values } = self;
+        keys.into_iter().map(move |key| values.remove(&key).unwrap())
+    }
+}
+
+fn validateSpecs(specs: &[Value], sourceFile: Option<&TsConfigSourceFile>, pointer: &str, errors: &mut Vec<Diagnostic<'static>>, disallowTrailingRecursion: bool) -> Vec<String> {
+    specs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, spec)| spec.as_str().map(|spec| (index, spec)))
+        .filter(|(index, spec)| match specToDiagnostic(spec, disallowTrailingRecursion) {
+            Some(diag) => {
+                errors.push(locateConfigDiagnostic(sourceFile, &jsonPointer(pointer, &index.to_string()), false, createCompilerDiagnostic(diag, vec![spec.to_string().into()])));
+                false
+            }
+            None => true,
+        })
+        .map(|(_, spec)| spec.to_string())
+        .collect()
+}
+
+fn specToDiagnostic(spec: &str, disallowTrailingRecursion: bool) -> Option<DiagnosticMessage> {
+    if disallowTrailingRecursion && isInvalidTrailingRecursion(spec) {
+        Some(Diagnostics::File_specification_cannot_end_in_a_recursive_directory_wildcard_asterisk_asterisk_colon_0())
+    } else if invalidDotDotAfterRecursiveWildcard(spec) {
+        Some(Diagnostics::File_specification_cannot_contain_a_parent_directory_that_appears_after_a_recursive_directory_wildcard_asterisk_asterisk_colon_0())
+    } else {
+        None
+    }
+}
+
+/** Matches `/(?:^|\/)\*\*\/?$/` */
+fn isInvalidTrailingRecursion(spec: &str) -> bool {
+    let spec = spec.strip_suffix('/').unwrap_or(spec);
+    spec == "**" || spec.ends_with("/**")
+}
+
+fn invalidDotDotAfterRecursiveWildcard(s: &str) -> bool {
+    // We used to use the regex /(^|\/)\*\*\/(.*\/)?\.\.($|\/)/ to check for this case, but
+    // in v8, that has polynomial performance because the recursive wildcard match - **/ -
+    // can be matched in many arbitrary positions when multiple are present, resulting
+    // in bad backtracking (and we don't care which is matched - just that some /.. segment
+    // comes after some **/ segment).
+    let Some(wildcardIndex) = (if s.starts_with("**/") { Some(0) } else { s.find("/**/") }) else {
+        return false;
+    };
+    let lastDotIndex = if s.ends_with("/..") { Some(s.len()) } else { s.rfind("/../") };
+    lastDotIndex.is_some_and(|lastDotIndex| lastDotIndex > wildcardIndex)
+}
+
+/**
+ * Determines whether a literal or wildcard file has already been included that has a higher
+ * extension priority.
+ *
+ * @param file The path to the file.
+ */
+fn hasFileWithHigherPriorityExtension(file: &str, literalFiles: &OrderedFileMap, wildcardFiles: &OrderedFileMap, extensions: &[&[Extension]], keyMapper: &impl Fn(&str) -> String) -> bool {
+    let Some(extensionGroup) = extensions.iter().find(|group| fileExtensionIsOneOf(file, group.iter().map(|e| e.as_str()).collect())) else {
+        return false;
+    };
+    for ext in extensionGroup.iter() {
+        // d.ts files match with .ts extension and with case sensitive sorting the file order for same files with ts tsx and dts extension is
+        // d.ts, .ts, .tsx in that order so we need to handle tsx and dts of same same name case here and in remove files with same extensions
+        // So dont match .d.ts files with .ts extension
+        if fileExtensionIs(file, ext.as_str()) && (*ext != Extension::Ts || !fileExtensionIs(file, Extension::Dts.as_str())) {
+            return false;
+        }
+        let higherPriorityPath = keyMapper(&changeExtension(file, ext.as_str()));
+        if literalFiles.has(&higherPriorityPath) || wildcardFiles.has(&higherPriorityPath) {
+            if *ext == Extension::Dts && (fileExtensionIs(file, Extension::Js.as_str()) || fileExtensionIs(file, Extension::Jsx.as_str())) {
+                // LEGACY BEHAVIOR: An off-by-one bug somewhere in the extension priority system for wildcard module loading allowed declaration
+                // files to be loaded alongside their js(x) counterparts. We regard this as generally undesirable, but retain the behavior to
+                // prevent breakage.
+                continue;
+            }
+            return true;
+        }
+    }
+
+    false
+}
+
+/**
+ * Removes files included via wildcard expansion with a lower extension priority that have
+ * already been included.
+ *
+ * @param file The path to the file.
+ */
+fn removeWildcardFilesWithLowerPriorityExtension(file: &str, wildcardFiles: &mut OrderedFileMap, extensions: &[&[Extension]], keyMapper: &impl Fn(&str) -> String) {
+    let Some(extensionGroup) = extensions.iter().find(|group| fileExtensionIsOneOf(file, group.iter().map(|e| e.as_str()).collect())) else {
+        return;
+    };
+    for ext in extensionGroup.iter().rev() {
+        if fileExtensionIs(file, ext.as_str()) {
+            return;
+        }
+        let lowerPriorityPath = keyMapper(&changeExtension(file, ext.as_str()));
+        wildcardFiles.delete(&lowerPriorityPath);
+    }
+}
+// endregion: 3875
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{convertJsoncToJson, parseJsonConfigFileText};
+
+    #[test]
+    fn removes_comments_and_trailing_commas_outside_strings() {
+        let text = r#"{
+    // a comment
+    "a": "// not a comment",
+    "b": "/* nor this */",
+    "c": [1, 2, /* trailing */],
+    "d": "1,]",
+}"#;
+        // comments are blanked rather than removed, so every offset stays the same
+        assert_eq!(convertJsoncToJson(text).len(), text.len());
+        let config = parseJsonConfigFileText("/proj/tsconfig.json", text);
+        assert!(config.parseDiagnostics.is_empty());
+        assert_eq!(config.json, json!({ "a": "// not a comment", "b": "/* nor this */", "c": [1, 2], "d": "1,]" }));
+    }
+
+    #[test]
+    fn skips_escaped_quotes_in_strings() {
+        let text = r#"{ "a": "say \"hi\" // still a string", "b": "c:\\", // a comment
+    "c": "\",}", }"#;
+        let config = parseJsonConfigFileText("/proj/tsconfig.json", text);
+        assert!(config.parseDiagnostics.is_empty());
+        assert_eq!(config.json, json!({ "a": "say \"hi\" // still a string", "b": "c:\\", "c": "\",}" }));
+    }
+
+    #[test]
+    fn locates_names_and_values() {
+        let text = "\u{feff}{ \"compilerOptions\": { \"strict\": true }, // a comment\n  \"files\": [\"a.ts\", \"b,]\\\".ts\"], \"a/b\": 1 }";
+        let span = |s: &str| (text.find(s).unwrap() as u32, s.len() as u32);
+        let config = parseJsonConfigFileText("/proj/tsconfig.json", text);
+        assert!(config.parseDiagnostics.is_empty());
+        // offsets are in the original text, after the byte order mark
+        assert_eq!(config.locations[""].value, (3, text.len() as u32 - 3));
+        assert_eq!(config.locations["/compilerOptions"].name, Some(span("\"compilerOptions\"")));
+        assert_eq!(config.locations["/compilerOptions"].value, span("{ \"strict\": true }"));
+        assert_eq!(config.locations["/compilerOptions/strict"].value, span("true"));
+        assert_eq!(config.locations["/files"].value, span("[\"a.ts\", \"b,]\\\".ts\"]"));
+        assert_eq!(config.locations["/files/1"].value, span("\"b,]\\\".ts\""));
+        assert_eq!(config.locations["/a~1b"].name, Some(span("\"a/b\"")));
+        assert_eq!(config.locations["/a~1b"].value, span("1"));
+    }
+}
@@ -12,6 +12,27 @@ pub fn contains<T: PartialEq>(array: Option<&[T]>, value: &T, equalityComparer:
 }
 // endregion: 230
 
+// region: 448
+/**
+ * Calls `cb` with the start and end (exclusive) indices of each run of elements satisfying `pred`.
+ *
+ * @internal
+ */
+pub fn getRangesWhere<T>(arr: &[T], pred: impl Fn(&T) -> bool, mut cb: impl FnMut(usize, usize)) {
+    let mut start: Option<usize> = None;
+    for i in 0..arr.len() {
+        if pred(&arr[i]) {
+            start = start.or(Some(i));
+        } else if let Some(s) = start.take() {
+            cb(s, i);
+        }
+    }
+    if let Some(s) = start {
+        cb(s, arr.len());
+    }
+}
+// endregion: 463
+
 // region: 996
 /**
  * @return Whether the value was added.
@@ -59,6 +80,105 @@ pub fn equateValues<T: PartialEq>(a: &T, b: &T) -> bool { a == b }
 pub fn equateStringsCaseInsensitive(a: &str, b: &str) -> bool { a == b || (!a.is_empty() && !b.is_empty() && a.to_uppercase() == b.to_uppercase()) }
 // endregion: 1956
 
+// region: 2266
+/**
+ * Given a name and a list of names that are *not* equal to the name, return a spelling suggestion if there is one that is close enough.
+ * Names less than length 3 only check for case-insensitive equality.
+ *
+ * find the candidate with the smallest Levenshtein distance,
+ *    except for candidates:
+ *      * With no name
+ *      * Whose length differs from the target name by more than 0.34 of the length of the name.
+ *      * Whose levenshtein distance is more than 0.4 of the length of the name
+ *        (0.4 allows 1 substitution/transposition for every 5 characters,
+ *         and 1 insertion/deletion at 3 characters)
+ *
+ * @internal
+ */
+pub fn getSpellingSuggestion<'c, T>(name: &str, candidates: impl IntoIterator<Item = &'c T>, getName: impl Fn(&T) -> Option<&str>) -> Option<&'c T> {
+    let nameLength = name.chars().count();
+    let maximumLengthDifference = 2.max((nameLength as f64 * 0.34).floor() as usize);
+    let mut bestDistance = (nameLength as f64 * 0.4).floor() + 1.0; // If the best result is worse than this, don't bother.
+    let mut bestCandidate = None;
+    for candidate in candidates {
+        if let Some(candidateName) = getName(candidate) {
+            let candidateLength = candidateName.chars().count();
+            if candidateLength.abs_diff(nameLength) <= maximumLengthDifference {
+                if candidateName == name {
+                    continue;
+                }
+                // Only consider candidates less than 3 characters long when they differ by case.
+                // Otherwise, don't bother, since a user would usually notice differences of a 2-character name.
+                if candidateLength < 3 && candidateName.to_lowercase() != name.to_lowercase() {
+                    continue;
+                }
+
+                let Some(distance) = levenshteinWithMax(name, candidateName, bestDistance - 0.1) else {
+                    continue;
+                };
+
+                debug_assert!(distance < bestDistance); // Else `levenshteinWithMax` should return undefined
+                bestDistance = distance;
+                bestCandidate = Some(candidate);
+            }
+        }
+    }
+    bestCandidate
+}
+
+fn levenshteinWithMax(s1: &str, s2: &str, max: f64) -> Option<f64> {
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    let mut previous = vec![0.0; s2.len() + 1];
+    let mut current = vec![0.0; s2.len() + 1];
+    /** Represents any value > max. We don't care about the particular value. */
+    let big = max + 0.01;
+
+    for i in 0..=s2.len() {
+        previous[i] = i as f64;
+    }
+
+    for i in 1..=s1.len() {
+        let c1 = s1[i - 1];
+        let minJ = (if i as f64 > max { i as f64 - max } else { 1.0 }).ceil() as usize;
+        let maxJ = (if s2.len() as f64 > max + i as f64 { max + i as f64 } else { s2.len() as f64 }).floor() as usize;
+        current[0] = i as f64;
+        /** Smallest value of the matrix in the ith column. */
+        let mut colMin = i as f64;
+        for j in 1..minJ {
+            current[j] = big;
+        }
+        for j in minJ..=maxJ {
+            // case difference should be significantly cheaper than other differences
+            let substitutionDistance = if c1.to_lowercase().eq(s2[j - 1].to_lowercase()) { previous[j - 1] + 0.1 } else { previous[j - 1] + 2.0 };
+            let dist = if c1 == s2[j - 1] {
+                previous[j - 1]
+            } else {
+                f64::min(f64::min(/*delete*/ previous[j] + 1.0, /*insert*/ current[j - 1] + 1.0), /*substitute*/ substitutionDistance)
+            };
+            current[j] = dist;
+            colMin = colMin.min(dist);
+        }
+        for j in maxJ + 1..=s2.len() {
+            current[j] = big;
+        }
+        if colMin > max {
+            // Give up -- everything in this column is > max and it can't get better in future columns.
+            return None;
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let res = previous[s2.len()];
+    if res > max {
+        None
+    } else {
+        Some(res)
+    }
+}
+// endregion: 2330
+
 // region: 2425
 /** @internal */
 pub fn startsWith(str: &str, prefix: &str, ignoreCase: Option<bool>) -> bool {
@@ -68,3 +188,50 @@ pub fn startsWith(str: &str, prefix: &str, ignoreCase: Option<bool>) -> bool {
     }
 }
 // endregion: 2432
+
+// region: 2480
+/** @internal */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub prefix: String,
+    pub suffix: String,
+}
+
+/** @internal */
+pub fn patternText(Pattern { prefix, suffix }: &Pattern) -> String { format!("{prefix}*{suffix}") }
+
+/**
+ * Given that candidate matches pattern, returns the text matching the '*'.
+ * E.g.: matchedText(tryParsePattern("foo*baz"), "foobarbaz") === "bar"
+ *
+ * @internal
+ */
+pub fn matchedText<'c>(pattern: &Pattern, candidate: &'c str) -> &'c str {
+    debug_assert!(isPatternMatch(pattern, candidate));
+    &candidate[pattern.prefix.len()..candidate.len() - pattern.suffix.len()]
+}
+
+/**
+ * Return the object corresponding to the best pattern to match `candidate`.
+ *
+ * @internal
+ */
+pub fn findBestPatternMatch<'v, T>(values: &'v [T], getPattern: impl Fn(&T) -> &Pattern, candidate: &str) -> Option<&'v T> {
+    let mut matchedValue = None;
+    // use length of prefix as betterness criteria
+    let mut longestMatchPrefixLength = None;
+
+    for v in values {
+        let pattern = getPattern(v);
+        if longestMatchPrefixLength.is_none_or(|length| pattern.prefix.len() > length) && isPatternMatch(pattern, candidate) {
+            longestMatchPrefixLength = Some(pattern.prefix.len());
+            matchedValue = Some(v);
+        }
+    }
+
+    matchedValue
+}
+
+/** @internal */
+pub fn isPatternMatch(Pattern { prefix, suffix }: &Pattern, candidate: &str) -> bool { candidate.len() >= prefix.len() + suffix.len() && candidate.starts_with(prefix.as_str()) && candidate.ends_with(suffix.as_str()) }
+// endregion: 2520
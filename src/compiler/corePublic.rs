@@ -0,0 +1,8 @@
+// region: 1
+// WARNING: The script `configurePrerelease.ts` uses a regexp to parse out these values.
+// If changing the text in this section, be sure to test `configurePrerelease` too.
+pub const versionMajorMinor: &str = "5.7";
+// The following is baselined as a literal template type without intervention
+/** The version of the TypeScript compiler release */
+pub const version: &str = "5.7.0-dev";
+// endregion: 6
@@ -0,0 +1,213 @@
+use std::cell::UnsafeCell;
+use std::rc::Rc;
+
+use crate::compiler::checker::TypeChecker;
+use crate::compiler::commandLineParser::*;
+use crate::compiler::corePublic::version;
+use crate::compiler::diagnostic_information_map_generated::Diagnostics;
+use crate::compiler::path::*;
+use crate::compiler::program::*;
+use crate::compiler::rb_extra::{SourceFileExt, RB_CTX};
+use crate::compiler::rb_host::RbTypeCheckerHost;
+use crate::compiler::types::*;
+use crate::compiler::utilities::{createCompilerDiagnostic, formatMessage};
+
+// region: 118
+fn printVersion() {
+    println!("{}", formatMessage(&Diagnostics::Version_0(), &vec![version.to_string().into()]));
+}
+
+fn printHelp() {
+    let example = |command: &str, description: DiagnosticMessage| {
+        println!("tsc {command}");
+        println!("  {}", formatMessage(&description, &vec![]));
+        println!();
+    };
+
+    println!("{}", formatMessage(&Diagnostics::COMMON_COMMANDS(), &vec![]));
+    println!();
+    example("", Diagnostics::Compiles_the_current_project_tsconfig_json_in_the_working_directory());
+    example("app.ts util.ts", Diagnostics::Ignoring_tsconfig_json_compiles_the_specified_files_with_default_compiler_options());
+    example("-p ./path/to/tsconfig.json", Diagnostics::Compiles_the_TypeScript_project_located_at_the_specified_path());
+}
+// endregion: 140
+
+// region: 595
+/**
+ * Runs a `tsc --noEmit` style compilation for the given command line arguments.
+ * Diagnostics are written to stdout and the resulting exit status is returned to the caller.
+ * Only option, resolution, syntax, binder and global declaration diagnostics are reported: the checker does not check
+ * statements yet (see `TypeChecker::getDiagnostics`), so a compilation without any of these diagnostics still fails
+ * rather than passing a program that may have type errors.
+ */
+pub fn executeCommandLine(currentDirectory: String, commandLineArgs: &[String]) -> ExitStatus {
+    let currentDirectory = normalizeSlashes(&currentDirectory);
+    let commandLine = parseCommandLine(commandLineArgs);
+    let sys = RbTypeCheckerHost::new(currentDirectory.clone(), Rc::new(CompilerOptions::default()));
+
+    if !commandLine.errors.is_empty() {
+        reportDiagnostics(&commandLine.errors, &sys);
+        return ExitStatus::DiagnosticsPresent_OutputsSkipped;
+    }
+
+    if commandLine.options.version == Some(true) {
+        printVersion();
+        return ExitStatus::Success;
+    }
+
+    if commandLine.options.help == Some(true) || commandLine.options.all == Some(true) {
+        printVersion();
+        printHelp();
+        return ExitStatus::Success;
+    }
+
+    let mut configFileName: Option<String> = None;
+    if let Some(project) = &commandLine.options.project {
+        if !commandLine.fileNames.is_empty() {
+            reportDiagnostics(&[createCompilerDiagnostic(Diagnostics::Option_project_cannot_be_mixed_with_source_files_on_a_command_line(), vec![])], &sys);
+            return ExitStatus::DiagnosticsPresent_OutputsSkipped;
+        }
+
+        let fileOrDirectory = getNormalizedAbsolutePath(project, Some(&currentDirectory));
+        if sys.directoryExists(&fileOrDirectory) == Some(true) {
+            let fileName = combinePaths(&fileOrDirectory, &[Some("tsconfig.json")]);
+            if !sys.fileExists(&fileName) {
+                reportDiagnostics(&[createCompilerDiagnostic(Diagnostics::Cannot_find_a_tsconfig_json_file_at_the_specified_directory_colon_0(), vec![project.clone().into()])], &sys);
+                return ExitStatus::DiagnosticsPresent_OutputsSkipped;
+            }
+            configFileName = Some(fileName);
+        } else {
+            if !sys.fileExists(&fileOrDirectory) {
+                reportDiagnostics(&[createCompilerDiagnostic(Diagnostics::The_specified_path_does_not_exist_colon_0(), vec![project.clone().into()])], &sys);
+                return ExitStatus::DiagnosticsPresent_OutputsSkipped;
+            }
+            configFileName = Some(fileOrDirectory);
+        }
+    } else if commandLine.fileNames.is_empty() {
+        configFileName = findConfigFile(&currentDirectory, |fileName| sys.fileExists(fileName), None);
+    }
+
+    if commandLine.fileNames.is_empty() && configFileName.is_none() {
+        printVersion();
+        printHelp();
+        return ExitStatus::DiagnosticsPresent_OutputsSkipped;
+    }
+
+    match configFileName {
+        Some(configFileName) => {
+            let commandLineOptions = convertToOptionsWithAbsolutePaths(&commandLine.options, |fileName| getNormalizedAbsolutePath(fileName, Some(&currentDirectory)));
+            match getParsedCommandLineOfConfigFile(&configFileName, Some(&commandLineOptions), &sys) {
+                Ok(configParseResult) => performCompilation(currentDirectory, configParseResult),
+                Err(diagnostic) => {
+                    reportDiagnostics(&[diagnostic], &sys);
+                    ExitStatus::DiagnosticsPresent_OutputsSkipped
+                }
+            }
+        }
+        None => {
+            let fileNames = commandLine.fileNames.iter().map(|fileName| getNormalizedAbsolutePath(fileName, Some(&currentDirectory))).collect();
+            performCompilation(currentDirectory, ParsedCommandLine { fileNames, ..commandLine })
+        }
+    }
+}
+// endregion: 720
+
+// region: 795
+fn performCompilation(currentDirectory: String, config: ParsedCommandLine) -> ExitStatus {
+    let ParsedCommandLine { options, fileNames, errors: configFileParsingDiagnostics, .. } = config;
+
+    let options = Rc::new(options);
+    let host = Rc::new(UnsafeCell::new(RbTypeCheckerHost::new(currentDirectory, options.clone())));
+    RB_CTX.set_type_checker_host(host.clone());
+    let mut programDiagnostics = vec![];
+    let mut resolutionDiagnostics = vec![];
+    let mut sourceFiles: Vec<_> = fileNames
+        .into_iter()
+        .filter_map(|fileName| match unsafe { &mut *host.get() }.addSourceFile(fileName) {
+            Ok(sourceFile) => Some(sourceFile),
+            Err(diagnostic) => {
+                programDiagnostics.push(diagnostic);
+                None
+            }
+        })
+        .collect();
+
+    // The files imported by the program's files are added as their module references are resolved, like processImportedModules
+    let mut index = 0;
+    while index < sourceFiles.len() {
+        let resolutions = {
+            let sourceFile = sourceFiles[index].borrow();
+            let host = unsafe { &*host.get() };
+            let packageJsonInfoCache = host.getPackageJsonInfoCache();
+            let mut packageJsonInfoCache = packageJsonInfoCache.as_ref().map(|cache| cache.borrow_mut());
+            resolveModuleNamesOfFile(&sourceFile, &options, host, packageJsonInfoCache.as_deref_mut())
+                .into_iter()
+                .filter_map(|(_, resolution)| {
+                    resolutionDiagnostics.extend(resolution.resolutionDiagnostics.unwrap_or_default());
+                    resolution.resolvedModule.filter(|resolvedModule| shouldAddResolvedModuleFile(&sourceFile, resolvedModule, &options))
+                })
+                .collect::<Vec<_>>()
+        };
+        for resolvedModule in resolutions {
+            if unsafe { &*host.get() }.getSourceFile(&resolvedModule.resolvedFileName).is_some() {
+                continue;
+            }
+            match unsafe { &mut *host.get() }.addSourceFile(resolvedModule.resolvedFileName) {
+                Ok(sourceFile) => sourceFiles.push(sourceFile),
+                Err(diagnostic) => programDiagnostics.push(diagnostic),
+            }
+        }
+        index += 1;
+    }
+
+    let typeChecker = TypeChecker::new(host.clone());
+    let host = unsafe { &*host.get() };
+
+    let mut diagnosticsCount = reportDiagnostics(&configFileParsingDiagnostics, host);
+
+    // Files that could not be read are reported like option diagnostics, which suppress the per-file diagnostics
+    let programDiagnosticsCount = reportDiagnostics(&programDiagnostics, host);
+    diagnosticsCount += programDiagnosticsCount;
+
+    // Report syntactic diagnostics first, the semantic diagnostics are only reported for programs that parsed cleanly
+    let mut syntacticDiagnosticsCount = 0;
+    if programDiagnosticsCount == 0 {
+        for sourceFile in &sourceFiles {
+            let parseDiagnostics = sourceFile.borrow().parseDiagnostics();
+            syntacticDiagnosticsCount += reportDiagnostics(&parseDiagnostics.borrow(), host);
+        }
+    }
+    diagnosticsCount += syntacticDiagnosticsCount;
+
+    // ! rb the semantic diagnostics are limited to what module resolution, the binder and the merging of globals report, see getDiagnostics
+    if programDiagnosticsCount == 0 && syntacticDiagnosticsCount == 0 {
+        // Modules that cannot be resolved are reported by the checker in TypeScript, with the file's other semantic diagnostics
+        diagnosticsCount += reportDiagnostics(&resolutionDiagnostics, host);
+        let typeChecker = typeChecker.borrow();
+        diagnosticsCount += reportDiagnostics(&typeChecker.getGlobalDiagnostics(), host);
+        for sourceFile in &sourceFiles {
+            let sourceFile = sourceFile.borrow();
+            let bindDiagnostics = sourceFile.bindDiagnostics();
+            diagnosticsCount += reportDiagnostics(&bindDiagnostics.borrow(), host);
+            diagnosticsCount += reportDiagnostics(&typeChecker.getDiagnostics(Some(&*sourceFile), None, None), host);
+        }
+    }
+
+    // Nothing is emitted, so any diagnostic means the outputs were skipped
+    if diagnosticsCount > 0 {
+        return ExitStatus::DiagnosticsPresent_OutputsSkipped;
+    }
+
+    // ! rb statements are not type checked yet, so a program without diagnostics is not known to be free of type errors.
+    // It is not reported as a clean pass, which would let a check like `tsc --noEmit` in CI succeed on code tsc rejects
+    eprintln!("error: the program was not type checked, checking statements is not supported yet");
+    ExitStatus::DiagnosticsPresent_OutputsSkipped
+}
+
+fn reportDiagnostics(diagnostics: &[Diagnostic], host: &dyn FormatDiagnosticsHost) -> usize {
+    if !diagnostics.is_empty() {
+        print!("{}", formatDiagnostics(diagnostics, host));
+    }
+    diagnostics.len()
+}
+// endregion: 844
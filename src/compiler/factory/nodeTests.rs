@@ -3,7 +3,7 @@ use oxc_ast::{
     AstKind,
 };
 
-use crate::compiler::types::{BindingElement, Identifier, PropertyAccessExpression};
+use crate::compiler::types::{BindingElement, Identifier, JSDocTag, JSDocTagKind, PropertyAccessExpression};
 
 // region: 234
 pub fn isNumericLiteral(node: &AstKind) -> bool { matches!(node, AstKind::NumericLiteral(_)) }
@@ -117,3 +117,17 @@ pub fn isJsxNamespacedName(node: &AstKind) -> bool { matches!(node, AstKind::JSX
 
 pub fn isPropertyAssignment(node: &AstKind) -> bool { matches!(node, AstKind::ObjectProperty(n) if !n.shorthand) }
 // endregion: 998
+
+// region: 1108
+// JSDoc Elements
+
+pub fn isJSDocParameterTag(node: &JSDocTag) -> bool { matches!(node.kind, JSDocTagKind::Parameter { .. }) }
+
+pub fn isJSDocReturnTag(node: &JSDocTag) -> bool { matches!(node.kind, JSDocTagKind::Return { .. }) }
+
+pub fn isJSDocTypeTag(node: &JSDocTag) -> bool { matches!(node.kind, JSDocTagKind::Type { .. }) }
+
+pub fn isJSDocTemplateTag(node: &JSDocTag) -> bool { matches!(node.kind, JSDocTagKind::Template { .. }) }
+
+pub fn isJSDocDeprecatedTag(node: &JSDocTag) -> bool { matches!(node.kind, JSDocTagKind::Deprecated) }
+// endregion: 1150
@@ -1,7 +1,10 @@
 pub mod binder;
 pub mod checker;
+pub mod commandLineParser;
 pub mod core;
+pub mod corePublic;
 pub mod diagnostic_information_map_generated;
+pub mod executeCommandLine;
 pub mod factory;
 pub mod moduleNameResolver;
 pub mod parser;
@@ -12,7 +15,9 @@ pub mod rb_host;
 pub mod rb_macros;
 pub mod rb_parser;
 pub mod rb_unions;
+pub mod rb_vfs_host;
 pub mod scanner;
+pub mod semver;
 pub mod types;
 pub mod utilities;
 pub mod utilitiesPublic;
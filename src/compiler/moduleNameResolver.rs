@@ -1,7 +1,190 @@
-use super::path::{combinePaths, forEachAncestorDirectory};
+use super::core::{equateStringsCaseInsensitive, matchedText, patternText};
+use super::diagnostic_information_map_generated::Diagnostics;
+use super::path::{
+    combinePaths, containsPath, directorySeparator, ensureTrailingDirectorySeparator, fileExtensionIs, fileExtensionIsOneOf, forEachAncestorDirectory, getBaseFileName, getDirectoryPath, getNormalizedAbsolutePath, getPathComponents, getRelativePathFromDirectory, hasTrailingDirectorySeparator,
+    isRootedDiskPath, normalizePath, normalizeSlashes, pathIsRelative,
+};
+use super::rb_unions::StringOrPattern;
+use super::semver::{Version, VersionRange};
+use super::utilities::{
+    createCompilerDiagnostic, formatMessage, getAllowJSCompilerOption, getEmitModuleResolutionKind, getPathsBasePath, getResolveJsonModule, getResolvePackageJsonExports, getResolvePackageJsonImports, isDeclarationFileName, isExternalModuleNameRelative, matchPatternOrExact,
+    moduleResolutionSupportsPackageJsonExportsAndImports, packageIdToString, removeExtension, removeFileExtension, supportedDeclarationExtensions, supportedTSImplementationExtensions, tryExtractTSExtension, tryGetExtensionFromPath, tryParsePatterns, ParsedPatterns,
+};
 use crate::compiler::types::*;
-use serde_derive::{Deserialize, Serialize};
+use crate::{define_flags, flag_names_impl};
+use serde_json::Value;
+use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::rc::Rc;
+
+// region: 50
+/** @internal */
+pub fn trace(host: &dyn ModuleResolutionHost, message: DiagnosticMessage, args: &[&str]) { host.trace(&formatMessage(&message, &args.iter().map(|arg| arg.to_string().into()).collect())); }
+
+/** @internal */
+// ! rb `trace` is a required host method, so only the option is checked
+pub fn isTraceEnabled(compilerOptions: &CompilerOptions, _host: &dyn ModuleResolutionHost) -> bool {
+    compilerOptions.traceResolution == Some(true) /* && host.trace !== undefined */
+}
+
+fn traceIfEnabled(state: &ModuleResolutionState, message: DiagnosticMessage, args: &[&str]) {
+    if state.traceEnabled {
+        trace(state.host, message, args);
+    }
+}
+
+/** The `typeof` of a package.json value, as reported by trace messages. */
+fn typeOfJsonValue(value: &Value) -> &'static str {
+    match value {
+        Value::Null | Value::Array(_) | Value::Object(_) => "object",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+    }
+}
+// endregion: 75
+
+// region: 118
+/**
+ * Kinds of file that we are currently looking for.
+ */
+// @internal
+define_flags!(Extensions {
+    None = 0,
+    TypeScript = 1 << 0, // '.ts', '.tsx', '.mts', '.cts'
+    JavaScript = 1 << 1, // '.js', '.jsx', '.mjs', '.cjs'
+    Declaration = 1 << 2, // '.d.ts', etc.
+    Json = 1 << 3, // '.json'
+
+    ImplementationFiles = Self::TypeScript.0 | Self::JavaScript.0,
+});
+
+fn formatExtensions(extensions: Extensions) -> String {
+    let mut result = vec![];
+    if extensions.intersects(Extensions::TypeScript) {
+        result.push("TypeScript");
+    }
+    if extensions.intersects(Extensions::JavaScript) {
+        result.push("JavaScript");
+    }
+    if extensions.intersects(Extensions::Declaration) {
+        result.push("Declaration");
+    }
+    if extensions.intersects(Extensions::Json) {
+        result.push("JSON");
+    }
+    result.join(", ")
+}
+
+/** Result of trying to resolve a module at a file. Needs to have 'packageId' added later. */
+#[derive(Debug, Clone)]
+struct PathAndExtension {
+    path: String,
+    // (Use a different name than `extension` to make sure Resolved isn't assignable to PathAndExtension.)
+    ext: String,
+    resolvedUsingTsExtension: Option<bool>,
+}
+
+/**
+ * Result of trying to resolve a module.
+ * At least one of `ts` and `js` should be defined, or the whole thing should be `undefined`.
+ */
+#[derive(Debug, Clone)]
+struct Resolved {
+    path: String,
+    extension: String,
+    packageId: Option<PackageId>,
+    /**
+     * When the resolved is not created from cache, the value is
+     *  - string if it is symbolic link to the resolved `path`
+     *  - undefined if `path` is not a symbolic link
+     */
+    originalPath: Option<String>,
+    resolvedUsingTsExtension: Option<bool>,
+}
+
+/**
+ * Wrapper for a search result that may be `undefined`.
+ * `Some(SearchResult { value: None })` means the search is over and nothing was found, while `None` means keep looking.
+ */
+#[derive(Debug, Clone)]
+struct SearchResult<T> {
+    value: Option<T>,
+}
+
+fn toSearchResult<T>(value: Option<T>) -> Option<SearchResult<T>> { value.map(|value| SearchResult { value: Some(value) }) }
+
+#[derive(Debug, Clone)]
+struct NodeResolutionResult {
+    resolved: Resolved,
+    isExternalLibraryImport: bool,
+}
+// endregion: 199
+
+// region: 201
+fn withPackageId(packageInfo: Option<&PackageJsonInfo>, r: Option<PathAndExtension>, state: &mut ModuleResolutionState) -> Option<Resolved> {
+    let r = r?;
+    let mut packageId = None;
+    if let Some(packageInfo) = packageInfo {
+        let packageJsonContent = &packageInfo.contents.packageJsonContent;
+        if let (Some(Value::String(name)), Some(Value::String(version))) = (packageJsonContent.get("name"), packageJsonContent.get("version")) {
+            let (name, version) = (name.clone(), version.clone());
+            let subModuleName = r.path.get(packageInfo.packageDirectory.len() + directorySeparator.len()..).unwrap_or_default().to_string();
+            let peerDependencies = getPeerDependenciesOfPackageJsonInfo(packageInfo, state);
+            packageId = Some(PackageId { name, subModuleName, version, peerDependencies });
+        }
+    }
+    Some(Resolved { path: r.path, extension: r.ext, packageId, originalPath: None, resolvedUsingTsExtension: r.resolvedUsingTsExtension })
+}
+
+fn noPackageId(r: Option<PathAndExtension>) -> Option<Resolved> { r.map(|r| Resolved { path: r.path, extension: r.ext, packageId: None, originalPath: None, resolvedUsingTsExtension: r.resolvedUsingTsExtension }) }
+
+fn removeIgnoredPackageId(r: Option<Resolved>) -> Option<PathAndExtension> { r.map(|r| PathAndExtension { path: r.path, ext: r.extension, resolvedUsingTsExtension: r.resolvedUsingTsExtension }) }
+// endregion: 224
+
+// region: 227
+fn createResolvedModuleWithFailedLookupLocationsHandlingSymlink(moduleName: &str, resolved: Option<Resolved>, isExternalLibraryImport: Option<bool>, state: &mut ModuleResolutionState, alternateResult: Option<String>) -> ResolvedModuleWithFailedLookupLocations {
+    let mut resolved = resolved;
+    // If this is from node_modules for non relative name, always respect preserveSymlinks
+    if let Some(resolved) = &mut resolved {
+        if state.compilerOptions.preserveSymlinks != Some(true) && isExternalLibraryImport == Some(true) && resolved.originalPath.is_none() && !isExternalModuleNameRelative(moduleName) {
+            let (resolvedFileName, originalPath) = getOriginalAndResolvedFileName(&resolved.path, state.host, state.traceEnabled);
+            if originalPath.is_some() {
+                resolved.path = resolvedFileName;
+                resolved.originalPath = originalPath;
+            }
+        }
+    }
+    createResolvedModuleWithFailedLookupLocations(resolved, isExternalLibraryImport, state.failedLookupLocations.take().unwrap_or_default(), state.affectingLocations.take().unwrap_or_default(), state.resolutionDiagnostics.take().unwrap_or_default(), alternateResult)
+}
+
+fn createResolvedModuleWithFailedLookupLocations(
+    resolved: Option<Resolved>, isExternalLibraryImport: Option<bool>, failedLookupLocations: Vec<String>, affectingLocations: Vec<String>, diagnostics: Vec<Diagnostic<'static>>, alternateResult: Option<String>,
+) -> ResolvedModuleWithFailedLookupLocations {
+    ResolvedModuleWithFailedLookupLocations {
+        resolvedModule: resolved.map(|resolved| ResolvedModuleFull {
+            resolvedFileName: resolved.path,
+            originalPath: resolved.originalPath,
+            extension: resolved.extension,
+            isExternalLibraryImport,
+            packageId: resolved.packageId,
+            resolvedUsingTsExtension: Some(resolved.resolvedUsingTsExtension == Some(true)),
+        }),
+        failedLookupLocations: initializeResolutionField(failedLookupLocations),
+        affectingLocations: initializeResolutionField(affectingLocations),
+        resolutionDiagnostics: initializeResolutionField(diagnostics),
+        alternateResult,
+    }
+}
+
+fn initializeResolutionField<T>(value: Vec<T>) -> Option<Vec<T>> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+// endregion: 280
 
 // region: 312
 /** @internal */
@@ -16,7 +199,8 @@ pub struct ModuleResolutionState<'a> {
     pub features: NodeResolutionFeatures,
     pub conditions: Vec<String>,
     pub requestContainingDirectory: Option<String>,
-    //pub reportDiagnostic: DiagnosticReporter,
+    /** Diagnostics explaining why the module could not be resolved, or `None` when they are not collected. */
+    pub resolutionDiagnostics: Option<Vec<Diagnostic<'static>>>,
     pub isConfigLookup: bool,
     pub candidateIsFromPackageJsonField: bool,
     pub resolvedPackageDirectory: bool,
@@ -26,28 +210,62 @@ pub struct ModuleResolutionState<'a> {
  *
  * @internal
  */
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PackageJsonPathFields {
-    pub typings: Option<String>,
-    pub types: Option<String>,
-    pub typesVersions: Option<HashMap<String, HashMap<String, Vec<String>>>>,
-    pub main: Option<String>,
-    pub tsconfig: Option<String>,
-    pub r#type: Option<String>,
-    // pub imports: Option<Box<dyn std::any::Any>>,
-    // pub exports: Option<Box<dyn std::any::Any>>,
-    pub name: Option<String>,
-    pub dependencies: Option<HashMap<String, String>>,
-    pub peerDependencies: Option<HashMap<String, String>>,
-    pub optionalDependencies: Option<HashMap<String, String>>,
-
-    pub version: Option<String>, // from PackageJson
-}
+// ! rb the package.json is kept as the object it was parsed from, the fields are read with a type check (see readPackageJsonField),
+// so a field with an unexpected type is ignored rather than the whole file
+pub type PackageJsonPathFields = serde_json::Map<String, Value>;
 
 // I just merged these 2
 pub type PackageJson = PackageJsonPathFields;
+
+// a field that is `null` counts as missing, like the falsy fields TypeScript checks with `!`
+fn rb_package_json_value<'j>(jsonContent: &'j PackageJson, fieldName: &str) -> Option<&'j Value> { jsonContent.get(fieldName).filter(|value| !value.is_null()) }
 // endregion: 352
 
+// region: 380
+fn readPackageJsonField<'j>(jsonContent: &'j PackageJson, fieldName: &str, typeOfTag: &str, state: &ModuleResolutionState) -> Option<&'j Value> {
+    let Some(value) = jsonContent.get(fieldName) else {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::package_json_does_not_have_a_0_field(), &[fieldName]);
+        }
+        return None;
+    };
+    if typeOfJsonValue(value) != typeOfTag || value.is_null() {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Expected_type_of_0_field_in_package_json_to_be_1_got_2(), &[fieldName, typeOfTag, if value.is_null() { "null" } else { typeOfJsonValue(value) }]);
+        }
+        return None;
+    }
+    Some(value)
+}
+
+fn readPackageJsonPathField(jsonContent: &PackageJson, fieldName: &str, baseDirectory: &str, state: &ModuleResolutionState) -> Option<String> {
+    let fileName = readPackageJsonField(jsonContent, fieldName, "string", state)?.as_str()?;
+    if fileName.is_empty() {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::package_json_had_a_falsy_0_field(), &[fieldName]);
+        }
+        return None;
+    }
+    let path = normalizePath(&combinePaths(baseDirectory, &[Some(fileName)]));
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::package_json_has_0_field_1_that_references_2(), &[fieldName, fileName, &path]);
+    }
+    Some(path)
+}
+
+/** @internal */
+pub fn readPackageJsonTypesFields(jsonContent: &PackageJson, baseDirectory: &str, state: &ModuleResolutionState) -> Option<String> { readPackageJsonPathField(jsonContent, "typings", baseDirectory, state).or_else(|| readPackageJsonPathField(jsonContent, "types", baseDirectory, state)) }
+
+fn readPackageJsonTSConfigField(jsonContent: &PackageJson, baseDirectory: &str, state: &ModuleResolutionState) -> Option<String> { readPackageJsonPathField(jsonContent, "tsconfig", baseDirectory, state) }
+
+fn readPackageJsonMainField(jsonContent: &PackageJson, baseDirectory: &str, state: &ModuleResolutionState) -> Option<String> { readPackageJsonPathField(jsonContent, "main", baseDirectory, state) }
+// endregion: 410
+
+// region: 411
+/** @internal */
+pub fn directoryProbablyExists(directoryName: &str, host: &dyn ModuleResolutionHost) -> bool { host.directoryExists(directoryName).unwrap_or(true) }
+// endregion: 414
+
 // region: 416
 /** @internal */
 #[derive(Debug, Clone)]
@@ -55,18 +273,185 @@ pub struct VersionPaths {
     pub version: String,
     pub paths: HashMap<String, Vec<String>>,
 }
-// endregion: 420
+
+fn readPackageJsonTypesVersionsField<'j>(jsonContent: &'j PackageJson, state: &ModuleResolutionState) -> Option<&'j serde_json::Map<String, Value>> {
+    let typesVersions = readPackageJsonField(jsonContent, "typesVersions", "object", state)?;
+    // ! rb an array passes as an object in TypeScript, and has no version keys to match either
+    let typesVersions = typesVersions.as_object()?;
+
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::package_json_has_a_typesVersions_field_with_version_specific_path_mappings(), &[]);
+    }
+
+    Some(typesVersions)
+}
+
+fn readPackageJsonTypesVersionPaths(jsonContent: &PackageJson, state: &ModuleResolutionState) -> Option<VersionPaths> {
+    let typesVersions = readPackageJsonTypesVersionsField(jsonContent, state)?;
+
+    if state.traceEnabled {
+        for key in typesVersions.keys() {
+            if VersionRange::tryParse(key).is_none() {
+                trace(state.host, Diagnostics::package_json_has_a_typesVersions_entry_0_that_is_not_a_valid_semver_range(), &[key]);
+            }
+        }
+    }
+
+    let typeScriptVersion = Version::parse(&state.host.getTypeScriptVersion());
+    let Some((bestVersionKey, bestVersionPaths)) = getPackageJsonTypesVersionsPaths(typesVersions, &typeScriptVersion) else {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::package_json_does_not_have_a_typesVersions_entry_that_matches_version_0(), &[&format!("{}.{}", typeScriptVersion.major, typeScriptVersion.minor)]);
+        }
+        return None;
+    };
+
+    let Value::Object(bestVersionPaths) = bestVersionPaths else {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Expected_type_of_0_field_in_package_json_to_be_1_got_2(), &[&format!("typesVersions['{bestVersionKey}']"), "object", typeOfJsonValue(bestVersionPaths)]);
+        }
+        return None;
+    };
+
+    let paths = bestVersionPaths
+        .iter()
+        .map(|(key, substitutions)| {
+            let substitutions = match substitutions {
+                Value::Array(substitutions) => substitutions.iter().filter_map(|substitution| substitution.as_str().map(str::to_string)).collect(),
+                _ => vec![],
+            };
+            (key.clone(), substitutions)
+        })
+        .collect();
+    Some(VersionPaths { version: bestVersionKey.to_string(), paths })
+}
+
+/** @internal */
+pub fn getPackageJsonTypesVersionsPaths<'a>(typesVersions: &'a serde_json::Map<String, Value>, typeScriptVersion: &Version) -> Option<(&'a str, &'a Value)> {
+    for (key, paths) in typesVersions {
+        let Some(keyRange) = VersionRange::tryParse(key) else {
+            continue;
+        };
+
+        // return the first entry whose range matches the current compiler version.
+        if keyRange.test(typeScriptVersion) {
+            return Some((key, paths));
+        }
+    }
+    None
+}
+// endregion: 470
+
+// region: 480
+/**
+ * Returns the path to every node_modules/@types directory from some ancestor directory.
+ * Returns undefined if there are none.
+ */
+fn getDefaultTypeRoots(currentDirectory: &str) -> Option<Vec<String>> {
+    let mut typeRoots: Option<Vec<String>> = None;
+    forEachAncestorDirectory::<()>(&normalizePath(currentDirectory), |directory| {
+        let atTypes = combinePaths(directory, &[Some(nodeModulesAtTypes)]);
+        typeRoots.get_or_insert_with(Vec::new).push(atTypes);
+        None
+    });
+    typeRoots
+}
+const nodeModulesAtTypes: &str = "node_modules/@types";
+
+/** @internal */
+pub fn getEffectiveTypeRoots(options: &CompilerOptions, host: &dyn ModuleResolutionHost) -> Option<Vec<String>> {
+    if let Some(typeRoots) = &options.typeRoots {
+        return Some(typeRoots.clone());
+    }
+
+    let currentDirectory = match &options.configFilePath {
+        Some(configFilePath) => Some(getDirectoryPath(configFilePath)),
+        None => host.getCurrentDirectory(),
+    };
+
+    currentDirectory.and_then(|currentDirectory| getDefaultTypeRoots(&currentDirectory))
+}
+
+fn arePathsEqual(path1: &str, path2: &str, host: &dyn ModuleResolutionHost) -> bool {
+    let useCaseSensitiveFileNames = host.useCaseSensitiveFileNames().unwrap_or(false);
+    let path1 = normalizePath(path1);
+    let path2 = normalizePath(path2);
+    if useCaseSensitiveFileNames {
+        path1 == path2
+    } else {
+        equateStringsCaseInsensitive(&path1, &path2)
+    }
+}
+
+fn getOriginalAndResolvedFileName(fileName: &str, host: &dyn ModuleResolutionHost, traceEnabled: bool) -> (String, Option<String>) {
+    let resolvedFileName = realPath(fileName, host, traceEnabled);
+    let pathsAreEqual = arePathsEqual(fileName, &resolvedFileName, host);
+    // If the fileName and realpath are differing only in casing prefer fileName so that we can issue correct errors for casing under forceConsistentCasingInFileNames
+    (if pathsAreEqual { fileName.to_string() } else { resolvedFileName }, if pathsAreEqual { None } else { Some(fileName.to_string()) })
+}
+// endregion: 548
+
+// region: 745
+fn getNodeResolutionFeatures(options: &CompilerOptions) -> NodeResolutionFeatures {
+    let mut features = NodeResolutionFeatures::None;
+    match getEmitModuleResolutionKind(options) {
+        ModuleResolutionKind::Node16 => features = NodeResolutionFeatures::Node16Default,
+        ModuleResolutionKind::NodeNext => features = NodeResolutionFeatures::NodeNextDefault,
+        ModuleResolutionKind::Bundler => features = NodeResolutionFeatures::BundlerDefault,
+        _ => {}
+    }
+    if getResolvePackageJsonExports(options) {
+        features = features | NodeResolutionFeatures::Exports;
+    } else {
+        features = features & !NodeResolutionFeatures::Exports;
+    }
+    if getResolvePackageJsonImports(options) {
+        features = features | NodeResolutionFeatures::Imports;
+    } else {
+        features = features & !NodeResolutionFeatures::Imports;
+    }
+    features
+}
+
+/** @internal */
+pub fn getConditions(options: &CompilerOptions, resolutionMode: ResolutionMode) -> Vec<String> {
+    let moduleResolution = getEmitModuleResolutionKind(options);
+    let resolutionMode = if resolutionMode == ResolutionMode::Undefined {
+        if moduleResolution == ModuleResolutionKind::Bundler {
+            ResolutionMode::ESNext
+        } else if moduleResolution == ModuleResolutionKind::Node10 {
+            return Vec::new();
+        } else {
+            resolutionMode
+        }
+    } else {
+        resolutionMode
+    };
+    // conditions are only used by the node16/nodenext/bundler resolvers - there's no priority order in the list,
+    // it's essentially a set (priority is determined by object insertion order in the object we look at).
+    let mut conditions = vec![if resolutionMode == ResolutionMode::ESNext { "import" } else { "require" }.to_string()];
+    if options.noDtsResolution != Some(true) {
+        conditions.push("types".to_string());
+    }
+    if moduleResolution != ModuleResolutionKind::Bundler {
+        conditions.push("node".to_string());
+    }
+    if let Some(customConditions) = &options.customConditions {
+        conditions.extend(customConditions.iter().cloned());
+    }
+    conditions
+}
+// endregion: 775
 
 // region: 908
 /** @internal */
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MissingPackageJsonInfo {
     pub packageDirectory: String,
     pub directoryExists: bool,
 }
 
 /** @internal */
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PackageJsonInfoCacheEntry {
     PackageJsonInfo(PackageJsonInfo),
     MissingPackageJsonInfo(MissingPackageJsonInfo),
@@ -99,6 +484,270 @@ pub trait PackageJsonInfoCache {
 }
 // endregion: 933
 
+// region: 1130
+/**
+ * Package.json lookups keyed by the canonical path of the package.json file, remembering both the
+ * package.json files that were found and the ones that are known to be missing.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct PackageJsonInfoCacheMap {
+    currentDirectory: String,
+    useCaseSensitiveFileNames: bool,
+    cache: Option<HashMap<String, PackageJsonInfoCacheEntry>>,
+}
+
+impl PackageJsonInfoCacheMap {
+    fn toPath(&self, packageJsonPath: &str) -> String {
+        let path = getNormalizedAbsolutePath(packageJsonPath, Some(&self.currentDirectory));
+        if self.useCaseSensitiveFileNames {
+            path
+        } else {
+            path.to_lowercase()
+        }
+    }
+}
+
+impl PackageJsonInfoCache for PackageJsonInfoCacheMap {
+    fn getPackageJsonInfo(&self, packageJsonPath: &str) -> Option<PackageJsonInfoCacheEntry> { self.cache.as_ref()?.get(&self.toPath(packageJsonPath)).cloned() }
+
+    fn setPackageJsonInfo(&mut self, packageJsonPath: &str, info: PackageJsonInfoCacheEntry) {
+        let path = self.toPath(packageJsonPath);
+        self.cache.get_or_insert_with(HashMap::new).insert(path, info);
+    }
+
+    fn getInternalMap(&self) -> Option<HashMap<String, PackageJsonInfoCacheEntry>> { self.cache.clone() }
+
+    fn clear(&mut self) { self.cache = None; }
+
+    fn isReadonly(&self) -> bool { false }
+}
+
+/** @internal */
+pub fn createPackageJsonInfoCache(currentDirectory: &str, useCaseSensitiveFileNames: bool) -> PackageJsonInfoCacheMap { PackageJsonInfoCacheMap { currentDirectory: currentDirectory.to_string(), useCaseSensitiveFileNames, cache: None } }
+// endregion: 1150
+
+// region: 1420
+/**
+ * Resolves `moduleName` imported from `containingFile` using the module resolution strategy selected by `compilerOptions`.
+ * `resolutionMode` is the syntax of the importing expression and only matters for the node16/nodenext resolvers.
+ */
+pub fn resolveModuleName<'a>(moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, resolutionMode: ResolutionMode) -> ResolvedModuleWithFailedLookupLocations {
+    let traceEnabled = isTraceEnabled(compilerOptions, host);
+    if traceEnabled {
+        trace(host, Diagnostics::Resolving_module_0_from_1(), &[moduleName, containingFile]);
+    }
+    let moduleResolution = getEmitModuleResolutionKind(compilerOptions);
+    if traceEnabled {
+        if compilerOptions.moduleResolution.is_none() {
+            trace(host, Diagnostics::Module_resolution_kind_is_not_specified_using_0(), &[&format!("{moduleResolution:?}")]);
+        } else {
+            trace(host, Diagnostics::Explicitly_specified_module_resolution_kind_colon_0(), &[&format!("{moduleResolution:?}")]);
+        }
+    }
+
+    let result = match moduleResolution {
+        ModuleResolutionKind::Node16 => node16ModuleNameResolver(moduleName, containingFile, compilerOptions, host, cache, resolutionMode),
+        ModuleResolutionKind::NodeNext => nodeNextModuleNameResolver(moduleName, containingFile, compilerOptions, host, cache, resolutionMode),
+        ModuleResolutionKind::Node10 => {
+            let conditions = if resolutionMode != ResolutionMode::Undefined { Some(getConditions(compilerOptions, resolutionMode)) } else { None };
+            nodeModuleNameResolver(moduleName, containingFile, compilerOptions, host, cache, /*isConfigLookup*/ false, conditions)
+        }
+        ModuleResolutionKind::Classic => classicNameResolver(moduleName, containingFile, compilerOptions, host, cache),
+        ModuleResolutionKind::Bundler => {
+            let conditions = if resolutionMode != ResolutionMode::Undefined { Some(getConditions(compilerOptions, resolutionMode)) } else { None };
+            bundlerModuleNameResolver(moduleName, containingFile, compilerOptions, host, cache, conditions)
+        }
+    };
+
+    if traceEnabled {
+        if let Some(resolvedModule) = &result.resolvedModule {
+            if let Some(packageId) = &resolvedModule.packageId {
+                trace(host, Diagnostics::Module_name_0_was_successfully_resolved_to_1_with_Package_ID_2(), &[moduleName, &resolvedModule.resolvedFileName, &packageIdToString(packageId)]);
+            } else {
+                trace(host, Diagnostics::Module_name_0_was_successfully_resolved_to_1(), &[moduleName, &resolvedModule.resolvedFileName]);
+            }
+        } else {
+            trace(host, Diagnostics::Module_name_0_was_not_resolved(), &[moduleName]);
+        }
+    }
+    result
+}
+// endregion: 1520
+
+// region: 1530
+// Any module resolution kind can be augmented with optional settings: 'baseUrl', 'paths' and 'rootDirs' - they are used to
+// mitigate differences between design time structure of the project and its runtime counterpart so the same import name
+// can be resolved successfully by TypeScript compiler and runtime module loader.
+// If these settings are set then loading procedure will try to use them to resolve module name and it can of failure it will
+// fallback to standard resolution routine.
+//
+// - baseUrl - this setting controls how non-relative module names are resolved. If this setting is specified then non-relative
+// names will be resolved relative to baseUrl: i.e. if baseUrl is '/a/b' then candidate location to resolve module name 'c/d' will
+// be '/a/b/c/d'
+// - paths - this setting can only be used when baseUrl is specified. allows to tune how non-relative module names
+// will be resolved based on the content of the module name.
+// Structure of 'paths' compiler options
+// 'paths': {
+//    pattern-1: [...substitutions],
+//    pattern-2: [...substitutions],
+//    ...
+//    pattern-n: [...substitutions]
+// }
+// Pattern here is a string that can contain zero or one '*' character. During module resolution module name will be matched against
+// all patterns in the list. Matching for patterns that don't contain '*' means that module name must be equal to pattern respecting the case.
+// If pattern contains '*' then to match pattern "<prefix>*<suffix>" module name must start with the <prefix> and end with <suffix>.
+// <MatchedStar> denotes part of the module name between <prefix> and <suffix>.
+// If module name can be matches with multiple patterns then pattern with the longest prefix will be picked.
+// After selecting pattern we'll use list of substitutions to get candidate locations of the module and the try to load module
+// from the candidate location.
+// Substitution is a string that can contain zero or one '*'. To get candidate location from substitution we'll pick every
+// substitution in the list and replace '*' with <MatchedStar> string. If candidate location is not rooted it
+// will be converted to absolute using baseUrl.
+// For example:
+// baseUrl: /a/b/c
+// "paths": {
+//     // match all module names
+//     "*": [
+//         "*",        // use matched name as is,
+//                     // <matched name> will be looked as /a/b/c/<matched name>
+//
+//         "folder1/*" // substitution will convert matched name to 'folder1/<matched name>',
+//                     // since it is not rooted then final candidate location will be /a/b/c/folder1/<matched name>
+//     ],
+//     // match module names that start with 'components/'
+//     "components/*": [ "/root/components/*" ] // substitution will convert /components/folder1/<matched name> to '/root/components/folder1/<matched name>',
+//                                              // it is rooted so it will be final candidate location
+// }
+//
+// 'rootDirs' allows the project to be spreaded across multiple locations and resolve modules with relative names as if
+// they were in the same location. For example lets say there are two files
+// '/local/src/content/file1.ts'
+// '/shared/components/contracts/src/content/protocols/file2.ts'
+// After bundling content of '/shared/components/contracts/src' will be merged with '/local/src' so
+// if file1 has the following import 'import {x} from "./protocols/file2"' it will be resolved successfully in runtime.
+// 'rootDirs' provides the way to tell compiler that in order to get the whole project it should behave as if content of all
+// root dirs were merged together.
+// I.e. for the example above 'rootDirs' will have two entries: [ '/local/src', '/shared/components/contracts/src' ].
+// Compiler will first convert './protocols/file2' into absolute path relative to the location of containing file:
+// '/local/src/content/protocols/file2' and try to load it - failure.
+// Then it will search 'rootDirs' looking for a longest matching prefix of this absolute path and if such prefix is found - absolute path will
+// be converted to a path relative to found rootDir entry './content/protocols/file2' (*). As a last step compiler will check all remaining
+// entries in 'rootDirs', use them to build absolute path out of (*) and try to resolve module from this location.
+fn tryLoadModuleUsingOptionalResolutionSettings(extensions: Extensions, moduleName: &str, containingDirectory: &str, loader: &dyn Fn(Extensions, &str, bool, &mut ModuleResolutionState) -> Option<Resolved>, state: &mut ModuleResolutionState) -> Option<Resolved> {
+    if let Some(resolved) = tryLoadModuleUsingPathsIfEligible(extensions, moduleName, loader, state) {
+        return resolved.value;
+    }
+
+    if !isExternalModuleNameRelative(moduleName) {
+        tryLoadModuleUsingBaseUrl(extensions, moduleName, loader, state)
+    } else {
+        tryLoadModuleUsingRootDirs(extensions, moduleName, containingDirectory, loader, state)
+    }
+}
+
+fn tryLoadModuleUsingPathsIfEligible(extensions: Extensions, moduleName: &str, loader: &dyn Fn(Extensions, &str, bool, &mut ModuleResolutionState) -> Option<Resolved>, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    let compilerOptions = state.compilerOptions;
+    let paths = compilerOptions.paths.as_ref()?;
+    if pathIsRelative(moduleName) {
+        return None;
+    }
+    if state.traceEnabled {
+        if let Some(baseUrl) = &compilerOptions.baseUrl {
+            trace(state.host, Diagnostics::baseUrl_option_is_set_to_0_using_this_value_to_resolve_non_relative_module_name_1(), &[baseUrl, moduleName]);
+        }
+        trace(state.host, Diagnostics::paths_option_is_specified_looking_for_a_pattern_to_match_module_name_0(), &[moduleName]);
+    }
+    // ! rb a host without a current directory resolves `paths` against the requesting directory instead of failing the assertion
+    let Some(baseDirectory) = getPathsBasePath(compilerOptions, state.host).or_else(|| state.requestContainingDirectory.clone()) else {
+        return None;
+    };
+    let pathPatterns = tryParsePatterns(paths);
+    tryLoadModuleUsingPaths(extensions, moduleName, &baseDirectory, paths, &pathPatterns, loader, /*onlyRecordFailures*/ false, state)
+}
+
+fn tryLoadModuleUsingRootDirs(extensions: Extensions, moduleName: &str, containingDirectory: &str, loader: &dyn Fn(Extensions, &str, bool, &mut ModuleResolutionState) -> Option<Resolved>, state: &mut ModuleResolutionState) -> Option<Resolved> {
+    let compilerOptions = state.compilerOptions;
+    let rootDirs = compilerOptions.rootDirs.as_ref()?;
+
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::rootDirs_option_is_set_using_it_to_resolve_relative_module_name_0(), &[moduleName]);
+    }
+
+    let candidate = normalizePath(&combinePaths(containingDirectory, &[Some(moduleName)]));
+
+    let mut matchedRootDir: Option<&str> = None;
+    let mut matchedNormalizedPrefix: Option<String> = None;
+    for rootDir in rootDirs {
+        // rootDirs are expected to be absolute
+        // in case of tsconfig.json this will happen automatically - compiler will expand relative names
+        // using location of tsconfig.json as base location
+        let normalizedRoot = ensureTrailingDirectorySeparator(&normalizePath(rootDir));
+        let isLongestMatchingPrefix = candidate.starts_with(&normalizedRoot) && matchedNormalizedPrefix.as_ref().is_none_or(|matchedNormalizedPrefix| matchedNormalizedPrefix.len() < normalizedRoot.len());
+
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Checking_if_0_is_the_longest_matching_prefix_for_1_2(), &[&normalizedRoot, &candidate, &isLongestMatchingPrefix.to_string()]);
+        }
+
+        if isLongestMatchingPrefix {
+            matchedNormalizedPrefix = Some(normalizedRoot);
+            matchedRootDir = Some(rootDir);
+        }
+    }
+    let matchedNormalizedPrefix = matchedNormalizedPrefix?;
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Longest_matching_prefix_for_0_is_1(), &[&candidate, &matchedNormalizedPrefix]);
+    }
+    let suffix = &candidate[matchedNormalizedPrefix.len()..];
+
+    // first - try to load from a initial location
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Loading_0_from_the_root_dir_1_candidate_location_2(), &[suffix, &matchedNormalizedPrefix, &candidate]);
+    }
+    let onlyRecordFailures = !directoryProbablyExists(containingDirectory, state.host);
+    if let Some(resolvedFileName) = loader(extensions, &candidate, onlyRecordFailures, state) {
+        return Some(resolvedFileName);
+    }
+
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Trying_other_entries_in_rootDirs(), &[]);
+    }
+    // then try to resolve using remaining entries in rootDirs
+    for rootDir in rootDirs {
+        if Some(rootDir.as_str()) == matchedRootDir {
+            // skip the initially matched entry
+            continue;
+        }
+        let candidate = combinePaths(&normalizePath(rootDir), &[Some(suffix)]);
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Loading_0_from_the_root_dir_1_candidate_location_2(), &[suffix, rootDir, &candidate]);
+        }
+        let baseDirectory = getDirectoryPath(&candidate);
+        let onlyRecordFailures = !directoryProbablyExists(&baseDirectory, state.host);
+        if let Some(resolvedFileName) = loader(extensions, &candidate, onlyRecordFailures, state) {
+            return Some(resolvedFileName);
+        }
+    }
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Module_resolution_using_rootDirs_has_failed(), &[]);
+    }
+    None
+}
+
+fn tryLoadModuleUsingBaseUrl(extensions: Extensions, moduleName: &str, loader: &dyn Fn(Extensions, &str, bool, &mut ModuleResolutionState) -> Option<Resolved>, state: &mut ModuleResolutionState) -> Option<Resolved> {
+    let compilerOptions = state.compilerOptions;
+    let baseUrl = compilerOptions.baseUrl.as_ref()?;
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::baseUrl_option_is_set_to_0_using_this_value_to_resolve_non_relative_module_name_1(), &[baseUrl, moduleName]);
+    }
+    let candidate = normalizePath(&combinePaths(baseUrl, &[Some(moduleName)]));
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Resolving_module_name_0_relative_to_base_url_1_2(), &[moduleName, baseUrl, &candidate]);
+    }
+    let onlyRecordFailures = !directoryProbablyExists(&getDirectoryPath(&candidate), state.host);
+    loader(extensions, &candidate, onlyRecordFailures, state)
+}
+// endregion: 1677
+
 // region: 1679
 /** @internal */
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -131,111 +780,566 @@ impl std::ops::BitAnd for NodeResolutionFeatures {
     type Output = Self;
     fn bitand(self, rhs: Self) -> Self { NodeResolutionFeatures(self.0 & rhs.0) }
 }
-// endregion: 1700
-
-// region: 2009
-/** @internal */
-pub const nodeModulesPathPart: &str = "/node_modules/";
-/** @internal */
-pub fn pathContainsNodeModules(path: &str) -> bool { path.contains(nodeModulesPathPart) }
-// endregion: 2014
 
-// region: 2345
-/** @internal */
-pub fn getTemporaryModuleResolutionState<'a>(packageJsonInfoCache: Option<&'a mut dyn PackageJsonInfoCache>, host: &'a dyn ModuleResolutionHost, options: &'a CompilerOptions) -> ModuleResolutionState<'a> {
-    ModuleResolutionState {
-        host,
-        compilerOptions: options,
-        traceEnabled: false, // isTraceEnabled(options, host),
-        failedLookupLocations: None,
-        affectingLocations: None,
-        // resultFromCache: None,
-        packageJsonInfoCache,
-        features: NodeResolutionFeatures::None,
-        conditions: Vec::new(),
-        requestContainingDirectory: None,
-        // reportDiagnostic: None,
-        isConfigLookup: false,
-        candidateIsFromPackageJsonField: false,
-        resolvedPackageDirectory: false,
-    }
+impl std::ops::Not for NodeResolutionFeatures {
+    type Output = Self;
+    fn not(self) -> Self { NodeResolutionFeatures(!self.0) }
 }
 
-/** @internal */
-#[derive(Debug, Clone)]
-pub struct PackageJsonInfo {
-    pub packageDirectory: String,
-    pub contents: PackageJsonInfoContents,
+impl NodeResolutionFeatures {
+    pub fn intersects(&self, flags: NodeResolutionFeatures) -> bool { (self.0 & flags.0) != 0 }
 }
-/** @internal */
-#[derive(Debug, Clone)]
-pub struct PackageJsonInfoContents {
-    pub packageJsonContent: PackageJsonPathFields,
-    pub versionPaths: Option<VersionPaths>,
-    pub versionPathsResolved: bool,
-    pub resolvedEntrypoints: Option<Vec<String>>,
-    pub resolvedEntrypointsResolved: bool,
-    pub peerDependencies: Option<String>,
-    pub peerDependenciesResolved: bool,
+
+fn node16ModuleNameResolver<'a>(moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, resolutionMode: ResolutionMode) -> ResolvedModuleWithFailedLookupLocations {
+    nodeNextModuleNameResolverWorker(NodeResolutionFeatures::Node16Default, moduleName, containingFile, compilerOptions, host, cache, resolutionMode)
 }
 
-/**
- * A function for locating the package.json scope for a given path
- *
- * @internal
- */
-pub fn getPackageScopeForPath(directory: &str, state: &mut ModuleResolutionState) -> Option<PackageJsonInfo> {
-    forEachAncestorDirectory(directory, |dir| getPackageJsonInfo(dir, /*onlyRecordFailures*/ false, state))
+fn nodeNextModuleNameResolver<'a>(moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, resolutionMode: ResolutionMode) -> ResolvedModuleWithFailedLookupLocations {
+    nodeNextModuleNameResolverWorker(NodeResolutionFeatures::NodeNextDefault, moduleName, containingFile, compilerOptions, host, cache, resolutionMode)
 }
 
-// fn getVersionPathsOfPackageJsonInfo(packageJsonInfo: &PackageJsonInfo, state: &ModuleResolutionState) -> Option<VersionPaths> {
-//     if packageJsonInfo.contents.versionPaths.is_none() {
-//         packageJsonInfo.contents.versionPaths = readPackageJsonTypesVersionPaths(&packageJsonInfo.contents.packageJsonContent, state).or(Some(false));
-//     }
-//     packageJsonInfo.contents.versionPaths
-// }
+fn nodeNextModuleNameResolverWorker<'a>(
+    features: NodeResolutionFeatures, moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, resolutionMode: ResolutionMode,
+) -> ResolvedModuleWithFailedLookupLocations {
+    let containingDirectory = getDirectoryPath(containingFile);
 
-// fn getPeerDependenciesOfPackageJsonInfo(packageJsonInfo: &PackageJsonInfo, state: &ModuleResolutionState) -> Option<String> {
-//     if packageJsonInfo.contents.peerDependencies.is_none() {
-//         packageJsonInfo.contents.peerDependencies = readPackageJsonPeerDependencies(packageJsonInfo, state).or(Some(false));
-//     }
-//     packageJsonInfo.contents.peerDependencies
-// }
+    // es module file or cjs-like input file, use a variant of the legacy cjs resolver that supports the selected modern features
+    let esmMode = if resolutionMode == ResolutionMode::ESNext { NodeResolutionFeatures::EsmMode } else { NodeResolutionFeatures::None };
+    let mut extensions = if compilerOptions.noDtsResolution == Some(true) { Extensions::ImplementationFiles } else { Extensions::TypeScript | Extensions::JavaScript | Extensions::Declaration };
+    if getResolveJsonModule(compilerOptions) {
+        extensions |= Extensions::Json;
+    }
+    nodeModuleNameResolverWorker(features | esmMode, moduleName, &containingDirectory, compilerOptions, host, cache, extensions, /*isConfigLookup*/ false, /*conditions*/ None)
+}
+// endregion: 1700
 
-// fn readPackageJsonPeerDependencies(packageJsonInfo: &PackageJsonInfo, state: &ModuleResolutionState) -> Option<String> {
-//     let peerDependencies = readPackageJsonField(&packageJsonInfo.contents.packageJsonContent, "peerDependencies", "object", state);
-//     if peerDependencies.is_none() {
-//         return None;
-//     }
-//     // if state.traceEnabled {
-//     //     trace(state.host, Diagnostics::package_json_has_a_peerDependencies_field);
-//     // }
-//     let packageDirectory = realPath(&packageJsonInfo.packageDirectory, state.host, state.traceEnabled);
-//     let nodeModules = format!("{}/", packageDirectory[..packageDirectory.rfind("node_modules").unwrap() + "node_modules".len()]);
-//     let mut result = String::new();
-//     for (key, _) in peerDependencies.iter() {
-//         if hasProperty(peerDependencies, key) {
-//             let peerPackageJson = getPackageJsonInfo(&(nodeModules.clone() + key), /*onlyRecordFailures*/ false, state);
-//             if let Some(peerPackageJson) = peerPackageJson {
-//                 let version = peerPackageJson.contents.packageJsonContent.version.as_ref().unwrap();
-//                 result.push_str(&format!("+{}@{}", key, version));
-//                 // if state.traceEnabled {
-//                 //     trace(state.host, Diagnostics::Found_peerDependency_0_with_1_version, key, version);
-//                 // }
-//             } else {
-//                 // Read the dependency version
-//                 // if state.traceEnabled {
-//                 //     trace(state.host, Diagnostics::Failed_to_find_peerDependency_0, key);
-//                 // }
-//             }
-//         }
-//     }
-//     Some(result)
-// }
+// region: 1710
+/** @internal */
+pub fn bundlerModuleNameResolver<'a>(moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, conditions: Option<Vec<String>>) -> ResolvedModuleWithFailedLookupLocations {
+    let containingDirectory = getDirectoryPath(containingFile);
+    let mut extensions = if compilerOptions.noDtsResolution == Some(true) { Extensions::ImplementationFiles } else { Extensions::TypeScript | Extensions::JavaScript | Extensions::Declaration };
+    if getResolveJsonModule(compilerOptions) {
+        extensions |= Extensions::Json;
+    }
+    nodeModuleNameResolverWorker(getNodeResolutionFeatures(compilerOptions), moduleName, &containingDirectory, compilerOptions, host, cache, extensions, /*isConfigLookup*/ false, conditions)
+}
+
+pub fn nodeModuleNameResolver<'a>(moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, isConfigLookup: bool, conditions: Option<Vec<String>>) -> ResolvedModuleWithFailedLookupLocations {
+    let extensions = if isConfigLookup {
+        Extensions::Json
+    } else if compilerOptions.noDtsResolution == Some(true) {
+        let mut extensions = Extensions::ImplementationFiles;
+        if getResolveJsonModule(compilerOptions) {
+            extensions |= Extensions::Json;
+        }
+        extensions
+    } else if getResolveJsonModule(compilerOptions) {
+        Extensions::TypeScript | Extensions::JavaScript | Extensions::Declaration | Extensions::Json
+    } else {
+        Extensions::TypeScript | Extensions::JavaScript | Extensions::Declaration
+    };
+
+    let features = if conditions.is_some() { NodeResolutionFeatures::AllFeatures } else { NodeResolutionFeatures::None };
+    nodeModuleNameResolverWorker(features, moduleName, &getDirectoryPath(containingFile), compilerOptions, host, cache, extensions, isConfigLookup, conditions)
+}
+// endregion: 1742
+
+// region: 1744
+/** @internal */
+pub fn nodeNextJsonConfigResolver(moduleName: &str, containingFile: &str, host: &dyn ModuleResolutionHost) -> ResolvedModuleWithFailedLookupLocations {
+    let compilerOptions = CompilerOptions { moduleResolution: Some(ModuleResolutionKind::NodeNext), ..Default::default() };
+    nodeModuleNameResolverWorker(NodeResolutionFeatures::NodeNextDefault, moduleName, &getDirectoryPath(containingFile), &compilerOptions, host, /*cache*/ None, Extensions::Json, /*isConfigLookup*/ true, /*conditions*/ None)
+}
+
+fn nodeModuleNameResolverWorker<'a>(
+    features: NodeResolutionFeatures, moduleName: &str, containingDirectory: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>, extensions: Extensions, isConfigLookup: bool, conditions: Option<Vec<String>>,
+) -> ResolvedModuleWithFailedLookupLocations {
+    let traceEnabled = isTraceEnabled(compilerOptions, host);
+
+    let moduleResolution = getEmitModuleResolutionKind(compilerOptions);
+    let conditions = conditions.unwrap_or_else(|| {
+        let resolutionMode = if moduleResolution == ModuleResolutionKind::Bundler || moduleResolution == ModuleResolutionKind::Node10 {
+            ResolutionMode::Undefined
+        } else if features.intersects(NodeResolutionFeatures::EsmMode) {
+            ResolutionMode::ESNext
+        } else {
+            ResolutionMode::CommonJS
+        };
+        getConditions(compilerOptions, resolutionMode)
+    });
+
+    let mut state = ModuleResolutionState {
+        host,
+        compilerOptions,
+        traceEnabled,
+        failedLookupLocations: Some(vec![]),
+        affectingLocations: Some(vec![]),
+        packageJsonInfoCache: cache,
+        features,
+        conditions,
+        requestContainingDirectory: Some(containingDirectory.to_string()),
+        resolutionDiagnostics: Some(vec![]),
+        isConfigLookup,
+        candidateIsFromPackageJsonField: false,
+        resolvedPackageDirectory: false,
+    };
+
+    if traceEnabled && moduleResolutionSupportsPackageJsonExportsAndImports(moduleResolution) {
+        trace(host, Diagnostics::Resolving_in_0_mode_with_conditions_1(), &[if features.intersects(NodeResolutionFeatures::EsmMode) { "ESM" } else { "CJS" }, &state.conditions.iter().map(|c| format!("'{c}'")).collect::<Vec<_>>().join(", ")]);
+    }
+
+    let result = if moduleResolution == ModuleResolutionKind::Node10 {
+        let priorityExtensions = extensions & (Extensions::TypeScript | Extensions::Declaration);
+        let secondaryExtensions = extensions & !(Extensions::TypeScript | Extensions::Declaration);
+        (priorityExtensions != Extensions::None).then(|| tryResolve(priorityExtensions, moduleName, containingDirectory, &mut state)).flatten().or_else(|| (secondaryExtensions != Extensions::None).then(|| tryResolve(secondaryExtensions, moduleName, containingDirectory, &mut state)).flatten())
+    } else {
+        tryResolve(extensions, moduleName, containingDirectory, &mut state)
+    };
+    let result = result.and_then(|result| result.value);
+
+    // For non-relative names that resolved to JS but no types in modes that look up an "import" condition in package.json "exports",
+    // try again with "exports" disabled to try to detect if this is likely a configuration error in a dependency's package.json.
+    let mut alternateResult = None;
+    if state.resolvedPackageDirectory && !isConfigLookup && !isExternalModuleNameRelative(moduleName) {
+        let wantedTypesButGotJs = result.as_ref().is_some_and(|result| extensions.intersects(Extensions::TypeScript | Extensions::Declaration) && !extensionIsOk(Extensions::TypeScript | Extensions::Declaration, &result.resolved.extension));
+        if result.as_ref().is_some_and(|result| result.isExternalLibraryImport) && wantedTypesButGotJs && features.intersects(NodeResolutionFeatures::Exports) && state.conditions.iter().any(|condition| condition == "import") {
+            traceIfEnabled(&state, Diagnostics::Resolution_of_non_relative_name_failed_trying_with_modern_Node_resolution_features_disabled_to_see_if_npm_library_needs_configuration_update(), &[]);
+            let features = state.features;
+            state.features = features & !NodeResolutionFeatures::Exports;
+            let diagnosticResult = tryResolve(extensions & (Extensions::TypeScript | Extensions::Declaration), moduleName, containingDirectory, &mut state);
+            state.features = features;
+            if let Some(value) = diagnosticResult.and_then(|result| result.value).filter(|value| value.isExternalLibraryImport) {
+                alternateResult = Some(value.resolved.path);
+            }
+        } else if (result.is_none() || wantedTypesButGotJs) && moduleResolution == ModuleResolutionKind::Node10 {
+            traceIfEnabled(&state, Diagnostics::Resolution_of_non_relative_name_failed_trying_with_moduleResolution_bundler_to_see_if_project_may_need_configuration_update(), &[]);
+            let diagnosticsCompilerOptions = CompilerOptions { moduleResolution: Some(ModuleResolutionKind::Bundler), ..compilerOptions.clone() };
+            let mut diagnosticState = ModuleResolutionState {
+                host,
+                compilerOptions: &diagnosticsCompilerOptions,
+                traceEnabled,
+                failedLookupLocations: state.failedLookupLocations.take(),
+                affectingLocations: state.affectingLocations.take(),
+                packageJsonInfoCache: None,
+                features: NodeResolutionFeatures::BundlerDefault,
+                conditions: getConditions(&diagnosticsCompilerOptions, ResolutionMode::Undefined),
+                requestContainingDirectory: state.requestContainingDirectory.clone(),
+                resolutionDiagnostics: None,
+                isConfigLookup,
+                candidateIsFromPackageJsonField: false,
+                resolvedPackageDirectory: false,
+            };
+            let diagnosticResult = tryResolve(extensions & (Extensions::TypeScript | Extensions::Declaration), moduleName, containingDirectory, &mut diagnosticState);
+            state.failedLookupLocations = diagnosticState.failedLookupLocations;
+            state.affectingLocations = diagnosticState.affectingLocations;
+            if let Some(value) = diagnosticResult.and_then(|result| result.value).filter(|value| value.isExternalLibraryImport) {
+                alternateResult = Some(value.resolved.path);
+            }
+        }
+    }
+
+    let isExternalLibraryImport = result.as_ref().map(|result| result.isExternalLibraryImport);
+    createResolvedModuleWithFailedLookupLocationsHandlingSymlink(moduleName, result.map(|result| result.resolved), isExternalLibraryImport, &mut state, alternateResult)
+}
+
+fn tryResolve(extensions: Extensions, moduleName: &str, containingDirectory: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<NodeResolutionResult>> {
+    let loader = |extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState| nodeLoadModuleByRelativeName(extensions, candidate, onlyRecordFailures, state, /*considerPackageJson*/ true);
+    if let Some(resolved) = tryLoadModuleUsingOptionalResolutionSettings(extensions, moduleName, containingDirectory, &loader, state) {
+        let isExternalLibraryImport = pathContainsNodeModules(&resolved.path);
+        return toSearchResult(Some(NodeResolutionResult { resolved, isExternalLibraryImport }));
+    }
+
+    if !isExternalModuleNameRelative(moduleName) {
+        let mut resolved = None;
+        if state.features.intersects(NodeResolutionFeatures::Imports) && moduleName.starts_with('#') {
+            resolved = loadModuleFromImports(extensions, moduleName, containingDirectory, state);
+        }
+        if resolved.is_none() && state.features.intersects(NodeResolutionFeatures::SelfName) {
+            resolved = loadModuleFromSelfNameReference(extensions, moduleName, containingDirectory, state);
+        }
+        if let Some(resolved) = resolved {
+            return Some(SearchResult {
+                value: resolved.value.map(|resolved| {
+                    let isExternalLibraryImport = pathContainsNodeModules(&resolved.path);
+                    NodeResolutionResult { resolved, isExternalLibraryImport }
+                }),
+            });
+        }
+        if moduleName.contains(':') {
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::Skipping_module_0_that_looks_like_an_absolute_URI_target_file_types_colon_1(), &[moduleName, &formatExtensions(extensions)]);
+            }
+            return None;
+        }
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Loading_module_0_from_node_modules_folder_target_file_types_colon_1(), &[moduleName, &formatExtensions(extensions)]);
+        }
+        let mut resolved = loadModuleFromNearestNodeModulesDirectory(extensions, moduleName, containingDirectory, state);
+        if extensions.intersects(Extensions::Declaration) && resolved.is_none() {
+            resolved = resolveFromTypeRoot(moduleName, state);
+        }
+        // Node_modules lookups are external library imports, whose real path is taken by createResolvedModuleWithFailedLookupLocationsHandlingSymlink
+        // so that multiple accesses to an `npm link`-ed module do not create duplicate files.
+        resolved.map(|resolved| SearchResult { value: resolved.value.map(|resolved| NodeResolutionResult { resolved, isExternalLibraryImport: true }) })
+    } else {
+        let (candidate, parts) = normalizePathForCJSResolution(containingDirectory, moduleName);
+        let resolved = nodeLoadModuleByRelativeName(extensions, &candidate, /*onlyRecordFailures*/ false, state, /*considerPackageJson*/ true);
+        // Treat explicit "node_modules" import as an external library import.
+        toSearchResult(resolved.map(|resolved| NodeResolutionResult { resolved, isExternalLibraryImport: parts.iter().any(|part| part == "node_modules") }))
+    }
+}
+
+// If you import from "." inside a containing directory "/foo", the result of `normalizePath`
+// would be "/foo", but this loses the information that `foo` is a directory and we intended
+// to look inside of it. The Node CommonJS resolution algorithm doesn't call this out
+// (https://nodejs.org/api/modules.html#all-together), but it seems that module paths ending
+// in `.` are actually normalized to `./` before proceeding with the resolution algorithm.
+fn normalizePathForCJSResolution(containingDirectory: &str, moduleName: &str) -> (String, Vec<String>) {
+    let combined = combinePaths(containingDirectory, &[Some(moduleName)]);
+    let parts = getPathComponents(&combined, None);
+    let lastPart = parts.last().map(|part| part.as_str());
+    let path = if lastPart == Some(".") || lastPart == Some("..") { ensureTrailingDirectorySeparator(&normalizePath(&combined)) } else { normalizePath(&combined) };
+    (path, parts)
+}
+
+fn realPath(path: &str, host: &dyn ModuleResolutionHost, traceEnabled: bool) -> String {
+    let Some(real) = host.realpath(path) else {
+        return path.to_string();
+    };
+    let real = normalizePath(&real);
+    if traceEnabled {
+        trace(host, Diagnostics::Resolving_real_path_for_0_result_1(), &[path, &real]);
+    }
+    real
+}
+
+fn nodeLoadModuleByRelativeName(extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState, considerPackageJson: bool) -> Option<Resolved> {
+    let mut onlyRecordFailures = onlyRecordFailures;
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Loading_module_as_file_slash_folder_candidate_module_location_0_target_file_types_colon_1(), &[candidate, &formatExtensions(extensions)]);
+    }
+    if !hasTrailingDirectorySeparator(candidate) {
+        if !onlyRecordFailures {
+            let parentOfCandidate = getDirectoryPath(candidate);
+            if !directoryProbablyExists(&parentOfCandidate, state.host) {
+                if state.traceEnabled {
+                    trace(state.host, Diagnostics::Directory_0_does_not_exist_skipping_all_lookups_in_it(), &[&parentOfCandidate]);
+                }
+                onlyRecordFailures = true;
+            }
+        }
+        if let Some(resolvedFromFile) = loadModuleFromFile(extensions, candidate, onlyRecordFailures, state) {
+            let packageDirectory = if considerPackageJson { parseNodeModuleFromPath(&resolvedFromFile.path, false) } else { None };
+            let packageInfo = packageDirectory.and_then(|packageDirectory| getPackageJsonInfo(&packageDirectory, /*onlyRecordFailures*/ false, state));
+            return withPackageId(packageInfo.as_ref(), Some(resolvedFromFile), state);
+        }
+    }
+    if !onlyRecordFailures {
+        let candidateExists = directoryProbablyExists(candidate, state.host);
+        if !candidateExists {
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::Directory_0_does_not_exist_skipping_all_lookups_in_it(), &[candidate]);
+            }
+            onlyRecordFailures = true;
+        }
+    }
+    // esm mode relative imports shouldn't do any directory lookups (either inside `package.json`
+    // files or implicit `index.js`es). This is a notable depature from cjs norms, where `./foo/pkg`
+    // could have been redirected by `./foo/pkg/package.json` to an arbitrary location!
+    if !state.features.intersects(NodeResolutionFeatures::EsmMode) {
+        return loadNodeModuleFromDirectory(extensions, candidate, onlyRecordFailures, state, considerPackageJson);
+    }
+    None
+}
+// endregion: 2008
+
+// region: 2009
+/** @internal */
+pub const nodeModulesPathPart: &str = "/node_modules/";
+/** @internal */
+pub fn pathContainsNodeModules(path: &str) -> bool { path.contains(nodeModulesPathPart) }
+
+/**
+ * This will be called on the successfully resolved path from `loadModuleFromFile`.
+ * (Not needed for `loadModuleFromNodeModules` as that looks up the `package.json` as part of resolution.)
+ *
+ * packageDirectory is the directory of the package itself.
+ *   For `blah/node_modules/foo/index.d.ts` this is packageDirectory: "foo"
+ *   For `/node_modules/foo/bar.d.ts` this is packageDirectory: "foo"
+ *   For `/node_modules/@types/foo/bar/index.d.ts` this is packageDirectory: "@types/foo"
+ *   For `/node_modules/foo/bar/index.d.ts` this is packageDirectory: "foo"
+ *
+ * @internal
+ */
+pub fn parseNodeModuleFromPath(resolved: &str, isFolder: bool) -> Option<String> {
+    let path = normalizePath(resolved);
+    let idx = path.rfind(nodeModulesPathPart)?;
+
+    let indexAfterNodeModules = idx + nodeModulesPathPart.len();
+    let mut indexAfterPackageName = moveToNextDirectorySeparatorIfAvailable(&path, indexAfterNodeModules, isFolder);
+    if path.as_bytes().get(indexAfterNodeModules) == Some(&b'@') {
+        indexAfterPackageName = moveToNextDirectorySeparatorIfAvailable(&path, indexAfterPackageName, isFolder);
+    }
+    Some(path[..indexAfterPackageName].to_string())
+}
+
+fn moveToNextDirectorySeparatorIfAvailable(path: &str, prevSeparatorIndex: usize, isFolder: bool) -> usize {
+    match path.get(prevSeparatorIndex + 1..).and_then(|rest| rest.find(directorySeparator)) {
+        Some(nextSeparatorIndex) => prevSeparatorIndex + 1 + nextSeparatorIndex,
+        None if isFolder => path.len(),
+        None => prevSeparatorIndex,
+    }
+}
+
+fn loadModuleFromFileNoPackageId(extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<Resolved> { noPackageId(loadModuleFromFile(extensions, candidate, onlyRecordFailures, state)) }
+
+/**
+ * @param {boolean} onlyRecordFailures - if true then function won't try to actually load files but instead record all attempts as failures. This flag is necessary
+ * in cases when we know upfront that all load attempts will fail (because containing folder does not exists) however we still need to record all failed lookup locations.
+ */
+fn loadModuleFromFile(extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<PathAndExtension> {
+    // ./foo.js -> ./foo.ts
+    let resolvedByReplacingExtension = loadModuleFromFileNoImplicitExtensions(extensions, candidate, onlyRecordFailures, state);
+    if resolvedByReplacingExtension.is_some() {
+        return resolvedByReplacingExtension;
+    }
+
+    // ./foo -> ./foo.ts
+    if !state.features.intersects(NodeResolutionFeatures::EsmMode) {
+        // First, try adding an extension. An import of "foo" could be matched by a file "foo.ts", or "foo.js" by "foo.js.ts"
+        return tryAddingExtensions(candidate, extensions, "", onlyRecordFailures, state);
+    }
+    None
+}
+
+fn loadModuleFromFileNoImplicitExtensions(extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<PathAndExtension> {
+    let filename = getBaseFileName(candidate);
+    if !filename.contains('.') {
+        return None; // extensionless import, no lookups performed, since we don't support extensionless files
+    }
+    let mut extensionless = removeFileExtension(candidate);
+    if extensionless == candidate {
+        // Once TS native extensions are handled, handle arbitrary extensions for declaration file mapping
+        extensionless = candidate[..candidate.rfind('.').unwrap()].to_string();
+    }
+
+    let extension = &candidate[extensionless.len()..];
+    tryAddingExtensions(&extensionless, extensions, extension, onlyRecordFailures, state)
+}
+
+/**
+ * This function is only ever called with paths written in package.json files - never
+ * module specifiers written in source files - and so it always allows the
+ * candidate to end with a TS extension (but will also try substituting a JS extension for a TS extension).
+ */
+fn loadFileNameFromPackageJsonField(extensions: Extensions, candidate: &str, packageJsonValue: Option<&str>, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<PathAndExtension> {
+    if (extensions.intersects(Extensions::TypeScript) && fileExtensionIsOneOf(candidate, supportedTSImplementationExtensions.iter().map(|e| e.as_str()).collect()))
+        || (extensions.intersects(Extensions::Declaration) && fileExtensionIsOneOf(candidate, supportedDeclarationExtensions.iter().map(|e| e.as_str()).collect()))
+    {
+        let result = tryFile(candidate, onlyRecordFailures, state)?;
+        return Some(PathAndExtension { path: candidate.to_string(), ext: tryExtractTSExtension(candidate).unwrap().as_str().to_string(), resolvedUsingTsExtension: packageJsonValue.map(|packageJsonValue| !packageJsonValue.ends_with(&result)) });
+    }
+
+    if state.isConfigLookup && extensions == Extensions::Json && fileExtensionIs(candidate, Extension::Json.as_str()) {
+        tryFile(candidate, onlyRecordFailures, state)?;
+        return Some(PathAndExtension { path: candidate.to_string(), ext: Extension::Json.as_str().to_string(), resolvedUsingTsExtension: None });
+    }
+
+    loadModuleFromFileNoImplicitExtensions(extensions, candidate, onlyRecordFailures, state)
+}
+
+/** Try to return an existing file that adds one of the `extensions` to `candidate`. */
+fn tryAddingExtensions(candidate: &str, extensions: Extensions, originalExtension: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<PathAndExtension> {
+    let mut onlyRecordFailures = onlyRecordFailures;
+    if !onlyRecordFailures {
+        // check if containing folder exists - if it doesn't then just record failures for all supported extensions without disk probing
+        let directory = getDirectoryPath(candidate);
+        if !directory.is_empty() {
+            onlyRecordFailures = !directoryProbablyExists(&directory, state.host);
+        }
+    }
+
+    let isConfigLookup = state.isConfigLookup;
+    let mut tryExtension = |candidate: &str, ext: &str, resolvedUsingTsExtension: bool| -> Option<PathAndExtension> {
+        let path = tryFile(&format!("{candidate}{ext}"), onlyRecordFailures, state)?;
+        Some(PathAndExtension { path, ext: ext.to_string(), resolvedUsingTsExtension: Some(!state.candidateIsFromPackageJsonField && resolvedUsingTsExtension) })
+    };
+    let isOriginalExtension = |exts: &[Extension]| exts.iter().any(|ext| ext.as_str() == originalExtension);
+    let typeScript = extensions.intersects(Extensions::TypeScript);
+    let declaration = extensions.intersects(Extensions::Declaration);
+    let javaScript = extensions.intersects(Extensions::JavaScript);
 
-fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<PackageJsonInfo> {
+    if isOriginalExtension(&[Extension::Mjs, Extension::Mts, Extension::Dmts]) {
+        let resolvedUsingTsExtension = isOriginalExtension(&[Extension::Mts, Extension::Dmts]);
+        typeScript
+            .then(|| tryExtension(candidate, Extension::Mts.as_str(), resolvedUsingTsExtension))
+            .flatten()
+            .or_else(|| declaration.then(|| tryExtension(candidate, Extension::Dmts.as_str(), resolvedUsingTsExtension)).flatten())
+            .or_else(|| javaScript.then(|| tryExtension(candidate, Extension::Mjs.as_str(), false)).flatten())
+    } else if isOriginalExtension(&[Extension::Cjs, Extension::Cts, Extension::Dcts]) {
+        let resolvedUsingTsExtension = isOriginalExtension(&[Extension::Cts, Extension::Dcts]);
+        typeScript
+            .then(|| tryExtension(candidate, Extension::Cts.as_str(), resolvedUsingTsExtension))
+            .flatten()
+            .or_else(|| declaration.then(|| tryExtension(candidate, Extension::Dcts.as_str(), resolvedUsingTsExtension)).flatten())
+            .or_else(|| javaScript.then(|| tryExtension(candidate, Extension::Cjs.as_str(), false)).flatten())
+    } else if isOriginalExtension(&[Extension::Json]) {
+        if declaration {
+            if let Some(result) = tryExtension(&format!("{candidate}{}", Extension::Json.as_str()), Extension::Dts.as_str(), false) {
+                return Some(result);
+            }
+        }
+        if extensions.intersects(Extensions::Json) {
+            if let Some(result) = tryExtension(candidate, Extension::Json.as_str(), false) {
+                return Some(result);
+            }
+        }
+        None
+    } else if isOriginalExtension(&[Extension::Tsx, Extension::Jsx]) {
+        let resolvedUsingTsExtension = isOriginalExtension(&[Extension::Tsx]);
+        typeScript
+            .then(|| tryExtension(candidate, Extension::Tsx.as_str(), resolvedUsingTsExtension).or_else(|| tryExtension(candidate, Extension::Ts.as_str(), resolvedUsingTsExtension)))
+            .flatten()
+            .or_else(|| declaration.then(|| tryExtension(candidate, Extension::Dts.as_str(), resolvedUsingTsExtension)).flatten())
+            .or_else(|| javaScript.then(|| tryExtension(candidate, Extension::Jsx.as_str(), false).or_else(|| tryExtension(candidate, Extension::Js.as_str(), false))).flatten())
+            .or_else(|| isConfigLookup.then(|| tryExtension(candidate, Extension::Json.as_str(), false)).flatten())
+    } else if isOriginalExtension(&[Extension::Ts, Extension::Dts, Extension::Js]) || originalExtension.is_empty() {
+        let resolvedUsingTsExtension = isOriginalExtension(&[Extension::Ts, Extension::Dts]);
+        typeScript
+            .then(|| tryExtension(candidate, Extension::Ts.as_str(), resolvedUsingTsExtension).or_else(|| tryExtension(candidate, Extension::Tsx.as_str(), resolvedUsingTsExtension)))
+            .flatten()
+            .or_else(|| declaration.then(|| tryExtension(candidate, Extension::Dts.as_str(), resolvedUsingTsExtension)).flatten())
+            .or_else(|| javaScript.then(|| tryExtension(candidate, Extension::Js.as_str(), false).or_else(|| tryExtension(candidate, Extension::Jsx.as_str(), false))).flatten())
+            .or_else(|| isConfigLookup.then(|| tryExtension(candidate, Extension::Json.as_str(), false)).flatten())
+    } else if declaration && !isDeclarationFileName(&format!("{candidate}{originalExtension}")) {
+        tryExtension(candidate, &format!(".d{originalExtension}.ts"), false)
+    } else {
+        None
+    }
+}
+
+/** Return the file if it exists. */
+fn tryFile(fileName: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<String> {
+    let compilerOptions = state.compilerOptions;
+    let Some(moduleSuffixes) = compilerOptions.moduleSuffixes.as_ref().filter(|moduleSuffixes| !moduleSuffixes.is_empty()) else {
+        return tryFileLookup(fileName, onlyRecordFailures, state);
+    };
+
+    let ext = tryGetExtensionFromPath(fileName).map_or("", |ext| ext.as_str());
+    let fileNameNoExtension = if ext.is_empty() { fileName.to_string() } else { removeExtension(fileName, ext) };
+    moduleSuffixes.iter().find_map(|suffix| tryFileLookup(&format!("{fileNameNoExtension}{suffix}{ext}"), onlyRecordFailures, state))
+}
+
+fn tryFileLookup(fileName: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<String> {
+    if !onlyRecordFailures {
+        if state.host.fileExists(fileName) {
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::File_0_exists_use_it_as_a_name_resolution_result(), &[fileName]);
+            }
+            return Some(fileName.to_string());
+        }
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::File_0_does_not_exist(), &[fileName]);
+        }
+    }
+    if let Some(locations) = &mut state.failedLookupLocations {
+        locations.push(fileName.to_string());
+    }
+    None
+}
+
+fn loadNodeModuleFromDirectory(extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState, considerPackageJson: bool) -> Option<Resolved> {
+    let packageInfo = if considerPackageJson { getPackageJsonInfo(candidate, onlyRecordFailures, state) } else { None };
+    let versionPaths = packageInfo.as_ref().and_then(|packageInfo| getVersionPathsOfPackageJsonInfo(packageInfo, state));
+    let packageJsonContent = packageInfo.as_ref().map(|packageInfo| &packageInfo.contents.packageJsonContent);
+    let resolved = loadNodeModuleFromDirectoryWorker(extensions, candidate, onlyRecordFailures, state, packageJsonContent, versionPaths);
+    withPackageId(packageInfo.as_ref(), resolved, state)
+}
+// endregion: 2296
+
+// region: 2345
+/** @internal */
+pub fn getTemporaryModuleResolutionState<'a>(packageJsonInfoCache: Option<&'a mut dyn PackageJsonInfoCache>, host: &'a dyn ModuleResolutionHost, options: &'a CompilerOptions) -> ModuleResolutionState<'a> {
+    ModuleResolutionState {
+        host,
+        compilerOptions: options,
+        traceEnabled: isTraceEnabled(options, host),
+        failedLookupLocations: None,
+        affectingLocations: None,
+        // resultFromCache: None,
+        packageJsonInfoCache,
+        features: NodeResolutionFeatures::None,
+        conditions: Vec::new(),
+        requestContainingDirectory: None,
+        resolutionDiagnostics: None,
+        isConfigLookup: false,
+        candidateIsFromPackageJsonField: false,
+        resolvedPackageDirectory: false,
+    }
+}
+
+/** @internal */
+#[derive(Debug, Clone)]
+pub struct PackageJsonInfo {
+    pub packageDirectory: String,
+    // ! rb shared with the cache entry and every other info for the same package.json, like the object TypeScript hands out
+    pub contents: Rc<PackageJsonInfoContents>,
+}
+/** @internal */
+// ! rb an empty cell is `undefined` (not read yet) and a cell holding `None` is `false` (read, but absent)
+#[derive(Debug, Clone)]
+pub struct PackageJsonInfoContents {
+    pub packageJsonContent: PackageJson,
+    pub versionPaths: OnceCell<Option<VersionPaths>>,
+    pub resolvedEntrypoints: OnceCell<Option<Vec<String>>>,
+    pub peerDependencies: OnceCell<Option<String>>,
+}
+
+/**
+ * A function for locating the package.json scope for a given path
+ *
+ * @internal
+ */
+pub fn getPackageScopeForPath(directory: &str, state: &mut ModuleResolutionState) -> Option<PackageJsonInfo> {
+    forEachAncestorDirectory(directory, |dir| getPackageJsonInfo(dir, /*onlyRecordFailures*/ false, state))
+}
+
+fn getVersionPathsOfPackageJsonInfo<'p>(packageJsonInfo: &'p PackageJsonInfo, state: &ModuleResolutionState) -> Option<&'p VersionPaths> { packageJsonInfo.contents.versionPaths.get_or_init(|| readPackageJsonTypesVersionPaths(&packageJsonInfo.contents.packageJsonContent, state)).as_ref() }
+
+fn getPeerDependenciesOfPackageJsonInfo(packageJsonInfo: &PackageJsonInfo, state: &mut ModuleResolutionState) -> Option<String> {
+    packageJsonInfo.contents.peerDependencies.get_or_init(|| readPackageJsonPeerDependencies(packageJsonInfo, state).filter(|peerDependencies| !peerDependencies.is_empty())).clone()
+}
+
+fn readPackageJsonPeerDependencies(packageJsonInfo: &PackageJsonInfo, state: &mut ModuleResolutionState) -> Option<String> {
+    let peerDependencies = readPackageJsonField(&packageJsonInfo.contents.packageJsonContent, "peerDependencies", "object", state)?.as_object()?;
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::package_json_has_a_peerDependencies_field(), &[]);
+    }
+    let packageDirectory = realPath(&packageJsonInfo.packageDirectory, state.host, state.traceEnabled);
+    let nodeModules = format!("{}{}", &packageDirectory[..packageDirectory.rfind("node_modules").map_or(0, |index| index + "node_modules".len())], directorySeparator);
+    let mut result = String::new();
+    for key in peerDependencies.keys() {
+        let peerPackageJson = getPackageJsonInfo(&format!("{nodeModules}{key}"), /*onlyRecordFailures*/ false, state);
+        if let Some(peerPackageJson) = peerPackageJson {
+            let version = peerPackageJson.contents.packageJsonContent.get("version").and_then(Value::as_str).unwrap_or_default().to_string();
+            result.push_str(&format!("+{key}@{version}"));
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::Found_peerDependency_0_with_1_version(), &[key, &version]);
+            }
+        } else {
+            // Read the dependency version
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::Failed_to_find_peerDependency_0(), &[key]);
+            }
+        }
+    }
+    Some(result)
+}
+
+/** @internal */
+pub fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState) -> Option<PackageJsonInfo> {
     let host = state.host;
-    // let traceEnabled = state.traceEnabled;
+    let traceEnabled = state.traceEnabled;
     let packageJsonPath = combinePaths(packageDirectory, &[Some("package.json")]);
     if onlyRecordFailures {
         if let Some(locations) = &mut state.failedLookupLocations {
@@ -247,9 +1351,9 @@ fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &
     let existing = state.packageJsonInfoCache.as_ref().and_then(|cache| cache.getPackageJsonInfo(&packageJsonPath));
     if let Some(existing) = existing {
         if let PackageJsonInfoCacheEntry::PackageJsonInfo(existing) = existing {
-            // if traceEnabled {
-            //     trace(host, Diagnostics::File_0_exists_according_to_earlier_cached_lookups, &packageJsonPath);
-            // }
+            if traceEnabled {
+                trace(host, Diagnostics::File_0_exists_according_to_earlier_cached_lookups(), &[&packageJsonPath]);
+            }
             if let Some(locations) = &mut state.affectingLocations {
                 locations.push(packageJsonPath.to_string());
             }
@@ -258,9 +1362,9 @@ fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &
             }
             return Some(PackageJsonInfo { packageDirectory: packageDirectory.to_string(), contents: existing.contents });
         } else {
-            // if existing.directoryExists && traceEnabled {
-            //     trace(host, Diagnostics::File_0_does_not_exist_according_to_earlier_cached_lookups, &packageJsonPath);
-            // }
+            if existing.directoryExists && traceEnabled {
+                trace(host, Diagnostics::File_0_does_not_exist_according_to_earlier_cached_lookups(), &[&packageJsonPath]);
+            }
             if let Some(locations) = &mut state.failedLookupLocations {
                 locations.push(packageJsonPath.to_string());
             }
@@ -269,16 +1373,12 @@ fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &
     }
     let directoryExists = host.directoryExists(packageDirectory).unwrap_or(true); // if host does not support 'directoryExists' assume that directory will exist
     if directoryExists && host.fileExists(&packageJsonPath) {
-        let contents = host.readFile(&packageJsonPath).unwrap();
-        let packageJsonContent: PackageJsonPathFields = serde_json::from_str(&contents.as_str()).unwrap();
-        // let packageJsonContent = readJson(&packageJsonPath, host) as PackageJson;
-        // if traceEnabled {
-        //     trace(host, Diagnostics::Found_package_json_at_0, &packageJsonPath);
-        // }
-        let result = PackageJsonInfo {
-            packageDirectory: packageDirectory.to_string(),
-            contents: PackageJsonInfoContents { packageJsonContent, versionPaths: None, versionPathsResolved: false, resolvedEntrypoints: None, resolvedEntrypointsResolved: false, peerDependencies: None, peerDependenciesResolved: false },
-        };
+        // A package.json that cannot be read or parsed behaves like an empty object, same as `readJson`
+        let packageJsonContent = rb_read_package_json(host, &packageJsonPath);
+        if traceEnabled {
+            trace(host, Diagnostics::Found_package_json_at_0(), &[&packageJsonPath]);
+        }
+        let result = PackageJsonInfo { packageDirectory: packageDirectory.to_string(), contents: Rc::new(PackageJsonInfoContents { packageJsonContent, versionPaths: OnceCell::new(), resolvedEntrypoints: OnceCell::new(), peerDependencies: OnceCell::new() }) };
         if let Some(cache) = &mut state.packageJsonInfoCache {
             if !cache.isReadonly() {
                 cache.setPackageJsonInfo(&packageJsonPath, PackageJsonInfoCacheEntry::PackageJsonInfo(result.clone()));
@@ -289,9 +1389,9 @@ fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &
         }
         Some(result)
     } else {
-        // if directoryExists && traceEnabled {
-        //     trace(host, Diagnostics::File_0_does_not_exist, &packageJsonPath);
-        // }
+        if directoryExists && traceEnabled {
+            trace(host, Diagnostics::File_0_does_not_exist(), &[&packageJsonPath]);
+        }
         if let Some(cache) = &mut state.packageJsonInfoCache {
             if !cache.isReadonly() {
                 cache.setPackageJsonInfo(&packageJsonPath, PackageJsonInfoCacheEntry::MissingPackageJsonInfo(MissingPackageJsonInfo { packageDirectory: packageDirectory.to_string(), directoryExists }));
@@ -304,4 +1404,908 @@ fn getPackageJsonInfo(packageDirectory: &str, onlyRecordFailures: bool, state: &
         None
     }
 }
-// endregion: 2473
+
+// a package.json whose root is not an object is read as an empty one too
+fn rb_read_package_json(host: &dyn ModuleResolutionHost, path: &str) -> PackageJson {
+    match host.readFile(path).and_then(|contents| serde_json::from_str(&contents).ok()) {
+        Some(Value::Object(contents)) => contents,
+        _ => PackageJson::new(),
+    }
+}
+
+fn loadNodeModuleFromDirectoryWorker(extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState, jsonContent: Option<&PackageJson>, versionPaths: Option<&VersionPaths>) -> Option<PathAndExtension> {
+    let mut packageFile = None;
+    if let Some(jsonContent) = jsonContent {
+        if state.isConfigLookup {
+            packageFile = readPackageJsonTSConfigField(jsonContent, candidate, state);
+        } else {
+            packageFile = extensions
+                .intersects(Extensions::Declaration)
+                .then(|| readPackageJsonTypesFields(jsonContent, candidate, state))
+                .flatten()
+                .or_else(|| extensions.intersects(Extensions::ImplementationFiles | Extensions::Declaration).then(|| readPackageJsonMainField(jsonContent, candidate, state)).flatten());
+        }
+    }
+
+    let loader = |extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState| -> Option<Resolved> {
+        let fromFile = loadFileNameFromPackageJsonField(extensions, candidate, /*packageJsonValue*/ None, onlyRecordFailures, state);
+        if fromFile.is_some() {
+            return noPackageId(fromFile);
+        }
+
+        // Even if extensions is DtsOnly, we can still look up a .ts file as a result of package.json "types"
+        let expandedExtensions = if extensions == Extensions::Declaration { Extensions::TypeScript | Extensions::Declaration } else { extensions };
+        // Don't do package.json lookup recursively, because Node.js' package lookup doesn't.
+
+        // Disable `EsmMode` for the resolution of the package path for CJS-mode packages (so the `main` field can omit extensions)
+        // (technically it only emits a deprecation warning in esm packages right now, but that's probably
+        // enough to mean we don't need to support it)
+        let features = state.features;
+        let candidateIsFromPackageJsonField = state.candidateIsFromPackageJsonField;
+        state.candidateIsFromPackageJsonField = true;
+        if jsonContent.and_then(|jsonContent| jsonContent.get("type")).and_then(Value::as_str) != Some("module") {
+            state.features = features & !NodeResolutionFeatures::EsmMode;
+        }
+        let result = nodeLoadModuleByRelativeName(expandedExtensions, candidate, onlyRecordFailures, state, /*considerPackageJson*/ false);
+        state.features = features;
+        state.candidateIsFromPackageJsonField = candidateIsFromPackageJsonField;
+        result
+    };
+
+    let onlyRecordFailuresForPackageFile = packageFile.as_ref().is_some_and(|packageFile| !directoryProbablyExists(&getDirectoryPath(packageFile), state.host));
+    let onlyRecordFailuresForIndex = onlyRecordFailures || !directoryProbablyExists(candidate, state.host);
+    let indexPath = combinePaths(candidate, &[Some(if state.isConfigLookup { "tsconfig" } else { "index" })]);
+
+    if let Some(versionPaths) = versionPaths {
+        if packageFile.as_ref().is_none_or(|packageFile| containsPath(candidate, packageFile, None, false)) {
+            let moduleName = getRelativePathFromDirectory(candidate, packageFile.as_deref().unwrap_or(&indexPath), |fileName| fileName.to_string());
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::package_json_has_a_typesVersions_entry_0_that_matches_compiler_version_1_looking_for_a_pattern_to_match_module_name_2(), &[&versionPaths.version, &state.host.getTypeScriptVersion(), &moduleName]);
+            }
+            let pathPatterns = tryParsePatterns(&versionPaths.paths);
+            let result = tryLoadModuleUsingPaths(extensions, &moduleName, candidate, &versionPaths.paths, &pathPatterns, &loader, onlyRecordFailuresForPackageFile || onlyRecordFailuresForIndex, state);
+            if let Some(result) = result {
+                return removeIgnoredPackageId(result.value);
+            }
+        }
+    }
+
+    if let Some(packageFile) = &packageFile {
+        let packageFileResult = removeIgnoredPackageId(loader(extensions, packageFile, onlyRecordFailuresForPackageFile, state));
+        if packageFileResult.is_some() {
+            return packageFileResult;
+        }
+    }
+
+    loadModuleFromFile(extensions, &indexPath, onlyRecordFailuresForIndex, state)
+}
+
+/** Resolve from an arbitrarily specified file. Return `undefined` if it has an unsupported extension. */
+fn extensionIsOk(extensions: Extensions, extension: &str) -> bool {
+    let isOneOf = |exts: &[Extension]| exts.iter().any(|ext| ext.as_str() == extension);
+    (extensions.intersects(Extensions::JavaScript) && isOneOf(&[Extension::Js, Extension::Jsx, Extension::Mjs, Extension::Cjs]))
+        || (extensions.intersects(Extensions::TypeScript) && isOneOf(&[Extension::Ts, Extension::Tsx, Extension::Mts, Extension::Cts]))
+        || (extensions.intersects(Extensions::Declaration) && isOneOf(&[Extension::Dts, Extension::Dmts, Extension::Dcts]))
+        || (extensions.intersects(Extensions::Json) && isOneOf(&[Extension::Json]))
+}
+// endregion: 2550
+
+// region: 2610
+fn loadModuleFromSelfNameReference(extensions: Extensions, moduleName: &str, directory: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    let directoryPath = getNormalizedAbsolutePath(directory, state.host.getCurrentDirectory().as_deref());
+    let scope = getPackageScopeForPath(&directoryPath, state)?;
+    rb_package_json_value(&scope.contents.packageJsonContent, "exports")?;
+    let name = rb_package_json_value(&scope.contents.packageJsonContent, "name")?.as_str()?;
+    let parts = getPathComponents(moduleName, None); // unrooted paths should have `""` as their 0th entry
+    let nameParts = getPathComponents(name, None);
+    if !nameParts.iter().enumerate().all(|(i, p)| parts.get(i) == Some(p)) {
+        return None;
+    }
+    let trailingParts = &parts[nameParts.len()..];
+    let subpath = if trailingParts.is_empty() { ".".to_string() } else { format!(".{directorySeparator}{}", trailingParts.join(directorySeparator)) };
+    // Maybe TODO: splitting extensions into two priorities should be unnecessary, except
+    // https://github.com/microsoft/TypeScript/issues/50762 makes the behavior different.
+    // As long as that bug exists, we need to do two passes here in self-name loading
+    // in order to be consistent with (non-self) library-name loading in
+    // `loadModuleFromNearestNodeModulesDirectoryWorker`, which uses two passes in order
+    // to prioritize `@types` packages higher up the directory tree over untyped
+    // implementation packages.
+    if getAllowJSCompilerOption(state.compilerOptions) && !pathContainsNodeModules(directory) {
+        return loadModuleFromExports(&scope, extensions, &subpath, state);
+    }
+    let priorityExtensions = extensions & (Extensions::TypeScript | Extensions::Declaration);
+    let secondaryExtensions = extensions & !(Extensions::TypeScript | Extensions::Declaration);
+    loadModuleFromExports(&scope, priorityExtensions, &subpath, state).or_else(|| loadModuleFromExports(&scope, secondaryExtensions, &subpath, state))
+}
+
+fn loadModuleFromExports(scope: &PackageJsonInfo, extensions: Extensions, subpath: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    let exports = rb_package_json_value(&scope.contents.packageJsonContent, "exports")?;
+
+    if subpath == "." {
+        let mainExport = match exports {
+            Value::Object(exports) if !noKeyStartsWithDot(exports) => exports.get("."),
+            _ => Some(exports),
+        };
+        if let Some(mainExport) = mainExport {
+            return loadModuleFromTargetExportOrImport(extensions, state, subpath, scope, /*isImports*/ false, mainExport, "", /*pattern*/ false, ".");
+        }
+    } else if let Value::Object(exports) = exports {
+        if allKeysStartWithDot(exports) {
+            let result = loadModuleFromExportsOrImports(extensions, state, subpath, exports, scope, /*isImports*/ false);
+            if result.is_some() {
+                return result;
+            }
+        }
+    }
+
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Export_specifier_0_does_not_exist_in_package_json_scope_at_path_1(), &[subpath, &scope.packageDirectory]);
+    }
+    reportDiagnostic(state, Diagnostics::Export_specifier_0_does_not_exist_in_package_json_scope_at_path_1(), &[subpath, scope.packageDirectory.as_str()]);
+    toSearchResult(/*value*/ None)
+}
+
+fn loadModuleFromImports(extensions: Extensions, moduleName: &str, directory: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    if moduleName == "#" || moduleName.starts_with("#/") {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Invalid_import_specifier_0_has_no_possible_resolutions(), &[moduleName]);
+        }
+        reportDiagnostic(state, Diagnostics::Invalid_import_specifier_0_has_no_possible_resolutions(), &[moduleName]);
+        return toSearchResult(/*value*/ None);
+    }
+    let directoryPath = getNormalizedAbsolutePath(directory, state.host.getCurrentDirectory().as_deref());
+    let Some(scope) = getPackageScopeForPath(&directoryPath, state) else {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Directory_0_has_no_containing_package_json_scope_Imports_will_not_resolve(), &[&directoryPath]);
+        }
+        reportDiagnostic(state, Diagnostics::Directory_0_has_no_containing_package_json_scope_Imports_will_not_resolve(), &[directoryPath.as_str()]);
+        return toSearchResult(/*value*/ None);
+    };
+    let Some(Value::Object(imports)) = scope.contents.packageJsonContent.get("imports") else {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::package_json_scope_0_has_no_imports_defined(), &[&scope.packageDirectory]);
+        }
+        reportDiagnostic(state, Diagnostics::package_json_scope_0_has_no_imports_defined(), &[scope.packageDirectory.as_str()]);
+        return toSearchResult(/*value*/ None);
+    };
+
+    let result = loadModuleFromExportsOrImports(extensions, state, moduleName, imports, &scope, /*isImports*/ true);
+    if result.is_some() {
+        return result;
+    }
+
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Import_specifier_0_does_not_exist_in_package_json_scope_at_path_1(), &[moduleName, &scope.packageDirectory]);
+    }
+    reportDiagnostic(state, Diagnostics::Import_specifier_0_does_not_exist_in_package_json_scope_at_path_1(), &[moduleName, scope.packageDirectory.as_str()]);
+    toSearchResult(/*value*/ None)
+}
+
+fn reportDiagnostic(state: &mut ModuleResolutionState, message: DiagnosticMessage, args: &[&str]) {
+    if let Some(diagnostics) = &mut state.resolutionDiagnostics {
+        diagnostics.push(createCompilerDiagnostic(message, args.iter().map(|arg| arg.to_string().into()).collect()));
+    }
+}
+
+fn noKeyStartsWithDot(obj: &serde_json::Map<String, Value>) -> bool { !obj.keys().any(|k| k.starts_with('.')) }
+
+fn allKeysStartWithDot(obj: &serde_json::Map<String, Value>) -> bool { obj.keys().all(|k| k.starts_with('.')) }
+
+/**
+ * From https://github.com/nodejs/node/blob/8f39f51cbbd3b2de14b9ee896e26421cc5b20121/lib/internal/modules/esm/resolve.js#L722 -
+ * "longest" has some nuance as to what "longest" means in the presence of pattern trailers
+ *
+ * @internal
+ */
+pub fn comparePatternKeys(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    let aPatternIndex = a.find('*');
+    let bPatternIndex = b.find('*');
+    let baseLenA = aPatternIndex.map_or(a.len(), |index| index + 1);
+    let baseLenB = bPatternIndex.map_or(b.len(), |index| index + 1);
+    if baseLenA > baseLenB {
+        return Ordering::Less;
+    }
+    if baseLenB > baseLenA {
+        return Ordering::Greater;
+    }
+    if aPatternIndex.is_none() {
+        return Ordering::Greater;
+    }
+    if bPatternIndex.is_none() {
+        return Ordering::Less;
+    }
+    b.len().cmp(&a.len())
+}
+
+fn loadModuleFromExportsOrImports(extensions: Extensions, state: &mut ModuleResolutionState, moduleName: &str, lookupTable: &serde_json::Map<String, Value>, scope: &PackageJsonInfo, isImports: bool) -> Option<SearchResult<Resolved>> {
+    if !moduleName.ends_with(directorySeparator) && !moduleName.contains('*') {
+        if let Some(target) = lookupTable.get(moduleName) {
+            return loadModuleFromTargetExportOrImport(extensions, state, moduleName, scope, isImports, target, /*subpath*/ "", /*pattern*/ false, moduleName);
+        }
+    }
+    let mut expandingKeys: Vec<&String> = lookupTable.keys().filter(|k| hasOneAsterisk(k) || k.ends_with('/')).collect();
+    expandingKeys.sort_by(|a, b| comparePatternKeys(a, b));
+    for potentialTarget in expandingKeys {
+        let target = &lookupTable[potentialTarget];
+        if state.features.intersects(NodeResolutionFeatures::ExportsPatternTrailers) && matchesPatternWithTrailer(potentialTarget, moduleName) {
+            let starPos = potentialTarget.find('*').unwrap();
+            let subpath = &moduleName[starPos..moduleName.len() - (potentialTarget.len() - 1 - starPos)];
+            return loadModuleFromTargetExportOrImport(extensions, state, moduleName, scope, isImports, target, subpath, /*pattern*/ true, potentialTarget);
+        } else if let Some(prefix) = potentialTarget.strip_suffix('*').filter(|prefix| moduleName.starts_with(prefix)) {
+            let subpath = &moduleName[prefix.len()..];
+            return loadModuleFromTargetExportOrImport(extensions, state, moduleName, scope, isImports, target, subpath, /*pattern*/ true, potentialTarget);
+        } else if moduleName.starts_with(potentialTarget.as_str()) {
+            let subpath = &moduleName[potentialTarget.len()..];
+            return loadModuleFromTargetExportOrImport(extensions, state, moduleName, scope, isImports, target, subpath, /*pattern*/ false, potentialTarget);
+        }
+    }
+
+    fn matchesPatternWithTrailer(target: &str, name: &str) -> bool {
+        if target.ends_with('*') {
+            return false; // handled by next case in loop
+        }
+        let Some(starPos) = target.find('*') else {
+            return false; // handled by last case in loop
+        };
+        // The prefix and the trailer must not overlap
+        name.len() >= target.len() - 1 && name.starts_with(&target[..starPos]) && name.ends_with(&target[starPos + 1..])
+    }
+    None
+}
+
+fn hasOneAsterisk(patternKey: &str) -> bool { patternKey.find('*').is_some_and(|firstStar| Some(firstStar) == patternKey.rfind('*')) }
+// endregion: 2790
+
+// region: 2795
+/** @internal */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedPackageName {
+    pub packageName: String,
+    pub rest: String,
+}
+
+/** @internal */
+pub fn parsePackageName(moduleName: &str) -> ParsedPackageName {
+    let mut idx = moduleName.find(directorySeparator);
+    if moduleName.starts_with('@') {
+        idx = idx.and_then(|idx| moduleName[idx + 1..].find(directorySeparator).map(|next| idx + 1 + next));
+    }
+    match idx {
+        None => ParsedPackageName { packageName: moduleName.to_string(), rest: String::new() },
+        Some(idx) => ParsedPackageName { packageName: moduleName[..idx].to_string(), rest: moduleName[idx + 1..].to_string() },
+    }
+}
+// endregion: 2806
+
+// region: 2808
+/**
+ * Loads `target`, the value found in the "exports" or "imports" map of `scope` under `key` while resolving `moduleName`.
+ * For pattern keys `subpath` is substituted for every `*` in the target, otherwise it is appended to the target.
+ */
+fn loadModuleFromTargetExportOrImport(extensions: Extensions, state: &mut ModuleResolutionState, moduleName: &str, scope: &PackageJsonInfo, isImports: bool, target: &Value, subpath: &str, pattern: bool, key: &str) -> Option<SearchResult<Resolved>> {
+    match target {
+        Value::String(target) => {
+            if !pattern && !subpath.is_empty() && !target.ends_with('/') {
+                return invalidTarget(state, scope, moduleName);
+            }
+            if !target.starts_with("./") {
+                if isImports && !target.starts_with("../") && !target.starts_with('/') && !isRootedDiskPath(target) {
+                    let combinedLookup = if pattern { target.replace('*', subpath) } else { format!("{target}{subpath}") };
+                    traceIfEnabled(state, Diagnostics::Using_0_subpath_1_with_target_2(), &["imports", key, &combinedLookup]);
+                    traceIfEnabled(state, Diagnostics::Resolving_module_0_from_1(), &[&combinedLookup, &format!("{}/", scope.packageDirectory)]);
+                    let containingDirectory = format!("{}/", scope.packageDirectory);
+                    let result = nodeModuleNameResolverWorker(
+                        state.features,
+                        &combinedLookup,
+                        &containingDirectory,
+                        state.compilerOptions,
+                        state.host,
+                        state.packageJsonInfoCache.as_deref_mut().map(|cache| cache as &mut dyn PackageJsonInfoCache),
+                        extensions,
+                        /*isConfigLookup*/ false,
+                        Some(state.conditions.clone()),
+                    );
+                    if let Some(locations) = &mut state.failedLookupLocations {
+                        locations.extend(result.failedLookupLocations.unwrap_or_default());
+                    }
+                    if let Some(locations) = &mut state.affectingLocations {
+                        locations.extend(result.affectingLocations.unwrap_or_default());
+                    }
+                    return toSearchResult(result.resolvedModule.map(|resolvedModule| Resolved {
+                        path: resolvedModule.resolvedFileName,
+                        extension: resolvedModule.extension,
+                        packageId: resolvedModule.packageId,
+                        originalPath: resolvedModule.originalPath,
+                        resolvedUsingTsExtension: resolvedModule.resolvedUsingTsExtension,
+                    }));
+                }
+                return invalidTarget(state, scope, moduleName);
+            }
+            let parts = getPathComponents(target, None);
+            let parts = if pathIsRelative(target) { &parts[1..] } else { &parts[..] };
+            let partsAfterFirst = parts.get(1..).unwrap_or_default();
+            if partsAfterFirst.iter().any(|part| part == ".." || part == "." || part == "node_modules") {
+                return invalidTarget(state, scope, moduleName);
+            }
+            let resolvedTarget = combinePaths(&scope.packageDirectory, &[Some(target)]);
+            // TODO: Assert that `resolvedTarget` is actually within the package directory? That's what the spec says.... but I'm not sure we need
+            // to be in the business of validating everyone's import and export map correctness.
+            let subpathParts = getPathComponents(subpath, None);
+            if subpathParts.iter().any(|part| part == ".." || part == "." || part == "node_modules") {
+                return invalidTarget(state, scope, moduleName);
+            }
+
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::Using_0_subpath_1_with_target_2(), &[if isImports { "imports" } else { "exports" }, key, &if pattern { target.replace('*', subpath) } else { format!("{target}{subpath}") }]);
+            }
+            let finalPath = getNormalizedAbsolutePath(&if pattern { resolvedTarget.replace('*', subpath) } else { format!("{resolvedTarget}{subpath}") }, state.host.getCurrentDirectory().as_deref());
+            toSearchResult(withPackageId(Some(scope), loadFileNameFromPackageJsonField(extensions, &finalPath, Some(target), /*onlyRecordFailures*/ false, state), state))
+        }
+        Value::Object(target) => {
+            traceIfEnabled(state, Diagnostics::Entering_conditional_exports(), &[]);
+            for (condition, subTarget) in target {
+                if condition != "default" && !state.conditions.contains(condition) && !isApplicableVersionedTypesKey(&state.conditions, condition, &Version::parse(&state.host.getTypeScriptVersion())) {
+                    traceIfEnabled(state, Diagnostics::Saw_non_matching_condition_0(), &[condition]);
+                    continue;
+                }
+                traceIfEnabled(state, Diagnostics::Matched_0_condition_1(), &[if isImports { "imports" } else { "exports" }, condition]);
+                let result = loadModuleFromTargetExportOrImport(extensions, state, moduleName, scope, isImports, subTarget, subpath, pattern, key);
+                if result.is_some() {
+                    traceIfEnabled(state, Diagnostics::Resolved_under_condition_0(), &[condition]);
+                    traceIfEnabled(state, Diagnostics::Exiting_conditional_exports(), &[]);
+                    return result;
+                }
+                traceIfEnabled(state, Diagnostics::Failed_to_resolve_under_condition_0(), &[condition]);
+            }
+            traceIfEnabled(state, Diagnostics::Exiting_conditional_exports(), &[]);
+            None
+        }
+        Value::Array(target) => {
+            if target.is_empty() {
+                return invalidTarget(state, scope, moduleName);
+            }
+            target.iter().find_map(|elem| loadModuleFromTargetExportOrImport(extensions, state, moduleName, scope, isImports, elem, subpath, pattern, key)).or_else(|| invalidTarget(state, scope, moduleName))
+        }
+        Value::Null => {
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::package_json_scope_0_explicitly_maps_specifier_1_to_null(), &[&scope.packageDirectory, moduleName]);
+            }
+            reportDiagnostic(state, Diagnostics::package_json_scope_0_explicitly_maps_specifier_1_to_null(), &[scope.packageDirectory.as_str(), moduleName]);
+            toSearchResult(/*value*/ None)
+        }
+        _ => invalidTarget(state, scope, moduleName),
+    }
+}
+
+fn invalidTarget(state: &mut ModuleResolutionState, scope: &PackageJsonInfo, moduleName: &str) -> Option<SearchResult<Resolved>> {
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::package_json_scope_0_has_invalid_type_for_target_of_specifier_1(), &[&scope.packageDirectory, moduleName]);
+    }
+    reportDiagnostic(state, Diagnostics::package_json_scope_0_has_invalid_type_for_target_of_specifier_1(), &[scope.packageDirectory.as_str(), moduleName]);
+    toSearchResult(/*value*/ None)
+}
+// endregion: 2945
+
+// region: 2979
+/** @internal */
+pub fn isApplicableVersionedTypesKey(conditions: &[String], key: &str, typeScriptVersion: &Version) -> bool {
+    if !conditions.iter().any(|condition| condition == "types") {
+        return false; // only apply versioned types conditions if the types condition is applied
+    }
+    let Some(range) = key.strip_prefix("types@") else {
+        return false;
+    };
+    let Some(range) = VersionRange::tryParse(range) else {
+        return false;
+    };
+    range.test(typeScriptVersion)
+}
+// endregion: 2988
+
+// region: 2990
+fn loadModuleFromNearestNodeModulesDirectory(extensions: Extensions, moduleName: &str, directory: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    loadModuleFromNearestNodeModulesDirectoryWorker(extensions, moduleName, directory, state, /*typesScopeOnly*/ false)
+}
+
+fn loadModuleFromNearestNodeModulesDirectoryTypesScope(moduleName: &str, directory: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    // Extensions parameter here doesn't actually matter, because typesOnly ensures we're just doing @types lookup, which is always DtsOnly.
+    loadModuleFromNearestNodeModulesDirectoryWorker(Extensions::Declaration, moduleName, directory, state, /*typesScopeOnly*/ true)
+}
+
+fn loadModuleFromNearestNodeModulesDirectoryWorker(extensions: Extensions, moduleName: &str, directory: &str, state: &mut ModuleResolutionState, typesScopeOnly: bool) -> Option<SearchResult<Resolved>> {
+    let priorityExtensions = extensions & (Extensions::TypeScript | Extensions::Declaration);
+    let secondaryExtensions = extensions & !(Extensions::TypeScript | Extensions::Declaration);
+    // (1) if priorityExtensions are present, do a lookup across all node_modules directories
+    if priorityExtensions != Extensions::None {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Searching_all_ancestor_node_modules_directories_for_preferred_extensions_colon_0(), &[&formatExtensions(priorityExtensions)]);
+        }
+        let result = lookup(priorityExtensions, moduleName, directory, state, typesScopeOnly);
+        if result.is_some() {
+            return result;
+        }
+    }
+    // (2) if secondaryExtensions are present, do a lookup across all node_modules directories
+    if secondaryExtensions != Extensions::None && !typesScopeOnly {
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Searching_all_ancestor_node_modules_directories_for_fallback_extensions_colon_0(), &[&formatExtensions(secondaryExtensions)]);
+        }
+        return lookup(secondaryExtensions, moduleName, directory, state, typesScopeOnly);
+    }
+
+    fn lookup(extensions: Extensions, moduleName: &str, directory: &str, state: &mut ModuleResolutionState, typesScopeOnly: bool) -> Option<SearchResult<Resolved>> {
+        forEachAncestorDirectory(&normalizeSlashes(directory), |ancestorDirectory| {
+            if getBaseFileName(ancestorDirectory) != "node_modules" {
+                return toSearchResult(loadModuleFromImmediateNodeModulesDirectory(extensions, moduleName, ancestorDirectory, state, typesScopeOnly));
+            }
+            None
+        })
+    }
+    None
+}
+
+fn loadModuleFromImmediateNodeModulesDirectory(extensions: Extensions, moduleName: &str, directory: &str, state: &mut ModuleResolutionState, typesScopeOnly: bool) -> Option<Resolved> {
+    let nodeModulesFolder = combinePaths(directory, &[Some("node_modules")]);
+    let nodeModulesFolderExists = directoryProbablyExists(&nodeModulesFolder, state.host);
+    if !nodeModulesFolderExists && state.traceEnabled {
+        trace(state.host, Diagnostics::Directory_0_does_not_exist_skipping_all_lookups_in_it(), &[&nodeModulesFolder]);
+    }
+
+    if !typesScopeOnly {
+        let packageResult = loadModuleFromSpecificNodeModulesDirectory(extensions, moduleName, &nodeModulesFolder, nodeModulesFolderExists, state);
+        if packageResult.is_some() {
+            return packageResult;
+        }
+    }
+
+    if extensions.intersects(Extensions::Declaration) {
+        let nodeModulesAtTypesFolder = combinePaths(&nodeModulesFolder, &[Some("@types")]);
+        let mut nodeModulesAtTypesExists = nodeModulesFolderExists;
+        if nodeModulesFolderExists && !directoryProbablyExists(&nodeModulesAtTypesFolder, state.host) {
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::Directory_0_does_not_exist_skipping_all_lookups_in_it(), &[&nodeModulesAtTypesFolder]);
+            }
+            nodeModulesAtTypesExists = false;
+        }
+        return loadModuleFromSpecificNodeModulesDirectory(Extensions::Declaration, &mangleScopedPackageNameWithTrace(moduleName, state), &nodeModulesAtTypesFolder, nodeModulesAtTypesExists, state);
+    }
+    None
+}
+
+fn loadModuleFromSpecificNodeModulesDirectory(extensions: Extensions, moduleName: &str, nodeModulesDirectory: &str, nodeModulesDirectoryExists: bool, state: &mut ModuleResolutionState) -> Option<Resolved> {
+    let candidate = normalizePath(&combinePaths(nodeModulesDirectory, &[Some(moduleName)]));
+    let ParsedPackageName { packageName, rest } = parsePackageName(moduleName);
+    let packageDirectory = combinePaths(nodeModulesDirectory, &[Some(&packageName)]);
+
+    // First look for a nested package.json, as in `node_modules/foo/bar/package.json`.
+    let mut packageInfo = getPackageJsonInfo(&candidate, !nodeModulesDirectoryExists, state);
+    let mut rootPackageInfo = None;
+    if !rest.is_empty() && packageInfo.is_some() && state.features.intersects(NodeResolutionFeatures::Exports) {
+        rootPackageInfo = getPackageJsonInfo(&packageDirectory, !nodeModulesDirectoryExists, state);
+    }
+    // But only if we're not respecting export maps (if we are, we might redirect around this location)
+    if !rest.is_empty() && packageInfo.is_some() && rootPackageInfo.as_ref().is_none_or(|rootPackageInfo| rb_package_json_value(&rootPackageInfo.contents.packageJsonContent, "exports").is_none()) {
+        let fromFile = loadModuleFromFile(extensions, &candidate, !nodeModulesDirectoryExists, state);
+        if fromFile.is_some() {
+            return noPackageId(fromFile);
+        }
+
+        let versionPaths = packageInfo.as_ref().and_then(|packageInfo| getVersionPathsOfPackageJsonInfo(packageInfo, state));
+        let fromDirectory = loadNodeModuleFromDirectoryWorker(extensions, &candidate, !nodeModulesDirectoryExists, state, packageInfo.as_ref().map(|packageInfo| &packageInfo.contents.packageJsonContent), versionPaths);
+        return withPackageId(packageInfo.as_ref(), fromDirectory, state);
+    }
+
+    if !rest.is_empty() {
+        // Previous `packageInfo` may have been from a nested package.json; ensure we have the one from the package root now.
+        packageInfo = rootPackageInfo.or_else(|| getPackageJsonInfo(&packageDirectory, !nodeModulesDirectoryExists, state));
+    }
+    if packageInfo.is_some() {
+        state.resolvedPackageDirectory = true;
+    }
+
+    if let Some(packageInfo) = &packageInfo {
+        if rb_package_json_value(&packageInfo.contents.packageJsonContent, "exports").is_some() && state.features.intersects(NodeResolutionFeatures::Exports) {
+            return loadModuleFromExports(packageInfo, extensions, &combinePaths(".", &[Some(&rest)]), state).and_then(|result| result.value);
+        }
+    }
+
+    let versionPaths = packageInfo.as_ref().and_then(|packageInfo| getVersionPathsOfPackageJsonInfo(packageInfo, state));
+    let loader = |extensions: Extensions, candidate: &str, onlyRecordFailures: bool, state: &mut ModuleResolutionState| -> Option<Resolved> {
+        let mut pathAndExtension = if !rest.is_empty() || !state.features.intersects(NodeResolutionFeatures::EsmMode) { loadModuleFromFile(extensions, candidate, onlyRecordFailures, state) } else { None };
+        if pathAndExtension.is_none() {
+            pathAndExtension = loadNodeModuleFromDirectoryWorker(extensions, candidate, onlyRecordFailures, state, packageInfo.as_ref().map(|packageInfo| &packageInfo.contents.packageJsonContent), versionPaths);
+        }
+        if pathAndExtension.is_none() && packageInfo.as_ref().is_some_and(|packageInfo| rb_package_json_value(&packageInfo.contents.packageJsonContent, "exports").is_none()) && state.features.intersects(NodeResolutionFeatures::EsmMode) {
+            // EsmMode disables index lookup in `loadNodeModuleFromDirectoryWorker` generally, however non-relative package resolutions still assume
+            // a default `index.js` entrypoint if no `main` or `exports` are present
+            pathAndExtension = loadModuleFromFile(extensions, &combinePaths(candidate, &[Some("index.js")]), onlyRecordFailures, state);
+        }
+        withPackageId(packageInfo.as_ref(), pathAndExtension, state)
+    };
+
+    if !rest.is_empty() {
+        if let Some(versionPaths) = versionPaths {
+            if state.traceEnabled {
+                trace(state.host, Diagnostics::package_json_has_a_typesVersions_entry_0_that_matches_compiler_version_1_looking_for_a_pattern_to_match_module_name_2(), &[&versionPaths.version, &state.host.getTypeScriptVersion(), &rest]);
+            }
+            let packageDirectoryExists = nodeModulesDirectoryExists && directoryProbablyExists(&packageDirectory, state.host);
+            let pathPatterns = tryParsePatterns(&versionPaths.paths);
+            let fromPaths = tryLoadModuleUsingPaths(extensions, &rest, &packageDirectory, &versionPaths.paths, &pathPatterns, &loader, !packageDirectoryExists, state);
+            if let Some(fromPaths) = fromPaths {
+                return fromPaths.value;
+            }
+        }
+    }
+
+    loader(extensions, &candidate, !nodeModulesDirectoryExists, state)
+}
+
+fn tryLoadModuleUsingPaths(
+    extensions: Extensions, moduleName: &str, containingDirectory: &str, paths: &HashMap<String, Vec<String>>, pathPatterns: &ParsedPatterns, loader: &dyn Fn(Extensions, &str, bool, &mut ModuleResolutionState) -> Option<Resolved>, onlyRecordFailures: bool, state: &mut ModuleResolutionState,
+) -> Option<SearchResult<Resolved>> {
+    let matchedPattern = matchPatternOrExact(pathPatterns, moduleName)?;
+    let (matchedStar, matchedPatternText) = match &matchedPattern {
+        StringOrPattern::String(matchedPattern) => (None, matchedPattern.clone()),
+        StringOrPattern::Pattern(matchedPattern) => (Some(matchedText(matchedPattern, moduleName)), patternText(matchedPattern)),
+    };
+    if state.traceEnabled {
+        trace(state.host, Diagnostics::Module_name_0_matched_pattern_1(), &[moduleName, &matchedPatternText]);
+    }
+    let substitutions = paths.get(&matchedPatternText).map(Vec::as_slice).unwrap_or_default();
+    let resolved = substitutions.iter().find_map(|subst| {
+        let path = match matchedStar {
+            Some(matchedStar) => subst.replacen('*', matchedStar, 1),
+            None => subst.clone(),
+        };
+        // When baseUrl is not specified, the command line parser resolves relative paths to the config file location.
+        let candidate = normalizePath(&combinePaths(containingDirectory, &[Some(&path)]));
+        if state.traceEnabled {
+            trace(state.host, Diagnostics::Trying_substitution_0_candidate_module_location_colon_1(), &[subst, &path]);
+        }
+        // A path mapping may have an extension, in contrast to an import, which should omit it.
+        if let Some(extension) = tryGetExtensionFromPath(subst) {
+            if let Some(path) = tryFile(&candidate, onlyRecordFailures, state) {
+                return noPackageId(Some(PathAndExtension { path, ext: extension.as_str().to_string(), resolvedUsingTsExtension: None }));
+            }
+        }
+        let onlyRecordFailures = onlyRecordFailures || !directoryProbablyExists(&getDirectoryPath(&candidate), state.host);
+        loader(extensions, &candidate, onlyRecordFailures, state)
+    });
+    toSearchResult(resolved)
+}
+// endregion: 3150
+
+// region: 3210
+fn resolveFromTypeRoot(moduleName: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+    let typeRoots = getEffectiveTypeRoots(state.compilerOptions, state.host)?;
+    for typeRoot in &typeRoots {
+        let candidate = getCandidateFromTypeRoot(typeRoot, moduleName, state);
+        let directoryExists = directoryProbablyExists(typeRoot, state.host);
+        if !directoryExists && state.traceEnabled {
+            trace(state.host, Diagnostics::Directory_0_does_not_exist_skipping_all_lookups_in_it(), &[typeRoot]);
+        }
+        if let Some(resolvedFromFile) = loadModuleFromFile(Extensions::Declaration, &candidate, !directoryExists, state) {
+            let packageDirectory = parseNodeModuleFromPath(&resolvedFromFile.path, false);
+            let packageInfo = packageDirectory.and_then(|packageDirectory| getPackageJsonInfo(&packageDirectory, /*onlyRecordFailures*/ false, state));
+            return toSearchResult(withPackageId(packageInfo.as_ref(), Some(resolvedFromFile), state));
+        }
+        let resolved = loadNodeModuleFromDirectory(Extensions::Declaration, &candidate, !directoryExists, state, /*considerPackageJson*/ true);
+        if resolved.is_some() {
+            return toSearchResult(resolved);
+        }
+    }
+    None
+}
+
+fn getCandidateFromTypeRoot(typeRoot: &str, typeReferenceDirectiveName: &str, state: &ModuleResolutionState) -> String {
+    let nameForLookup = if typeRoot.ends_with("/node_modules/@types") || typeRoot.ends_with("/node_modules/@types/") { mangleScopedPackageNameWithTrace(typeReferenceDirectiveName, state) } else { typeReferenceDirectiveName.to_string() };
+    combinePaths(typeRoot, &[Some(&nameForLookup)])
+}
+// endregion: 3226
+
+// region: 3280
+/** Double underscores are used in DefinitelyTyped to delimit scoped packages. */
+const mangledScopedPackageSeparator: &str = "__";
+
+/** For a scoped package, we must look in `@types/foo__bar` instead of `@types/@foo/bar`. */
+fn mangleScopedPackageNameWithTrace(packageName: &str, state: &ModuleResolutionState) -> String {
+    let mangled = mangleScopedPackageName(packageName);
+    if state.traceEnabled && mangled != packageName {
+        trace(state.host, Diagnostics::Scoped_package_detected_looking_in_0(), &[&mangled]);
+    }
+    mangled
+}
+
+/** @internal */
+pub fn getTypesPackageName(packageName: &str) -> String { format!("@types/{}", mangleScopedPackageName(packageName)) }
+
+/** @internal */
+pub fn mangleScopedPackageName(packageName: &str) -> String {
+    if let Some(withoutAtTypePrefix) = packageName.strip_prefix('@') {
+        let replaceSlash = withoutAtTypePrefix.replacen(directorySeparator, mangledScopedPackageSeparator, 1);
+        if replaceSlash != withoutAtTypePrefix {
+            return replaceSlash;
+        }
+    }
+    packageName.to_string()
+}
+// endregion: 3310
+
+// region: 3330
+pub fn classicNameResolver<'a>(moduleName: &str, containingFile: &str, compilerOptions: &'a CompilerOptions, host: &'a dyn ModuleResolutionHost, cache: Option<&'a mut dyn PackageJsonInfoCache>) -> ResolvedModuleWithFailedLookupLocations {
+    let traceEnabled = isTraceEnabled(compilerOptions, host);
+    let containingDirectory = getDirectoryPath(containingFile);
+    let mut state = ModuleResolutionState {
+        host,
+        compilerOptions,
+        traceEnabled,
+        failedLookupLocations: Some(vec![]),
+        affectingLocations: Some(vec![]),
+        packageJsonInfoCache: cache,
+        features: NodeResolutionFeatures::None,
+        conditions: Vec::new(),
+        requestContainingDirectory: Some(containingDirectory.clone()),
+        resolutionDiagnostics: None,
+        isConfigLookup: false,
+        candidateIsFromPackageJsonField: false,
+        resolvedPackageDirectory: false,
+    };
+
+    fn tryResolve(extensions: Extensions, moduleName: &str, containingDirectory: &str, state: &mut ModuleResolutionState) -> Option<SearchResult<Resolved>> {
+        let resolvedUsingSettings = tryLoadModuleUsingOptionalResolutionSettings(extensions, moduleName, containingDirectory, &loadModuleFromFileNoPackageId, state);
+        if resolvedUsingSettings.is_some() {
+            return toSearchResult(resolvedUsingSettings);
+        }
+
+        if !isExternalModuleNameRelative(moduleName) {
+            // Climb up parent directories looking for a module.
+            let resolved = forEachAncestorDirectory(containingDirectory, |directory| {
+                let searchName = normalizePath(&combinePaths(directory, &[Some(moduleName)]));
+                toSearchResult(loadModuleFromFileNoPackageId(extensions, &searchName, /*onlyRecordFailures*/ false, state))
+            });
+            if resolved.is_some() {
+                return resolved;
+            }
+
+            if extensions.intersects(Extensions::TypeScript | Extensions::Declaration) {
+                // If we didn't find the file normally, look it up in @types.
+                let mut resolved = loadModuleFromNearestNodeModulesDirectoryTypesScope(moduleName, containingDirectory, state);
+                if extensions.intersects(Extensions::Declaration) && resolved.is_none() {
+                    resolved = resolveFromTypeRoot(moduleName, state);
+                }
+                return resolved;
+            }
+            None
+        } else {
+            let candidate = normalizePath(&combinePaths(containingDirectory, &[Some(moduleName)]));
+            toSearchResult(loadModuleFromFileNoPackageId(extensions, &candidate, /*onlyRecordFailures*/ false, state))
+        }
+    }
+
+    let secondaryExtensions = if getResolveJsonModule(compilerOptions) { Extensions::JavaScript | Extensions::Json } else { Extensions::JavaScript };
+    let resolved = tryResolve(Extensions::TypeScript | Extensions::Declaration, moduleName, &containingDirectory, &mut state).or_else(|| tryResolve(secondaryExtensions, moduleName, &containingDirectory, &mut state));
+    // No originalPath because classic resolution doesn't resolve realPath
+    createResolvedModuleWithFailedLookupLocations(
+        resolved.and_then(|resolved| resolved.value),
+        /*isExternalLibraryImport*/ Some(false),
+        state.failedLookupLocations.take().unwrap_or_default(),
+        state.affectingLocations.take().unwrap_or_default(),
+        /*diagnostics*/ Vec::new(),
+        /*alternateResult*/ None,
+    )
+}
+// endregion: 3400
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use super::{createPackageJsonInfoCache, getPackageJsonInfo, getTemporaryModuleResolutionState, getVersionPathsOfPackageJsonInfo, resolveModuleName, PackageJsonInfoCache};
+    use crate::compiler::diagnostic_information_map_generated::Diagnostics;
+    use crate::compiler::rb_vfs_host::VirtualFileSystemHost;
+    use crate::compiler::types::{CompilerOptions, DiagnosticMessage, ModuleKind, ModuleResolutionKind, ResolutionMode, ResolvedModuleWithFailedLookupLocations};
+
+    fn host(compiler_options: CompilerOptions, files: &[(&str, &str)]) -> VirtualFileSystemHost<'static> {
+        let compiler_options = CompilerOptions { traceResolution: Some(true), ..compiler_options };
+        let mut host = VirtualFileSystemHost::new("/proj".to_string(), Rc::new(compiler_options), true);
+        for (file_name, contents) in files {
+            host.writeFile(file_name, contents);
+        }
+        host
+    }
+
+    fn node10() -> CompilerOptions { CompilerOptions { moduleResolution: Some(ModuleResolutionKind::Node10), ..Default::default() } }
+
+    fn nodenext() -> CompilerOptions { CompilerOptions { module: Some(ModuleKind::NodeNext), moduleResolution: Some(ModuleResolutionKind::NodeNext), ..Default::default() } }
+
+    fn resolve(host: &VirtualFileSystemHost, module_name: &str, containing_file: &str, resolution_mode: ResolutionMode) -> ResolvedModuleWithFailedLookupLocations { resolveModuleName(module_name, containing_file, &host.compiler_options, host, None, resolution_mode) }
+
+    fn reported(result: &ResolvedModuleWithFailedLookupLocations, message: DiagnosticMessage) -> bool { result.resolutionDiagnostics.iter().flatten().any(|diagnostic| diagnostic.code == message.code) }
+
+    fn resolved_file_name(host: &VirtualFileSystemHost, module_name: &str, resolution_mode: ResolutionMode) -> Option<String> { resolve(host, module_name, "/proj/src/index.ts", resolution_mode).resolvedModule.map(|resolved| resolved.resolvedFileName) }
+
+    #[test]
+    fn probes_extensions_and_directory_indexes_of_relative_names() {
+        let host = host(node10(), &[("/proj/src/a.ts", ""), ("/proj/src/b.d.ts", ""), ("/proj/src/c.tsx", ""), ("/proj/src/dir/index.ts", ""), ("/proj/src/plain.js", "")]);
+        assert_eq!(resolved_file_name(&host, "./a", ResolutionMode::Undefined).as_deref(), Some("/proj/src/a.ts"));
+        assert_eq!(resolved_file_name(&host, "./b", ResolutionMode::Undefined).as_deref(), Some("/proj/src/b.d.ts"));
+        assert_eq!(resolved_file_name(&host, "./c", ResolutionMode::Undefined).as_deref(), Some("/proj/src/c.tsx"));
+        assert_eq!(resolved_file_name(&host, "./dir", ResolutionMode::Undefined).as_deref(), Some("/proj/src/dir/index.ts"));
+        // a JavaScript extension is replaced by the TypeScript ones before JavaScript files are tried
+        assert_eq!(resolved_file_name(&host, "./a.js", ResolutionMode::Undefined).as_deref(), Some("/proj/src/a.ts"));
+        assert_eq!(resolved_file_name(&host, "./plain", ResolutionMode::Undefined).as_deref(), Some("/proj/src/plain.js"));
+    }
+
+    #[test]
+    fn requires_full_paths_of_relative_names_in_esm_mode() {
+        let host = host(nodenext(), &[("/proj/src/a.ts", ""), ("/proj/src/dir/index.ts", "")]);
+        assert_eq!(resolved_file_name(&host, "./a", ResolutionMode::ESNext), None);
+        assert_eq!(resolved_file_name(&host, "./dir", ResolutionMode::ESNext), None);
+        assert_eq!(resolved_file_name(&host, "./a.js", ResolutionMode::ESNext).as_deref(), Some("/proj/src/a.ts"));
+        assert_eq!(resolved_file_name(&host, "./dir/index.js", ResolutionMode::ESNext).as_deref(), Some("/proj/src/dir/index.ts"));
+        assert_eq!(resolved_file_name(&host, "./a", ResolutionMode::CommonJS).as_deref(), Some("/proj/src/a.ts"));
+        assert_eq!(resolved_file_name(&host, "./dir", ResolutionMode::CommonJS).as_deref(), Some("/proj/src/dir/index.ts"));
+    }
+
+    #[test]
+    fn walks_up_to_the_nearest_node_modules_directory() {
+        let host = host(
+            node10(),
+            &[
+                ("/proj/node_modules/pkg/package.json", r#"{ "types": "lib/index.d.ts" }"#),
+                ("/proj/node_modules/pkg/lib/index.d.ts", ""),
+                ("/proj/node_modules/near/index.d.ts", ""),
+                ("/proj/src/node_modules/near/index.d.ts", ""),
+                ("/proj/node_modules/untyped/index.js", ""),
+                ("/proj/node_modules/@types/untyped/index.d.ts", ""),
+            ],
+        );
+        let resolved = resolve(&host, "pkg", "/proj/src/deep/file.ts", ResolutionMode::Undefined).resolvedModule.expect("pkg should resolve");
+        assert_eq!(resolved.resolvedFileName, "/proj/node_modules/pkg/lib/index.d.ts");
+        assert_eq!(resolved.isExternalLibraryImport, Some(true));
+
+        let resolved = resolve(&host, "near", "/proj/src/deep/file.ts", ResolutionMode::Undefined).resolvedModule.expect("near should resolve");
+        assert_eq!(resolved.resolvedFileName, "/proj/src/node_modules/near/index.d.ts");
+
+        // declarations from @types win over the package's own JavaScript
+        let resolved = resolve(&host, "untyped", "/proj/src/deep/file.ts", ResolutionMode::Undefined).resolvedModule.expect("untyped should resolve");
+        assert_eq!(resolved.resolvedFileName, "/proj/node_modules/@types/untyped/index.d.ts");
+    }
+
+    #[test]
+    fn skips_package_json_fields_of_the_wrong_type() {
+        let host = host(node10(), &[("/proj/node_modules/bad/package.json", r#"{ "name": "bad", "types": 1, "main": "lib/main.js" }"#), ("/proj/node_modules/bad/lib/main.d.ts", "")]);
+        assert_eq!(resolved_file_name(&host, "bad", ResolutionMode::Undefined).as_deref(), Some("/proj/node_modules/bad/lib/main.d.ts"));
+        assert!(host.traces.borrow().iter().any(|trace| trace == "Expected type of 'types' field in 'package.json' to be 'string', got 'number'."));
+    }
+
+    const CONDITIONAL_PACKAGE: [(&str, &str); 7] = [
+        (
+            "/proj/node_modules/cond/package.json",
+            r#"{
+            "name": "cond",
+            "exports": {
+                ".": { "import": { "types": "./esm/index.d.mts" }, "require": { "types": "./cjs/index.d.cts" } },
+                "./features/*.js": { "types": "./types/features/*.d.ts" },
+                "./features/private/*": null,
+                "./custom": { "my-condition": "./custom/mine.d.ts", "default": "./custom/default.d.ts" }
+            }
+        }"#,
+        ),
+        ("/proj/node_modules/cond/esm/index.d.mts", ""),
+        ("/proj/node_modules/cond/cjs/index.d.cts", ""),
+        ("/proj/node_modules/cond/types/features/a.d.ts", ""),
+        ("/proj/node_modules/cond/types/features/private/x.d.ts", ""),
+        ("/proj/node_modules/cond/custom/mine.d.ts", ""),
+        ("/proj/node_modules/cond/custom/default.d.ts", ""),
+    ];
+
+    #[test]
+    fn matches_export_conditions_against_the_resolution_mode() {
+        let host = host(nodenext(), &CONDITIONAL_PACKAGE);
+        assert_eq!(resolved_file_name(&host, "cond", ResolutionMode::ESNext).as_deref(), Some("/proj/node_modules/cond/esm/index.d.mts"));
+        assert_eq!(resolved_file_name(&host, "cond", ResolutionMode::CommonJS).as_deref(), Some("/proj/node_modules/cond/cjs/index.d.cts"));
+        assert_eq!(resolved_file_name(&host, "cond/custom", ResolutionMode::ESNext).as_deref(), Some("/proj/node_modules/cond/custom/default.d.ts"));
+
+        let custom = host(CompilerOptions { customConditions: Some(vec!["my-condition".to_string()]), ..nodenext() }, &CONDITIONAL_PACKAGE);
+        assert_eq!(resolved_file_name(&custom, "cond/custom", ResolutionMode::ESNext).as_deref(), Some("/proj/node_modules/cond/custom/mine.d.ts"));
+    }
+
+    #[test]
+    fn substitutes_subpath_patterns_in_export_targets() {
+        let host = host(nodenext(), &CONDITIONAL_PACKAGE);
+        assert_eq!(resolved_file_name(&host, "cond/features/a.js", ResolutionMode::ESNext).as_deref(), Some("/proj/node_modules/cond/types/features/a.d.ts"));
+
+        // the longer "./features/private/*" key wins and maps the subpath to null
+        let result = resolve(&host, "cond/features/private/x.js", "/proj/src/index.ts", ResolutionMode::ESNext);
+        assert!(result.resolvedModule.is_none());
+        assert!(reported(&result, Diagnostics::package_json_scope_0_explicitly_maps_specifier_1_to_null()));
+
+        let result = resolve(&host, "cond/missing", "/proj/src/index.ts", ResolutionMode::ESNext);
+        assert!(result.resolvedModule.is_none());
+        assert!(reported(&result, Diagnostics::Export_specifier_0_does_not_exist_in_package_json_scope_at_path_1()));
+    }
+
+    #[test]
+    fn ignores_exports_without_package_json_exports_support() {
+        let host = host(node10(), &CONDITIONAL_PACKAGE);
+        let result = resolve(&host, "cond", "/proj/src/index.ts", ResolutionMode::Undefined);
+        assert!(result.resolvedModule.is_none());
+        // the bundler retry that reads "exports" is only reported as an alternate result
+        assert_eq!(result.alternateResult.as_deref(), Some("/proj/node_modules/cond/esm/index.d.mts"));
+    }
+
+    #[test]
+    fn resolves_imports_from_the_enclosing_package_scope() {
+        let host = host(nodenext(), &[("/proj/package.json", r##"{ "name": "app", "imports": { "#utils/*": "./src/utils/*.ts" } }"##), ("/proj/src/utils/log.ts", "")]);
+        assert_eq!(resolved_file_name(&host, "#utils/log", ResolutionMode::ESNext).as_deref(), Some("/proj/src/utils/log.ts"));
+
+        let result = resolve(&host, "#other", "/proj/src/index.ts", ResolutionMode::ESNext);
+        assert!(result.resolvedModule.is_none());
+        assert!(reported(&result, Diagnostics::Import_specifier_0_does_not_exist_in_package_json_scope_at_path_1()));
+    }
+    #[test]
+    fn redirects_through_the_first_types_versions_entry_matching_the_compiler() {
+        let mut host = host(
+            node10(),
+            &[
+                ("/proj/node_modules/versioned/package.json", r#"{ "types": "index.d.ts", "typesVersions": { ">=99.0": { "*": ["ts99/*"] }, ">=3.1": { "*": ["ts3.1/*"] } } }"#),
+                ("/proj/node_modules/versioned/index.d.ts", ""),
+                ("/proj/node_modules/versioned/sub.d.ts", ""),
+                ("/proj/node_modules/versioned/ts3.1/index.d.ts", ""),
+                ("/proj/node_modules/versioned/ts3.1/sub.d.ts", ""),
+                ("/proj/node_modules/versioned/ts99/index.d.ts", ""),
+            ],
+        );
+        host.typescript_version = "5.0.0".to_string();
+        assert_eq!(resolved_file_name(&host, "versioned", ResolutionMode::Undefined).as_deref(), Some("/proj/node_modules/versioned/ts3.1/index.d.ts"));
+        assert_eq!(resolved_file_name(&host, "versioned/sub", ResolutionMode::Undefined).as_deref(), Some("/proj/node_modules/versioned/ts3.1/sub.d.ts"));
+
+        // the earlier entry matches first, and a mapping that finds nothing falls back to the package itself
+        host.typescript_version = "99.1.0".to_string();
+        assert_eq!(resolved_file_name(&host, "versioned", ResolutionMode::Undefined).as_deref(), Some("/proj/node_modules/versioned/ts99/index.d.ts"));
+        assert_eq!(resolved_file_name(&host, "versioned/sub", ResolutionMode::Undefined).as_deref(), Some("/proj/node_modules/versioned/sub.d.ts"));
+
+        host.typescript_version = "2.9.0".to_string();
+        assert_eq!(resolved_file_name(&host, "versioned", ResolutionMode::Undefined).as_deref(), Some("/proj/node_modules/versioned/index.d.ts"));
+        assert!(host.traces.borrow().iter().any(|trace| trace == "'package.json' does not have a 'typesVersions' entry that matches version '2.9'."));
+    }
+    #[test]
+    fn maps_paths_by_longest_prefix_before_falling_back() {
+        let paths = HashMap::from([("@app/*".to_string(), vec!["src/app/*".to_string()]), ("@app/shared/*".to_string(), vec!["src/shared/*".to_string(), "src/fallback/*".to_string()]), ("*".to_string(), vec!["types/*".to_string()])]);
+        let host = host(CompilerOptions { baseUrl: Some("/proj".to_string()), paths: Some(paths), ..node10() }, &[("/proj/src/app/main.ts", ""), ("/proj/src/app/shared/log.ts", ""), ("/proj/src/fallback/log.ts", ""), ("/proj/types/env.d.ts", ""), ("/proj/node_modules/lib/index.d.ts", "")]);
+        assert_eq!(resolved_file_name(&host, "@app/main", ResolutionMode::Undefined).as_deref(), Some("/proj/src/app/main.ts"));
+        // "@app/shared/*" is the longer prefix, and its substitutions are tried in order
+        assert_eq!(resolved_file_name(&host, "@app/shared/log", ResolutionMode::Undefined).as_deref(), Some("/proj/src/fallback/log.ts"));
+        assert_eq!(resolved_file_name(&host, "env", ResolutionMode::Undefined).as_deref(), Some("/proj/types/env.d.ts"));
+        // names no substitution finds are looked up relative to baseUrl, then in node_modules
+        assert_eq!(resolved_file_name(&host, "src/app/main", ResolutionMode::Undefined).as_deref(), Some("/proj/src/app/main.ts"));
+        let resolved = resolve(&host, "lib", "/proj/src/index.ts", ResolutionMode::Undefined).resolvedModule.expect("lib should resolve");
+        assert_eq!(resolved.resolvedFileName, "/proj/node_modules/lib/index.d.ts");
+        assert_eq!(resolved.isExternalLibraryImport, Some(true));
+    }
+
+    #[test]
+    fn resolves_paths_against_the_current_directory_without_base_url() {
+        let paths = HashMap::from([("~/*".to_string(), vec!["./src/*".to_string()])]);
+        let host = host(CompilerOptions { paths: Some(paths), ..node10() }, &[("/proj/src/util.ts", ""), ("/proj/util.ts", "")]);
+        assert_eq!(resolved_file_name(&host, "~/util", ResolutionMode::Undefined).as_deref(), Some("/proj/src/util.ts"));
+        // without baseUrl, names outside `paths` are not looked up in the project directory
+        assert_eq!(resolved_file_name(&host, "util", ResolutionMode::Undefined), None);
+    }
+    #[test]
+    fn shares_cached_package_json_contents() {
+        let host = host(node10(), &[("/proj/node_modules/versioned/package.json", r#"{ "typesVersions": { "*": { "*": ["ts/*"] } } }"#)]);
+        let mut cache = createPackageJsonInfoCache("/proj", true);
+        let mut state = getTemporaryModuleResolutionState(Some(&mut cache as &mut dyn PackageJsonInfoCache), &host, &host.compiler_options);
+        let first = getPackageJsonInfo("/proj/node_modules/versioned", /*onlyRecordFailures*/ false, &mut state).expect("package.json should be found");
+        assert!(getVersionPathsOfPackageJsonInfo(&first, &state).is_some());
+
+        // a cache hit under another spelling of the directory still shares the lazily read fields
+        let second = getPackageJsonInfo("/proj/node_modules/versioned/", /*onlyRecordFailures*/ false, &mut state).expect("package.json should be cached");
+        assert!(Rc::ptr_eq(&first.contents, &second.contents));
+        assert!(second.contents.versionPaths.get().is_some_and(Option::is_some));
+    }
+}
@@ -1,26 +1,30 @@
 use oxc_allocator::Allocator;
 use oxc_ast::{
-    ast::{MetaProperty, SourceFile, Statement},
-    match_module_declaration, Visit,
+    ast::{MetaProperty, SourceFile, Statement, TSModuleReference},
+    match_module_declaration, AstBuilder, AstKind, Visit,
 };
-use oxc_parser::{ParseOptions, Parser};
-use oxc_span::SourceType;
+use oxc_parser::{ParseOptions, Parser, ParserReturn};
+use oxc_span::{GetSpan, SourceType, Span};
 use std::path::Path;
-use std::sync::LazyLock;
+use std::rc::Rc;
+
+use crate::new_rc_cell;
 
 use super::{
     moduleNameResolver::PackageJsonInfo,
     program::getImpliedNodeFormatForFile,
-    rb_extra::{SourceFileExt, RB_CTX},
-    rb_parser::rb_fill_parents,
-    types::{ResolutionMode, ScriptTarget, TypeCheckerHost},
+    rb_extra::{AstKindExt, SourceFileExt, RB_CTX},
+    rb_parser::{rb_fill_parents, rb_json_syntax_diagnostics, rb_parse_diagnostics},
+    types::{JSDoc, JSDocTag, JSDocTagKind, JSDocTypeExpression, ModuleSpecifierResolutionHost, NodeFlags, ResolutionMode, ScriptKind, ScriptTarget, TextChangeRange, TypeCheckerHost},
+    utilities::{ensureScriptKind, getExternalModuleIndicator, getJSDocCommentRanges, getSourceFileOfNode, isDeclarationFileName},
 };
 
-static ALLOCATOR: LazyLock<Allocator> = LazyLock::new(|| Allocator::default());
-
 // region: 460
 /** @internal */
-pub fn isJSDocLikeText(text: &str, start: usize) -> bool { text.chars().nth(start + 1) == Some('*') && text.chars().nth(start + 2) == Some('*') && text.chars().nth(start + 3) != Some('/') }
+pub fn isJSDocLikeText(text: &str, start: usize) -> bool {
+    let bytes = text.as_bytes();
+    bytes.get(start + 1) == Some(&b'*') && bytes.get(start + 2) == Some(&b'*') && bytes.get(start + 3) != Some(&b'/')
+}
 
 /** @internal */
 pub fn isFileProbablyExternalModule(source_file: &SourceFile) -> bool {
@@ -34,12 +38,18 @@ fn isAnExternalModuleIndicatorNode(node: &Statement) -> bool {
     // (isImportEqualsDeclaration(node) && isExternalModuleReference(&node.moduleReference)) || isImportDeclaration(node) || isExportAssignment(node) || isExportDeclaration(node)
 
     match node {
-        match_module_declaration!(Statement) => true,
-        _ => false, // todo handle isExternalModuleReference (use of require())
+        // exported declarations (including `export import x = N.y`) are ExportNamedDeclarations in oxc;
+        // `export as namespace N` is a NamespaceExportDeclaration, which does not make a file a module
+        match_module_declaration!(Statement) => !matches!(node, Statement::TSNamespaceExportDeclaration(_)),
+        Statement::TSImportEqualsDeclaration(decl) => matches!(decl.module_reference, TSModuleReference::ExternalModuleReference(_)),
+        _ => false,
     }
 }
 
 fn getImportMetaIfNecessary(source_file: &SourceFile) -> bool {
+    if !AstKind::SourceFile(source_file).flags().intersects(NodeFlags::PossiblyContainsImportMeta) {
+        return false;
+    }
     let mut finder = FindImportMeta { has_import_meta: false };
     finder.visit_source_file(source_file);
     return finder.has_import_meta;
@@ -95,7 +105,8 @@ impl Default for CreateSourceFileOptions {
 // pub fn setExternalModuleIndicator(source_file: &mut SourceFile) { source_file.externalModuleIndicator = isFileProbablyExternalModule(source_file); }
 
 // pub fn createSourceFile<'a>(file_name: &'a str, source_text: &'a str, language_version_or_options: CreateSourceFileOptions, set_parent_nodes: bool, script_kind: Option<ScriptKind>) -> SourceFile<'a> {
-pub fn createSourceFile<'a>(file_name: &'a str, source_text: &'a str) -> SourceFile<'a> {
+// The AST is allocated in `allocator`, the arena of this file alone, which is freed with the file (see `OwnedSourceFile`)
+pub fn createSourceFile<'a>(allocator: &'a Allocator, file_name: &'a str, source_text: &'a str, script_kind: Option<ScriptKind>) -> SourceFile<'a> {
     // tracing.as_mut().map(|t| t.push(Phase::Parse, "createSourceFile", json!({ "path": file_name }), true));
     // performance::mark("beforeParse");
     let mut result: SourceFile;
@@ -142,15 +153,30 @@ pub fn createSourceFile<'a>(file_name: &'a str, source_text: &'a str) -> SourceF
     // }
 
     let path = Path::new(file_name);
-    let source_type = SourceType::from_path(&path).unwrap(); // todo merge with params?
-    let ret = Parser::new(&ALLOCATOR, &source_text, source_type).with_options(ParseOptions { parse_regular_expression: true, ..ParseOptions::default() }).parse();
+    let script_kind = ensureScriptKind(file_name, script_kind);
+    let ret = if script_kind == ScriptKind::JSON { parseJsonText(allocator, source_text) } else { Parser::new(allocator, &source_text, rb_source_type(file_name, script_kind)).with_options(ParseOptions { parse_regular_expression: true, ..ParseOptions::default() }).parse() };
     result = ret.source_file;
     result.set_filepath(path.to_path_buf());
+    result.set_scriptKind(script_kind);
+    let errors = ret.errors.iter().map(|error| (error.message.to_string(), error.labels.as_ref().and_then(|labels| labels.first()).map(|label| (label.offset() as u32, label.len() as u32))));
+    let mut parseDiagnostics = rb_parse_diagnostics(file_name, source_text, errors, ret.panicked);
+    // a value that does not parse has already been reported; one that does may still use syntax JSON does not allow
+    if script_kind == ScriptKind::JSON && parseDiagnostics.is_empty() {
+        parseDiagnostics = rb_json_syntax_diagnostics(file_name, &result);
+    }
+    result.set_parseDiagnostics(new_rc_cell!(parseDiagnostics));
     result.set_packageJsonScope(None); // todo
-    result.set_externalModuleIndicator(isFileProbablyExternalModule(&result));
     let tc_host = unsafe { &*RB_CTX.get_type_checker_host().as_ref().get() };
-    result.set_impliedNodeFormat(getImpliedNodeFormatForFile(file_name, tc_host, &tc_host.getCompilerOptions()));
+    let options = tc_host.getCompilerOptions();
+    let packageJsonInfoCache = tc_host.getPackageJsonInfoCache();
+    let mut packageJsonInfoCache = packageJsonInfoCache.as_ref().map(|cache| cache.borrow_mut());
+    result.set_impliedNodeFormat(getImpliedNodeFormatForFile(file_name, packageJsonInfoCache.as_deref_mut(), tc_host, &options));
     rb_fill_parents(&mut result);
+    // the indicator depends on the implied node format and on the `PossiblyContainsImportMeta` flag, so it is set last;
+    // JSON files are never modules, like parseJsonText which does not set it
+    if script_kind != ScriptKind::JSON {
+        result.set_externalModuleIndicator(getExternalModuleIndicator(&options, &result));
+    }
 
     // performance::mark("afterParse");
     // performance::measure("Parse", "beforeParse", "afterParse");
@@ -159,7 +185,313 @@ pub fn createSourceFile<'a>(file_name: &'a str, source_text: &'a str) -> SourceF
 }
 // endregion: 1369
 
+// oxc describes the language of a file with a `SourceType` instead of a script kind; JS files may contain JSX, as in getLanguageVariant
+fn rb_source_type(file_name: &str, script_kind: ScriptKind) -> SourceType {
+    // the extension still decides the module kind and whether a TS file is a declaration file
+    let source_type = SourceType::from_path(file_name).unwrap_or_default();
+    match script_kind {
+        ScriptKind::JS | ScriptKind::JSX => source_type.with_javascript(true).with_jsx(true),
+        ScriptKind::TS => source_type.with_typescript(true).with_standard(true).with_typescript_definition(isDeclarationFileName(file_name)),
+        ScriptKind::TSX => source_type.with_typescript(true).with_jsx(true),
+        _ => source_type,
+    }
+}
+
 // region: 1384
 // See also `isExternalOrCommonJsModule` in utilities.ts
 pub fn isExternalModule(file: &SourceFile) -> bool { file.externalModuleIndicator() }
-// endregion: 1387
+
+// Produces a new SourceFile for the 'newText' provided. The 'textChangeRange' parameter
+// indicates what changed between the 'text' that this SourceFile has and the 'newText'.
+// The SourceFile will be created with the compiler attempting to reuse as many nodes from
+// this file as possible.
+pub fn updateSourceFile<'a>(allocator: &'a Allocator, sourceFile: &SourceFile, fileName: &'a str, newText: &'a str, textChangeRange: &TextChangeRange, aggressiveChecks: bool) -> SourceFile<'a> {
+    // oxc cannot graft old nodes into a new tree, so the edited file is always parsed from scratch, which is what
+    // IncrementalParser.updateSourceFile falls back to as well. Other files keep their trees and binder results.
+    let newSourceFile = createSourceFile(allocator, fileName, newText, Some(sourceFile.scriptKind()));
+    // A change range that does not describe an edit of the old text, like a stale one, says nothing about the new file,
+    // so the file is treated as newly parsed, as when there is no old tree to reuse
+    if !checkChangeRange(sourceFile, newText, textChangeRange, aggressiveChecks) {
+        return newSourceFile;
+    }
+    // Because new source file node is created, it may not have the flag PossiblyContainDynamicImport. This is the case if there is no new edit to add dynamic import.
+    // We will manually port the flag to the new source file.
+    let newSourceFileNode = AstKind::SourceFile(&newSourceFile);
+    newSourceFileNode.set_flags(newSourceFileNode.flags() | (AstKind::SourceFile(sourceFile).flags() & NodeFlags::PermanentlySetIncrementalFlags));
+    newSourceFile
+}
+// endregion: 1395
+
+// region: 1442
+// JSON files are parsed as a single expression statement holding the JSON value, like Parser.parseJsonText
+pub fn parseJsonText<'a>(allocator: &'a Allocator, sourceText: &'a str) -> ParserReturn<'a> {
+    // oxc has no JSON entry point, so an empty script provides the source file that the parsed value is placed in
+    let mut ret = Parser::new(allocator, "", SourceType::default()).parse();
+    // an empty file has no statements, and the checker gives it an empty object type. Otherwise the value is parsed as
+    // a JavaScript expression, and the syntax JSON does not allow in it is reported by rb_json_syntax_diagnostics
+    if !sourceText.trim().is_empty() {
+        match Parser::new(allocator, sourceText, SourceType::default()).parse_expression() {
+            Ok(expression) => {
+                let statement = AstBuilder::new(allocator).statement_expression(expression.span(), expression);
+                ret.source_file.body.push(statement);
+            }
+            Err(errors) => ret.errors = errors,
+        }
+    }
+    ret.source_file.source_text = sourceText;
+    ret.source_file.span = Span::new(0, sourceText.len() as u32);
+    ret
+}
+// endregion: 1520
+
+// region: 1712
+// ! rb the parser attaches JSDoc to every JSDoc container it creates; here it is parsed the first time it is asked for (see `getJSDocNodes`)
+pub fn addJSDocComment(node: &AstKind) -> Rc<Vec<JSDoc>> {
+    let sourceText = getSourceFileOfNode(Some(node)).unwrap().source_text;
+    let jsDoc: Vec<JSDoc> = getJSDocCommentRanges(node, sourceText).iter().filter_map(|comment| parseIsolatedJSDocComment(sourceText, Some(comment.pos), Some(comment.end - comment.pos))).collect();
+    let jsDoc = Rc::new(jsDoc);
+    node.set_jsDoc(Some(jsDoc.clone()));
+    jsDoc
+}
+// endregion: 1725
+
+// region: 8790
+// ! rb JSDocParser drives the scanner over the comment and parses the types in it into nodes. Here the comment is split
+// into lines and tags with string operations, and the types are kept as text (see `JSDocTypeExpression`).
+/** @internal */
+pub fn parseIsolatedJSDocComment(content: &str, start: Option<usize>, length: Option<usize>) -> Option<JSDoc> {
+    let start = start.unwrap_or(0);
+    let end = length.map_or(content.len(), |length| start + length);
+    // Check for /** (JSDoc opening part)
+    if !isJSDocLikeText(content, start) || end < start + 5 || !content[..end].ends_with("*/") {
+        return None;
+    }
+
+    let mut comments: Vec<&str> = Vec::new();
+    // the lines of each tag, with the position of each line
+    let mut tags: Vec<Vec<(usize, &str)>> = Vec::new();
+    let mut pos = start + 3;
+    for line in content[start + 3..end - 2].split('\n') {
+        let lineStart = pos;
+        pos += line.len() + 1;
+        // Ignore the leading whitespace and the asterisk that continues the comment on each line
+        let mut offset = line.len() - line.trim_start().len();
+        if line[offset..].starts_with('*') {
+            offset += 1;
+            offset = line.len() - line[offset..].trim_start().len();
+        }
+        let text = line[offset..].trim_end();
+        let textStart = lineStart + offset;
+
+        if text.starts_with('@') && text[1..].starts_with(|ch: char| ch.is_alphabetic() || ch == '_' || ch == '$') {
+            tags.push(vec![(textStart, text)]);
+        } else if let Some(tag) = tags.last_mut() {
+            tag.push((textStart, text));
+        } else {
+            comments.push(text);
+        }
+    }
+
+    let comment = comments.join("\n").trim().to_string();
+    Some(JSDoc { pos: start, end, tags: tags.iter().map(|lines| parseTag(lines)).collect(), comment: if comment.is_empty() { None } else { Some(comment) } })
+}
+
+fn parseTag(lines: &[(usize, &str)]) -> JSDocTag {
+    // The tag is parsed as a single string; `positions` maps each byte of it back to the comment
+    let mut text = String::new();
+    let mut positions: Vec<usize> = Vec::new();
+    for (lineStart, line) in lines {
+        if !text.is_empty() {
+            text.push('\n');
+            positions.push(*positions.last().unwrap() + 1);
+        }
+        text.push_str(line);
+        positions.extend(*lineStart..*lineStart + line.len());
+    }
+    let trimmedEnd = text.trim_end().len();
+    let tagEnd = positions[trimmedEnd - 1] + 1;
+    let position = |index: usize| if index < positions.len() { positions[index] } else { tagEnd };
+
+    let tagNameEnd = 1 + text[1..].find(|ch: char| !isJSDocIdentifierPart(ch)).unwrap_or(text.len() - 1);
+    let tagName = text[1..tagNameEnd].to_string();
+    let mut pos = skipJSDocWhitespace(&text, tagNameEnd);
+
+    let kind = match tagName.as_str() {
+        "deprecated" => JSDocTagKind::Deprecated,
+        "return" | "returns" => {
+            let typeExpression = tryParseTypeExpression(&text, &mut pos, &position);
+            JSDocTagKind::Return { typeExpression }
+        }
+        "type" => {
+            // The braces around the type of a @type tag may be omitted
+            let typeExpression = tryParseTypeExpression(&text, &mut pos, &position).unwrap_or_else(|| {
+                let typeEnd = text[pos..].find('\n').map_or(trimmedEnd, |index| pos + index);
+                let typeExpression = JSDocTypeExpression { pos: position(pos), end: position(typeEnd), r#type: text[pos..typeEnd].trim().to_string() };
+                pos = typeEnd;
+                typeExpression
+            });
+            JSDocTagKind::Type { typeExpression }
+        }
+        "param" | "arg" | "argument" => {
+            let mut typeExpression = tryParseTypeExpression(&text, &mut pos, &position);
+            let isNameFirst = typeExpression.is_none();
+            let (name, isBracketed) = parseBracketNameInPropertyAndParamTag(&text, &mut pos);
+            if isNameFirst {
+                typeExpression = tryParseTypeExpression(&text, &mut pos, &position);
+            }
+            JSDocTagKind::Parameter { name, typeExpression, isNameFirst, isBracketed }
+        }
+        "template" => {
+            let constraint = tryParseTypeExpression(&text, &mut pos, &position);
+            let mut typeParameters = Vec::new();
+            loop {
+                let (name, _) = parseBracketNameInPropertyAndParamTag(&text, &mut pos);
+                if name.is_empty() {
+                    break;
+                }
+                typeParameters.push(name);
+                pos = skipJSDocWhitespace(&text, pos);
+                match text[pos..].strip_prefix(',') {
+                    Some(_) => pos = skipJSDocWhitespace(&text, pos + 1),
+                    None => break,
+                }
+            }
+            JSDocTagKind::Template { constraint, typeParameters }
+        }
+        _ => JSDocTagKind::Unknown,
+    };
+
+    let comment = text[pos.min(trimmedEnd)..trimmedEnd].trim();
+    JSDocTag { pos: positions[0], end: tagEnd, tagName, comment: if comment.is_empty() { None } else { Some(comment.to_string()) }, kind }
+}
+
+fn isJSDocIdentifierPart(ch: char) -> bool { ch.is_alphanumeric() || ch == '_' || ch == '$' }
+
+fn skipJSDocWhitespace(text: &str, pos: usize) -> usize { text.len() - text[pos..].trim_start().len() }
+
+/** Parses a `{type}` at `pos`, skipping the whitespace after it */
+fn tryParseTypeExpression(text: &str, pos: &mut usize, position: &impl Fn(usize) -> usize) -> Option<JSDocTypeExpression> {
+    if !text[*pos..].starts_with('{') {
+        return None;
+    }
+    let mut depth = 0;
+    let mut typeEnd = text.len();
+    for (index, ch) in text[*pos..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    typeEnd = *pos + index;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let typeExpression = JSDocTypeExpression { pos: position(*pos), end: position(typeEnd + 1), r#type: text[*pos + 1..typeEnd].trim().to_string() };
+    *pos = skipJSDocWhitespace(text, (typeEnd + 1).min(text.len()));
+    Some(typeExpression)
+}
+
+/** Parses a name like `a`, `a.b` or `[a=1]`, skipping the whitespace after it */
+fn parseBracketNameInPropertyAndParamTag(text: &str, pos: &mut usize) -> (String, bool) {
+    // Looking for something like '[foo]', 'foo', '[foo.bar]' or 'foo.bar'
+    let isBracketed = text[*pos..].starts_with('[');
+    let nameStart = if isBracketed { skipJSDocWhitespace(text, *pos + 1) } else { *pos };
+    let nameEnd = nameStart + text[nameStart..].find(|ch: char| !isJSDocIdentifierPart(ch) && ch != '.').unwrap_or(text.len() - nameStart);
+    let name = text[nameStart..nameEnd].to_string();
+    *pos = nameEnd;
+    if isBracketed {
+        // skip the default value, if any, up to the closing bracket
+        *pos = text[nameEnd..].find(']').map_or(text.len(), |index| nameEnd + index + 1);
+    }
+    *pos = skipJSDocWhitespace(text, *pos);
+    (name, isBracketed)
+}
+// endregion: 9180
+
+// region: 10156
+// ! rb TypeScript asserts that the change range agrees with the old and new texts. It comes from the editor here, so a
+// range that is stale or does not fall on character boundaries is reported to the caller instead of panicking
+fn checkChangeRange(sourceFile: &SourceFile, newText: &str, textChangeRange: &TextChangeRange, aggressiveChecks: bool) -> bool {
+    let oldText = sourceFile.source_text;
+    let start = textChangeRange.span.start as usize;
+    let oldEnd = start + textChangeRange.span.length as usize;
+    let newEnd = start + textChangeRange.newLength as usize;
+    if oldEnd > oldText.len() || oldText.len() - textChangeRange.span.length as usize + textChangeRange.newLength as usize != newText.len() {
+        return false;
+    }
+
+    if aggressiveChecks {
+        let oldTextPrefix = oldText.get(..start);
+        let newTextPrefix = newText.get(..start);
+        if oldTextPrefix.is_none() || oldTextPrefix != newTextPrefix {
+            return false;
+        }
+
+        let oldTextSuffix = oldText.get(oldEnd..);
+        let newTextSuffix = newText.get(newEnd..);
+        if oldTextSuffix.is_none() || oldTextSuffix != newTextSuffix {
+            return false;
+        }
+    }
+    true
+}
+// endregion: 10176
+
+#[cfg(test)]
+mod tests {
+    use super::parseIsolatedJSDocComment;
+    use crate::compiler::types::{JSDocTagKind, JSDocTypeExpression};
+
+    #[test]
+    fn parses_type_tags_with_and_without_braces() {
+        let js_doc = parseIsolatedJSDocComment("/** @type {string} */", None, None).expect("a JSDoc comment");
+        assert_eq!((js_doc.pos, js_doc.end, js_doc.comment), (0, 21, None));
+        let [tag] = &js_doc.tags[..] else { panic!("expected one tag, got {:?}", js_doc.tags) };
+        assert_eq!((tag.tagName.as_str(), tag.pos, tag.end, tag.comment.as_deref()), ("type", 4, 18, None));
+        assert!(matches!(&tag.kind, JSDocTagKind::Type { typeExpression } if *typeExpression == JSDocTypeExpression { pos: 10, end: 18, r#type: "string".to_string() }));
+
+        let js_doc = parseIsolatedJSDocComment("/** @type string */", None, None).expect("a JSDoc comment");
+        assert!(matches!(&js_doc.tags[0].kind, JSDocTagKind::Type { typeExpression } if typeExpression.r#type == "string"));
+    }
+
+    #[test]
+    fn parses_param_tags_spanning_lines() {
+        let content = "/**\n * Adds numbers.\n * @param {number} a the first\n * @param [b=1] the\n *   second\n */";
+        let js_doc = parseIsolatedJSDocComment(content, None, None).expect("a JSDoc comment");
+        assert_eq!((js_doc.pos, js_doc.end, js_doc.comment.as_deref()), (0, 87, Some("Adds numbers.")));
+        let [first, second] = &js_doc.tags[..] else { panic!("expected two tags, got {:?}", js_doc.tags) };
+
+        assert_eq!((first.pos, first.end, first.comment.as_deref()), (24, 51, Some("the first")));
+        let JSDocTagKind::Parameter { name, typeExpression, isNameFirst, isBracketed } = &first.kind else { panic!("expected a parameter tag, got {:?}", first.kind) };
+        assert_eq!((name.as_str(), *isNameFirst, *isBracketed), ("a", false, false));
+        assert_eq!(typeExpression.as_ref(), Some(&JSDocTypeExpression { pos: 31, end: 39, r#type: "number".to_string() }));
+
+        // the comment continues on the next line, and the tag ends with it
+        assert_eq!((second.pos, second.end, second.comment.as_deref()), (55, 83, Some("the\nsecond")));
+        let JSDocTagKind::Parameter { name, typeExpression, isNameFirst, isBracketed } = &second.kind else { panic!("expected a parameter tag, got {:?}", second.kind) };
+        assert_eq!((name.as_str(), typeExpression, *isNameFirst, *isBracketed), ("b", &None, true, true));
+    }
+
+    #[test]
+    fn parses_template_returns_and_deprecated_tags() {
+        let js_doc = parseIsolatedJSDocComment("/** @template T, U\n * @returns {T} the result\n * @deprecated */", None, None).expect("a JSDoc comment");
+        let [template, returns, deprecated] = &js_doc.tags[..] else { panic!("expected three tags, got {:?}", js_doc.tags) };
+        assert!(matches!(&template.kind, JSDocTagKind::Template { constraint: None, typeParameters } if *typeParameters == ["T", "U"]));
+        assert_eq!((returns.pos, returns.comment.as_deref()), (22, Some("the result")));
+        assert!(matches!(&returns.kind, JSDocTagKind::Return { typeExpression: Some(typeExpression) } if *typeExpression == JSDocTypeExpression { pos: 31, end: 34, r#type: "T".to_string() }));
+        assert_eq!((deprecated.pos, deprecated.end, deprecated.comment.as_deref()), (49, 60, None));
+        assert!(matches!(deprecated.kind, JSDocTagKind::Deprecated));
+    }
+
+    #[test]
+    fn ignores_comments_that_are_not_jsdoc() {
+        assert!(parseIsolatedJSDocComment("/* plain */", None, None).is_none());
+        assert!(parseIsolatedJSDocComment("/**/", None, None).is_none());
+        // only the given range is parsed
+        let js_doc = parseIsolatedJSDocComment("let a; /** @deprecated */ let b;", Some(7), Some(18)).expect("a JSDoc comment");
+        assert_eq!((js_doc.pos, js_doc.end, js_doc.tags.len()), (7, 25, 1));
+    }
+}
@@ -1,3 +1,8 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::core::equateStringsCaseInsensitive;
+
 // region: 21
 /**
  * Internally, we represent paths as strings with '/' as the directory separator.
@@ -84,6 +89,18 @@ pub fn pathIsAbsolute(path: &str) -> bool {
 
 // endregion: 93
 
+// region: 95
+/**
+ * Determines whether a path starts with a relative path component (i.e. `.` or `..`).
+ *
+ * @internal
+ */
+pub fn pathIsRelative(path: &str) -> bool {
+    let rest = path.strip_prefix("..").or_else(|| path.strip_prefix('.'));
+    rest.is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '\\']))
+}
+// endregion: 101
+
 // region: 119
 /** @internal */
 pub fn fileExtensionIs(path: &str, extension: &str) -> bool { return path.len() > extension.len() && path.ends_with(extension); }
@@ -98,6 +115,10 @@ pub fn fileExtensionIsOneOf(path: &str, extensions: Vec<&str>) -> bool {
 
     return false;
 }
+
+/** @internal */
+pub fn hasExtension(fileName: &str) -> bool { getBaseFileName(fileName).contains('.') }
+
 /**
  * Determines whether a path has a trailing separator (`/` or `\\`).
  *
@@ -141,7 +162,7 @@ fn getEncodedRootLength(path: &str) -> RootLength {
     let ch0 = path.chars().next().unwrap() as u32;
     // POSIX or UNC
     if ch0 == b'/' as u32 || ch0 == b'\\' as u32 {
-        if path.chars().nth(1).map_or(false, |c| (c as u32) != ch0) {
+        if path.chars().nth(1).map_or(true, |c| (c as u32) != ch0) {
             return RootLength::RootedDiskPath(1); // POSIX: "/" (or non-normalized "\")
         }
 
@@ -311,6 +332,150 @@ pub fn getDirectoryPath(path_arg: &str) -> String {
 }
 // endregion: 323
 
+// region: 373
+/**
+ * Returns the path except for its containing directory name.
+ * Semantics align with NodeJS's `path.basename` except that we support URL's as well.
+ *
+ * ```ts
+ * // POSIX
+ * getBaseFileName("/path/to/file.ext") === "file.ext"
+ * getBaseFileName("/path/to/") === "to"
+ * getBaseFileName("/") === ""
+ * // DOS
+ * getBaseFileName("c:/path/to/file.ext") === "file.ext"
+ * getBaseFileName("c:/path/to/") === "to"
+ * getBaseFileName("c:/") === ""
+ * getBaseFileName("c:") === ""
+ * ```
+ *
+ * @internal
+ */
+pub fn getBaseFileName(path_arg: &str) -> String {
+    let path = normalizeSlashes(path_arg);
+
+    // if the path provided is itself the root, then it has not file name.
+    let root_length = getRootLength(&path);
+    if root_length == path.len() {
+        return String::new();
+    }
+
+    // return the trailing portion of the path starting after the last (non-terminal) directory
+    // separator but not including any trailing directory separator.
+    let path = removeTrailingDirectorySeparator(&path);
+    let last_sep = path[root_length..].rfind(directorySeparator).map(|i| i + root_length + 1).unwrap_or(root_length);
+    path[last_sep..].to_string()
+}
+
+fn tryGetExtensionFromPath(path: &str, extension: &str, ignoreCase: bool) -> Option<String> {
+    let extension = if extension.starts_with('.') { extension.to_string() } else { format!(".{extension}") };
+    if path.len() >= extension.len() && path.as_bytes()[path.len() - extension.len()] == b'.' {
+        let pathExtension = &path[path.len() - extension.len()..];
+        if if ignoreCase { equateStringsCaseInsensitive(pathExtension, &extension) } else { pathExtension == extension } {
+            return Some(pathExtension.to_string());
+        }
+    }
+    None
+}
+
+fn getAnyExtensionFromPathWorker(path: &str, extensions: &[&str], ignoreCase: bool) -> String {
+    for extension in extensions {
+        if let Some(result) = tryGetExtensionFromPath(path, extension, ignoreCase) {
+            return result;
+        }
+    }
+    String::new()
+}
+
+/**
+ * Gets the file extension for a path.
+ * If extensions are provided, gets the file extension for a path, provided it is one of the provided extensions.
+ *
+ * ```ts
+ * getAnyExtensionFromPath("/path/to/file.ext") === ".ext"
+ * getAnyExtensionFromPath("/path/to/file.ext/") === ".ext"
+ * getAnyExtensionFromPath("/path/to/file") === ""
+ * getAnyExtensionFromPath("/path/to.ext/file") === ""
+ * getAnyExtensionFromPath("/path/to/file.ext", ".ext", true) === ".ext"
+ * getAnyExtensionFromPath("/path/to/file.js", [".ext", ".js"], true) === ".js"
+ * getAnyExtensionFromPath("/path/to/file.ext", ".EXT", false) === ""
+ * ```
+ *
+ * @internal
+ */
+pub fn getAnyExtensionFromPath(path: &str, extensions: Option<&[&str]>, ignoreCase: bool) -> String {
+    // Retrieves any string from the final "." onwards from a base file name.
+    // Unlike extensionFromPath, which throws an exception on unrecognized extensions.
+    if let Some(extensions) = extensions {
+        return getAnyExtensionFromPathWorker(&removeTrailingDirectorySeparator(path), extensions, ignoreCase);
+    }
+    let baseFileName = getBaseFileName(path);
+    match baseFileName.rfind('.') {
+        Some(extensionIndex) => baseFileName[extensionIndex..].to_string(),
+        None => String::new(),
+    }
+}
+// endregion: 420
+
+// region: 473
+fn pathComponents(path: &str, root_length: usize) -> Vec<String> {
+    let root = path[..root_length].to_string();
+    let mut rest: Vec<String> = path[root_length..].split(directorySeparator).map(|s| s.to_string()).collect();
+    if rest.last().map_or(false, |s| s.is_empty()) {
+        rest.pop();
+    }
+    let mut components = vec![root];
+    components.extend(rest);
+    components
+}
+
+/**
+ * Parse a path into an array containing a root component (at index 0) and zero or more path
+ * components (at indices > 0). The result is not normalized.
+ * If the path is relative, the root component is `""`.
+ * If the path is absolute, the root component includes the first path separator (`/`).
+ *
+ * ```ts
+ * // POSIX
+ * getPathComponents("/path/to/file.ext") === ["/", "path", "to", "file.ext"]
+ * getPathComponents("/path/to/") === ["/", "path", "to"]
+ * getPathComponents("/") === ["/"]
+ * // DOS
+ * getPathComponents("c:/path/to/file.ext") === ["c:/", "path", "to", "file.ext"]
+ * // Relative
+ * getPathComponents("path/to/file.ext") === ["", "path", "to", "file.ext"]
+ * ```
+ *
+ * @internal
+ */
+pub fn getPathComponents(path: &str, current_directory: Option<&str>) -> Vec<String> {
+    let path = combinePaths(current_directory.unwrap_or(""), &[Some(path)]);
+    pathComponents(&path, getRootLength(&path))
+}
+// endregion: 511
+
+// region: 512
+/**
+ * Formats a parsed path consisting of a root component (at index 0) and zero or more path
+ * segments (at indices > 0).
+ *
+ * ```ts
+ * getPathFromPathComponents(["/", "path", "to", "file.ext"]) === "/path/to/file.ext"
+ * ```
+ *
+ * @internal
+ */
+pub fn getPathFromPathComponents(path_components: &[String], length: Option<usize>) -> String {
+    if path_components.is_empty() {
+        return String::new();
+    }
+
+    let length = length.unwrap_or(path_components.len()).min(path_components.len());
+    let root = if !path_components[0].is_empty() { ensureTrailingDirectorySeparator(&path_components[0]) } else { String::new() };
+    format!("{}{}", root, path_components[1..length].join(directorySeparator))
+}
+// endregion: 518
+
 // region: 519
 //// Path Normalization
 
@@ -326,7 +491,40 @@ pub fn normalizeSlashes(path: &str) -> String {
         path.to_string()
     }
 }
-// endregion: 530
+
+/**
+ * Reduce an array of path components to a more simplified path by navigating any
+ * `"."` or `".."` entries in the path.
+ *
+ * @internal
+ */
+pub fn reducePathComponents(components: &[String]) -> Vec<String> {
+    if components.is_empty() {
+        return vec![];
+    }
+    let mut reduced = vec![components[0].clone()];
+    for component in &components[1..] {
+        if component.is_empty() {
+            continue;
+        }
+        if component == "." {
+            continue;
+        }
+        if component == ".." {
+            if reduced.len() > 1 {
+                if reduced[reduced.len() - 1] != ".." {
+                    reduced.pop();
+                    continue;
+                }
+            } else if !reduced[0].is_empty() {
+                continue;
+            }
+        }
+        reduced.push(component.clone());
+    }
+    reduced
+}
+// endregion: 557
 
 // region: 559
 /**
@@ -365,7 +563,70 @@ pub fn combinePaths(path: &str, paths: &[Option<&str>]) -> String {
 
     result
 }
-// endregion: 592
+
+/**
+ * Combines and resolves paths. If a path is absolute, it replaces any previous path. Any
+ * `.` and `..` path components are resolved. Trailing directory separators are preserved.
+ *
+ * ```ts
+ * resolvePath("/path", "to", "file.ext") === "path/to/file.ext"
+ * resolvePath("/path", "to", "file.ext/") === "path/to/file.ext/"
+ * resolvePath("/path", "dir", "..", "to", "file.ext") === "path/to/file.ext"
+ * ```
+ *
+ * @internal
+ */
+pub fn resolvePath(path: &str, paths: &[Option<&str>]) -> String { normalizePath(&(if paths.iter().any(|p| p.is_some()) { combinePaths(path, paths) } else { normalizeSlashes(path) })) }
+
+/**
+ * Parse a path into an array containing a root component (at index 0) and zero or more path
+ * components (at indices > 0). The result is normalized.
+ * If the path is relative, the root component is `""`.
+ * If the path is absolute, the root component includes the first path separator (`/`).
+ *
+ * ```ts
+ * getNormalizedPathComponents("to/dir/../file.ext", "/path/") === ["/", "path", "to", "file.ext"]
+ * ```
+ *
+ * @internal
+ */
+pub fn getNormalizedPathComponents(path: &str, current_directory: Option<&str>) -> Vec<String> { reducePathComponents(&getPathComponents(path, current_directory)) }
+
+/** @internal */
+pub fn getNormalizedAbsolutePath(path: &str, current_directory: Option<&str>) -> String { getPathFromPathComponents(&getNormalizedPathComponents(path, current_directory), None) }
+
+lazy_static! {
+    static ref relativePathSegmentRegExp: Regex = Regex::new(r"//|(?:^|/)\.\.?(?:$|/)").unwrap();
+}
+
+/** @internal */
+pub fn normalizePath(path: &str) -> String {
+    let mut path = normalizeSlashes(path);
+    // Most paths don't require normalization
+    if !relativePathSegmentRegExp.is_match(&path) {
+        return path;
+    }
+    // Some paths only require cleanup of `/./` or leading `./`
+    let mut simplified = path.replace("/./", "/");
+    while simplified.contains("/./") {
+        simplified = simplified.replace("/./", "/");
+    }
+    let simplified = simplified.strip_prefix("./").map(|s| s.to_string()).unwrap_or(simplified);
+    if simplified != path {
+        path = simplified;
+        if !relativePathSegmentRegExp.is_match(&path) {
+            return path;
+        }
+    }
+    // Other paths require full normalization
+    let normalized = getPathFromPathComponents(&reducePathComponents(&getPathComponents(&path, None)), None);
+    if !normalized.is_empty() && hasTrailingDirectorySeparator(&path) {
+        ensureTrailingDirectorySeparator(&normalized)
+    } else {
+        normalized
+    }
+}
+// endregion: 666
 
 // region: 669
 //// Path Mutation
@@ -407,6 +668,126 @@ pub fn ensureTrailingDirectorySeparator(path: &str) -> String {
 }
 // endregion: 713
 
+// region: 812
+/**
+ * Changes the extension of a path to the provided extension if it has one of the provided extensions.
+ *
+ * ```ts
+ * changeAnyExtension("/path/to/file.ext", ".js", ".ext") === "/path/to/file.js"
+ * changeAnyExtension("/path/to/file.ext", ".js", [".ext", ".tsx"]) === "/path/to/file.js"
+ * changeAnyExtension("/path/to/file.ext", ".js", ".ts") === "/path/to/file.ext"
+ * ```
+ *
+ * @internal
+ */
+pub fn changeAnyExtension(path: &str, ext: &str, extensions: Option<&[&str]>, ignoreCase: bool) -> String {
+    let pathext = getAnyExtensionFromPath(path, extensions, ignoreCase);
+    if pathext.is_empty() {
+        return path.to_string();
+    }
+    format!("{}{}{}", &path[..path.len() - pathext.len()], if ext.starts_with('.') { "" } else { "." }, ext)
+}
+// endregion: 827
+
+// region: 881
+//// Path Comparisons
+
+/**
+ * Determines whether a `parent` path contains a `child` path using the provide case sensitivity.
+ *
+ * @internal
+ */
+pub fn containsPath(parent: &str, child: &str, currentDirectory: Option<&str>, ignoreCase: bool) -> bool {
+    let (parent, child) = match currentDirectory {
+        Some(currentDirectory) => (combinePaths(currentDirectory, &[Some(parent)]), combinePaths(currentDirectory, &[Some(child)])),
+        None => (parent.to_string(), child.to_string()),
+    };
+    if parent == child {
+        return true;
+    }
+    let parentComponents = reducePathComponents(&getPathComponents(&parent, None));
+    let childComponents = reducePathComponents(&getPathComponents(&child, None));
+    if childComponents.len() < parentComponents.len() {
+        return false;
+    }
+
+    for i in 0..parentComponents.len() {
+        let equal = if i == 0 || ignoreCase { equateStringsCaseInsensitive(&parentComponents[i], &childComponents[i]) } else { parentComponents[i] == childComponents[i] };
+        if !equal {
+            return false;
+        }
+    }
+
+    true
+}
+// endregion: 914
+
+// region: 916
+
+/** @internal */
+pub fn getPathComponentsRelativeTo(from: &str, to: &str, get_canonical_file_name: impl Fn(&str) -> String) -> Vec<String> {
+    let from_components = reducePathComponents(&getPathComponents(from, None));
+    let to_components = reducePathComponents(&getPathComponents(to, None));
+
+    let mut start = 0;
+    while start < from_components.len() && start < to_components.len() {
+        let from_component = get_canonical_file_name(&from_components[start]);
+        let to_component = get_canonical_file_name(&to_components[start]);
+        let comparer_equal = if start == 0 { from_component.eq_ignore_ascii_case(&to_component) } else { from_component == to_component };
+        if !comparer_equal {
+            break;
+        }
+        start += 1;
+    }
+
+    if start == 0 {
+        return to_components;
+    }
+
+    let mut relative = vec![String::new()];
+    for _ in start..from_components.len() {
+        relative.push("..".to_string());
+    }
+    relative.extend(to_components[start..].iter().cloned());
+    relative
+}
+
+/**
+ * Gets a relative path that can be used to traverse between `from` and `to`.
+ *
+ * @internal
+ */
+pub fn getRelativePathFromDirectory(from_directory: &str, to: &str, get_canonical_file_name: impl Fn(&str) -> String) -> String {
+    debug_assert!((getRootLength(from_directory) > 0) == (getRootLength(to) > 0), "Paths must either both be absolute or both be relative");
+    let path_components = getPathComponentsRelativeTo(from_directory, to, get_canonical_file_name);
+    getPathFromPathComponents(&path_components, None)
+}
+
+/** @internal */
+pub fn convertToRelativePath(absolute_or_relative_path: &str, base_path: &str, get_canonical_file_name: impl Fn(&str) -> String) -> String {
+    if !isRootedDiskPath(absolute_or_relative_path) {
+        absolute_or_relative_path.to_string()
+    } else {
+        getRelativePathToDirectoryOrUrl(base_path, absolute_or_relative_path, base_path, get_canonical_file_name, false)
+    }
+}
+
+/** @internal */
+pub fn getRelativePathToDirectoryOrUrl(directory_path_or_url: &str, relative_or_absolute_path: &str, current_directory: &str, get_canonical_file_name: impl Fn(&str) -> String, is_absolute_path_an_url: bool) -> String {
+    let path_components = getPathComponentsRelativeTo(&resolvePath(current_directory, &[Some(directory_path_or_url)]), &resolvePath(current_directory, &[Some(relative_or_absolute_path)]), get_canonical_file_name);
+
+    let first_component = &path_components[0];
+    if is_absolute_path_an_url && isRootedDiskPath(first_component) {
+        let prefix = if first_component.starts_with(directorySeparator) { "file://" } else { "file:///" };
+        let mut path_components = path_components.clone();
+        path_components[0] = format!("{}{}", prefix, first_component);
+        return getPathFromPathComponents(&path_components, None);
+    }
+
+    getPathFromPathComponents(&path_components, None)
+}
+// endregion: 983
+
 // region: 985
 
 //// Path Traversal
@@ -1,10 +1,174 @@
+use super::diagnostic_information_map_generated::Diagnostics;
 use super::parser::CreateSourceFileOptions;
 use super::rb_extra::SourceFileExt;
+use super::rb_unions::StringOrDiagnosticMessageChain;
+use super::scanner::{computeLineAndCharacterOfPosition, computeLineStarts, getLineAndCharacterOfPosition};
 use crate::compiler::moduleNameResolver::*;
 use crate::compiler::path::*;
 use crate::compiler::types::*;
 use crate::compiler::utilities::*;
-use oxc_ast::ast::SourceFile;
+use oxc_ast::ast::{Declaration, SourceFile, Statement, TSModuleReference};
+use serde_json::Value;
+
+// region: 352
+pub fn findConfigFile(searchPath: &str, fileExists: impl Fn(&str) -> bool, configName: Option<&str>) -> Option<String> {
+    let configName = configName.unwrap_or("tsconfig.json");
+    forEachAncestorDirectory(searchPath, |ancestor| {
+        let fileName = combinePaths(ancestor, &[Some(configName)]);
+        if fileExists(&fileName) {
+            Some(fileName)
+        } else {
+            None
+        }
+    })
+}
+// endregion: 359
+
+// region: 575
+/** @internal */
+pub fn computeCommonSourceDirectoryOfFilenames(fileNames: &[String], currentDirectory: &str, getCanonicalFileName: impl Fn(&str) -> String) -> String {
+    let mut commonPathComponents: Option<Vec<String>> = None;
+    for sourceFile in fileNames {
+        let mut sourcePathComponents = getNormalizedPathComponents(sourceFile, Some(currentDirectory));
+        sourcePathComponents.pop(); // The base file name is not part of the common directory path
+
+        let Some(commonPathComponents) = &mut commonPathComponents else {
+            // first file
+            commonPathComponents = Some(sourcePathComponents);
+            continue;
+        };
+
+        let n = commonPathComponents.len().min(sourcePathComponents.len());
+        for i in 0..n {
+            if getCanonicalFileName(&commonPathComponents[i]) != getCanonicalFileName(&sourcePathComponents[i]) {
+                if i == 0 {
+                    // Failed to find any common path component
+                    return String::new();
+                }
+
+                // New common path found that is 0 -> i-1
+                commonPathComponents.truncate(i);
+                break;
+            }
+        }
+
+        // If the sourcePathComponents was shorter than the commonPathComponents, truncate to the sourcePathComponents
+        commonPathComponents.truncate(sourcePathComponents.len());
+    }
+
+    match commonPathComponents {
+        // Can happen when all input files are .d.ts files
+        None => currentDirectory.to_string(),
+        Some(commonPathComponents) => getPathFromPathComponents(&commonPathComponents, None),
+    }
+}
+// endregion: 602
+
+// region: 604
+pub trait FormatDiagnosticsHost {
+    fn getCurrentDirectory(&self) -> String;
+    fn getCanonicalFileName(&self, fileName: &str) -> String;
+    fn getNewLine(&self) -> String;
+    /** Reads a file that detached diagnostics point into, like a config file, so that they can be given a line and column. */
+    fn readDetachedDiagnosticFile(&self, _fileName: &str) -> Option<String> { None }
+}
+
+pub fn formatDiagnostics(diagnostics: &[Diagnostic], host: &dyn FormatDiagnosticsHost) -> String {
+    let mut output = String::new();
+
+    for diagnostic in diagnostics {
+        output += &formatDiagnostic(diagnostic, host);
+    }
+    output
+}
+
+pub fn formatDiagnostic(diagnostic: &Diagnostic, host: &dyn FormatDiagnosticsHost) -> String {
+    let errorMessage = format!("{} TS{}: {}{}", diagnosticCategoryName(&diagnostic.category, true), diagnostic.code, flattenDiagnosticMessageText(Some(&diagnostic.messageText), &host.getNewLine(), None), host.getNewLine());
+
+    if let Some(file) = diagnostic.file {
+        let LineAndCharacter { line, character } = getLineAndCharacterOfPosition(file, diagnostic.start.unwrap() as usize);
+        let fileName = file.filepath().to_string_lossy().to_string();
+        let relativeFileName = convertToRelativePath(&fileName, &host.getCurrentDirectory(), |fileName| host.getCanonicalFileName(fileName));
+        return format!("{}({},{}): {}", relativeFileName, line + 1, character + 1, errorMessage);
+    }
+
+    if let (Some(fileName), Some(start)) = (&diagnostic.fileName, diagnostic.start) {
+        let relativeFileName = convertToRelativePath(fileName, &host.getCurrentDirectory(), |fileName| host.getCanonicalFileName(fileName));
+        let Some(text) = host.readDetachedDiagnosticFile(fileName) else {
+            return format!("{}: {}", relativeFileName, errorMessage);
+        };
+        let LineAndCharacter { line, character } = computeLineAndCharacterOfPosition(&computeLineStarts(&text), (start as usize).min(text.len()), &text);
+        return format!("{}({},{}): {}", relativeFileName, line + 1, character + 1, errorMessage);
+    }
+
+    errorMessage
+}
+// endregion: 624
+
+// region: 864
+pub fn flattenDiagnosticMessageText(diag: Option<&StringOrDiagnosticMessageChain>, newLine: &str, indent: Option<usize>) -> String {
+    match diag {
+        None => String::new(),
+        Some(StringOrDiagnosticMessageChain::String(text)) => text.clone(),
+        Some(StringOrDiagnosticMessageChain::DiagnosticMessageChain(chain)) => flattenDiagnosticMessageChain(chain, newLine, indent.unwrap_or(0)),
+    }
+}
+
+fn flattenDiagnosticMessageChain(diag: &DiagnosticMessageChain, newLine: &str, indent: usize) -> String {
+    let mut result = String::new();
+    if indent != 0 {
+        result += newLine;
+
+        for _ in 0..indent {
+            result += "  ";
+        }
+    }
+    result += &diag.messageText;
+    let indent = indent + 1;
+    if let Some(next) = &diag.next {
+        for kid in next {
+            result += &flattenDiagnosticMessageChain(kid, newLine, indent);
+        }
+    }
+    result
+}
+// endregion: 890
+
+// region: 930
+/**
+ * Calculates the resulting resolution mode for some reference in some file - this is generally the explicitly
+ * provided resolution mode in the reference, unless one is not present, in which case it is the mode of the containing file.
+ * @param file The file the import or import-like reference is contained within
+ * @param isImportEqualsRequire Whether the reference is the `require` of an import equals declaration
+ * @param compilerOptions The compiler options for the program that owns the file
+ */
+// ! rb resolution-mode import attributes are not read, as the references are collected as plain specifiers
+pub fn getModeForUsageLocation(file: &SourceFile, isImportEqualsRequire: bool, compilerOptions: &CompilerOptions) -> ResolutionMode {
+    if !importSyntaxAffectsModuleResolution(compilerOptions) {
+        return ResolutionMode::Undefined;
+    }
+    if isImportEqualsRequire {
+        return ResolutionMode::CommonJS;
+    }
+    let fileEmitMode = match getImpliedNodeFormatForEmitWorker(file, compilerOptions) {
+        ResolutionMode::CommonJS => ModuleKind::CommonJS,
+        ResolutionMode::ESNext => ModuleKind::ESNext,
+        ResolutionMode::Undefined => getEmitModuleKind(compilerOptions),
+    };
+    if fileEmitMode == ModuleKind::CommonJS {
+        ResolutionMode::CommonJS
+    } else if emitModuleKindIsNonNodeESM(fileEmitMode) || fileEmitMode == ModuleKind::Preserve {
+        ResolutionMode::ESNext
+    } else {
+        ResolutionMode::Undefined
+    }
+}
+
+fn importSyntaxAffectsModuleResolution(options: &CompilerOptions) -> bool {
+    let moduleResolution = getEmitModuleResolutionKind(options);
+    ModuleResolutionKind::Node16 <= moduleResolution && moduleResolution <= ModuleResolutionKind::NodeNext || getResolvePackageJsonExports(options) || getResolvePackageJsonImports(options)
+}
+// endregion: 975
 
 // region: 1372
 /**
@@ -17,8 +181,8 @@ use oxc_ast::ast::SourceFile;
  * @param options The compiler options to perform the analysis under - relevant options are `moduleResolution` and `traceResolution`
  * @returns `undefined` if the path has no relevant implied format, `ModuleKind.ESNext` for esm format, and `ModuleKind.CommonJS` for cjs format
  */
-pub fn getImpliedNodeFormatForFile(file_name: &str, host: &dyn ModuleResolutionHost, options: &CompilerOptions) -> ResolutionMode {
-    let result = getImpliedNodeFormatForFileWorker(file_name, host, options);
+pub fn getImpliedNodeFormatForFile(file_name: &str, packageJsonInfoCache: Option<&mut dyn PackageJsonInfoCache>, host: &dyn ModuleResolutionHost, options: &CompilerOptions) -> ResolutionMode {
+    let result = getImpliedNodeFormatForFileWorker(file_name, packageJsonInfoCache, host, options);
     match result {
         Some(options) => options.impliedNodeFormat.unwrap_or(ResolutionMode::Undefined),
         None => ResolutionMode::Undefined,
@@ -26,7 +190,7 @@ pub fn getImpliedNodeFormatForFile(file_name: &str, host: &dyn ModuleResolutionH
 }
 
 /** @internal */
-pub fn getImpliedNodeFormatForFileWorker(file_name: &str, host: &dyn ModuleResolutionHost, options: &CompilerOptions) -> Option<CreateSourceFileOptions> {
+pub fn getImpliedNodeFormatForFileWorker(file_name: &str, packageJsonInfoCache: Option<&mut dyn PackageJsonInfoCache>, host: &dyn ModuleResolutionHost, options: &CompilerOptions) -> Option<CreateSourceFileOptions> {
     let module_resolution = getEmitModuleResolutionKind(options);
     let should_lookup_from_package_json = ModuleResolutionKind::Node16 <= module_resolution && module_resolution <= ModuleResolutionKind::NodeNext || pathContainsNodeModules(file_name);
 
@@ -39,26 +203,89 @@ pub fn getImpliedNodeFormatForFileWorker(file_name: &str, host: &dyn ModuleResol
     }
 
     if should_lookup_from_package_json && fileExtensionIsOneOf(file_name, vec![Extension::Dts.as_str(), Extension::Ts.as_str(), Extension::Tsx.as_str(), Extension::Js.as_str(), Extension::Jsx.as_str()]) {
-        return lookupFromPackageJson(file_name, host, options);
+        return lookupFromPackageJson(file_name, packageJsonInfoCache, host, options);
     }
 
     // other extensions, like `json` or `tsbuildinfo`, are set as `None` here but they should never be fed through the transformer pipeline
     None
 }
 
-fn lookupFromPackageJson(file_name: &str, host: &dyn ModuleResolutionHost, options: &CompilerOptions) -> Option<CreateSourceFileOptions> {
-    let mut state = getTemporaryModuleResolutionState(None, host, options); // todo consider a thread local cache
-    let package_json_locations: Vec<String> = Vec::new();
-    state.failedLookupLocations = Some(package_json_locations.clone());
-    state.affectingLocations = Some(package_json_locations.clone());
+fn lookupFromPackageJson(file_name: &str, packageJsonInfoCache: Option<&mut dyn PackageJsonInfoCache>, host: &dyn ModuleResolutionHost, options: &CompilerOptions) -> Option<CreateSourceFileOptions> {
+    let mut state = getTemporaryModuleResolutionState(packageJsonInfoCache, host, options);
+    state.failedLookupLocations = Some(Vec::new());
+    state.affectingLocations = Some(Vec::new());
     let package_json_scope = getPackageScopeForPath(&getDirectoryPath(file_name), &mut state);
+    // failed and affecting lookups share one list of package.json locations
+    let mut package_json_locations = state.failedLookupLocations.take().unwrap_or_default();
+    package_json_locations.extend(state.affectingLocations.take().unwrap_or_default());
 
-    let implied_node_format = if package_json_scope.as_ref().and_then(|scope| scope.contents.packageJsonContent.r#type.as_ref()).map_or(false, |t| t == "module") { ResolutionMode::ESNext } else { ResolutionMode::CommonJS };
+    let implied_node_format = if package_json_scope.as_ref().and_then(|scope| scope.contents.packageJsonContent.get("type")).and_then(Value::as_str) == Some("module") { ResolutionMode::ESNext } else { ResolutionMode::CommonJS };
 
     Some(CreateSourceFileOptions { languageVersion: ScriptTarget::ESNext, impliedNodeFormat: Some(implied_node_format), packageJsonLocations: Some(package_json_locations), packageJsonScope: package_json_scope })
 }
 // endregion: 1397
 
+// region: 3383
+/**
+ * The module specifiers of the top-level imports, re-exports and `import x = require()` declarations of `file`, in source order.
+ * Each specifier is paired with whether it is the `require` of an import equals declaration, which always resolves as CommonJS.
+ */
+// ! rb dynamic imports, `require` calls in JavaScript files and the imports inside ambient module declarations are not collected
+pub fn collectExternalModuleReferences<'a>(file: &'a SourceFile<'a>) -> Vec<(&'a str, bool)> {
+    let mut imports = vec![];
+    for statement in &file.body {
+        match statement {
+            Statement::ImportDeclaration(decl) => imports.push((decl.source.value.as_str(), false)),
+            Statement::ExportAllDeclaration(decl) => imports.push((decl.source.value.as_str(), false)),
+            Statement::ExportNamedDeclaration(decl) => match (&decl.source, &decl.declaration) {
+                (Some(source), _) => imports.push((source.value.as_str(), false)),
+                (None, Some(Declaration::TSImportEqualsDeclaration(decl))) => {
+                    if let TSModuleReference::ExternalModuleReference(reference) = &decl.module_reference {
+                        imports.push((reference.expression.value.as_str(), true));
+                    }
+                }
+                _ => {}
+            },
+            Statement::TSImportEqualsDeclaration(decl) => {
+                if let TSModuleReference::ExternalModuleReference(reference) = &decl.module_reference {
+                    imports.push((reference.expression.value.as_str(), true));
+                }
+            }
+            _ => {}
+        }
+    }
+    imports
+}
+// endregion: 3450
+
+// region: 4046
+/**
+ * Resolves the module references of `file` through `host`, in the order of `collectExternalModuleReferences`.
+ */
+pub fn resolveModuleNamesOfFile(file: &SourceFile, compilerOptions: &CompilerOptions, host: &dyn ModuleResolutionHost, mut cache: Option<&mut dyn PackageJsonInfoCache>) -> Vec<(String, ResolvedModuleWithFailedLookupLocations)> {
+    let containingFile = file.filepath().to_string_lossy().to_string();
+    collectExternalModuleReferences(file)
+        .into_iter()
+        .map(|(moduleName, isImportEqualsRequire)| {
+            let resolutionMode = getModeForUsageLocation(file, isImportEqualsRequire, compilerOptions);
+            (moduleName.to_string(), resolveModuleName(moduleName, &containingFile, compilerOptions, host, cache.as_deref_mut(), resolutionMode))
+        })
+        .collect()
+}
+
+/**
+ * Whether the file a module reference resolved to is added to the program, as in `processImportedModules`.
+ */
+pub fn shouldAddResolvedModuleFile(file: &SourceFile, resolution: &ResolvedModuleFull, options: &CompilerOptions) -> bool {
+    let isFromNodeModulesSearch = resolution.isExternalLibraryImport == Some(true);
+    let isJsFile = !resolutionExtensionIsTSOrJson(&resolution.extension);
+    let isJsFileFromNodeModules = isFromNodeModulesSearch && isJsFile;
+    // ! rb the node_modules depth is not tracked, so JavaScript files from node_modules are elided like with the default maxNodeModuleJsDepth of 0
+    let elideImport = isJsFileFromNodeModules;
+    getResolutionDiagnostic(options, resolution, file).is_none() && options.noResolve != Some(true) && !elideImport && !(isJsFile && !getAllowJSCompilerOption(options))
+}
+// endregion: 4105
+
 // region: 5190
 /** @internal Prefer `program.getImpliedNodeFormatForEmit` when possible. */
 pub fn getImpliedNodeFormatForEmitWorker(sourceFile: &SourceFile, options: &CompilerOptions) -> ResolutionMode {
@@ -66,7 +293,7 @@ pub fn getImpliedNodeFormatForEmitWorker(sourceFile: &SourceFile, options: &Comp
     if ModuleKind::Node16 <= moduleKind && moduleKind <= ModuleKind::NodeNext {
         return sourceFile.impliedNodeFormat();
     }
-    let packageJsonContentType = sourceFile.packageJsonScope().map(|scope| scope.contents.packageJsonContent.r#type).flatten();
+    let packageJsonContentType = sourceFile.packageJsonScope().and_then(|scope| scope.contents.packageJsonContent.get("type").and_then(Value::as_str).map(str::to_string));
     if sourceFile.impliedNodeFormat() == ResolutionMode::CommonJS && (packageJsonContentType.as_deref() == Some("commonjs") || fileExtensionIsOneOf(&sourceFile.filepath().to_string_lossy(), [Extension::Cjs.as_str(), Extension::Cts.as_str()].to_vec())) {
         return ResolutionMode::CommonJS;
     }
@@ -76,3 +303,34 @@ pub fn getImpliedNodeFormatForEmitWorker(sourceFile: &SourceFile, options: &Comp
     return ResolutionMode::Undefined;
 }
 // endregion: 5211
+
+// region: 5240
+/**
+ * Returns a DiagnosticMessage if we won't include a resolved module due to its extension.
+ * The DiagnosticMessage's parameters are the imported module name, and the filename it resolved to.
+ * This returns a diagnostic even if the module will be an untyped module.
+ *
+ * @internal
+ */
+pub fn getResolutionDiagnostic(options: &CompilerOptions, resolution: &ResolvedModuleFull, file: &SourceFile) -> Option<DiagnosticMessage> {
+    let needJsx = || if options.jsx.is_some() { None } else { Some(Diagnostics::Module_0_was_resolved_to_1_but_jsx_is_not_set()) };
+    let needAllowJs = || if getAllowJSCompilerOption(options) || !getStrictOptionValue(options, StrictOptionName::NoImplicitAny) { None } else { Some(Diagnostics::Could_not_find_a_declaration_file_for_module_0_1_implicitly_has_an_any_type()) };
+    let needResolveJsonModule = || if getResolveJsonModule(options) { None } else { Some(Diagnostics::Module_0_was_resolved_to_1_but_resolveJsonModule_is_not_used()) };
+    let needAllowArbitraryExtensions = || if file.source_type.is_typescript_definition() || options.allowArbitraryExtensions == Some(true) { None } else { Some(Diagnostics::Module_0_was_resolved_to_1_but_allowArbitraryExtensions_is_not_set()) };
+
+    let extension = resolution.extension.as_str();
+    if [Extension::Ts, Extension::Dts, Extension::Mts, Extension::Dmts, Extension::Cts, Extension::Dcts].iter().any(|ext| ext.as_str() == extension) {
+        None
+    } else if extension == Extension::Tsx.as_str() {
+        needJsx()
+    } else if extension == Extension::Jsx.as_str() {
+        needJsx().or_else(needAllowJs)
+    } else if [Extension::Js, Extension::Mjs, Extension::Cjs].iter().any(|ext| ext.as_str() == extension) {
+        needAllowJs()
+    } else if extension == Extension::Json.as_str() {
+        needResolveJsonModule()
+    } else {
+        needAllowArbitraryExtensions()
+    }
+}
+// endregion: 5276
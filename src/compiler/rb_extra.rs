@@ -16,11 +16,14 @@ use super::types::Diagnostic;
 use super::types::FlowNode;
 use super::types::HasLocals;
 use super::types::HasLocalsExt;
+use super::types::JSDoc;
 use super::types::ModifierFlags;
 use super::types::NodeFlags;
 use super::types::ResolutionMode;
+use super::types::ScriptKind;
 use super::types::Symbol;
 use super::types::SymbolTable;
+use super::types::TypeCheckerHost;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -76,7 +79,7 @@ macro_rules! entity_properties {
                         [<$entity:upper _INFO_MAP>].with(|map| {
                             let mut map = map.borrow_mut();
                             let info = map.entry(ptr).or_insert_with(|| [<$entity Info>]::default());
-                            unsafe { std::mem::transmute_copy(&info.$name) }
+                            unsafe { std::mem::transmute(info.$name.clone()) }
                         })
                     }
                 )*
@@ -85,6 +88,10 @@ macro_rules! entity_properties {
             pub fn [<cleanup_ $entity:upper _INFO_MAP>]() {
                 [<$entity:upper _INFO_MAP>].with(|map| map.borrow_mut().clear());
             }
+
+            pub fn [<remove_ $entity:upper _INFO>](node_id: u32) {
+                [<$entity:upper _INFO_MAP>].with(|map| map.borrow_mut().remove(&node_id));
+            }
         }
 
     };
@@ -96,12 +103,16 @@ entity_properties!(SourceFile, {
     filepath: PathBuf = PathBuf::new(),
     packageJsonScope: Option<PackageJsonInfo> = None,
     externalModuleIndicator: bool = false,
+    commonJsModuleIndicator: Option<AstKind<'static>> = None,
     jsGlobalAugmentations: Option<Rc<RefCell<SymbolTable<'static>>>> = None,
     impliedNodeFormat: ResolutionMode = ResolutionMode::Undefined,
+    scriptKind: ScriptKind = ScriptKind::Unknown,
+    lineMap: Option<Rc<Vec<usize>>> = None,
     // locals: stored on HasLocals
     symbolCount: usize = 0,
-    parseDiagnostics: RefCell<Vec<Diagnostic<'static>>> = RefCell::new(Vec::new()),
-    bindDiagnostics: RefCell<Vec<Diagnostic<'static>>> = RefCell::new(Vec::new()),
+    parseDiagnostics: Rc<RefCell<Vec<Diagnostic<'static>>>> = Rc::new(RefCell::new(Vec::new())),
+    bindDiagnostics: Rc<RefCell<Vec<Diagnostic<'static>>>> = Rc::new(RefCell::new(Vec::new())),
+    bindSuggestionDiagnostics: Rc<RefCell<Vec<Diagnostic<'static>>>> = Rc::new(RefCell::new(Vec::new())),
     classifiableNames: Option<Rc<RefCell<HashSet<String>>>> = None,
 });
 pub trait SourceFilePassthrough<'a> {
@@ -114,9 +125,15 @@ impl<'a> SourceFilePassthrough<'a> for SourceFile<'a> {
 entity_properties!(AstKind, {
     parent: Option<AstKind<'static>> = None,
     flowNode: Option<Rc<RefCell<FlowNode<'static>>>> = None,
+    endFlowNode: Option<Rc<RefCell<FlowNode<'static>>>> = None,
+    returnFlowNode: Option<Rc<RefCell<FlowNode<'static>>>> = None,
+    fallthroughFlowNode: Option<Rc<RefCell<FlowNode<'static>>>> = None,
+    possiblyExhaustive: bool = false,
     symbol: Option<Rc<RefCell<Symbol<'static>>>> = None,
+    localSymbol: Option<Rc<RefCell<Symbol<'static>>>> = None,
     flags: NodeFlags = NodeFlags::None,
     modifierFlagsCache: ModifierFlags = ModifierFlags::None,
+    jsDoc: Option<Rc<Vec<JSDoc>>> = None,
 });
 
 /**
@@ -168,5 +185,5 @@ macro_rules! thread_local_store {
 }
 
 thread_local_store!(RB_CTX, {
-    type_checker_host: Rc<UnsafeCell<RbTypeCheckerHost<'static>>> = Rc::new(UnsafeCell::new(RbTypeCheckerHost::new(String::new(), Rc::new(CompilerOptions::default())))),
+    type_checker_host: Rc<UnsafeCell<dyn TypeCheckerHost<'static>>> = Rc::new(UnsafeCell::new(RbTypeCheckerHost::new(String::new(), Rc::new(CompilerOptions::default())))),
 });
@@ -1,41 +1,90 @@
 use oxc_ast::ast::SourceFile;
 use std::path::Path;
-use std::{fs, rc::Rc};
+use std::{fs, io, rc::Rc};
 
 use crate::{new_rc_cell, rc_cell};
 
-use super::parser::createSourceFile;
-use super::types::{CompilerOptions, ModuleResolutionHost, ModuleSpecifierResolutionHost, TypeCheckerHost};
+use super::diagnostic_information_map_generated::Diagnostics;
+use super::moduleNameResolver::{createPackageJsonInfoCache, getPackageJsonInfo, getTemporaryModuleResolutionState, getTypesPackageName, readPackageJsonTypesFields, PackageJsonInfoCache, PackageJsonInfoCacheMap};
+use super::path::{combinePaths, directorySeparator, getDirectoryPath, getNormalizedAbsolutePath, normalizeSlashes};
+use super::program::{computeCommonSourceDirectoryOfFilenames, FormatDiagnosticsHost};
+use super::rb_extra::SourceFileExt;
+use super::rb_parser::{rb_clear_node_info, OwnedSourceFile, SourceFileArena};
+use super::types::{CompilerOptions, Diagnostic, ModuleResolutionHost, ModuleSpecifierResolutionHost, ParseConfigHost, TextChangeRange, TypeCheckerHost};
+use super::utilities::{attachFileToDiagnostics, createCompilerDiagnostic, isDeclarationFileName, matchFiles, FileSystemEntries};
+use super::utilitiesPublic::textChangeRangeIsUnchanged;
 
 #[derive(Debug, Clone)]
 pub struct RbTypeCheckerHost<'a> {
     pub current_directory: String,
     pub compiler_options: Rc<CompilerOptions>,
-    pub source_files: rc_cell!(Vec<rc_cell!(SourceFile<'a>)>),
-
-    string_arena: Vec<Box<str>>, // allocator for strings
+    pub source_files: rc_cell!(Vec<Rc<OwnedSourceFile<'a>>>),
+    pub package_json_info_cache: rc_cell!(PackageJsonInfoCacheMap),
+    pub use_case_sensitive_file_names: bool,
 }
 
 impl<'a> RbTypeCheckerHost<'a> {
-    pub fn new(current_directory: String, compiler_options: Rc<CompilerOptions>) -> Self { Self { current_directory, compiler_options, source_files: new_rc_cell!(vec![]), string_arena: vec![] } }
-
-    // Add a string to the arena and get a reference with lifetime 'a
-    fn allocate_str(&mut self, s: String) -> &'a str {
-        let boxed = s.into_boxed_str();
-        // Safety: we're storing the string in self.string_arena which has the same lifetime
-        // as self, so this reference will be valid as long as self is valid
-        let str_ref = unsafe { std::mem::transmute::<&str, &'a str>(boxed.as_ref()) };
-        self.string_arena.push(boxed);
-        str_ref
-    }
-
-    pub fn addSourceFile(&mut self, name: String) -> rc_cell!(SourceFile<'a>) {
-        let name = self.allocate_str(name);
-        let source_text = self.allocate_str(fs::read_to_string(Path::new(name)).unwrap());
-        let source_file = new_rc_cell!(createSourceFile(name, source_text));
+    pub fn new(current_directory: String, compiler_options: Rc<CompilerOptions>) -> Self {
+        let use_case_sensitive_file_names = isFileSystemCaseSensitive();
+        let package_json_info_cache = new_rc_cell!(createPackageJsonInfoCache(&current_directory, use_case_sensitive_file_names));
+        Self { current_directory, compiler_options, source_files: new_rc_cell!(vec![]), package_json_info_cache, use_case_sensitive_file_names }
+    }
+
+    /// Reads and parses `name`, or returns a `File_0_not_found` / `Cannot_read_file_0_colon_1` diagnostic when it cannot be read.
+    pub fn addSourceFile(&mut self, name: String) -> Result<Rc<OwnedSourceFile<'a>>, Diagnostic<'static>> {
+        let source_text = match fs::read_to_string(Path::new(&name)) {
+            Ok(source_text) => source_text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Err(createCompilerDiagnostic(Diagnostics::File_0_not_found(), vec![name.into()])),
+            Err(error) => return Err(createCompilerDiagnostic(Diagnostics::Cannot_read_file_0_colon_1(), vec![name.into(), error.to_string().into()])),
+        };
+        Ok(self.parseSourceFile(name, source_text))
+    }
+
+    /// Parses `source_text` into a fresh arena, replacing any file previously parsed under the same name.
+    pub fn parseSourceFile(&mut self, name: String, source_text: String) -> Rc<OwnedSourceFile<'a>> { self.insertSourceFile(SourceFileArena::new(name, source_text), |arena| arena.parse(None)) }
+
+    /// Reparses `source_file` after an edit that replaced `text_change_range` of its text, giving `new_text`.
+    /// `source_file` is unlinked and loses its binder results, and its tree is freed once the last handle to it is dropped; the other files keep theirs.
+    pub fn updateSourceFile(&mut self, source_file: &Rc<OwnedSourceFile<'a>>, new_text: String, text_change_range: TextChangeRange) -> Rc<OwnedSourceFile<'a>> {
+        if textChangeRangeIsUnchanged(&text_change_range) && source_file.borrow().source_text == new_text {
+            return source_file.clone();
+        }
+        let old_source_file = source_file.borrow();
+        let name = old_source_file.filepath().to_string_lossy().to_string();
+        self.insertSourceFile(SourceFileArena::new(name, new_text), |arena| arena.update(&old_source_file, &text_change_range))
+    }
+
+    fn insertSourceFile(&mut self, arena: SourceFileArena, parse: impl FnOnce(&'a SourceFileArena) -> SourceFile<'a>) -> Rc<OwnedSourceFile<'a>> {
+        let source_file = Rc::new(OwnedSourceFile::new(arena, parse));
+        // the parse diagnostics can only point at the source file now that it has its final address
+        // Safety: source files are never borrowed mutably once inserted, and the diagnostics are only reachable through
+        // the file's own side-table entry, so they are never read once the file has been dropped
+        let file = unsafe { &*source_file.as_ptr() };
+        let parse_diagnostics = file.parseDiagnostics();
+        let attached = attachFileToDiagnostics(&parse_diagnostics.borrow(), file);
+        *parse_diagnostics.borrow_mut() = attached;
+        // the old tree is only unlinked now, as the new one may have been parsed from it
+        self.removeSourceFile(source_file.arena().file_name());
         self.source_files.borrow_mut().push(source_file.clone());
         source_file
     }
+
+    /// Unlinks the source file parsed from `name` and drops its side-table entries. Returns whether such a file was loaded.
+    /// The file is freed with the host's handle unless the caller still holds one, which stays valid but is no longer bound or returned by getSourceFile.
+    pub fn removeSourceFile(&mut self, name: &str) -> bool {
+        let path = FormatDiagnosticsHost::getCanonicalFileName(self, name);
+        let mut removed = false;
+        self.source_files.borrow_mut().retain(|source_file| {
+            let source_file = source_file.borrow();
+            if FormatDiagnosticsHost::getCanonicalFileName(self, &source_file.filepath().to_string_lossy()) != path {
+                return true;
+            }
+            rb_clear_node_info(&source_file);
+            removed = true;
+            false
+        });
+        removed
+    }
 }
 
 impl<'a> ModuleResolutionHost for RbTypeCheckerHost<'a> {
@@ -43,7 +92,10 @@ impl<'a> ModuleResolutionHost for RbTypeCheckerHost<'a> {
 
     fn readFile(&self, fileName: &str) -> Option<String> { fs::read_to_string(fileName).ok() }
 
-    fn trace(&self, _s: &str) -> Option<()> { None }
+    fn trace(&self, s: &str) -> Option<()> {
+        println!("{s}");
+        Some(())
+    }
 
     fn directoryExists(&self, directoryName: &str) -> Option<bool> { Some(Path::new(directoryName).is_dir()) }
 
@@ -53,42 +105,176 @@ impl<'a> ModuleResolutionHost for RbTypeCheckerHost<'a> {
 
     fn getDirectories(&self, path: &str) -> Option<Vec<String>> { fs::read_dir(path).ok().map(|entries| entries.filter_map(|e| e.ok()).filter(|e| e.path().is_dir()).filter_map(|e| e.path().to_str().map(|s| s.to_string())).collect()) }
 
-    fn useCaseSensitiveFileNames(&self) -> Option<bool> {
+    fn useCaseSensitiveFileNames(&self) -> Option<bool> { Some(self.use_case_sensitive_file_names) }
+}
+
+// from sys.ts
+fn isFileSystemCaseSensitive() -> bool {
+    // win32\win64 are case insensitive platforms
+    if cfg!(target_os = "windows") {
+        return false;
+    }
+    // If this file exists under a different case, we must be case-insensitve.
+    // ! rb the running executable stands in for __filename
+    let Some(file_name) = std::env::current_exe().ok().and_then(|path| path.to_str().map(str::to_string)) else {
+        return true;
+    };
+    !Path::new(&swapCase(&file_name)).exists()
+}
+
+fn swapCase(s: &str) -> String { s.chars().map(|ch| if ch.is_ascii_uppercase() { ch.to_ascii_lowercase() } else { ch.to_ascii_uppercase() }).collect() }
+
+impl<'a> ParseConfigHost for RbTypeCheckerHost<'a> {
+    fn readDirectory(&self, rootDir: &str, extensions: &[&str], excludes: Option<&[String]>, includes: &[String], depth: Option<usize>) -> Vec<String> {
+        let realpath = |path: &str| self.realpath(path).map(|p| normalizeSlashes(&p)).unwrap_or_else(|| path.to_string());
+        matchFiles(rootDir, Some(extensions), excludes, Some(includes), self.useCaseSensitiveFileNames() == Some(true), &normalizeSlashes(&self.current_directory), depth, &getAccessibleFileSystemEntries, &realpath)
+    }
+}
+
+fn getAccessibleFileSystemEntries(path: &str) -> FileSystemEntries {
+    let Ok(entries) = fs::read_dir(if path.is_empty() { "." } else { path }) else {
+        return FileSystemEntries::default();
+    };
+    let mut files = vec![];
+    let mut directories = vec![];
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        // Symbolic links are followed so that linked files and directories are picked up
+        let Ok(metadata) = fs::metadata(entry.path()) else { continue };
+        if metadata.is_file() {
+            files.push(name);
+        } else if metadata.is_dir() {
+            directories.push(name);
+        }
+    }
+    files.sort();
+    directories.sort();
+    FileSystemEntries { files, directories }
+}
+
+impl<'a> FormatDiagnosticsHost for RbTypeCheckerHost<'a> {
+    fn getCurrentDirectory(&self) -> String { normalizeSlashes(&self.current_directory) }
+
+    fn getCanonicalFileName(&self, fileName: &str) -> String {
+        if self.useCaseSensitiveFileNames() == Some(true) {
+            fileName.to_string()
+        } else {
+            fileName.to_lowercase()
+        }
+    }
+
+    fn getNewLine(&self) -> String {
         #[cfg(target_os = "windows")]
-        return Some(false);
+        return "\r\n".to_string();
         #[cfg(not(target_os = "windows"))]
-        return Some(true);
+        return "\n".to_string();
     }
+
+    fn readDetachedDiagnosticFile(&self, fileName: &str) -> Option<String> { fs::read_to_string(fileName).ok() }
 }
 
 #[allow(unused_variables)]
 impl<'a> ModuleSpecifierResolutionHost for RbTypeCheckerHost<'a> {
-    fn getPackageJsonInfoCache(&self) -> Option<&dyn super::moduleNameResolver::PackageJsonInfoCache> { todo!() }
+    fn getPackageJsonInfoCache(&self) -> Option<rc_cell!(dyn PackageJsonInfoCache)> { Some(self.package_json_info_cache.clone()) }
 
-    fn getGlobalTypingsCacheLocation(&self) -> Option<String> { todo!() }
+    // tsc has no global typings cache; only language service hosts acquire typings
+    fn getGlobalTypingsCacheLocation(&self) -> Option<String> { None }
 
-    fn getNearestAncestorDirectoryWithPackageJson(&self, file_name: &str, root_dir: Option<&str>) -> Option<String> { todo!() }
+    fn getNearestAncestorDirectoryWithPackageJson(&self, file_name: &str, root_dir: Option<&str>) -> Option<String> {
+        let root_dir = root_dir.map(|root_dir| getNormalizedAbsolutePath(root_dir, Some(&self.current_directory)));
+        let mut directory = getDirectoryPath(&getNormalizedAbsolutePath(file_name, Some(&self.current_directory)));
+        loop {
+            if self.fileExists(&combinePaths(&directory, &[Some("package.json")])) {
+                return Some(directory);
+            }
+            // don't look above the root directory
+            if root_dir.as_deref() == Some(directory.as_str()) {
+                return None;
+            }
+            let parent_path = getDirectoryPath(&directory);
+            if parent_path == directory {
+                return None;
+            }
+            directory = parent_path;
+        }
+    }
 
-    fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String> { todo!() }
+    // project references are not supported, so nothing is ever redirected
+    fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String> { None }
 
-    fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool { todo!() }
+    fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool { false }
 
-    fn getCommonSourceDirectory(&self) -> String { todo!() }
+    fn getCommonSourceDirectory(&self) -> String {
+        let options = &self.compiler_options;
+        let mut common_source_directory = if let Some(root_dir) = &options.rootDir {
+            getNormalizedAbsolutePath(root_dir, Some(&self.current_directory))
+        } else if let (Some(true), Some(config_file_path)) = (options.composite, &options.configFilePath) {
+            getDirectoryPath(&normalizeSlashes(config_file_path))
+        } else {
+            let emitted_files: Vec<String> = self.source_files.borrow().iter().map(|file| file.borrow().filepath().to_string_lossy().to_string()).filter(|file_name| !isDeclarationFileName(file_name)).collect();
+            computeCommonSourceDirectoryOfFilenames(&emitted_files, &self.current_directory, |file_name| FormatDiagnosticsHost::getCanonicalFileName(self, file_name))
+        };
+        if !common_source_directory.is_empty() && !common_source_directory.ends_with(directorySeparator) {
+            common_source_directory.push_str(directorySeparator);
+        }
+        common_source_directory
+    }
 }
 
 #[allow(unused_variables)]
 impl<'a> TypeCheckerHost<'a> for RbTypeCheckerHost<'a> {
     fn getCompilerOptions(&self) -> Rc<CompilerOptions> { self.compiler_options.clone() }
 
-    fn getSourceFiles(&self) -> rc_cell!(Vec<rc_cell!(SourceFile<'a>)>) { self.source_files.clone() }
+    fn getSourceFiles(&self) -> rc_cell!(Vec<Rc<OwnedSourceFile<'a>>>) { self.source_files.clone() }
 
-    fn getSourceFile(&self, file_name: &str) -> Option<&SourceFile> { todo!() }
+    fn getSourceFile(&self, file_name: &str) -> Option<Rc<OwnedSourceFile<'a>>> {
+        let path = FormatDiagnosticsHost::getCanonicalFileName(self, &getNormalizedAbsolutePath(file_name, Some(&self.current_directory)));
+        self.source_files.borrow().iter().find(|source_file| FormatDiagnosticsHost::getCanonicalFileName(self, &getNormalizedAbsolutePath(&source_file.borrow().filepath().to_string_lossy(), Some(&self.current_directory))) == path).cloned()
+    }
 
-    fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String> { todo!() }
+    fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String> { None }
 
-    fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool { todo!() }
+    fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool { false }
 
-    fn typesPackageExists(&self, package_name: &str) -> bool { todo!() }
+    fn typesPackageExists(&self, package_name: &str) -> bool { rb_types_package_exists(self, &self.current_directory, package_name) }
+
+    fn packageBundlesTypes(&self, package_name: &str) -> bool { rb_package_bundles_types(self, &self.compiler_options, &self.current_directory, package_name) }
+}
 
-    fn packageBundlesTypes(&self, package_name: &str) -> bool { todo!() }
+/**
+ * Whether `@types/<package_name>` is installed in a node_modules directory of `directory` or one of its ancestors.
+ * Stands in for the program's map of resolved packages, which is not built.
+ */
+pub fn rb_types_package_exists(host: &dyn ModuleResolutionHost, directory: &str, package_name: &str) -> bool {
+    let types_package_name = getTypesPackageName(package_name);
+    let mut directory = directory.to_string();
+    loop {
+        if host.directoryExists(&combinePaths(&directory, &[Some("node_modules"), Some(&types_package_name)])) == Some(true) {
+            return true;
+        }
+        let parent_path = getDirectoryPath(&directory);
+        if parent_path == directory {
+            return false;
+        }
+        directory = parent_path;
+    }
+}
+
+/**
+ * Whether the nearest installation of `package_name` in the node_modules directories of `directory` and its ancestors declares its own typings.
+ */
+pub fn rb_package_bundles_types(host: &dyn ModuleResolutionHost, options: &CompilerOptions, directory: &str, package_name: &str) -> bool {
+    let mut state = getTemporaryModuleResolutionState(None, host, options);
+    let mut directory = directory.to_string();
+    loop {
+        let package_directory = combinePaths(&directory, &[Some("node_modules"), Some(package_name)]);
+        if let Some(package_json_info) = getPackageJsonInfo(&package_directory, /*onlyRecordFailures*/ false, &mut state) {
+            return readPackageJsonTypesFields(&package_json_info.contents.packageJsonContent, &package_directory, &state).is_some();
+        }
+        let parent_path = getDirectoryPath(&directory);
+        if parent_path == directory {
+            return false;
+        }
+        directory = parent_path;
+    }
 }
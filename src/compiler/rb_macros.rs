@@ -140,6 +140,12 @@ macro_rules! flow_node_enum {
                     $(Self::$variant(f) => f.id),*
                 }
             }
+
+            pub fn set_flags(&mut self, flags: FlowFlags) {
+                match self {
+                    $(Self::$variant(f) => f.flags = flags),*
+                }
+            }
         }
     };
 }
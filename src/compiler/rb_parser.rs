@@ -1,18 +1,410 @@
-use std::mem;
+use std::cell::{Ref, RefCell};
+use std::{fmt, mem};
 
-use oxc_ast::{ast::SourceFile, AstKind};
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::{Expression, ObjectPropertyKind, PropertyKey, PropertyKind, SourceFile, Statement, TSModuleDeclaration, TSModuleDeclarationBody, TSModuleDeclarationKind, UnaryOperator, VariableDeclarationKind},
+    AstKind,
+};
+use oxc_span::{GetSpan, Span};
 
-use crate::compiler::rb_extra::AstKindExt;
+use crate::compiler::diagnostic_information_map_generated::Diagnostics;
+use crate::compiler::parser::{createSourceFile, updateSourceFile};
+use crate::compiler::rb_extra::{remove_ASTKIND_INFO, remove_SOURCEFILE_INFO, AstKindExt, SourceFileExt};
+use crate::compiler::rb_unions::StringOrDiagnosticMessageChain;
+use crate::compiler::types::{Diagnostic, DiagnosticArguments, DiagnosticMessage, NodeFlags, ScriptKind, TextChangeRange};
+use crate::compiler::utilities::createDetachedDiagnostic;
 
 pub fn rb_fill_parents(source_file: &mut SourceFile) {
-    // todo(RB): fill in NodeFlags
-    fn dfs(node: AstKind) {
+    // Context flags are inherited from the enclosing nodes, the way the parser's contextFlags apply to every node it creates
+    let mut context_flags = NodeFlags::None;
+    match source_file.scriptKind() {
+        ScriptKind::JS | ScriptKind::JSX => context_flags |= NodeFlags::JavaScriptFile,
+        ScriptKind::JSON => context_flags |= NodeFlags::JavaScriptFile | NodeFlags::JsonFile,
+        _ => {}
+    }
+    if source_file.source_type.is_typescript_definition() {
+        context_flags |= NodeFlags::Ambient;
+    }
+
+    // Flags that the parser only ever sets on the source file itself
+    let mut source_flags = NodeFlags::None;
+
+    // `HasImplicitReturn`/`HasExplicitReturn` depend on control flow and are set by the binder
+    fn dfs(node: AstKind, parent: Option<AstKind>, context_flags: NodeFlags, source_flags: &mut NodeFlags) {
+        let mut flags = context_flags;
+        match node {
+            AstKind::VariableDeclarationList(decl) => {
+                flags |= match decl.kind {
+                    VariableDeclarationKind::Var => NodeFlags::None,
+                    VariableDeclarationKind::Let => NodeFlags::Let,
+                    VariableDeclarationKind::Const => NodeFlags::Const,
+                    VariableDeclarationKind::Using => NodeFlags::Using,
+                    VariableDeclarationKind::AwaitUsing => NodeFlags::AwaitUsing,
+                };
+                if decl.declare {
+                    flags |= NodeFlags::Ambient;
+                }
+            }
+            AstKind::TSModuleDeclaration(module) => {
+                match module.kind {
+                    TSModuleDeclarationKind::Global => flags |= NodeFlags::GlobalAugmentation,
+                    TSModuleDeclarationKind::Namespace => flags |= NodeFlags::Namespace,
+                    TSModuleDeclarationKind::Module => {}
+                }
+                // `namespace A.B {}` nests B inside A, and B inherits the keyword of A
+                if let Some(AstKind::TSModuleDeclaration(parent_module)) = parent {
+                    flags |= NodeFlags::NestedNamespace;
+                    if parent_module.kind == TSModuleDeclarationKind::Namespace {
+                        flags |= NodeFlags::Namespace;
+                    }
+                }
+                if module.declare {
+                    flags |= NodeFlags::Ambient;
+                }
+                // an ambient module without export declarations implicitly exports all of its declarations
+                if flags.intersects(NodeFlags::Ambient) && !rb_module_has_export_declarations(module) {
+                    flags |= NodeFlags::ExportContext;
+                }
+            }
+            AstKind::Function(func) if func.declare => flags |= NodeFlags::Ambient,
+            AstKind::Class(class) if class.declare => flags |= NodeFlags::Ambient,
+            AstKind::TSEnumDeclaration(decl) if decl.declare => flags |= NodeFlags::Ambient,
+            AstKind::TSInterfaceDeclaration(decl) if decl.declare => flags |= NodeFlags::Ambient,
+            AstKind::TSTypeAliasDeclaration(decl) if decl.declare => flags |= NodeFlags::Ambient,
+            AstKind::ImportExpression(_) => *source_flags |= NodeFlags::PossiblyContainsDynamicImport,
+            AstKind::MetaProperty(meta) if meta.meta.name == "import" && meta.property.name == "meta" => *source_flags |= NodeFlags::PossiblyContainsImportMeta,
+            _ => {}
+        }
+        node.set_flags(flags);
+
+        // the context the children of this node are parsed in
+        let mut child_context_flags = flags & NodeFlags::ContextFlags;
+        match node {
+            AstKind::Function(func) => {
+                child_context_flags &= !(NodeFlags::YieldContext | NodeFlags::AwaitContext);
+                if func.generator {
+                    child_context_flags |= NodeFlags::YieldContext;
+                }
+                if func.r#async {
+                    child_context_flags |= NodeFlags::AwaitContext;
+                }
+            }
+            AstKind::ArrowFunctionExpression(arrow) => {
+                child_context_flags &= !(NodeFlags::YieldContext | NodeFlags::AwaitContext);
+                if arrow.r#async {
+                    child_context_flags |= NodeFlags::AwaitContext;
+                }
+            }
+            AstKind::Decorator(_) => child_context_flags |= NodeFlags::DecoratorContext,
+            _ => {}
+        }
+
         let children = node.get_children();
         for child in children {
             child.set_parent(Some(unsafe { mem::transmute(node) }));
+            let mut child_flags = child_context_flags;
+            // only the statement of a `with` is in it, not the object expression
+            if let AstKind::WithStatement(with) = node {
+                if child.span().start >= with.body.span().start {
+                    child_flags |= NodeFlags::InWithStatement;
+                }
+            }
+            dfs(child, Some(node), child_flags, source_flags);
+        }
+    }
+
+    dfs(AstKind::SourceFile(source_file), None, context_flags, &mut source_flags);
+
+    let source_file_node = AstKind::SourceFile(source_file);
+    let mut flags = source_file_node.flags() | source_flags;
+    // a declaration file without export declarations implicitly exports all of its declarations
+    if flags.intersects(NodeFlags::Ambient) && !source_file.body.iter().any(rb_is_export_declaration) {
+        flags |= NodeFlags::ExportContext;
+    }
+    source_file_node.set_flags(flags);
+}
+
+// hasExportDeclarations
+fn rb_module_has_export_declarations(module: &TSModuleDeclaration) -> bool {
+    match &module.body {
+        Some(TSModuleDeclarationBody::TSModuleBlock(block)) => block.body.iter().any(rb_is_export_declaration),
+        _ => false,
+    }
+}
+
+// isExportDeclaration(s) || isExportAssignment(s); `export const x` is a declaration with an export modifier in TypeScript
+fn rb_is_export_declaration(statement: &Statement) -> bool {
+    match statement {
+        Statement::ExportNamedDeclaration(decl) => decl.declaration.is_none(),
+        Statement::ExportAllDeclaration(_) | Statement::ExportDefaultDeclaration(_) | Statement::TSExportAssignment(_) => true,
+        _ => false,
+    }
+}
+
+/**
+ * Removes the side-table entries (parents, flags, symbols, flow nodes, diagnostics...) of every node in `source_file`.
+ * Must be called before the arena of the file is dropped, so that a later tree reusing the memory starts out clean.
+ */
+pub fn rb_clear_node_info(source_file: &SourceFile) {
+    fn dfs(node: AstKind) {
+        for child in node.get_children() {
             dfs(child);
         }
+        remove_ASTKIND_INFO(node.get_node_id());
     }
 
     dfs(AstKind::SourceFile(source_file));
+    remove_SOURCEFILE_INFO(source_file.get_node_id());
+}
+
+/**
+ * Owns the memory of a single parsed file: the arena its AST is allocated in, and the name and text the AST borrows.
+ * Dropping the arena frees the whole file, so the `SourceFile` parsed from it must be dropped first.
+ */
+pub struct SourceFileArena {
+    allocator: Allocator,
+    file_name: Box<str>,
+    source_text: Box<str>,
+}
+
+impl SourceFileArena {
+    pub fn new(file_name: String, source_text: String) -> Self { Self { allocator: Allocator::default(), file_name: file_name.into_boxed_str(), source_text: source_text.into_boxed_str() } }
+
+    pub fn file_name(&self) -> &str { &self.file_name }
+
+    pub fn source_text(&self) -> &str { &self.source_text }
+
+    /// Parses the source text into this arena; the result borrows the arena and cannot outlive it.
+    /// The script kind is inferred from the file name unless one is given.
+    pub fn parse(&self, script_kind: Option<ScriptKind>) -> SourceFile<'_> { createSourceFile(&self.allocator, &self.file_name, &self.source_text, script_kind) }
+
+    /// Parses the source text into this arena as the result of applying `text_change_range` to `old_source_file`.
+    pub fn update(&self, old_source_file: &SourceFile, text_change_range: &TextChangeRange) -> SourceFile<'_> { updateSourceFile(&self.allocator, old_source_file, &self.file_name, &self.source_text, text_change_range, false) }
+}
+
+impl fmt::Debug for SourceFileArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.debug_struct("SourceFileArena").field("file_name", &self.file_name).field("source_text_len", &self.source_text.len()).finish() }
+}
+
+/**
+ * A parsed file together with the arena its tree is allocated in, so that the memory of a file is freed with it.
+ * Hosts hand out `Rc`s of it: a file that is replaced or removed is freed once the last of them is dropped.
+ * The tree borrows the arena for `'a`, which the borrow checker cannot tie to the handle, so nothing borrowed from the tree,
+ * such as the declarations a checker merged into its globals, may be used once the last handle is gone.
+ */
+pub struct OwnedSourceFile<'a> {
+    // declared before the arena, so that the tree is dropped before the memory it borrows
+    source_file: RefCell<SourceFile<'a>>,
+    arena: Box<SourceFileArena>,
+}
+
+impl<'a> OwnedSourceFile<'a> {
+    /// Moves `arena` into the handle and builds the tree in it with `parse`.
+    pub fn new(arena: SourceFileArena, parse: impl FnOnce(&'a SourceFileArena) -> SourceFile<'a>) -> Self {
+        let arena = Box::new(arena);
+        // Safety: the arena is boxed, so its address is unaffected by moves of the handle, and it is never borrowed mutably.
+        // It is only dropped with the handle, after the tree, which is only reachable through the handle.
+        let source_file = parse(unsafe { &*(&*arena as *const SourceFileArena) });
+        Self { source_file: RefCell::new(source_file), arena }
+    }
+
+    pub fn arena(&self) -> &SourceFileArena { &self.arena }
+
+    pub fn borrow(&self) -> Ref<'_, SourceFile<'a>> { self.source_file.borrow() }
+
+    pub fn as_ptr(&self) -> *mut SourceFile<'a> { self.source_file.as_ptr() }
+}
+
+impl fmt::Debug for OwnedSourceFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.debug_struct("OwnedSourceFile").field("arena", &self.arena).finish() }
+}
+
+/**
+ * Converts the errors oxc reported while parsing `file_name` into parse diagnostics, detached from the source file
+ * (the hosts attach them once it has a stable address). Each error is a message with the span of its first label.
+ */
+pub fn rb_parse_diagnostics<'a>(file_name: &str, source_text: &str, errors: impl IntoIterator<Item = (String, Option<(u32, u32)>)>, panicked: bool) -> Vec<Diagnostic<'a>> {
+    let mut diagnostics: Vec<Diagnostic<'a>> = errors
+        .into_iter()
+        .map(|(message, span)| {
+            // errors without a location are reported at the end of the file, like unexpected ends of input
+            let (start, length) = span.map(|(start, length)| (start.min(source_text.len() as u32), length)).unwrap_or((source_text.len() as u32, 0));
+            match rb_parse_error_message(&message) {
+                Some((diagnostic_message, args)) => createDetachedDiagnostic(file_name, source_text, start, length, diagnostic_message, args),
+                None => {
+                    // no TypeScript counterpart, so keep oxc's wording under the generic syntax error code
+                    let mut diagnostic = createDetachedDiagnostic(file_name, source_text, start, length, Diagnostics::Unexpected_token(), vec![]);
+                    diagnostic.messageText = StringOrDiagnosticMessageChain::String(message);
+                    diagnostic
+                }
+            }
+        })
+        .collect();
+    // a panicking parser always reports why, but make sure a file with an empty AST never passes for a clean parse
+    if panicked && diagnostics.is_empty() {
+        diagnostics.push(createDetachedDiagnostic(file_name, source_text, source_text.len() as u32, 0, Diagnostics::Unexpected_end_of_text(), vec![]));
+    }
+    diagnostics
+}
+
+/**
+ * Reports the syntax a JSON file cannot contain, which oxc accepts because the value is parsed as a JavaScript expression.
+ * The checks and messages are the ones convertToJson applies to config files: property names and strings must be
+ * double quoted, and every value must be a string, number, `true`, `false`, `null`, object or array.
+ */
+pub fn rb_json_syntax_diagnostics<'a>(file_name: &str, source_file: &SourceFile) -> Vec<Diagnostic<'a>> {
+    let source_text = source_file.source_text;
+    let mut diagnostics = vec![];
+    let mut report = |span: Span, message: DiagnosticMessage| diagnostics.push(createDetachedDiagnostic(file_name, source_text, span.start, span.size(), message, vec![]));
+    for statement in &source_file.body {
+        if let Statement::ExpressionStatement(statement) = statement {
+            rb_check_json_value(source_text, &statement.expression, &mut report);
+        }
+    }
+    diagnostics
+}
+
+fn rb_check_json_value(source_text: &str, expression: &Expression, report: &mut impl FnMut(Span, DiagnosticMessage)) {
+    let is_double_quoted = |span: Span| source_text.as_bytes().get(span.start as usize) == Some(&b'"');
+    match expression {
+        Expression::BooleanLiteral(_) | Expression::NullLiteral(_) | Expression::NumericLiteral(_) => {}
+        Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::UnaryNegation && matches!(unary.argument, Expression::NumericLiteral(_)) => {}
+        Expression::StringLiteral(literal) => {
+            if !is_double_quoted(literal.span) {
+                report(literal.span, Diagnostics::String_literal_with_double_quotes_expected());
+            }
+        }
+        Expression::ObjectExpression(object) => {
+            for property in &object.properties {
+                let ObjectPropertyKind::ObjectProperty(property) = property else {
+                    report(property.span(), Diagnostics::Property_assignment_expected());
+                    continue;
+                };
+                if property.kind != PropertyKind::Init || property.method || property.shorthand {
+                    report(property.span, Diagnostics::Property_assignment_expected());
+                    continue;
+                }
+                if property.computed || !matches!(&property.key, PropertyKey::StringLiteral(key) if is_double_quoted(key.span)) {
+                    report(property.key.span(), Diagnostics::String_literal_with_double_quotes_expected());
+                }
+                rb_check_json_value(source_text, &property.value, report);
+            }
+        }
+        Expression::ArrayExpression(array) => {
+            for element in &array.elements {
+                match element.as_expression() {
+                    Some(element) => rb_check_json_value(source_text, element, report),
+                    // spreads and holes
+                    None => report(element.span(), Diagnostics::Property_value_can_only_be_string_literal_numeric_literal_true_false_null_object_literal_or_array_literal()),
+                }
+            }
+        }
+        _ => report(expression.span(), Diagnostics::Property_value_can_only_be_string_literal_numeric_literal_true_false_null_object_literal_or_array_literal()),
+    }
+}
+
+/// Maps an oxc parser error message onto the closest TypeScript diagnostic message.
+fn rb_parse_error_message(message: &str) -> Option<(DiagnosticMessage, DiagnosticArguments)> {
+    // "Expected `{expected}` but found `{found}`"
+    if let Some((expected, _)) = message.strip_prefix("Expected `").and_then(|rest| rest.split_once("` but found")) {
+        return Some((Diagnostics::_0_expected(), vec![expected.to_string().into()]));
+    }
+    if message.starts_with("Expected a semicolon") {
+        return Some((Diagnostics::_0_expected(), vec![";".to_string().into()]));
+    }
+    if message.starts_with("Invalid Character") {
+        return Some((Diagnostics::Invalid_character(), vec![]));
+    }
+    if message.starts_with("Identifier expected") || message == "Expected function name" {
+        return Some((Diagnostics::Identifier_expected(), vec![]));
+    }
+    if message.ends_with("may not have a trailing comma.") {
+        return Some((Diagnostics::Trailing_comma_not_allowed(), vec![]));
+    }
+    let diagnostic_message = match message {
+        "Unexpected token" => Diagnostics::Unexpected_token(),
+        "Unexpected end of file" => Diagnostics::Unexpected_end_of_text(),
+        "Unterminated string" => Diagnostics::Unterminated_string_literal(),
+        "Unterminated template" => Diagnostics::Unterminated_template_literal(),
+        "Unterminated regular expression" => Diagnostics::Unterminated_regular_expression_literal(),
+        "Unterminated multiline comment" => Diagnostics::asterisk_slash_expected(),
+        "Invalid characters after number" => Diagnostics::An_identifier_or_keyword_cannot_immediately_follow_a_numeric_literal(),
+        "Expected function body" => return Some((Diagnostics::_0_expected(), vec!["{".to_string().into()])),
+        "Expected switch clause" => Diagnostics::case_or_default_expected(),
+        "Missing catch or finally clause" => Diagnostics::catch_or_finally_expected(),
+        "Missing initializer in const declaration" => return Some((Diagnostics::_0_declarations_must_be_initialized(), vec!["const".to_string().into()])),
+        "Using declarations must have an initializer." => return Some((Diagnostics::_0_declarations_must_be_initialized(), vec!["using".to_string().into()])),
+        "A rest parameter must be last in a parameter list" => Diagnostics::A_rest_parameter_must_be_last_in_a_parameter_list(),
+        "A rest element must be last in a destructuring pattern" => Diagnostics::A_rest_element_must_be_last_in_a_destructuring_pattern(),
+        "A 'get' accessor must not have any formal parameters." => Diagnostics::A_get_accessor_cannot_have_parameters(),
+        "A 'set' accessor must have exactly one parameter." => Diagnostics::A_set_accessor_must_have_exactly_one_parameter(),
+        "A 'yield' expression is only allowed in a generator body." => Diagnostics::A_yield_expression_is_only_allowed_in_a_generator_body(),
+        "Cannot assign to this expression" => Diagnostics::The_left_hand_side_of_an_assignment_expression_must_be_a_variable_or_a_property_access(),
+        _ => return None,
+    };
+    Some((diagnostic_message, vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rb_parse_diagnostics, rb_parse_error_message};
+    use crate::compiler::rb_unions::StringOrDiagnosticMessageChain;
+    use crate::compiler::utilities::formatMessage;
+
+    fn mapped(message: &str) -> Option<String> { rb_parse_error_message(message).map(|(message, args)| formatMessage(&message, &args)) }
+
+    #[test]
+    fn maps_oxc_messages_to_typescript_messages() {
+        let cases = [
+            ("Expected `;` but found `}`", "';' expected."),
+            ("Expected a semicolon or an implicit semicolon after a statement, but found none", "';' expected."),
+            ("Invalid Character `@`", "Invalid character."),
+            ("Identifier expected. 'class' is a reserved word that cannot be used here.", "Identifier expected."),
+            ("Expected function name", "Identifier expected."),
+            ("A rest parameter or binding pattern may not have a trailing comma.", "Trailing comma not allowed."),
+            ("Unexpected token", "Unexpected token."),
+            ("Unexpected end of file", "Unexpected end of text."),
+            ("Unterminated string", "Unterminated string literal."),
+            ("Unterminated template", "Unterminated template literal."),
+            ("Unterminated regular expression", "Unterminated regular expression literal."),
+            ("Unterminated multiline comment", "'*/' expected."),
+            ("Invalid characters after number", "An identifier or keyword cannot immediately follow a numeric literal."),
+            ("Expected function body", "'{' expected."),
+            ("Expected switch clause", "'case' or 'default' expected."),
+            ("Missing catch or finally clause", "'catch' or 'finally' expected."),
+            ("Missing initializer in const declaration", "'const' declarations must be initialized."),
+            ("Using declarations must have an initializer.", "'using' declarations must be initialized."),
+            ("A rest parameter must be last in a parameter list", "A rest parameter must be last in a parameter list."),
+            ("A rest element must be last in a destructuring pattern", "A rest element must be last in a destructuring pattern."),
+            ("A 'get' accessor must not have any formal parameters.", "A 'get' accessor cannot have parameters."),
+            ("A 'set' accessor must have exactly one parameter.", "A 'set' accessor must have exactly one parameter."),
+            ("A 'yield' expression is only allowed in a generator body.", "A 'yield' expression is only allowed in a generator body."),
+            ("Cannot assign to this expression", "The left-hand side of an assignment expression must be a variable or a property access."),
+        ];
+        for (oxc_message, expected) in cases {
+            assert_eq!(mapped(oxc_message).as_deref(), Some(expected), "{oxc_message}");
+        }
+        assert_eq!(mapped("Lexical declaration cannot appear in a single-statement context"), None);
+    }
+
+    #[test]
+    fn keeps_the_wording_of_unmapped_messages_under_the_generic_code() {
+        let errors = vec![("Lexical declaration cannot appear in a single-statement context".to_string(), Some((8, 1))), ("Unexpected end of file".to_string(), None)];
+        let diagnostics = rb_parse_diagnostics("/a.ts", "let x = ;", errors, /*panicked*/ false);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!((diagnostics[0].code, diagnostics[0].start, diagnostics[0].length), (1012, Some(8), Some(1)));
+        assert!(matches!(&diagnostics[0].messageText, StringOrDiagnosticMessageChain::String(text) if text == "Lexical declaration cannot appear in a single-statement context"));
+        // errors without a span are reported at the end of the file
+        assert_eq!((diagnostics[1].code, diagnostics[1].start, diagnostics[1].length), (1126, Some(9), Some(0)));
+    }
+
+    #[test]
+    fn reports_a_panicked_parse_that_gave_no_reason() {
+        let no_errors: Vec<(String, Option<(u32, u32)>)> = Vec::new();
+        let diagnostics = rb_parse_diagnostics("/a.ts", "class", no_errors.clone(), /*panicked*/ true);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].code, diagnostics[0].start, diagnostics[0].length), (1126, Some(5), Some(0)));
+        assert!(rb_parse_diagnostics("/a.ts", "class", no_errors, /*panicked*/ false).is_empty());
+    }
 }
@@ -7,6 +7,7 @@ use oxc_ast::{
 
 use crate::define_subset_enum;
 
+use super::core::Pattern;
 use super::types::{DeclarationName, DiagnosticMessageChain, EntityNameExpression, Identifier, IsContainer, PropertyNameLiteral};
 
 define_subset_enum!(IsContainerOrEntityNameExpression from AstKind {
@@ -35,6 +36,12 @@ impl Display for StringOrNumber {
         }
     }
 }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringOrPattern {
+    String(String),
+    Pattern(Pattern),
+}
+
 #[derive(Debug, Clone)]
 pub enum StringOrDiagnosticMessageChain {
     String(String),
@@ -0,0 +1,349 @@
+use oxc_ast::ast::SourceFile;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::{new_rc_cell, rc_cell};
+
+use super::corePublic;
+use super::diagnostic_information_map_generated::Diagnostics;
+use super::moduleNameResolver::{createPackageJsonInfoCache, PackageJsonInfoCache, PackageJsonInfoCacheMap};
+use super::path::{combinePaths, directorySeparator, ensureTrailingDirectorySeparator, getDirectoryPath, getNormalizedAbsolutePath, normalizeSlashes};
+use super::program::{computeCommonSourceDirectoryOfFilenames, FormatDiagnosticsHost};
+use super::rb_extra::SourceFileExt;
+use super::rb_host::{rb_package_bundles_types, rb_types_package_exists};
+use super::rb_parser::{rb_clear_node_info, OwnedSourceFile, SourceFileArena};
+use super::types::{CompilerOptions, Diagnostic, ModuleResolutionHost, ModuleSpecifierResolutionHost, ParseConfigHost, TextChangeRange, TypeCheckerHost};
+use super::utilities::{attachFileToDiagnostics, createCompilerDiagnostic, isDeclarationFileName, matchFiles, FileSystemEntries};
+use super::utilitiesPublic::textChangeRangeIsUnchanged;
+
+/**
+ * A host that serves files from an in-memory map of absolute path to contents instead of the disk.
+ * Directories exist implicitly as the ancestors of the files that were written.
+ */
+#[derive(Debug, Clone)]
+pub struct VirtualFileSystemHost<'a> {
+    pub current_directory: String,
+    pub compiler_options: Rc<CompilerOptions>,
+    pub source_files: rc_cell!(Vec<Rc<OwnedSourceFile<'a>>>),
+    pub package_json_info_cache: rc_cell!(PackageJsonInfoCacheMap),
+    pub use_case_sensitive_file_names: bool,
+    pub traces: rc_cell!(Vec<String>),
+    /** The compiler version that package.json `typesVersions` are matched against, `version` unless overridden. */
+    pub typescript_version: String,
+
+    files: BTreeMap<String, VirtualFile>, // keyed by canonical path
+}
+
+#[derive(Debug, Clone)]
+struct VirtualFile {
+    path: String, // the path as written, before canonicalization
+    contents: String,
+}
+
+impl<'a> VirtualFileSystemHost<'a> {
+    pub fn new(current_directory: String, compiler_options: Rc<CompilerOptions>, use_case_sensitive_file_names: bool) -> Self {
+        let current_directory = normalizeSlashes(&current_directory);
+        let package_json_info_cache = new_rc_cell!(createPackageJsonInfoCache(&current_directory, use_case_sensitive_file_names));
+        Self { current_directory, compiler_options, source_files: new_rc_cell!(vec![]), package_json_info_cache, use_case_sensitive_file_names, traces: new_rc_cell!(vec![]), typescript_version: corePublic::version.to_string(), files: BTreeMap::new() }
+    }
+
+    /// Creates or overwrites the file at `file_name`, which is resolved against the current directory.
+    pub fn writeFile(&mut self, file_name: &str, contents: &str) {
+        let path = self.toAbsolutePath(file_name);
+        self.files.insert(self.toCanonicalPath(&path), VirtualFile { path, contents: contents.to_string() });
+        self.package_json_info_cache.borrow_mut().clear();
+    }
+
+    pub fn deleteFile(&mut self, file_name: &str) -> bool {
+        let removed = self.files.remove(&self.toCanonicalPath(&self.toAbsolutePath(file_name))).is_some();
+        self.package_json_info_cache.borrow_mut().clear();
+        removed
+    }
+
+    /// Parses the in-memory file `name`, or returns a `File_0_not_found` diagnostic when no such file was written.
+    pub fn addSourceFile(&mut self, name: String) -> Result<Rc<OwnedSourceFile<'a>>, Diagnostic<'static>> {
+        let Some(source_text) = self.readFile(&name) else {
+            return Err(createCompilerDiagnostic(Diagnostics::File_0_not_found(), vec![name.into()]));
+        };
+        Ok(self.parseSourceFile(self.toAbsolutePath(&name), source_text))
+    }
+
+    /// Parses `source_text` into a fresh arena, replacing any file previously parsed under the same name.
+    pub fn parseSourceFile(&mut self, name: String, source_text: String) -> Rc<OwnedSourceFile<'a>> { self.insertSourceFile(SourceFileArena::new(name, source_text), |arena| arena.parse(None)) }
+
+    /// Reparses `source_file` after an edit that replaced `text_change_range` of its text, giving `new_text`.
+    /// `source_file` is unlinked and loses its binder results, and its tree is freed once the last handle to it is dropped; the other files keep theirs.
+    pub fn updateSourceFile(&mut self, source_file: &Rc<OwnedSourceFile<'a>>, new_text: String, text_change_range: TextChangeRange) -> Rc<OwnedSourceFile<'a>> {
+        if textChangeRangeIsUnchanged(&text_change_range) && source_file.borrow().source_text == new_text {
+            return source_file.clone();
+        }
+        let old_source_file = source_file.borrow();
+        let name = old_source_file.filepath().to_string_lossy().to_string();
+        self.insertSourceFile(SourceFileArena::new(name, new_text), |arena| arena.update(&old_source_file, &text_change_range))
+    }
+
+    fn insertSourceFile(&mut self, arena: SourceFileArena, parse: impl FnOnce(&'a SourceFileArena) -> SourceFile<'a>) -> Rc<OwnedSourceFile<'a>> {
+        let source_file = Rc::new(OwnedSourceFile::new(arena, parse));
+        // the parse diagnostics can only point at the source file now that it has its final address
+        // Safety: source files are never borrowed mutably once inserted, and the diagnostics are only reachable through
+        // the file's own side-table entry, so they are never read once the file has been dropped
+        let file = unsafe { &*source_file.as_ptr() };
+        let parse_diagnostics = file.parseDiagnostics();
+        let attached = attachFileToDiagnostics(&parse_diagnostics.borrow(), file);
+        *parse_diagnostics.borrow_mut() = attached;
+        // the old tree is only unlinked now, as the new one may have been parsed from it
+        self.removeSourceFile(source_file.arena().file_name());
+        self.source_files.borrow_mut().push(source_file.clone());
+        source_file
+    }
+
+    /// Unlinks the source file parsed from `name` and drops its side-table entries. Returns whether such a file was loaded.
+    /// The file is freed with the host's handle unless the caller still holds one, which stays valid but is no longer bound or returned by getSourceFile.
+    pub fn removeSourceFile(&mut self, name: &str) -> bool {
+        let path = self.toCanonicalPath(&self.toAbsolutePath(name));
+        let mut removed = false;
+        self.source_files.borrow_mut().retain(|source_file| {
+            let source_file = source_file.borrow();
+            if self.toCanonicalPath(&source_file.filepath().to_string_lossy()) != path {
+                return true;
+            }
+            rb_clear_node_info(&source_file);
+            removed = true;
+            false
+        });
+        removed
+    }
+
+    fn toAbsolutePath(&self, file_name: &str) -> String { getNormalizedAbsolutePath(file_name, Some(&self.current_directory)) }
+
+    fn toCanonicalPath(&self, path: &str) -> String {
+        if self.use_case_sensitive_file_names {
+            path.to_string()
+        } else {
+            path.to_lowercase()
+        }
+    }
+
+    fn getFile(&self, file_name: &str) -> Option<&VirtualFile> { self.files.get(&self.toCanonicalPath(&self.toAbsolutePath(file_name))) }
+
+    fn getAccessibleFileSystemEntries(&self, path: &str) -> FileSystemEntries {
+        let directory = ensureTrailingDirectorySeparator(&self.toCanonicalPath(&self.toAbsolutePath(path)));
+        // canonicalization can change the byte length of a path but not its separators, so the written path is split at the same depth
+        let depth = directory.matches(directorySeparator).count();
+        let mut files = vec![];
+        let mut directories: Vec<String> = vec![];
+        for (canonical_path, file) in self.files.range(directory.clone()..) {
+            if !canonical_path.starts_with(&directory) {
+                break;
+            }
+            // report names with the casing they were written with
+            let Some(relative_path) = file.path.splitn(depth + 1, directorySeparator).nth(depth) else {
+                continue;
+            };
+            match relative_path.split_once(directorySeparator) {
+                None => files.push(relative_path.to_string()),
+                Some((name, _)) => {
+                    if directories.last().map(String::as_str) != Some(name) {
+                        directories.push(name.to_string());
+                    }
+                }
+            }
+        }
+        FileSystemEntries { files, directories }
+    }
+}
+
+impl<'a> ModuleResolutionHost for VirtualFileSystemHost<'a> {
+    fn fileExists(&self, fileName: &str) -> bool { self.getFile(fileName).is_some() }
+
+    fn readFile(&self, fileName: &str) -> Option<String> { self.getFile(fileName).map(|file| file.contents.clone()) }
+
+    fn trace(&self, s: &str) -> Option<()> {
+        self.traces.borrow_mut().push(s.to_string());
+        Some(())
+    }
+
+    fn directoryExists(&self, directoryName: &str) -> Option<bool> {
+        let directory = ensureTrailingDirectorySeparator(&self.toCanonicalPath(&self.toAbsolutePath(directoryName)));
+        Some(self.files.range(directory.clone()..).next().is_some_and(|(canonical_path, _)| canonical_path.starts_with(&directory)))
+    }
+
+    fn realpath(&self, path: &str) -> Option<String> { Some(self.toAbsolutePath(path)) }
+
+    fn getCurrentDirectory(&self) -> Option<String> { Some(self.current_directory.clone()) }
+
+    fn getDirectories(&self, path: &str) -> Option<Vec<String>> { Some(self.getAccessibleFileSystemEntries(path).directories) }
+
+    fn useCaseSensitiveFileNames(&self) -> Option<bool> { Some(self.use_case_sensitive_file_names) }
+
+    fn getTypeScriptVersion(&self) -> String { self.typescript_version.clone() }
+}
+
+impl<'a> ParseConfigHost for VirtualFileSystemHost<'a> {
+    fn readDirectory(&self, rootDir: &str, extensions: &[&str], excludes: Option<&[String]>, includes: &[String], depth: Option<usize>) -> Vec<String> {
+        let realpath = |path: &str| self.toAbsolutePath(path);
+        let getFileSystemEntries = |path: &str| self.getAccessibleFileSystemEntries(path);
+        matchFiles(rootDir, Some(extensions), excludes, Some(includes), self.use_case_sensitive_file_names, &self.current_directory, depth, &getFileSystemEntries, &realpath)
+    }
+}
+
+impl<'a> FormatDiagnosticsHost for VirtualFileSystemHost<'a> {
+    fn getCurrentDirectory(&self) -> String { self.current_directory.clone() }
+
+    fn getCanonicalFileName(&self, fileName: &str) -> String { self.toCanonicalPath(fileName) }
+
+    fn getNewLine(&self) -> String { "\n".to_string() }
+
+    fn readDetachedDiagnosticFile(&self, fileName: &str) -> Option<String> { self.readFile(fileName) }
+}
+
+#[allow(unused_variables)]
+impl<'a> ModuleSpecifierResolutionHost for VirtualFileSystemHost<'a> {
+    fn getPackageJsonInfoCache(&self) -> Option<rc_cell!(dyn PackageJsonInfoCache)> { Some(self.package_json_info_cache.clone()) }
+
+    fn getGlobalTypingsCacheLocation(&self) -> Option<String> { None }
+
+    fn getNearestAncestorDirectoryWithPackageJson(&self, file_name: &str, root_dir: Option<&str>) -> Option<String> {
+        let root_dir = root_dir.map(|root_dir| self.toCanonicalPath(&self.toAbsolutePath(root_dir)));
+        let mut directory = getDirectoryPath(&self.toAbsolutePath(file_name));
+        loop {
+            if self.fileExists(&combinePaths(&directory, &[Some("package.json")])) {
+                return Some(directory);
+            }
+            // don't look above the root directory
+            if root_dir.as_deref() == Some(self.toCanonicalPath(&directory).as_str()) {
+                return None;
+            }
+            let parent_path = getDirectoryPath(&directory);
+            if parent_path == directory {
+                return None;
+            }
+            directory = parent_path;
+        }
+    }
+
+    fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String> { None }
+
+    fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool { false }
+
+    fn getCommonSourceDirectory(&self) -> String {
+        let options = &self.compiler_options;
+        let mut common_source_directory = if let Some(root_dir) = &options.rootDir {
+            self.toAbsolutePath(root_dir)
+        } else if let (Some(true), Some(config_file_path)) = (options.composite, &options.configFilePath) {
+            getDirectoryPath(&normalizeSlashes(config_file_path))
+        } else {
+            let emitted_files: Vec<String> = self.source_files.borrow().iter().map(|file| file.borrow().filepath().to_string_lossy().to_string()).filter(|file_name| !isDeclarationFileName(file_name)).collect();
+            computeCommonSourceDirectoryOfFilenames(&emitted_files, &self.current_directory, |file_name| self.toCanonicalPath(file_name))
+        };
+        if !common_source_directory.is_empty() && !common_source_directory.ends_with(directorySeparator) {
+            common_source_directory.push_str(directorySeparator);
+        }
+        common_source_directory
+    }
+}
+
+#[allow(unused_variables)]
+impl<'a> TypeCheckerHost<'a> for VirtualFileSystemHost<'a> {
+    fn getCompilerOptions(&self) -> Rc<CompilerOptions> { self.compiler_options.clone() }
+
+    fn getSourceFiles(&self) -> rc_cell!(Vec<Rc<OwnedSourceFile<'a>>>) { self.source_files.clone() }
+
+    fn getSourceFile(&self, file_name: &str) -> Option<Rc<OwnedSourceFile<'a>>> {
+        let path = self.toCanonicalPath(&self.toAbsolutePath(file_name));
+        self.source_files.borrow().iter().find(|source_file| self.toCanonicalPath(&source_file.borrow().filepath().to_string_lossy()) == path).cloned()
+    }
+
+    fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String> { None }
+
+    fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool { false }
+
+    fn typesPackageExists(&self, package_name: &str) -> bool { rb_types_package_exists(self, &self.current_directory, package_name) }
+
+    fn packageBundlesTypes(&self, package_name: &str) -> bool { rb_package_bundles_types(self, &self.compiler_options, &self.current_directory, package_name) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::VirtualFileSystemHost;
+    use crate::compiler::moduleNameResolver::resolveModuleName;
+    use crate::compiler::types::{CompilerOptions, ModuleResolutionHost, ModuleResolutionKind, ResolutionMode, TypeCheckerHost};
+    use crate::compiler::utilitiesPublic::{createTextChangeRange, createTextSpan};
+
+    fn host(use_case_sensitive_file_names: bool) -> VirtualFileSystemHost<'static> {
+        let compiler_options = CompilerOptions { moduleResolution: Some(ModuleResolutionKind::Node10), ..Default::default() };
+        VirtualFileSystemHost::new("/proj".to_string(), Rc::new(compiler_options), use_case_sensitive_file_names)
+    }
+
+    #[test]
+    fn looks_up_files_by_canonical_path() {
+        let mut insensitive = host(false);
+        insensitive.writeFile("Src/Index.ts", "export {};");
+        assert!(insensitive.fileExists("/proj/src/index.ts"));
+        assert_eq!(insensitive.readFile("/PROJ/SRC/INDEX.TS").as_deref(), Some("export {};"));
+        assert_eq!(insensitive.directoryExists("/proj/src"), Some(true));
+
+        let mut sensitive = host(true);
+        sensitive.writeFile("Src/Index.ts", "export {};");
+        assert!(sensitive.fileExists("/proj/Src/Index.ts"));
+        assert!(!sensitive.fileExists("/proj/src/index.ts"));
+        assert!(sensitive.deleteFile("/proj/Src/Index.ts"));
+        assert_eq!(sensitive.directoryExists("/proj/Src"), Some(false));
+    }
+
+    #[test]
+    fn lists_directories_with_their_written_casing() {
+        let mut host = host(false);
+        // 'İ' lowercases to a longer string, so the canonical and written paths differ in length
+        host.writeFile("/proj/İdir/File.ts", "");
+        host.writeFile("/proj/İdir/Sub/a.ts", "");
+        host.writeFile("/proj/İdir/Sub/b.ts", "");
+        host.writeFile("/proj/other.ts", "");
+
+        let entries = host.getAccessibleFileSystemEntries("/proj/idir");
+        assert!(entries.files.is_empty() && entries.directories.is_empty());
+
+        let entries = host.getAccessibleFileSystemEntries("/proj/İdir");
+        assert_eq!(entries.files, vec!["File.ts".to_string()]);
+        assert_eq!(entries.directories, vec!["Sub".to_string()]);
+        assert_eq!(host.getDirectories("/proj"), Some(vec!["İdir".to_string()]));
+    }
+
+    #[test]
+    fn resolves_modules_through_the_virtual_file_system() {
+        let mut host = host(true);
+        host.writeFile("/proj/src/a.ts", "import { b } from './b'; import pkg from 'pkg';");
+        host.writeFile("/proj/src/b.ts", "export const b = 1;");
+        host.writeFile("/proj/node_modules/pkg/package.json", r#"{ "name": "pkg", "version": "1.0.0", "types": "lib/index.d.ts" }"#);
+        host.writeFile("/proj/node_modules/pkg/lib/index.d.ts", "export default 0;");
+        let compiler_options = host.compiler_options.clone();
+
+        let relative = resolveModuleName("./b", "/proj/src/a.ts", &compiler_options, &host, None, ResolutionMode::Undefined);
+        let resolved = relative.resolvedModule.expect("./b should resolve");
+        assert_eq!(resolved.resolvedFileName, "/proj/src/b.ts");
+        assert_eq!(resolved.isExternalLibraryImport, Some(false));
+
+        let package = resolveModuleName("pkg", "/proj/src/a.ts", &compiler_options, &host, None, ResolutionMode::Undefined);
+        let resolved = package.resolvedModule.expect("pkg should resolve");
+        assert_eq!(resolved.resolvedFileName, "/proj/node_modules/pkg/lib/index.d.ts");
+        assert_eq!(resolved.isExternalLibraryImport, Some(true));
+
+        let missing = resolveModuleName("./missing", "/proj/src/a.ts", &compiler_options, &host, None, ResolutionMode::Undefined);
+        assert!(missing.resolvedModule.is_none());
+        assert!(missing.failedLookupLocations.is_some_and(|locations| locations.iter().any(|location| location == "/proj/src/missing.ts")));
+    }
+
+    #[test]
+    fn reparses_files_edited_with_a_stale_change_range() {
+        let mut host = host(true);
+        let source_file = host.parseSourceFile("/proj/a.ts".to_string(), "const é = 1;".to_string());
+        // the range is shorter than the edit and ends inside 'é', neither of which may panic
+        let updated = host.updateSourceFile(&source_file, "const é = 12;".to_string(), createTextChangeRange(createTextSpan(6, 1), 1));
+        assert_eq!(updated.borrow().source_text, "const é = 12;");
+        let updated = host.updateSourceFile(&updated, "let é = 12;".to_string(), createTextChangeRange(createTextSpan(0, 40), 3));
+        assert_eq!(updated.borrow().source_text, "let é = 12;");
+        assert_eq!(host.getSourceFiles().borrow().len(), 1);
+    }
+}
@@ -1,5 +1,146 @@
 use super::{diagnostic_information_map_generated::Diagnostics, types::{CharacterCodes, DiagnosticMessage}, utilities::positionIsSynthesized};
 use lazy_static::lazy_static;
+use oxc_ast::ast::SourceFile;
+use std::rc::Rc;
+
+use super::rb_extra::SourceFileExt;
+use super::types::{CommentKind, CommentRange, LineAndCharacter};
+
+// region: 430
+/**
+ * @internal
+ * Line starts are UTF-8 byte offsets, like the positions in oxc spans.
+ */
+pub fn computeLineStarts(text: &str) -> Vec<usize> {
+    let mut result: Vec<usize> = Vec::new();
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    let mut line_start = 0;
+    while pos < bytes.len() {
+        let ch = bytes[pos];
+        pos += 1;
+        match ch {
+            b'\r' => {
+                if pos < bytes.len() && bytes[pos] == b'\n' {
+                    pos += 1;
+                }
+                result.push(line_start);
+                line_start = pos;
+            }
+            b'\n' => {
+                result.push(line_start);
+                line_start = pos;
+            }
+            // the lead byte of a non-ASCII character, which may be a line or paragraph separator
+            _ if ch >= 0xC0 => {
+                let ch = text[pos - 1..].chars().next().unwrap();
+                if isLineBreak(ch) {
+                    pos += ch.len_utf8() - 1;
+                    result.push(line_start);
+                    line_start = pos;
+                }
+            }
+            _ => {}
+        }
+    }
+    result.push(line_start);
+    result
+}
+
+/** @internal */
+pub fn getPositionOfLineAndCharacter(source_file: &SourceFile, line: usize, character: usize, allow_edits: Option<bool>) -> usize {
+    computePositionOfLineAndCharacter(&getLineStarts(source_file), line, character, source_file.source_text, allow_edits)
+}
+
+/**
+ * @internal
+ * `character` counts UTF-16 code units, like TypeScript and editors do, so the text is needed to turn it into a byte position.
+ */
+pub fn computePositionOfLineAndCharacter(line_starts: &[usize], line: usize, character: usize, text: &str, allow_edits: Option<bool>) -> usize {
+    let allow_edits = allow_edits.unwrap_or(false);
+    let mut line = line;
+    if line >= line_starts.len() {
+        if allow_edits {
+            // Clamp line to nearest allowable value
+            line = line_starts.len() - 1;
+        } else {
+            panic!("Bad line number. Line: {}, lineStarts.length: {} , line map is correct? {}", line, line_starts.len(), line_starts == computeLineStarts(text));
+        }
+    }
+
+    let mut res = line_starts[line];
+    let mut units = 0;
+    for ch in text[res..].chars() {
+        if units >= character {
+            break;
+        }
+        units += ch.len_utf16();
+        res += ch.len_utf8();
+    }
+    // characters past the end of the text are counted as if they were ASCII
+    res += character.saturating_sub(units);
+    if allow_edits {
+        // Clamp to nearest allowable values to allow the underlying to be edited without crashing (accuracy is lost, instead)
+        // TODO: Somehow track edits between file as it was during the creation of sourcemap we have and the current file and
+        // apply them to the computed position to improve accuracy
+        return if line + 1 < line_starts.len() && res > line_starts[line + 1] { line_starts[line + 1] } else { res.min(text.len()) };
+    }
+    if line < line_starts.len() - 1 {
+        debug_assert!(res < line_starts[line + 1]);
+    } else {
+        debug_assert!(res <= text.len()); // Allow single character overflow for trailing newline
+    }
+    res
+}
+// endregion: 500
+
+// region: 505
+/** @internal */
+pub fn getLineStarts(source_file: &SourceFile) -> Rc<Vec<usize>> {
+    if let Some(line_map) = source_file.lineMap() {
+        return line_map;
+    }
+    let line_map = Rc::new(computeLineStarts(source_file.source_text));
+    source_file.set_lineMap(Some(line_map.clone()));
+    line_map
+}
+
+/**
+ * @internal
+ * `position` is a UTF-8 byte offset while the character is counted in UTF-16 code units, so the text is needed to convert it.
+ */
+pub fn computeLineAndCharacterOfPosition(line_starts: &[usize], position: usize, text: &str) -> LineAndCharacter {
+    let line_number = computeLineOfPosition(line_starts, position);
+    let line_start = line_starts[line_number];
+    // a position inside a multi-byte character belongs to that character
+    let mut end = position.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    LineAndCharacter { line: line_number, character: text[line_start..end.max(line_start)].encode_utf16().count() }
+}
+
+/**
+ * @internal
+ * We assume the first line starts at position 0 and 'position' is non-negative.
+ */
+pub fn computeLineOfPosition(line_starts: &[usize], position: usize) -> usize {
+    match line_starts.binary_search(&position) {
+        Ok(line_number) => line_number,
+        // If the actual position was not found,
+        // the binary search returns the 2's-complement of the next line start
+        // e.g. if the line starts at [5, 10, 23, 80] and the position requested was 20
+        // then the search will return -2.
+        //
+        // We want the index of the previous line start, so we subtract 1.
+        // Review 2's-complement if this is confusing.
+        Err(line_number) => line_number - 1,
+    }
+}
+
+pub fn getLineAndCharacterOfPosition(source_file: &SourceFile, position: usize) -> LineAndCharacter { computeLineAndCharacterOfPosition(&getLineStarts(source_file), position, source_file.source_text) }
+// endregion: 548
+
 
 // region: 549
 pub fn isWhiteSpaceLike(ch: char) -> bool {
@@ -230,3 +371,174 @@ fn scanShebangTrivia(text: &str, pos: u32) -> u32 {
     pos + shebang.len() as u32
 }
 // endregion: 803
+
+// region: 842
+/**
+ * Collects the comment ranges in the trivia at `pos`, like `reduceEachLeadingCommentRange`/`reduceEachTrailingCommentRange`.
+ * Single-line comments contain their content but not the terminating newline, multi-line comments contain
+ * their content and the closing `*` `/`. Positions are UTF-8 byte offsets, like oxc spans.
+ *
+ * If `trailing` is true, the collected comments are those on the same line as `pos`; otherwise they are the
+ * comments after the first line break following `pos` (or all comments when `pos` is 0), which is how a node's
+ * leading comments are told apart from the trailing comments of the token before it.
+ */
+fn iterateCommentRanges(text: &str, mut pos: usize, trailing: bool) -> Vec<CommentRange> {
+    let mut result: Vec<CommentRange> = Vec::new();
+    let mut pending: Option<CommentRange> = None;
+    let mut collecting = trailing;
+    let bytes = text.as_bytes();
+    if pos == 0 {
+        collecting = true;
+        if let Some(shebang) = getShebang(text) {
+            pos = shebang.len();
+        }
+    }
+    while pos < bytes.len() {
+        let ch = bytes[pos];
+        match ch {
+            b'\r' | b'\n' => {
+                if ch == b'\r' && bytes.get(pos + 1) == Some(&b'\n') {
+                    pos += 1;
+                }
+                pos += 1;
+                if trailing {
+                    break;
+                }
+
+                collecting = true;
+                if let Some(pending) = pending.as_mut() {
+                    pending.hasTrailingNewLine = true;
+                }
+                continue;
+            }
+            b'\t' | 0x0B | 0x0C | b' ' => {
+                pos += 1;
+                continue;
+            }
+            b'/' => {
+                let next_char = bytes.get(pos + 1).copied();
+                if next_char == Some(b'/') || next_char == Some(b'*') {
+                    let kind = if next_char == Some(b'/') { CommentKind::SingleLineCommentTrivia } else { CommentKind::MultiLineCommentTrivia };
+                    let start_pos = pos;
+                    let mut has_trailing_new_line = false;
+                    pos += 2;
+                    if kind == CommentKind::SingleLineCommentTrivia {
+                        while let Some(ch) = text[pos..].chars().next() {
+                            if isLineBreak(ch) {
+                                has_trailing_new_line = true;
+                                break;
+                            }
+                            pos += ch.len_utf8();
+                        }
+                    } else {
+                        pos = text[pos..].find("*/").map_or(text.len(), |index| pos + index + 2);
+                    }
+
+                    if collecting {
+                        result.extend(pending.take());
+                        pending = Some(CommentRange { pos: start_pos, end: pos, hasTrailingNewLine: has_trailing_new_line, kind });
+                    }
+
+                    continue;
+                }
+                break;
+            }
+            _ => {
+                if ch > CharacterCodes::maxAsciiCharacter as u8 {
+                    let ch = text[pos..].chars().next().unwrap();
+                    if isWhiteSpaceLike(ch) {
+                        if let Some(pending) = pending.as_mut() {
+                            if isLineBreak(ch) {
+                                pending.hasTrailingNewLine = true;
+                            }
+                        }
+                        pos += ch.len_utf8();
+                        continue;
+                    }
+                }
+                break;
+            }
+        }
+    }
+
+    result.extend(pending);
+    result
+}
+
+pub fn getLeadingCommentRanges(text: &str, pos: usize) -> Option<Vec<CommentRange>> {
+    let comments = iterateCommentRanges(text, pos, /*trailing*/ false);
+    if comments.is_empty() { None } else { Some(comments) }
+}
+
+pub fn getTrailingCommentRanges(text: &str, pos: usize) -> Option<Vec<CommentRange>> {
+    let comments = iterateCommentRanges(text, pos, /*trailing*/ true);
+    if comments.is_empty() { None } else { Some(comments) }
+}
+
+/** Optionally, get the shebang */
+pub fn getShebang(text: &str) -> Option<&str> {
+    SHEBANG_TRIVIA_REGEX.find(text).map(|shebang| shebang.as_str())
+}
+// endregion: 1022
+
+#[cfg(test)]
+mod tests {
+    use super::{computeLineAndCharacterOfPosition, computeLineStarts, computePositionOfLineAndCharacter, getLeadingCommentRanges, getTrailingCommentRanges};
+    use crate::compiler::types::{CommentKind, CommentRange};
+
+    // "a😀b" CRLF "c" LS "d" PS "é" LF, where the emoji is 4 bytes and 2 UTF-16 units, the separators 3 bytes and 'é' 2 bytes
+    const TEXT: &str = "a\u{1F600}b\r\nc\u{2028}d\u{2029}\u{E9}\n";
+
+    #[test]
+    fn starts_lines_after_every_line_break() {
+        assert_eq!(computeLineStarts(TEXT), vec![0, 8, 12, 16, 19]);
+        assert_eq!(computeLineStarts("x\ry\n\nz"), vec![0, 2, 4, 5]);
+        assert_eq!(computeLineStarts(""), vec![0]);
+    }
+
+    #[test]
+    fn counts_characters_in_utf16_code_units() {
+        let line_starts = computeLineStarts(TEXT);
+        let line_and_character = |position| {
+            let result = computeLineAndCharacterOfPosition(&line_starts, position, TEXT);
+            (result.line, result.character)
+        };
+        assert_eq!(line_and_character(5), (0, 3));
+        // a position inside a multi-byte character belongs to that character
+        assert_eq!(line_and_character(3), (0, 1));
+        assert_eq!(line_and_character(6), (0, 4));
+        assert_eq!(line_and_character(8), (1, 0));
+        assert_eq!(line_and_character(12), (2, 0));
+        assert_eq!(line_and_character(17), (3, 0));
+        assert_eq!(line_and_character(18), (3, 1));
+        assert_eq!(line_and_character(19), (4, 0));
+    }
+
+    #[test]
+    fn converts_utf16_characters_back_to_positions() {
+        let line_starts = computeLineStarts(TEXT);
+        assert_eq!(computePositionOfLineAndCharacter(&line_starts, 0, 3, TEXT, None), 5);
+        assert_eq!(computePositionOfLineAndCharacter(&line_starts, 1, 0, TEXT, None), 8);
+        assert_eq!(computePositionOfLineAndCharacter(&line_starts, 2, 1, TEXT, None), 13);
+        assert_eq!(computePositionOfLineAndCharacter(&line_starts, 3, 1, TEXT, None), 18);
+        // with edits allowed, lines and characters out of range are clamped
+        assert_eq!(computePositionOfLineAndCharacter(&line_starts, 10, 0, TEXT, Some(true)), 19);
+        assert_eq!(computePositionOfLineAndCharacter(&line_starts, 1, 10, TEXT, Some(true)), 12);
+    }
+
+    fn ranges(comments: Option<Vec<CommentRange>>) -> Vec<(usize, usize, bool, bool)> {
+        comments.unwrap_or_default().iter().map(|comment| (comment.pos, comment.end, comment.hasTrailingNewLine, comment.kind == CommentKind::MultiLineCommentTrivia)).collect()
+    }
+
+    #[test]
+    fn splits_comments_into_trailing_and_leading_ranges() {
+        let text = "let a = 1; // one\n/* two */ /** three */\nlet b;";
+        // the comment on the same line trails the statement, the ones after the line break lead the next one
+        assert_eq!(ranges(getTrailingCommentRanges(text, 10)), vec![(11, 17, true, false)]);
+        assert_eq!(ranges(getLeadingCommentRanges(text, 10)), vec![(18, 27, false, true), (28, 40, true, true)]);
+        assert!(getTrailingCommentRanges(text, text.len()).is_none());
+
+        // at the start of the file every comment leads, after the shebang
+        assert_eq!(ranges(getLeadingCommentRanges("#!/usr/bin/env node\n// a\n", 0)), vec![(20, 24, true, false)]);
+    }
+}
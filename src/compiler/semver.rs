@@ -0,0 +1,480 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fmt;
+
+// region: 1
+lazy_static! {
+    // https://semver.org/#spec-item-2
+    // > A normal version number MUST take the form X.Y.Z where X, Y, and Z are non-negative
+    // > integers, and MUST NOT contain leading zeroes. X is the major version, Y is the minor
+    // > version, and Z is the patch version. Each element MUST increase numerically.
+    //
+    // NOTE: We differ here in that we allow X and X.Y, with missing parts having the default
+    // value of `0`.
+    static ref versionRegExp: Regex = Regex::new(r"(?i)^(0|[1-9][0-9]*)(?:\.(0|[1-9][0-9]*)(?:\.(0|[1-9][0-9]*)(?:-([a-z0-9-.]+))?(?:\+([a-z0-9-.]+))?)?)?$").unwrap();
+
+    // https://semver.org/#spec-item-9
+    // > A pre-release version MAY be denoted by appending a hyphen and a series of dot separated
+    // > identifiers immediately following the patch version. Identifiers MUST comprise only ASCII
+    // > alphanumerics and hyphen [0-9A-Za-z-]. Identifiers MUST NOT be empty. Numeric identifiers
+    // > MUST NOT include leading zeroes.
+    static ref prereleaseRegExp: Regex = Regex::new(r"(?i)^(?:0|[1-9][0-9]*|[a-z-][a-z0-9-]*)(?:\.(?:0|[1-9][0-9]*|[a-z-][a-z0-9-]*))*$").unwrap();
+    static ref prereleasePartRegExp: Regex = Regex::new(r"(?i)^(?:0|[1-9][0-9]*|[a-z-][a-z0-9-]*)$").unwrap();
+
+    // https://semver.org/#spec-item-10
+    // > Build metadata MAY be denoted by appending a plus sign and a series of dot separated
+    // > identifiers immediately following the patch or pre-release version. Identifiers MUST
+    // > comprise only ASCII alphanumerics and hyphen [0-9A-Za-z-]. Identifiers MUST NOT be empty.
+    static ref buildRegExp: Regex = Regex::new(r"(?i)^[a-z0-9-]+(?:\.[a-z0-9-]+)*$").unwrap();
+    static ref buildPartRegExp: Regex = Regex::new(r"(?i)^[a-z0-9-]+$").unwrap();
+
+    // https://semver.org/#spec-item-9
+    // > Numeric identifiers MUST NOT include leading zeroes.
+    static ref numericIdentifierRegExp: Regex = Regex::new(r"^(0|[1-9][0-9]*)$").unwrap();
+}
+
+/**
+ * Describes a precise semantic version number, https://semver.org
+ *
+ * @internal
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub prerelease: Vec<String>,
+    pub build: Vec<String>,
+}
+
+impl Version {
+    pub fn zero() -> Version { Version::new(0, 0, 0, "0", "") }
+
+    pub fn new(major: u64, minor: u64, patch: u64, prerelease: &str, build: &str) -> Version {
+        let prerelease: Vec<String> = if prerelease.is_empty() { Vec::new() } else { prerelease.split('.').map(String::from).collect() };
+        let build: Vec<String> = if build.is_empty() { Vec::new() } else { build.split('.').map(String::from).collect() };
+
+        debug_assert!(prerelease.iter().all(|s| prereleasePartRegExp.is_match(s)), "Invalid argument: prerelease");
+        debug_assert!(build.iter().all(|s| buildPartRegExp.is_match(s)), "Invalid argument: build");
+
+        Version { major, minor, patch, prerelease, build }
+    }
+
+    /** Parses `text`, panicking if it is not a valid version. */
+    pub fn parse(text: &str) -> Version { Version::tryParse(text).expect("Invalid version") }
+
+    pub fn tryParse(text: &str) -> Option<Version> {
+        let VersionComponents { major, minor, patch, prerelease, build } = tryParseComponents(text)?;
+        Some(Version::new(major, minor, patch, &prerelease, &build))
+    }
+
+    pub fn compareTo(&self, other: &Version) -> Ordering {
+        // https://semver.org/#spec-item-11
+        // > Precedence is determined by the first difference when comparing each of these
+        // > identifiers from left to right as follows: Major, minor, and patch versions are
+        // > always compared numerically.
+        //
+        // https://semver.org/#spec-item-11
+        // > Precedence for two pre-release versions with the same major, minor, and patch version
+        // > MUST be determined by comparing each dot separated identifier from left to right until
+        // > a difference is found [...]
+        //
+        // https://semver.org/#spec-item-11
+        // > Build metadata does not figure into precedence
+        self.major.cmp(&other.major).then(self.minor.cmp(&other.minor)).then(self.patch.cmp(&other.patch)).then_with(|| comparePrereleaseIdentifiers(&self.prerelease, &other.prerelease))
+    }
+
+    pub fn increment(&self, field: VersionField) -> Version {
+        match field {
+            VersionField::Major => Version::new(self.major + 1, 0, 0, "", ""),
+            VersionField::Minor => Version::new(self.major, self.minor + 1, 0, "", ""),
+            VersionField::Patch => Version::new(self.major, self.minor, self.patch + 1, "", ""),
+        }
+    }
+
+    /** Returns a copy of this version with its pre-release identifiers replaced by `prerelease`. */
+    pub fn withPrerelease(&self, prerelease: &str) -> Version { Version::new(self.major, self.minor, self.patch, prerelease, &self.build.join(".")) }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.prerelease.is_empty() {
+            write!(f, "-{}", self.prerelease.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/** @internal */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionField {
+    Major,
+    Minor,
+    Patch,
+}
+
+struct VersionComponents {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    prerelease: String,
+    build: String,
+}
+
+fn tryParseComponents(text: &str) -> Option<VersionComponents> {
+    let captures = versionRegExp.captures(text)?;
+    let group = |i: usize| captures.get(i).map(|m| m.as_str());
+
+    let prerelease = group(4).unwrap_or("");
+    let build = group(5).unwrap_or("");
+    if !prerelease.is_empty() && !prereleaseRegExp.is_match(prerelease) {
+        return None;
+    }
+    if !build.is_empty() && !buildRegExp.is_match(build) {
+        return None;
+    }
+    Some(VersionComponents { major: group(1)?.parse().ok()?, minor: group(2).unwrap_or("0").parse().ok()?, patch: group(3).unwrap_or("0").parse().ok()?, prerelease: prerelease.to_string(), build: build.to_string() })
+}
+
+fn comparePrereleaseIdentifiers(left: &[String], right: &[String]) -> Ordering {
+    // https://semver.org/#spec-item-11
+    // > When major, minor, and patch are equal, a pre-release version has lower precedence
+    // > than a normal version.
+    if left.is_empty() {
+        return if right.is_empty() { Ordering::Equal } else { Ordering::Greater };
+    }
+    if right.is_empty() {
+        return Ordering::Less;
+    }
+
+    // https://semver.org/#spec-item-11
+    // > Precedence for two pre-release versions with the same major, minor, and patch version
+    // > MUST be determined by comparing each dot separated identifier from left to right until
+    // > a difference is found [...]
+    for (leftIdentifier, rightIdentifier) in left.iter().zip(right) {
+        if leftIdentifier == rightIdentifier {
+            continue;
+        }
+
+        let leftIsNumeric = numericIdentifierRegExp.is_match(leftIdentifier);
+        let rightIsNumeric = numericIdentifierRegExp.is_match(rightIdentifier);
+        if leftIsNumeric || rightIsNumeric {
+            // https://semver.org/#spec-item-11
+            // > Numeric identifiers always have lower precedence than non-numeric identifiers.
+            if leftIsNumeric != rightIsNumeric {
+                return if leftIsNumeric { Ordering::Less } else { Ordering::Greater };
+            }
+
+            // https://semver.org/#spec-item-11
+            // > identifiers consisting of only digits are compared numerically
+            let result = leftIdentifier.len().cmp(&rightIdentifier.len()).then_with(|| leftIdentifier.cmp(rightIdentifier));
+            if result != Ordering::Equal {
+                return result;
+            }
+        } else {
+            // https://semver.org/#spec-item-11
+            // > identifiers with letters or hyphens are compared lexically in ASCII sort order.
+            let result = leftIdentifier.cmp(rightIdentifier);
+            if result != Ordering::Equal {
+                return result;
+            }
+        }
+    }
+
+    // https://semver.org/#spec-item-11
+    // > A larger set of pre-release fields has a higher precedence than a smaller set, if all
+    // > of the preceding identifiers are equal.
+    left.len().cmp(&right.len())
+}
+// endregion: 215
+
+// region: 216
+/**
+ * Describes a semantic version range, per https://github.com/npm/node-semver#ranges
+ *
+ * @internal
+ */
+#[derive(Debug, Clone)]
+pub struct VersionRange {
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl VersionRange {
+    pub fn tryParse(text: &str) -> Option<VersionRange> { parseRange(text).map(|alternatives| VersionRange { alternatives }) }
+
+    /**
+     * Tests whether a version matches the range. This is equivalent to `satisfies(version, range, { includePrerelease: true })`.
+     * in `node-semver`.
+     */
+    pub fn test(&self, version: &Version) -> bool { testDisjunction(version, &self.alternatives) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparatorOperator {
+    LessThan,
+    LessThanEquals,
+    GreaterThan,
+    GreaterThanEquals,
+    Equals,
+}
+
+#[derive(Debug, Clone)]
+struct Comparator {
+    operator: ComparatorOperator,
+    operand: Version,
+}
+
+lazy_static! {
+    // https://github.com/npm/node-semver#range-grammar
+    //
+    // partial      ::= xr ( '.' xr ( '.' xr qualifier ? )? )?
+    // xr           ::= 'x' | 'X' | '*' | nr
+    // nr           ::= '0' | ['1'-'9'] ( ['0'-'9'] ) *
+    // qualifier    ::= ( '-' pre )? ( '+' build )?
+    // pre          ::= parts
+    // build        ::= parts
+    // parts        ::= part ( '.' part ) *
+    // part         ::= nr | [-0-9A-Za-z]+
+    static ref partialRegExp: Regex = Regex::new(r"(?i)^([x*0]|[1-9][0-9]*)(?:\.([x*0]|[1-9][0-9]*)(?:\.([x*0]|[1-9][0-9]*)(?:-([a-z0-9-.]+))?(?:\+([a-z0-9-.]+))?)?)?$").unwrap();
+
+    // https://github.com/npm/node-semver#range-grammar
+    //
+    // hyphen       ::= partial ' - ' partial
+    static ref hyphenRegExp: Regex = Regex::new(r"(?i)^\s*([a-z0-9-+.*]+)\s+-\s+([a-z0-9-+.*]+)\s*$").unwrap();
+
+    // https://github.com/npm/node-semver#range-grammar
+    //
+    // simple       ::= primitive | partial | tilde | caret
+    // primitive    ::= ( '<' | '>' | '>=' | '<=' | '=' ) partial
+    // tilde        ::= '~' partial
+    // caret        ::= '^' partial
+    static ref rangeRegExp: Regex = Regex::new(r"(?i)^(<=|>=|[~^<>=])?\s*([a-z0-9-+.*]+)$").unwrap();
+}
+
+// https://github.com/npm/node-semver#range-grammar
+//
+// range-set    ::= range ( logical-or range ) *
+// range        ::= hyphen | simple ( ' ' simple ) * | ''
+// logical-or   ::= ( ' ' ) * '||' ( ' ' ) *
+fn parseRange(text: &str) -> Option<Vec<Vec<Comparator>>> {
+    let mut alternatives = Vec::new();
+    for range in text.trim().split("||") {
+        if range.is_empty() {
+            continue;
+        }
+        let mut comparators = Vec::new();
+        let range = range.trim();
+        if range.is_empty() {
+            return None;
+        }
+        if let Some(captures) = hyphenRegExp.captures(range) {
+            if !parseHyphen(&captures[1], &captures[2], &mut comparators) {
+                return None;
+            }
+        } else {
+            for simple in range.split_whitespace() {
+                let captures = rangeRegExp.captures(simple.trim())?;
+                if !parseComparator(captures.get(1).map(|m| m.as_str()), &captures[2], &mut comparators) {
+                    return None;
+                }
+            }
+        }
+        alternatives.push(comparators);
+    }
+    Some(alternatives)
+}
+
+struct PartialVersion<'t> {
+    version: Version,
+    major: &'t str,
+    minor: &'t str,
+    patch: &'t str,
+}
+
+fn parsePartial(text: &str) -> Option<PartialVersion<'_>> {
+    let captures = partialRegExp.captures(text)?;
+    let major = captures.get(1)?.as_str();
+    let minor = captures.get(2).map_or("*", |m| m.as_str());
+    let patch = captures.get(3).map_or("*", |m| m.as_str());
+    let prerelease = captures.get(4).map_or("", |m| m.as_str());
+    let build = captures.get(5).map_or("", |m| m.as_str());
+    let version = Version::new(if isWildcard(major) { 0 } else { major.parse().ok()? }, if isWildcard(major) || isWildcard(minor) { 0 } else { minor.parse().ok()? }, if isWildcard(major) || isWildcard(minor) || isWildcard(patch) { 0 } else { patch.parse().ok()? }, prerelease, build);
+
+    Some(PartialVersion { version, major, minor, patch })
+}
+
+fn parseHyphen(left: &str, right: &str, comparators: &mut Vec<Comparator>) -> bool {
+    let Some(leftResult) = parsePartial(left) else {
+        return false;
+    };
+    let Some(rightResult) = parsePartial(right) else {
+        return false;
+    };
+
+    if !isWildcard(leftResult.major) {
+        comparators.push(createComparator(ComparatorOperator::GreaterThanEquals, leftResult.version));
+    }
+
+    if !isWildcard(rightResult.major) {
+        comparators.push(if isWildcard(rightResult.minor) {
+            createComparator(ComparatorOperator::LessThan, rightResult.version.increment(VersionField::Major))
+        } else if isWildcard(rightResult.patch) {
+            createComparator(ComparatorOperator::LessThan, rightResult.version.increment(VersionField::Minor))
+        } else {
+            createComparator(ComparatorOperator::LessThanEquals, rightResult.version)
+        });
+    }
+
+    true
+}
+
+fn parseComparator(operator: Option<&str>, text: &str, comparators: &mut Vec<Comparator>) -> bool {
+    let Some(PartialVersion { version, major, minor, patch }) = parsePartial(text) else {
+        return false;
+    };
+    if !isWildcard(major) {
+        match operator {
+            Some("~") => {
+                let upper = version.increment(if isWildcard(minor) { VersionField::Major } else { VersionField::Minor });
+                comparators.push(createComparator(ComparatorOperator::GreaterThanEquals, version));
+                comparators.push(createComparator(ComparatorOperator::LessThan, upper));
+            }
+            Some("^") => {
+                let upper = version.increment(if version.major > 0 || isWildcard(minor) {
+                    VersionField::Major
+                } else if version.minor > 0 || isWildcard(patch) {
+                    VersionField::Minor
+                } else {
+                    VersionField::Patch
+                });
+                comparators.push(createComparator(ComparatorOperator::GreaterThanEquals, version));
+                comparators.push(createComparator(ComparatorOperator::LessThan, upper));
+            }
+            Some("<") | Some(">=") => {
+                let operator = if operator == Some("<") { ComparatorOperator::LessThan } else { ComparatorOperator::GreaterThanEquals };
+                comparators.push(if isWildcard(minor) || isWildcard(patch) { createComparator(operator, version.withPrerelease("0")) } else { createComparator(operator, version) });
+            }
+            Some("<=") | Some(">") => {
+                let wildcardOperator = if operator == Some("<=") { ComparatorOperator::LessThan } else { ComparatorOperator::GreaterThanEquals };
+                comparators.push(if isWildcard(minor) {
+                    createComparator(wildcardOperator, version.increment(VersionField::Major).withPrerelease("0"))
+                } else if isWildcard(patch) {
+                    createComparator(wildcardOperator, version.increment(VersionField::Minor).withPrerelease("0"))
+                } else {
+                    createComparator(if operator == Some("<=") { ComparatorOperator::LessThanEquals } else { ComparatorOperator::GreaterThan }, version)
+                });
+            }
+            Some("=") | None => {
+                if isWildcard(minor) || isWildcard(patch) {
+                    let upper = version.increment(if isWildcard(minor) { VersionField::Major } else { VersionField::Minor }).withPrerelease("0");
+                    comparators.push(createComparator(ComparatorOperator::GreaterThanEquals, version.withPrerelease("0")));
+                    comparators.push(createComparator(ComparatorOperator::LessThan, upper));
+                } else {
+                    comparators.push(createComparator(ComparatorOperator::Equals, version));
+                }
+            }
+            // unrecognized
+            Some(_) => return false,
+        }
+    } else if operator == Some("<") || operator == Some(">") {
+        comparators.push(createComparator(ComparatorOperator::LessThan, Version::zero()));
+    }
+
+    true
+}
+
+fn isWildcard(part: &str) -> bool { part == "*" || part == "x" || part == "X" }
+
+fn createComparator(operator: ComparatorOperator, operand: Version) -> Comparator { Comparator { operator, operand } }
+
+fn testDisjunction(version: &Version, alternatives: &[Vec<Comparator>]) -> bool {
+    // an empty disjunction is treated as "*" (all versions)
+    if alternatives.is_empty() {
+        return true;
+    }
+    alternatives.iter().any(|alternative| testAlternative(version, alternative))
+}
+
+fn testAlternative(version: &Version, comparators: &[Comparator]) -> bool { comparators.iter().all(|comparator| testComparator(version, comparator.operator, &comparator.operand)) }
+
+fn testComparator(version: &Version, operator: ComparatorOperator, operand: &Version) -> bool {
+    let cmp = version.compareTo(operand);
+    match operator {
+        ComparatorOperator::LessThan => cmp == Ordering::Less,
+        ComparatorOperator::LessThanEquals => cmp != Ordering::Greater,
+        ComparatorOperator::GreaterThan => cmp == Ordering::Greater,
+        ComparatorOperator::GreaterThanEquals => cmp != Ordering::Less,
+        ComparatorOperator::Equals => cmp == Ordering::Equal,
+    }
+}
+// endregion: 420
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{Version, VersionRange};
+
+    #[test]
+    fn parses_partial_and_qualified_versions() {
+        let version = Version::parse("1.2.3-pre.4+build.5");
+        assert_eq!((version.major, version.minor, version.patch), (1, 2, 3));
+        assert_eq!(version.prerelease, vec!["pre", "4"]);
+        assert_eq!(version.build, vec!["build", "5"]);
+        assert_eq!(version.to_string(), "1.2.3-pre.4+build.5");
+        assert_eq!(Version::parse("1.2"), Version::new(1, 2, 0, "", ""));
+        assert!(Version::tryParse("01.2.3").is_none());
+        assert!(Version::tryParse("1.2.3-pre..4").is_none());
+    }
+
+    #[test]
+    fn orders_versions_by_precedence() {
+        let ordered = ["1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0", "1.0.1", "1.1.0", "2.0.0"];
+        for pair in ordered.windows(2) {
+            assert_eq!(Version::parse(pair[0]).compareTo(&Version::parse(pair[1])), Ordering::Less, "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(Version::parse("1.0.0+build").compareTo(&Version::parse("1.0.0")), Ordering::Equal);
+    }
+
+    #[test]
+    fn tests_versions_against_ranges() {
+        let cases = [
+            ("1.2.3 - 2.3.4", "1.2.3", true),
+            ("1.2.3 - 2.3.4", "2.3.4", true),
+            ("1.2.3 - 2.3.4", "2.3.5", false),
+            ("1.2 - 2.3", "2.3.9", true),
+            ("1.2 - 2.3", "2.4.0", false),
+            ("~1.2.3", "1.2.9", true),
+            ("~1.2.3", "1.3.0", false),
+            ("^1.2.3", "1.9.0", true),
+            ("^1.2.3", "2.0.0", false),
+            ("^0.2.3", "0.2.9", true),
+            ("^0.2.3", "0.3.0", false),
+            (">=3.1", "3.1.0", true),
+            (">=3.1", "3.0.9", false),
+            ("<4", "3.9.9", true),
+            ("<4", "4.0.0-beta", false),
+            ("1.x || >=2.5.0", "1.4.0", true),
+            ("1.x || >=2.5.0", "2.4.0", false),
+            ("1.x || >=2.5.0", "2.5.0", true),
+            ("*", "0.0.1", true),
+            ("", "12.0.0", true),
+        ];
+        for (range, version, expected) in cases {
+            let parsed = VersionRange::tryParse(range).unwrap_or_else(|| panic!("'{range}' should parse"));
+            assert_eq!(parsed.test(&Version::parse(version)), expected, "'{version}' in '{range}'");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        for range in ["not-a-range", ">=", "~>1.2", "1.2.3 -"] {
+            assert!(VersionRange::tryParse(range).is_none(), "'{range}' should not parse");
+        }
+    }
+}
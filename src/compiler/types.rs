@@ -16,7 +16,9 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use crate::{define_flags, define_string_enum, define_subset_enum, entity_properties, flag_names_impl, flow_node_enum, opt_rc_cell, rc_cell};
 
 use super::{
+    corePublic,
     moduleNameResolver::PackageJsonInfoCache,
+    rb_parser::OwnedSourceFile,
     rb_unions::{EscapedText, StrName, StrText, StringOrDiagnosticMessageChain, StringOrNumber},
     utilitiesPublic::escapeLeadingUnderscores,
 };
@@ -762,6 +764,20 @@ define_subset_enum!(AstKindDeclaration from AstKind {
     TSEnumDeclaration,
     TSModuleDeclaration,
     TSImportEqualsDeclaration,
+    VariableDeclarator,
+    ArrowFunctionExpression,
+    SourceFile,
+    // ! rb assignment declarations in JS files, the binary expression or the access expression on its left
+    AssignmentExpression,
+    StaticMemberExpression,
+    ElementAccessExpression,
+    // ! rb namespaces created by expando assignments to `a.b.c`
+    IdentifierReference,
+    IdentifierName,
+    StringLiteral,
+    NumericLiteral,
+    // ! rb shorthand properties of `module.exports = { a, b }`
+    ObjectProperty,
 });
 impl<'a> PartialEq for AstKindDeclaration<'a> {
     fn eq(&self, other: &Self) -> bool { self.to_ast_kind().get_node_id() == other.to_ast_kind().get_node_id() }
@@ -1540,6 +1556,24 @@ define_subset_enum!(CallLikeExpression from AstKind {
 });
 // endregion: 3129
 
+// region: 3152
+// CommentKind = SyntaxKind.SingleLineCommentTrivia | SyntaxKind.MultiLineCommentTrivia
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    SingleLineCommentTrivia,
+    MultiLineCommentTrivia,
+}
+
+// export interface CommentRange extends TextRange {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentRange {
+    pub pos: usize,
+    pub end: usize,
+    pub hasTrailingNewLine: bool,
+    pub kind: CommentKind,
+}
+// endregion: 3164
+
 // region: 3270
 // export interface JsxSpreadAttribute extends ObjectLiteralElement {
 impl NamedDeclarationTrait for JSXSpreadAttribute<'_> {
@@ -1877,6 +1911,76 @@ impl NamedDeclarationTrait for ExportDefaultDeclaration<'_> {
 }
 // endregion: 3833
 
+// region: 3880
+// ! rb JSDoc is not part of the oxc AST, so it is modelled with plain structs that are attached to nodes through `AstKindExt::jsDoc`.
+// Positions are UTF-8 byte offsets into the source text, like oxc spans.
+
+/** The type between the braces of a JSDoc tag, e.g. `{string}`; it is kept as text because JSDoc types are not parsed into the AST. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JSDocTypeExpression {
+    pub pos: usize,
+    pub end: usize,
+    /** The text of the type, without the braces */
+    pub r#type: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct JSDoc {
+    pub pos: usize,
+    pub end: usize,
+    pub tags: Vec<JSDocTag>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JSDocTag {
+    pub pos: usize,
+    pub end: usize,
+    pub tagName: String,
+    pub comment: Option<String>,
+    pub kind: JSDocTagKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum JSDocTagKind {
+    Unknown,
+    // export interface JSDocDeprecatedTag extends JSDocTag {
+    Deprecated,
+    // export interface JSDocTemplateTag extends JSDocTag {
+    Template {
+        constraint: Option<JSDocTypeExpression>,
+        typeParameters: Vec<String>,
+    },
+    // export interface JSDocReturnTag extends JSDocTag {
+    Return {
+        typeExpression: Option<JSDocTypeExpression>,
+    },
+    // export interface JSDocTypeTag extends JSDocTag {
+    Type {
+        typeExpression: JSDocTypeExpression,
+    },
+    // export interface JSDocParameterTag extends JSDocPropertyLikeTag {
+    Parameter {
+        name: String,
+        typeExpression: Option<JSDocTypeExpression>,
+        /** Whether the property name came before the type -- non-standard for JSDoc, but Typescript-like */
+        isNameFirst: bool,
+        isBracketed: bool,
+    },
+}
+
+impl JSDocTag {
+    pub fn typeExpression(&self) -> Option<&JSDocTypeExpression> {
+        match &self.kind {
+            JSDocTagKind::Type { typeExpression } => Some(typeExpression),
+            JSDocTagKind::Return { typeExpression } | JSDocTagKind::Parameter { typeExpression, .. } => typeExpression.as_ref(),
+            JSDocTagKind::Template { constraint, .. } => constraint.as_ref(),
+            JSDocTagKind::Unknown | JSDocTagKind::Deprecated => None,
+        }
+    }
+}
+// endregion: 4093
+
 // region: 4120
 // NOTE: Ensure this is up-to-date with src/debug/debug.ts
 // @internal
@@ -1938,7 +2042,7 @@ pub struct FlowUnreachable {
 pub struct FlowStart<'a> {
     pub flags: FlowFlags,
     pub id: usize,
-    pub node: Option<&'a AstKind<'a>>, // FunctionExpression | ArrowFunction | MethodDeclaration | GetAccessorDeclaration | SetAccessorDeclaration | undefined;
+    pub node: Option<AstKind<'a>>, // FunctionExpression | ArrowFunction | MethodDeclaration | GetAccessorDeclaration | SetAccessorDeclaration | undefined;
 }
 
 // FlowLabel represents a junction with multiple possible preceding control flows.
@@ -1946,7 +2050,7 @@ pub struct FlowStart<'a> {
 pub struct FlowLabel<'a> {
     pub flags: FlowFlags,
     pub id: usize,
-    pub antecedent: Option<Vec<FlowNode<'a>>>,
+    pub antecedent: Option<Vec<rc_cell!(FlowNode<'a>)>>,
 }
 
 // FlowAssignment represents a node that assigns a value to a narrowable reference,
@@ -1955,14 +2059,8 @@ pub struct FlowLabel<'a> {
 pub struct FlowAssignment<'a> {
     pub flags: FlowFlags,
     pub id: usize,
-    pub node: FlowAssignmentNode<'a>,
-    pub antecedent: Box<FlowNode<'a>>,
-}
-
-pub enum FlowAssignmentNode<'a> {
-    Expression(&'a Expression<'a>),
-    VariableDeclarator(&'a VariableDeclarator<'a>),
-    BindingElement(&'a BindingElement<'a>),
+    pub node: AstKind<'a>, // Expression | VariableDeclaration | BindingElement
+    pub antecedent: rc_cell!(FlowNode<'a>),
 }
 
 /** @internal */
@@ -1970,7 +2068,7 @@ pub struct FlowCall<'a> {
     pub flags: FlowFlags,
     pub id: usize,
     pub node: &'a CallExpression<'a>,
-    pub antecedent: Box<FlowNode<'a>>,
+    pub antecedent: rc_cell!(FlowNode<'a>),
 }
 
 // FlowCondition represents a condition that is known to be true or false at the
@@ -1979,8 +2077,8 @@ pub struct FlowCall<'a> {
 pub struct FlowCondition<'a> {
     pub flags: FlowFlags,
     pub id: usize,
-    pub node: &'a Expression<'a>,
-    pub antecedent: Box<FlowNode<'a>>,
+    pub node: AstKind<'a>, // Expression
+    pub antecedent: rc_cell!(FlowNode<'a>),
 }
 
 /** @internal */
@@ -1988,7 +2086,7 @@ pub struct FlowSwitchClause<'a> {
     pub flags: FlowFlags,
     pub id: usize,
     pub node: FlowSwitchClauseData<'a>,
-    pub antecedents: Box<FlowNode<'a>>,
+    pub antecedent: rc_cell!(FlowNode<'a>),
 }
 
 /** @internal */
@@ -2005,12 +2103,12 @@ pub struct FlowArrayMutation<'a> {
     pub flags: FlowFlags,
     pub id: usize,
     pub node: FlowArrayMutationNode<'a>,
-    pub antecedent: Box<FlowNode<'a>>,
+    pub antecedent: rc_cell!(FlowNode<'a>),
 }
 
 pub enum FlowArrayMutationNode<'a> {
     CallExpression(&'a CallExpression<'a>),
-    BinaryExpression(&'a BinaryExpression<'a>),
+    BinaryExpression(BinaryExpression<'a>),
 }
 
 /** @internal */
@@ -2018,13 +2116,13 @@ pub struct FlowReduceLabel<'a> {
     pub flags: FlowFlags,
     pub id: usize,
     pub node: FlowReduceLabelData<'a>,
-    pub antecedents: Box<FlowNode<'a>>,
+    pub antecedent: rc_cell!(FlowNode<'a>),
 }
 
 /** @internal */
 pub struct FlowReduceLabelData<'a> {
-    pub target: FlowLabel<'a>,
-    pub antecedents: Vec<FlowNode<'a>>,
+    pub target: rc_cell!(FlowNode<'a>), // FlowLabel
+    pub antecedents: Vec<rc_cell!(FlowNode<'a>)>,
 }
 
 pub enum FlowType<'a> {
@@ -2050,12 +2148,34 @@ pub enum ResolutionMode {
 }
 // endregion: 4291
 
+// region: 4574
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    // Compiler ran successfully.  Either this was a simple do-nothing compilation (for example,
+    // when -version or -help was provided, or this was a normal compilation, no diagnostics
+    // were produced, and all outputs were generated successfully.
+    Success = 0,
+
+    // Diagnostics were produced and because of them no code was generated.
+    DiagnosticsPresent_OutputsSkipped = 1,
+
+    // Diagnostics were produced and outputs were generated in spite of them.
+    DiagnosticsPresent_OutputsGenerated = 2,
+
+    // When build skipped because passed in project is invalid
+    InvalidProject_OutputsSkipped = 3,
+
+    // When build is skipped because project references form cycle
+    ProjectReferenceCycle_OutputsSkipped = 4,
+}
+// endregion: 4591
+
 // region: 5010
 /** @internal */
 pub trait TypeCheckerHost<'a>: ModuleSpecifierResolutionHost + std::fmt::Debug {
     fn getCompilerOptions(&self) -> Rc<CompilerOptions>;
-    fn getSourceFiles(&self) -> rc_cell!(Vec<rc_cell!(SourceFile<'a>)>);
-    fn getSourceFile(&self, file_name: &str) -> Option<&SourceFile>;
+    fn getSourceFiles(&self) -> rc_cell!(Vec<Rc<OwnedSourceFile<'a>>>);
+    fn getSourceFile(&self, file_name: &str) -> Option<Rc<OwnedSourceFile<'a>>>;
     fn getProjectReferenceRedirect(&self, file_name: &str) -> Option<String>;
     fn isSourceOfProjectReferenceRedirect(&self, file_name: &str) -> bool;
     // fn getEmitSyntaxForUsageLocation(&self, file: &SourceFile, usage: &StringLiteralLike) -> ResolutionMode;
@@ -2561,9 +2681,9 @@ pub struct Symbol<'a> {
     pub escapedName: __String,                  // Name of symbol
     pub declarations: Option<Vec<AstKindDeclaration<'a>>>, // Declarations associated with this symbol
     pub valueDeclaration: Option<AstKindDeclaration<'a>>,  // First value declaration of the symbol
-    pub members: Option<SymbolTable<'a>>,           // Class, interface or object literal instance members
-    pub exports: Option<SymbolTable<'a>>,           // Module exports
-    pub globalExports: Option<SymbolTable<'a>>,     // Conditional global UMD exports
+    pub members: opt_rc_cell!(SymbolTable<'a>),     // Class, interface or object literal instance members
+    pub exports: opt_rc_cell!(SymbolTable<'a>),     // Module exports
+    pub globalExports: opt_rc_cell!(SymbolTable<'a>), // Conditional global UMD exports
     /** @internal */ pub id: SymbolId,          // Unique id (used to look up SymbolLinks)
     /** @internal */ pub mergeId: usize,        // Merge id (used to look up merged symbol)
     /** @internal */ pub parent: opt_rc_cell!(Symbol<'a>),         // Parent symbol
@@ -2572,7 +2692,7 @@ pub struct Symbol<'a> {
     /** @internal */ pub isReferenced: Option<SymbolFlags>,   // True if the symbol is referenced elsewhere. Keeps track of the meaning of a reference in case a symbol is both a type parameter and parameter.
     /** @internal */ pub lastAssignmentPos: Option<usize>,    // Source position of last node that assigns value to symbol
     /** @internal */ pub isReplaceableByMethod: Option<bool>, // Can this Javascript class property be replaced by a method symbol?
-    /** @internal */ pub assignmentDeclarationMembers: Option<HashMap<usize, &'a AstKindDeclaration<'a>>>, // detected late-bound assignment declarations associated with the symbol
+    /** @internal */ pub assignmentDeclarationMembers: Option<HashMap<u32, AstKindDeclaration<'a>>>, // detected late-bound assignment declarations associated with the symbol
 }
 // endregion: 5976
 
@@ -3556,6 +3676,82 @@ pub enum ScriptTarget {
 // import LanguageVariant from oxc
 // endregion: 7591
 
+// region: 7607
+#[derive(Debug, Clone, Default)]
+pub struct ParsedCommandLine {
+    pub options: CompilerOptions,
+    // pub typeAcquisition: Option<TypeAcquisition>,
+    pub fileNames: Vec<String>,
+    // pub projectReferences: Option<Vec<ProjectReference>>,
+    // pub watchOptions: Option<WatchOptions>,
+    pub raw: Option<serde_json::Value>,
+    pub errors: Vec<Diagnostic<'static>>,
+    // pub wildcardDirectories: Option<MapLike<WatchDirectoryFlags>>,
+    pub compileOnSave: Option<bool>,
+}
+// endregion: 7617
+
+// region: 7651
+pub trait ParseConfigHost: ModuleResolutionHost {
+    /**
+     * Gets the files under `rootDir` with one of the given extensions that match the `includes` globs and none of the `excludes`.
+     * Common package directories (node_modules, bower_components, jspm_packages) and dot-directories are skipped unless named explicitly.
+     */
+    fn readDirectory(&self, rootDir: &str, extensions: &[&str], excludes: Option<&[String]>, includes: &[String], depth: Option<usize>) -> Vec<String>;
+}
+// endregion: 7670
+
+// region: 7687
+/** @internal */
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFileSpecs {
+    pub filesSpecs: Option<Vec<serde_json::Value>>,
+    /**
+     * Present to report errors (user specified specs), validatedIncludeSpecs are used for file name matching
+     */
+    pub includeSpecs: Option<Vec<serde_json::Value>>,
+    /**
+     * Present to report errors (user specified specs), validatedExcludeSpecs are used for file name matching
+     */
+    pub excludeSpecs: Option<Vec<serde_json::Value>>,
+    pub validatedFilesSpec: Option<Vec<String>>,
+    pub validatedIncludeSpecs: Option<Vec<String>>,
+    pub validatedExcludeSpecs: Option<Vec<String>>,
+    pub isDefaultIncludeSpec: bool,
+}
+// endregion: 7704
+
+// region: 7712
+/** @internal */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CommandLineOptionType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+    Object,
+    List,
+    /** The accepted (lower case) names of the enum values */
+    Map(Vec<&'static str>),
+}
+
+/** @internal */
+#[derive(Debug, Clone, Default)]
+pub struct CommandLineOption {
+    pub name: &'static str,
+    pub type_: CommandLineOptionType,
+    /** True if option value is a path or fileName, for lists this applies to every element */
+    pub isFilePath: bool,
+    /** A short mnemonic for convenience - for instance, 'h' can be used in place of 'help' */
+    pub shortName: Option<&'static str>,
+    /** True if option can only be specified via tsconfig.json file */
+    pub isTSConfigOnly: bool,
+    pub isCommandLineOnly: bool,
+    /** Keeps empty strings in lists instead of dropping them */
+    pub listPreserveFalsyValues: bool,
+}
+// endregion: 7780
+
 // region: 7734
 // dprint-ignore
 /** @internal */
@@ -3733,9 +3929,84 @@ pub trait ModuleResolutionHost {
     fn getCurrentDirectory(&self) -> Option<String>;
     fn getDirectories(&self, path: &str) -> Option<Vec<String>>;
     fn useCaseSensitiveFileNames(&self) -> Option<bool>;
+    /**
+     * The compiler version that `typesVersions` and `types@` ranges in package.json files are tested against.
+     * @internal
+     */
+    fn getTypeScriptVersion(&self) -> String { corePublic::version.to_string() }
+}
+
+/**
+ * Represents the result of module resolution.
+ * Module resolution will pick up tsx/jsx/js files even if '--jsx' and '--allowJs' are turned off.
+ * The Program will then filter results based on these flags.
+ *
+ * Prefer to return a `ResolvedModuleFull` so that the file type does not have to be inferred.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedModuleFull {
+    /** Path of the file the module was resolved to. */
+    pub resolvedFileName: String,
+    /** True if `resolvedFileName` comes from `node_modules`. */
+    pub isExternalLibraryImport: Option<bool>,
+    /**
+     * True if the original module reference used a .ts extension to refer directly to a .ts file,
+     * which should produce an error during checking if emit is enabled.
+     */
+    pub resolvedUsingTsExtension: Option<bool>,
+    /**
+     * Extension of resolvedFileName. This must match what's at the end of resolvedFileName.
+     * This is optional for backwards-compatibility, but will be added if not provided.
+     */
+    pub extension: String,
+    pub packageId: Option<PackageId>,
+    /** @internal */
+    pub originalPath: Option<String>,
+}
+
+/**
+ * Unique identifier with a package name and version.
+ * If changing this, remember to change `packageIdIsEqual`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageId {
+    /**
+     * Name of the package.
+     * Should not include `@types`.
+     * If accessing a non-index file, this should include its name e.g. "foo/bar".
+     */
+    pub name: String,
+    /**
+     * Name of a submodule within this package.
+     * May be "".
+     */
+    pub subModuleName: String,
+    /** Version of the package, e.g. "1.2.3" */
+    pub version: String,
+    /** @internal */
+    pub peerDependencies: Option<String>,
 }
 // endregion: 7893
 
+// region: 7941
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedModuleWithFailedLookupLocations {
+    pub resolvedModule: Option<ResolvedModuleFull>,
+    /** @internal */
+    pub failedLookupLocations: Option<Vec<String>>,
+    /** @internal */
+    pub affectingLocations: Option<Vec<String>>,
+    /** @internal */
+    pub resolutionDiagnostics: Option<Vec<Diagnostic<'static>>>,
+    /**
+     * @internal
+     * Used to issue a better diagnostic when an unresolvable module may
+     * have been resolvable under different module resolution settings.
+     */
+    pub alternateResult: Option<String>,
+}
+// endregion: 7960
+
 // region: 7961
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Extension {
@@ -3819,7 +4090,7 @@ pub struct TextChangeRange {
 pub trait ModuleSpecifierResolutionHost: ModuleResolutionHost {
     // fn getSymlinkCache(&self) -> Option<SymlinkCache>;
     // fn getModuleSpecifierCache(&self) -> Option<ModuleSpecifierCache>;
-    fn getPackageJsonInfoCache(&self) -> Option<&dyn PackageJsonInfoCache>;
+    fn getPackageJsonInfoCache(&self) -> Option<rc_cell!(dyn PackageJsonInfoCache)>;
     fn getGlobalTypingsCacheLocation(&self) -> Option<String>;
     fn getNearestAncestorDirectoryWithPackageJson(&self, file_name: &str, root_dir: Option<&str>) -> Option<String>;
 
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::rc::Rc;
 
+use super::core::findBestPatternMatch;
 use super::core::startsWith;
+use super::core::Pattern;
 use super::factory::nodeTests::*;
 use super::factory::utilities::skipOuterExpressions;
 use super::factory::utilitiesPublic::canHaveModifiers;
@@ -12,7 +16,8 @@ use super::rb_unions::PropertyNameLiteralOrPrivateIdentifier;
 use super::rb_unions::StrName;
 use super::rb_unions::StrText;
 use super::rb_unions::StringOrDiagnosticMessageChain;
-use super::scanner::skipTrivia;
+use super::rb_unions::StringOrPattern;
+use super::scanner::{getLeadingCommentRanges, getTrailingCommentRanges, isWhiteSpaceLike, skipTrivia};
 use super::utilitiesPublic::*;
 use crate::compiler::checker::getSymbolId;
 use crate::compiler::factory::nodeTests::isObjectLiteralExpression;
@@ -43,6 +48,20 @@ pub fn createSymbolTable<'a>(symbols: Option<&[rc_cell!(Symbol<'a>)]>) -> Symbol
 }
 // endregion: 631
 
+// region: 660
+/** @internal */
+pub fn packageIdToPackageName(PackageId { name, subModuleName, .. }: &PackageId) -> String {
+    if !subModuleName.is_empty() {
+        format!("{name}/{subModuleName}")
+    } else {
+        name.clone()
+    }
+}
+
+/** @internal */
+pub fn packageIdToString(packageId: &PackageId) -> String { format!("{}@{}{}", packageIdToPackageName(packageId), packageId.version, packageId.peerDependencies.as_deref().unwrap_or_default()) }
+// endregion: 667
+
 // region: 774
 /** @internal */
 pub fn getFullWidth(node: &AstKind) -> u32 { node.span().end - node.span().start }
@@ -132,6 +151,16 @@ pub fn getTextOfNodeFromSourceText(source_text: &str, node: &AstKind, include_tr
 pub fn getTextOfNode(node: &AstKind, include_trivia: Option<bool>) -> String { getSourceTextOfNodeFromSourceFile(getSourceFileOfNode(Some(node)).unwrap(), node, include_trivia) }
 // endregion: 1328
 
+// region: 1862
+/** @internal */
+pub fn isExternalOrCommonJsModule(file: &SourceFile) -> bool { file.externalModuleIndicator() || file.commonJsModuleIndicator().is_some() }
+// endregion: 1866
+
+// region: 1869
+/** @internal */
+pub fn isJsonSourceFile(file: &SourceFile) -> bool { file.scriptKind() == ScriptKind::JSON }
+// endregion: 1872
+
 // region: 1926
 /** @internal */
 /** @internal */
@@ -156,6 +185,14 @@ pub fn isEffectiveModuleDeclaration(node: &AstKind) -> bool { isModuleDeclaratio
 pub fn isGlobalScopeAugmentation(module: &TSModuleDeclaration) -> bool { module.kind.is_global() }
 // endregion: 1977
 
+// region: 2026
+/** @internal */
+pub fn isBlockOrCatchScoped(declaration: &AstKind) -> bool {
+    // ! rb the binding of a catch clause is a CatchParameter in oxc rather than a VariableDeclaration
+    getCombinedNodeFlags(declaration).intersects(NodeFlags::BlockScoped) || matches!(declaration, AstKind::CatchParameter(_))
+}
+// endregion: 2030
+
 // region: 2194
 // Return display name of an identifier
 // Computed property names will just be emitted as "[<expr>]", where <expr> is the source
@@ -444,11 +481,147 @@ pub fn getErrorSpanForNode<'a>(source_file: &'a SourceFile<'a>, node: &AstKind<'
 }
 // endregion: 2481
 
+// region: 2737
+/** @internal */
+pub fn getJSDocCommentRanges(node: &AstKind, text: &str) -> Vec<CommentRange> {
+    let pos = rb_node_pos(node, text);
+    let commentRanges = match node {
+        AstKind::FormalParameter(_) | AstKind::TSTypeParameter(_) | AstKind::ArrowFunctionExpression(_) | AstKind::ParenthesizedExpression(_) | AstKind::VariableDeclarator(_) | AstKind::ExportSpecifier(_) => {
+            getTrailingCommentRanges(text, pos).into_iter().flatten().chain(getLeadingCommentRanges(text, pos).into_iter().flatten()).collect()
+        }
+        AstKind::Function(func) if func.is_expression() => getTrailingCommentRanges(text, pos).into_iter().flatten().chain(getLeadingCommentRanges(text, pos).into_iter().flatten()).collect(),
+        _ => getLeadingCommentRanges(text, pos).unwrap_or_default(),
+    };
+    // True if the comment starts with '/**' but not if it is '/**/'
+    commentRanges.into_iter().filter(|comment| comment.end <= node.span().end as usize && isJSDocLikeText(text, comment.pos)).collect()
+}
+
+// oxc spans start at the first token of a node, while `node.pos` in TypeScript is the end of the previous token,
+// so walk back over the whitespace and comments in front of the node
+fn rb_node_pos(node: &AstKind, text: &str) -> usize {
+    let comments = &getSourceFileOfNode(Some(node)).unwrap().comments;
+    let mut pos = node.span().start as usize;
+    loop {
+        pos = text[..pos].trim_end_matches(isWhiteSpaceLike).len();
+        match comments.binary_search_by_key(&(pos as u32), |comment| comment.span.end) {
+            Ok(index) => pos = comments[index].span.start as usize,
+            Err(_) => return pos,
+        }
+    }
+}
+// endregion: 2755
+
+// region: 2797
+/** @internal */
+pub fn getThisContainer<'a>(node: &AstKind<'a>, includeArrowFunctions: bool, includeClassComputedPropertyName: bool) -> AstKind<'a> {
+    debug_assert!(!matches!(node, AstKind::SourceFile(_)));
+    let mut prev = *node;
+    let mut node = *node;
+    loop {
+        node = node.parent().expect("A node other than a source file always has a this container");
+        // ! rb computed property names are not nodes in oxc, the key of a computed member is a child of the member itself
+        let computedKey = match node {
+            AstKind::MethodDefinition(member) if member.computed => Some(member.key.span()),
+            AstKind::PropertyDefinition(member) if member.computed => Some(member.key.span()),
+            AstKind::AccessorProperty(member) if member.computed => Some(member.key.span()),
+            _ => None,
+        };
+        if computedKey == Some(prev.span()) {
+            if includeClassComputedPropertyName {
+                return prev;
+            }
+            // skip the class body and the class
+            node = node.parent().unwrap().parent().unwrap();
+            prev = node;
+            continue;
+        }
+        match node {
+            AstKind::Decorator(_) => {
+                // Decorators are always applied outside of the body of a class or method.
+                let parent = node.parent().unwrap();
+                if matches!(parent, AstKind::FormalParameter(_)) {
+                    // If the decorator's parent is a Parameter, we resolve the this container from
+                    // the grandparent class declaration.
+                    if let Some(element) = findAncestor(Some(parent), |n| isClassElement(n).into()) {
+                        node = element;
+                    }
+                } else if isClassElement(&parent) {
+                    // If the decorator's parent is a class element, we resolve the 'this' container
+                    // from the parent class declaration.
+                    node = parent;
+                }
+            }
+            AstKind::ArrowFunctionExpression(_) if !includeArrowFunctions => {}
+            // ! rb methods, accessors and constructors wrap a function in oxc
+            AstKind::Function(_) if matches!(node.parent(), Some(AstKind::MethodDefinition(_))) => return node.parent().unwrap(),
+            AstKind::ArrowFunctionExpression(_) |
+            // FunctionDeclaration, FunctionExpression
+            AstKind::Function(_) |
+            AstKind::TSModuleDeclaration(_) |
+            AstKind::StaticBlock(_) |
+            AstKind::PropertyDefinition(_) |
+            AstKind::AccessorProperty(_) |
+            AstKind::TSPropertySignature(_) |
+            // MethodDeclaration, Constructor, GetAccessor, SetAccessor
+            AstKind::MethodDefinition(_) |
+            AstKind::TSMethodSignature(_) |
+            AstKind::TSCallSignatureDeclaration(_) |
+            AstKind::TSConstructSignatureDeclaration(_) |
+            AstKind::TSIndexSignature(_) |
+            AstKind::TSEnumDeclaration(_) |
+            AstKind::SourceFile(_) => return node,
+            _ => {}
+        }
+        prev = node;
+    }
+}
+// endregion: 2869
+
+// region: 2909
+/** @internal */
+pub fn isInTopLevelContext(node: &AstKind) -> bool {
+    let mut node = *node;
+    // The name of a class or function declaration is a BindingIdentifier in its surrounding scope.
+    if let Some(parent) = node.parent() {
+        let name = match parent {
+            AstKind::Class(class) if class.is_declaration() => class.id.as_ref().map(|id| id.span),
+            AstKind::Function(func) if func.is_declaration() => func.id.as_ref().map(|id| id.span),
+            _ => None,
+        };
+        if isIdentifier(&node) && name == Some(node.span()) {
+            node = parent;
+        }
+    }
+    let container = getThisContainer(&node, /*includeArrowFunctions*/ true, /*includeClassComputedPropertyName*/ false);
+    matches!(container, AstKind::SourceFile(_))
+}
+// endregion: 2917
+
 // region: 2922
 /** @internal */
 pub fn isObjectLiteralOrClassExpressionMethodOrAccessor(node: &AstKind) -> bool { matches!(node, AstKind::MethodDefinition(_)) && if let Some(parent) = node.parent() { matches!(parent, AstKind::ObjectExpression(_) | AstKind::Class(_)) } else { false } }
 // endregion: 2927
 
+// region: 2960
+/** @internal */
+pub fn getImmediatelyInvokedFunctionExpression<'a>(func: &AstKind<'a>) -> Option<&'a CallExpression<'a>> {
+    if matches!(func, AstKind::Function(f) if f.is_expression()) || matches!(func, AstKind::ArrowFunctionExpression(_)) {
+        let mut prev = *func;
+        let mut parent = func.parent()?;
+        while matches!(parent, AstKind::ParenthesizedExpression(_)) {
+            prev = parent;
+            parent = parent.parent()?;
+        }
+        if let AstKind::CallExpression(call) = parent {
+            if call.callee.span() == prev.span() {
+                return Some(call);
+            }
+        }
+    }
+    None
+}
+// endregion: 2975
+
 // region: 2988
 /** @internal */
 pub fn getContainingClass<'a>(node: AstKind<'a>) -> Option<&ClassLikeDeclaration<'a>> {
@@ -476,20 +649,155 @@ pub fn isPartOfTypeQuery(node: &AstKind) -> bool {
 }
 // endregion: 3614
 
+// region: 3616
+/** @internal */
+pub fn isThisInitializedDeclaration(node: Option<&AstKind>) -> bool { matches!(node, Some(AstKind::VariableDeclarator(decl)) if matches!(decl.init, Some(Expression::ThisExpression(_)))) }
+// endregion: 3620
+
 // region: 3646
 /** @internal */
-pub fn isSourceFileJS(file: &SourceFile) -> bool { file.source_type.is_javascript() }
+pub fn isSourceFileJS(file: &SourceFile) -> bool { isInJSFile(&AstKind::SourceFile(file)) }
 
 /** @internal */
-// ! rb consider making this faster?
-pub fn isInJSFile(node: &AstKind) -> bool { isSourceFileJS(getSourceFileOfNode(Some(node)).unwrap()) }
-// endregion: 3656
+pub fn isInJSFile(node: &AstKind) -> bool { node.flags().intersects(NodeFlags::JavaScriptFile) }
+
+/** @internal */
+pub fn isInJsonFile(node: &AstKind) -> bool { node.flags().intersects(NodeFlags::JsonFile) }
+
+/** @internal */
+pub fn isSourceFileNotJson(file: &SourceFile) -> bool { !isJsonSourceFile(file) }
+// endregion: 3668
+
+// region: 3680
+/**
+ * Returns true if the node is a CallExpression to the identifier 'require' with
+ * exactly one argument (of the form 'require("name")').
+ * This function does not test if the node is in a JavaScript file or not.
+ */
+pub fn isRequireCall(callExpression: &AstKind, requireStringLiteralLikeArgument: bool) -> bool {
+    let AstKind::CallExpression(call) = callExpression else {
+        return false;
+    };
+    if !matches!(&call.callee, Expression::Identifier(id) if id.name == "require") {
+        return false;
+    }
+    if call.arguments.len() != 1 {
+        return false;
+    }
+    let arg = call.arguments[0].to_ast_kind();
+    !requireStringLiteralLikeArgument || isStringLiteralLike(&arg)
+}
+// endregion: 3697
 
 // region: 3755
 /** @internal */
 pub fn isAssignmentDeclaration(decl: &AstKind) -> bool { isBinaryExpression(decl) || isAccessExpression(decl) || isIdentifier(decl) || isCallExpression(decl) }
 // endregion: 3760
 
+// region: 3777
+/** @internal */
+pub fn isFunctionSymbol(symbol: Option<&rc_cell!(Symbol)>) -> bool {
+    let Some(decl) = symbol.and_then(|symbol| symbol.borrow().valueDeclaration.clone()) else {
+        return false;
+    };
+    match decl {
+        AstKindDeclaration::Function(func) => func.is_declaration(),
+        AstKindDeclaration::VariableDeclarator(decl) => decl.init.as_ref().is_some_and(|init| isFunctionLike(Some(&init.to_ast_kind()))),
+        _ => false,
+    }
+}
+// endregion: 3784
+
+// region: 3808
+/**
+ * Get the assignment 'initializer' -- the righthand side-- when the initializer is container-like (See getExpandoInitializer).
+ * We treat the right hand side of assignments with container-like initializers as declarations.
+ *
+ * @internal
+ */
+pub fn getAssignedExpandoInitializer<'a>(node: Option<&AstKind<'a>>) -> Option<AstKind<'a>> {
+    if let Some(AstKind::AssignmentExpression(parent)) = node.and_then(|node| node.parent()) {
+        if parent.operator == AssignmentOperator::Assign {
+            let isPrototypeAssignment = isPrototypeAccess(&parent.left.to_ast_kind());
+            return getExpandoInitializer(&parent.right.to_ast_kind(), isPrototypeAssignment);
+            // || getDefaultedExpandoInitializer(node.parent.left, node.parent.right, isPrototypeAssignment);
+        }
+    }
+    // ! rb Object.defineProperty assignments are not bound
+    // if (node && isCallExpression(node) && isBindableObjectDefinePropertyCall(node)) {
+    //     const result = hasExpandoValueProperty(node.arguments[2], node.arguments[1].text === "prototype");
+    //     if (result) {
+    //         return result;
+    //     }
+    // }
+    None
+}
+
+/**
+ * Recognized expando initializers are:
+ * 1. (function() {})() -- IIFEs
+ * 2. function() { }, function foo() { } and () => { } -- function expressions and arrow functions
+ * 3. class { } -- class expressions
+ * 4. {} -- empty object literals
+ * 5. { ... } -- non-empty object literals, when used to initialize a prototype, like `C.prototype = { m() { } }`
+ *
+ * This function returns the provided initializer, or undefined if it is not valid.
+ *
+ * @internal
+ */
+pub fn getExpandoInitializer<'a>(initializer: &AstKind<'a>, isPrototypeAssignment: bool) -> Option<AstKind<'a>> {
+    if let AstKind::CallExpression(call) = initializer {
+        let e = skipParentheses(call.callee.to_ast_kind(), None);
+        return if matches!(e, AstKind::Function(_) | AstKind::ArrowFunctionExpression(_)) { Some(*initializer) } else { None };
+    }
+    if matches!(initializer, AstKind::Function(_) | AstKind::Class(_) | AstKind::ArrowFunctionExpression(_)) {
+        return Some(*initializer);
+    }
+    if let AstKind::ObjectExpression(object) = initializer {
+        if object.properties.is_empty() || isPrototypeAssignment {
+            return Some(*initializer);
+        }
+    }
+    None
+}
+// endregion: 3862
+
+// region: 3880
+/** @internal */
+pub fn getNameOfExpando<'a>(node: &AstKind<'a>) -> Option<AstKind<'a>> {
+    let parent = node.parent()?;
+    // ! rb `||` and `??` are LogicalExpressions and `=` is an AssignmentExpression in oxc
+    if matches!(parent, AstKind::AssignmentExpression(_) | AstKind::LogicalExpression(_)) {
+        let parent = match parent {
+            AstKind::LogicalExpression(logical) if matches!(logical.operator, LogicalOperator::Or | LogicalOperator::Coalesce) && matches!(parent.parent(), Some(AstKind::AssignmentExpression(_))) => parent.parent().unwrap(),
+            _ => parent,
+        };
+        if let AstKind::AssignmentExpression(assignment) = parent {
+            if assignment.operator == AssignmentOperator::Assign && isIdentifier(&assignment.left.to_ast_kind()) {
+                return Some(assignment.left.to_ast_kind());
+            }
+        }
+    } else if let AstKind::VariableDeclarator(declaration) = parent {
+        return Some(declaration.id.to_ast_kind());
+    }
+    None
+}
+// endregion: 3895
+
+// region: 3904
+/** @internal */
+pub fn isAliasableExpression(e: &AstKind) -> bool { isEntityNameExpression(e) || isClassExpression(e) }
+
+/** @internal */
+pub fn exportAssignmentIsAlias(node: &AstKind) -> bool {
+    let e = match node {
+        AstKind::TSExportAssignment(export) => export.expression.to_ast_kind(),
+        _ => BinaryExpression::from_ast_kind(node).unwrap().right().to_ast_kind(),
+    };
+    isAliasableExpression(&e)
+}
+// endregion: 3912
+
 // region: 3919
 /** @internal */
 pub fn getRightMostAssignedExpression<'a>(node: AstKind<'a>) -> AstKind<'a> {
@@ -595,7 +903,7 @@ pub fn isBindableStaticElementAccessExpression(node: &AstKind, excludeThisKeywor
 pub fn isBindableStaticNameExpression(node: &AstKind, excludeThisKeyword: Option<bool>) -> bool { isEntityNameExpression(node) || isBindableStaticAccessExpression(node, excludeThisKeyword) }
 
 /** @internal */
-pub fn getNameOrArgument<'a>(expr: &'a AccessExpression<'a>) -> AstKind<'a> {
+pub fn getNameOrArgument<'a>(expr: &AccessExpression<'a>) -> AstKind<'a> {
     match expr {
         AccessExpression::PropertyAccessExpression(property_access) => property_access.property().to_ast_kind(),
         AccessExpression::ElementAccessExpression(element_access) => element_access.argument_expression.to_ast_kind(),
@@ -718,12 +1026,12 @@ pub fn getAssignmentDeclarationPropertyAccessKind(lhs: AccessExpression) -> Assi
 }
 
 /** @internal */
-pub fn getInitializerOfBinaryExpression<'a>(expr: BinaryExpression<'a>) -> BinaryExpression<'a> {
+pub fn getInitializerOfBinaryExpression<'a>(expr: BinaryExpression<'a>) -> AstKindExpression<'a> {
     let mut expr = expr;
     while let Some(right) = BinaryExpression::from_ast_kind(&expr.right().to_ast_kind()) {
         expr = right;
     }
-    expr
+    expr.right()
 }
 // endregion: 4121
 
@@ -742,6 +1050,168 @@ pub fn setValueDeclaration<'a>(symbol: rc_cell!(Symbol<'a>), node: AstKindDeclar
 }
 // endregion: 4154
 
+// region: 4163
+/** @internal */
+pub fn canHaveJSDoc(node: &AstKind) -> bool {
+    // ! rb binary and property access expressions are left out: they start at the same token as the expression
+    // statement that owns their JSDoc in oxc, so the comment would be found twice
+    matches!(
+        node,
+        AstKind::ArrowFunctionExpression(_)
+            | AstKind::BlockStatement(_)
+            | AstKind::BreakStatement(_)
+            | AstKind::TSCallSignatureDeclaration(_)
+            | AstKind::SwitchCase(_)
+            | AstKind::Class(_)
+            | AstKind::StaticBlock(_)
+            | AstKind::TSConstructSignatureDeclaration(_)
+            | AstKind::TSConstructorType(_)
+            | AstKind::ContinueStatement(_)
+            | AstKind::DebuggerStatement(_)
+            | AstKind::DoWhileStatement(_)
+            | AstKind::EmptyStatement(_)
+            | AstKind::TSEnumDeclaration(_)
+            | AstKind::TSEnumMember(_)
+            | AstKind::TSExportAssignment(_)
+            | AstKind::ExportAllDeclaration(_)
+            | AstKind::ExportDefaultDeclaration(_)
+            | AstKind::ExportNamedDeclaration(_)
+            | AstKind::ExportSpecifier(_)
+            | AstKind::ExpressionStatement(_)
+            | AstKind::ForInStatement(_)
+            | AstKind::ForOfStatement(_)
+            | AstKind::ForStatement(_)
+            | AstKind::Function(_)
+            | AstKind::TSFunctionType(_)
+            | AstKind::IfStatement(_)
+            | AstKind::ImportDeclaration(_)
+            | AstKind::TSImportEqualsDeclaration(_)
+            | AstKind::TSIndexSignature(_)
+            | AstKind::TSInterfaceDeclaration(_)
+            | AstKind::LabeledStatement(_)
+            | AstKind::MethodDefinition(_)
+            | AstKind::TSMethodSignature(_)
+            | AstKind::TSModuleDeclaration(_)
+            | AstKind::TSNamedTupleMember(_)
+            | AstKind::TSNamespaceExportDeclaration(_)
+            | AstKind::ObjectExpression(_)
+            | AstKind::FormalParameter(_)
+            | AstKind::ParenthesizedExpression(_)
+            | AstKind::ObjectProperty(_)
+            | AstKind::PropertyDefinition(_)
+            | AstKind::AccessorProperty(_)
+            | AstKind::TSPropertySignature(_)
+            | AstKind::ReturnStatement(_)
+            | AstKind::SwitchStatement(_)
+            | AstKind::ThrowStatement(_)
+            | AstKind::TryStatement(_)
+            | AstKind::TSTypeAliasDeclaration(_)
+            | AstKind::TSTypeParameter(_)
+            | AstKind::VariableDeclarator(_)
+            | AstKind::VariableDeclarationList(_)
+            | AstKind::WhileStatement(_)
+            | AstKind::WithStatement(_)
+    )
+}
+
+// `(node as JSDocContainer).jsDoc`
+/** @internal */
+pub fn getJSDocNodes(node: &AstKind) -> Rc<Vec<JSDoc>> {
+    if !canHaveJSDoc(node) {
+        return Rc::new(Vec::new());
+    }
+    node.jsDoc().unwrap_or_else(|| addJSDocComment(node))
+}
+
+/** @internal */
+pub fn hasJSDocNodes(node: &AstKind) -> bool { !getJSDocNodes(node).is_empty() }
+
+// ! rb the JSDoc comments are flattened into their tags: the callers only ever need the tags
+/** @internal */
+pub fn getJSDocCommentsAndTags(hostNode: &AstKind) -> Vec<JSDocTag> {
+    let mut result: Vec<JSDocTag> = Vec::new();
+    // Pull parameter comments from declaring function as well
+    if let AstKind::VariableDeclarator(decl) = hostNode {
+        if let Some(init) = &decl.init {
+            result.extend(getJSDocNodes(&init.to_ast_kind()).iter().flat_map(|jsDoc| jsDoc.tags.iter().cloned()));
+        }
+    }
+
+    let mut node = Some(*hostNode);
+    while let Some(current) = node {
+        if current.parent().is_none() {
+            break;
+        }
+        result.extend(getJSDocNodes(&current).iter().flat_map(|jsDoc| jsDoc.tags.iter().cloned()));
+        if let AstKind::FormalParameter(param) = current {
+            result.extend(getJSDocParameterTags(param, &current));
+            break;
+        }
+        // if (node.kind === SyntaxKind.TypeParameter) {
+        //     result = addRange(result, (noCache ? getJSDocTypeParameterTagsNoCache : getJSDocTypeParameterTags)(node as TypeParameterDeclaration));
+        //     break;
+        // }
+        node = getNextJSDocCommentLocation(&current);
+    }
+    result
+}
+
+// getJSDocParameterTagsWorker
+fn getJSDocParameterTags(param: &FormalParameter, node: &AstKind) -> Vec<JSDocTag> {
+    let BindingPatternKind::BindingIdentifier(name) = &param.pattern.kind else {
+        // if (param.name) { if (isIdentifier(param.name)) {...} else { const i = param.parent.parameters.findIndex(p => p === param); ...
+        return Vec::new();
+    };
+    // the function is the parent of the parameter list
+    let Some(func) = node.parent().and_then(|params| params.parent()) else {
+        return Vec::new();
+    };
+    getJSDocCommentsAndTags(&func).into_iter().filter(|tag| matches!(&tag.kind, JSDocTagKind::Parameter { name: tagName, .. } if tagName == name.name.as_str())).collect()
+}
+
+// The variable statement is the declaration list in oxc
+/** @internal */
+pub fn getSingleVariableOfVariableStatement<'a>(node: &AstKind<'a>) -> Option<&'a VariableDeclarator<'a>> {
+    match node {
+        AstKind::VariableDeclarationList(list) if list.declarations.len() == 1 => list.declarations.first(),
+        _ => None,
+    }
+}
+
+fn getNextJSDocCommentLocation<'a>(node: &AstKind<'a>) -> Option<AstKind<'a>> {
+    let parent = node.parent()?;
+    if matches!(parent, AstKind::ObjectProperty(_) | AstKind::TSExportAssignment(_) | AstKind::ExportDefaultDeclaration(_) | AstKind::PropertyDefinition(_) | AstKind::ReturnStatement(_))
+        // ! rb `export` is a modifier in TypeScript, but it wraps the declaration in oxc
+        || matches!(parent, AstKind::ExportNamedDeclaration(_))
+        || matches!(parent, AstKind::ExpressionStatement(_)) && matches!(node, AstKind::StaticMemberExpression(_))
+        || matches!(parent, AstKind::TSModuleDeclaration(module) if matches!(module.body, Some(TSModuleDeclarationBody::TSModuleDeclaration(_))))
+        || isAssignmentExpression(node, None)
+    {
+        return Some(parent);
+    }
+    // The JSDoc of a variable is on its statement, which is its parent here
+    if getSingleVariableOfVariableStatement(&parent).is_some_and(|decl| decl.span == node.span()) {
+        return Some(parent);
+    }
+    // Try to recognize this pattern when node is initializer of variable declaration and JSDoc comments are on containing variable statement.
+    // /**
+    //   * @param {number} name
+    //   * @returns {number}
+    //   */
+    // var x = function(name) { return name.length; }
+    let grandparent = parent.parent()?;
+    if isAssignmentExpression(&parent, None) || getSingleVariableOfVariableStatement(&grandparent).and_then(|decl| decl.init.as_ref()).is_some_and(|init| init.span() == node.span()) {
+        return Some(grandparent);
+    }
+    None
+}
+// endregion: 4260
+
+// region: 4690
+/** @internal */
+pub fn isPushOrUnshiftIdentifier(node: &IdentifierName) -> bool { node.name == "push" || node.name == "unshift" }
+// endregion: 4693
+
 // region: 4871
 /** @internal */
 pub fn skipParentheses<'a>(node: AstKind<'a>, exclude_jsdoc_type_assertions: Option<bool>) -> AstKind<'a> {
@@ -838,6 +1308,19 @@ pub fn getSymbolNameForPrivateIdentifier(containingClassSymbol: rc_cell!(Symbol)
 pub fn isKnownSymbol(symbol: &Symbol) -> bool { startsWith(&symbol.escapedName.to_string(), "__@", None) }
 // endregion: 5288
 
+// region: 6970
+/** @internal */
+pub fn isStatic(node: &AstKind) -> bool {
+    // https://tc39.es/ecma262/#sec-static-semantics-isstatic
+    isClassElement(node) && hasStaticModifier(node) || isClassStaticBlockDeclaration(Some(node))
+}
+// endregion: 6975
+
+// region: 6984
+/** @internal */
+pub fn hasStaticModifier(node: &AstKind) -> bool { hasSyntacticModifier(node, ModifierFlags::Static) }
+// endregion: 6988
+
 // region: 6995
 /** @internal */
 pub fn hasSyntacticModifier(node: &AstKind, flags: ModifierFlags) -> bool { getSelectedSyntacticModifierFlags(node, flags).0 != 0 }
@@ -867,6 +1350,11 @@ pub fn getModifierFlagsWorker(node: &AstKind, includeJSDoc: bool, alwaysIncludeJ
 }
 // endregion: 7070
 
+// region: 7076
+/** @internal */
+pub fn getEffectiveModifierFlags(node: &AstKind) -> ModifierFlags { getModifierFlagsWorker(node, true, None) }
+// endregion: 7080
+
 // region: 7086
 /**
  * Gets the ModifierFlags for syntactic modifiers on the provided node. The modifiers will be cached on the node to improve performance.
@@ -935,6 +1423,11 @@ pub fn modifierToFlag(token: &ModifierLike) -> ModifierFlags {
 }
 // endregion: 7203
 
+// region: 7206
+/** @internal */
+pub fn isEmptyObjectLiteral(expression: &AstKind) -> bool { matches!(expression, AstKind::ObjectExpression(object) if object.properties.is_empty()) }
+// endregion: 7211
+
 // region: 7235
 /** @internal */
 pub fn isAssignmentOperator(op: BinaryOperator) -> bool { matches!(op, BinaryOperator::AssignmentOperator(_)) }
@@ -973,6 +1466,18 @@ pub fn isDestructuringAssignment(node: &AstKind) -> bool {
 pub fn isEntityNameExpression(node: &AstKind) -> bool { isIdentifier(node) || isPropertyAccessEntityNameExpression(node) }
 // endregion: 7305
 
+// region: 7316
+/** @internal */
+pub fn isDottedName(node: &Expression) -> bool {
+    match node {
+        Expression::Identifier(_) | Expression::ThisExpression(_) | Expression::Super(_) | Expression::MetaProperty(_) => true,
+        Expression::StaticMemberExpression(property_access) => isDottedName(&property_access.object),
+        Expression::ParenthesizedExpression(paren) => isDottedName(&paren.expression),
+        _ => false,
+    }
+}
+// endregion: 7325
+
 // region: 7335
 /** @internal */
 pub fn isPropertyAccessEntityNameExpression(node: &AstKind) -> bool {
@@ -993,6 +1498,17 @@ pub fn isPrototypeAccess(node: &AstKind) -> bool { isBindableStaticAccessExpress
 pub fn isAccessExpression(node: &AstKind) -> bool { AccessExpression::from_ast_kind(node).is_some() }
 // endregion: 8142
 
+// region: 8249
+/** @internal */
+pub fn getLeftmostAccessExpression<'a>(expr: &AstKind<'a>) -> AstKind<'a> {
+    let mut expr = *expr;
+    while let Some(access) = AccessExpression::from_ast_kind(&expr) {
+        expr = access.object().to_ast_kind();
+    }
+    expr
+}
+// endregion: 8255
+
 // region: 8366
 /** @internal */
 pub fn formatStringFromArgs(text: &str, args: &DiagnosticArguments) -> String {
@@ -1030,6 +1546,85 @@ where
 pub fn getLocaleSpecificMessage(message: &DiagnosticMessage) -> String { unsafe { localizedDiagnosticMessages.as_ref().and_then(|messages| messages.get(&message.key)).map(|s| s.to_string()).unwrap_or_else(|| message.message.clone()) } }
 // endregion: 8390
 
+// region: 8391
+/** @internal */
+pub fn createDetachedDiagnostic<'a>(fileName: &str, sourceText: &str, start: u32, mut length: u32, message: DiagnosticMessage, args: DiagnosticArguments) -> DiagnosticWithDetachedLocation<'a> {
+    if start + length > sourceText.len() as u32 {
+        length = sourceText.len() as u32 - start;
+    }
+
+    assertDiagnosticLocation(sourceText, start, length);
+    let mut text = getLocaleSpecificMessage(&message);
+
+    if !args.is_empty() {
+        text = formatStringFromArgs(&text, &args);
+    }
+
+    DiagnosticWithDetachedLocation {
+        file: None,
+        start: Some(start),
+        length: Some(length),
+        messageText: StringOrDiagnosticMessageChain::String(text),
+        category: message.category,
+        code: message.code,
+        reportsUnnecessary: message.reportsUnnecessary.map(|_| ()),
+        reportsDeprecated: message.reportsDeprecated.map(|_| ()),
+
+        fileName: Some(fileName.to_string()),
+        source: None,
+        relatedInformation: None,
+        skippedOn: None,
+        canonicalHead: None,
+    }
+}
+
+fn isDiagnosticWithDetachedLocation(diagnostic: &Diagnostic) -> bool { diagnostic.file.is_none() && diagnostic.start.is_some() && diagnostic.length.is_some() && diagnostic.fileName.is_some() }
+
+fn attachFileToDiagnostic<'a>(diagnostic: &DiagnosticWithDetachedLocation<'a>, file: &'a SourceFile<'a>) -> DiagnosticWithLocation<'a> {
+    let fileName = file.filepath().to_string_lossy().to_string();
+    let length = file.source_text.len() as u32;
+    assert_eq!(diagnostic.fileName.as_deref(), Some(fileName.as_str()));
+    assert!(diagnostic.start.unwrap() <= length);
+    assert!(diagnostic.start.unwrap() + diagnostic.length.unwrap() <= length);
+    let mut diagnosticWithLocation = DiagnosticWithLocation {
+        file: Some(file),
+        start: diagnostic.start,
+        length: diagnostic.length,
+        messageText: diagnostic.messageText.clone(),
+        category: diagnostic.category,
+        code: diagnostic.code,
+        reportsUnnecessary: diagnostic.reportsUnnecessary,
+        reportsDeprecated: None,
+
+        fileName: None,
+        source: None,
+        relatedInformation: None,
+        skippedOn: None,
+        canonicalHead: None,
+    };
+    if let Some(relatedInformation) = &diagnostic.relatedInformation {
+        diagnosticWithLocation.relatedInformation = Some(
+            relatedInformation
+                .iter()
+                .map(|related| {
+                    if isDiagnosticWithDetachedLocation(related) && related.fileName.as_deref() == Some(fileName.as_str()) {
+                        assert!(related.start.unwrap() <= length);
+                        assert!(related.start.unwrap() + related.length.unwrap() <= length);
+                        attachFileToDiagnostic(related, file)
+                    } else {
+                        related.clone()
+                    }
+                })
+                .collect(),
+        );
+    }
+    diagnosticWithLocation
+}
+
+/** @internal */
+pub fn attachFileToDiagnostics<'a>(diagnostics: &[DiagnosticWithDetachedLocation<'a>], file: &'a SourceFile<'a>) -> Vec<DiagnosticWithLocation<'a>> { diagnostics.iter().map(|diagnostic| attachFileToDiagnostic(diagnostic, file)).collect() }
+// endregion: 8464
+
 // region: 8465
 /** @internal */
 /** @internal */
@@ -1070,7 +1665,63 @@ pub fn formatMessage(message: &DiagnosticMessage, args: &DiagnosticArguments) ->
 
     text
 }
-// endregion: 8498
+
+/** @internal */
+pub fn createCompilerDiagnostic<'a>(message: DiagnosticMessage, args: DiagnosticArguments) -> Diagnostic<'a> {
+    let mut text = getLocaleSpecificMessage(&message);
+
+    if !args.is_empty() {
+        text = formatStringFromArgs(&text, &args);
+    }
+
+    Diagnostic {
+        file: None,
+        start: None,
+        length: None,
+        messageText: StringOrDiagnosticMessageChain::String(text),
+        category: message.category,
+        code: message.code,
+        reportsUnnecessary: message.reportsUnnecessary.map(|_| ()),
+        reportsDeprecated: message.reportsDeprecated.map(|_| ()),
+
+        fileName: None,
+        source: None,
+        relatedInformation: None,
+        skippedOn: None,
+        canonicalHead: None,
+    }
+}
+// endregion: 8527
+
+// region: 8645
+/** @internal */
+pub fn compareDiagnostics(d1: &Diagnostic, d2: &Diagnostic) -> std::cmp::Ordering { compareDiagnosticsSkipRelatedInformation(d1, d2).then_with(|| compareRelatedInformation(d1, d2)) }
+
+/** @internal */
+pub fn compareDiagnosticsSkipRelatedInformation(d1: &Diagnostic, d2: &Diagnostic) -> std::cmp::Ordering {
+    let code1 = d1.canonicalHead.as_ref().map_or(d1.code, |head| head.code);
+    let code2 = d2.canonicalHead.as_ref().map_or(d2.code, |head| head.code);
+    getDiagnosticFilePath(d1).cmp(&getDiagnosticFilePath(d2)).then(d1.start.cmp(&d2.start)).then(d1.length.cmp(&d2.length)).then(code1.cmp(&code2)).then_with(|| compareMessageText(d1, d2))
+}
+
+fn compareRelatedInformation(d1: &Diagnostic, d2: &Diagnostic) -> std::cmp::Ordering {
+    match (&d1.relatedInformation, &d2.relatedInformation) {
+        (None, None) => std::cmp::Ordering::Equal,
+        (Some(related1), Some(related2)) => related2.len().cmp(&related1.len()).then_with(|| related1.iter().zip(related2.iter()).map(|(d1i, d2i)| compareDiagnostics(d1i, d2i)).find(|comparison| comparison.is_ne()).unwrap_or(std::cmp::Ordering::Equal)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+    }
+}
+
+// ! rb message chains are compared by their flattened text
+fn compareMessageText(d1: &Diagnostic, d2: &Diagnostic) -> std::cmp::Ordering {
+    let text1 = d1.canonicalHead.as_ref().map_or_else(|| flattenDiagnosticMessageText(Some(&d1.messageText), "\n", None), |head| head.messageText.clone());
+    let text2 = d2.canonicalHead.as_ref().map_or_else(|| flattenDiagnosticMessageText(Some(&d2.messageText), "\n", None), |head| head.messageText.clone());
+    text1.cmp(&text2)
+}
+
+fn getDiagnosticFilePath(diagnostic: &Diagnostic) -> Option<String> { diagnostic.file.map(|file| file.filepath().to_string_lossy().to_string()) }
+// endregion: 8736
 
 // region: 8737
 /**
@@ -1240,7 +1891,7 @@ pub fn getResolvePackageJsonImports(compiler_options: &CompilerOptions) -> bool
         return false;
     }
 
-    match compiler_options.resolvePackageJsonExports {
+    match compiler_options.resolvePackageJsonImports {
         Some(value) => value,
         None => matches!(module_resolution, ModuleResolutionKind::Node16 | ModuleResolutionKind::NodeNext | ModuleResolutionKind::Bundler),
     }
@@ -1386,6 +2037,473 @@ pub fn hasZeroOrOneAsteriskCharacter(str: &str) -> bool {
 }
 // endregion: 9202
 
+// region: 9246
+/** @internal */
+#[derive(Debug, Clone, Default)]
+pub struct FileSystemEntries {
+    pub files: Vec<String>,
+    pub directories: Vec<String>,
+}
+
+/** @internal */
+#[derive(Debug, Clone, Default)]
+pub struct FileMatcherPatterns {
+    /** One pattern for each "include" spec. */
+    pub includeFilePatterns: Option<Vec<WildcardPattern>>,
+    /** A pattern that matches files matched by any of the "include" specs. */
+    pub includeFilePattern: Option<Vec<WildcardPattern>>,
+    pub includeDirectoryPattern: Option<Vec<WildcardPattern>>,
+    pub excludePattern: Option<Vec<WildcardPattern>>,
+    pub basePaths: Vec<String>,
+}
+
+/**
+ * The normalized, absolute path components of a wildcard spec. The `regex` crate has no lookahead, so rather than
+ * building the regular expressions TypeScript uses, specs are matched against paths one component at a time and
+ * the usage decides how `**`, `*` and `?` behave.
+ *
+ * @internal
+ */
+pub type WildcardPattern = Vec<String>;
+
+/** @internal */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WildcardMatcherUsage {
+    Files,
+    Directories,
+    Exclude,
+}
+
+/** @internal */
+pub const commonPackageFolders: [&str; 3] = ["node_modules", "bower_components", "jspm_packages"];
+
+/** @internal */
+pub fn getRegularExpressionForWildcard(specs: Option<&[String]>, basePath: &str, usage: WildcardMatcherUsage) -> Option<Vec<WildcardPattern>> {
+    let patterns = getRegularExpressionsForWildcards(specs, basePath, usage)?;
+    if patterns.is_empty() {
+        return None;
+    }
+    Some(patterns)
+}
+
+/** @internal */
+pub fn getRegularExpressionsForWildcards(specs: Option<&[String]>, basePath: &str, usage: WildcardMatcherUsage) -> Option<Vec<WildcardPattern>> {
+    let specs = specs?;
+    if specs.is_empty() {
+        return None;
+    }
+    Some(specs.iter().filter_map(|spec| getSubPatternFromSpec(spec, basePath, usage)).collect())
+}
+
+/**
+ * An "includes" path "foo" is implicitly a glob matching every file below "foo" if its last component has no extension,
+ * and does not contain any glob characters itself.
+ *
+ * @internal
+ */
+pub fn isImplicitGlob(lastPathComponent: &str) -> bool { !lastPathComponent.contains(['.', '*', '?']) }
+
+fn getSubPatternFromSpec(spec: &str, basePath: &str, usage: WildcardMatcherUsage) -> Option<WildcardPattern> {
+    let mut components = getNormalizedPathComponents(spec, Some(basePath));
+    let lastComponent = components.last().unwrap().clone();
+    if usage != WildcardMatcherUsage::Exclude && lastComponent == "**" {
+        return None;
+    }
+
+    // getNormalizedPathComponents includes the separator for the root component.
+    // We need to remove to create our pattern correctly.
+    components[0] = removeTrailingDirectorySeparator(&components[0]);
+
+    if isImplicitGlob(&lastComponent) {
+        components.push("**".to_string());
+        components.push("*".to_string());
+    }
+
+    Some(components)
+}
+
+/** @internal */
+pub fn matchesWildcardPattern(pattern: &WildcardPattern, path: &str, usage: WildcardMatcherUsage, useCaseSensitiveFileNames: bool) -> bool {
+    let canonicalize = |s: &str| if useCaseSensitiveFileNames { s.to_string() } else { s.to_lowercase() };
+    let mut pathComponents = getNormalizedPathComponents(path, None);
+    pathComponents[0] = removeTrailingDirectorySeparator(&pathComponents[0]);
+    let pattern: Vec<String> = pattern.iter().map(|c| canonicalize(c)).collect();
+    let pathComponents: Vec<String> = pathComponents.iter().map(|c| canonicalize(c)).collect();
+    matchesWildcardComponents(&pattern, &pathComponents, usage)
+}
+
+fn matchesWildcardComponents(pattern: &[String], path: &[String], usage: WildcardMatcherUsage) -> bool {
+    match pattern.first() {
+        // "exclude" patterns also match everything below the excluded path
+        None => path.is_empty() || usage == WildcardMatcherUsage::Exclude,
+        Some(component) if component == "**" => {
+            if matchesWildcardComponents(&pattern[1..], path, usage) {
+                return true;
+            }
+            match path.first() {
+                // Files and directories only recurse into directories that don't start with `.` and aren't package folders
+                Some(current) if usage == WildcardMatcherUsage::Exclude || (!current.starts_with('.') && !commonPackageFolders.contains(&current.as_str())) => matchesWildcardComponents(pattern, &path[1..], usage),
+                _ => false,
+            }
+        }
+        Some(component) => match path.first() {
+            // "directories" patterns match every directory that could contain a matching file
+            None => usage == WildcardMatcherUsage::Directories,
+            Some(current) => {
+                let isLastComponent = pattern.len() == 1 && path.len() == 1;
+                matchesWildcardComponent(component, current, usage, isLastComponent) && matchesWildcardComponents(&pattern[1..], &path[1..], usage)
+            }
+        },
+    }
+}
+
+fn matchesWildcardComponent(component: &str, name: &str, usage: WildcardMatcherUsage, isLastComponent: bool) -> bool {
+    if component == name {
+        return true;
+    }
+    if !component.contains(['*', '?']) {
+        return false;
+    }
+    // Patterns should not include subfolders like node_modules unless they are
+    // explicitly included as part of the path.
+    if usage != WildcardMatcherUsage::Exclude && commonPackageFolders.contains(&name) {
+        return false;
+    }
+    let component: Vec<char> = component.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matchesWildcardCharacters(&component, &name, 0, 0, usage, isLastComponent)
+}
+
+fn matchesWildcardCharacters(component: &[char], name: &[char], p: usize, n: usize, usage: WildcardMatcherUsage, isLastComponent: bool) -> bool {
+    if p == component.len() {
+        return n == name.len();
+    }
+    match component[p] {
+        '*' => {
+            let mut end = n;
+            loop {
+                if matchesWildcardCharacters(component, name, p + 1, end, usage, isLastComponent) {
+                    return true;
+                }
+                if end == name.len() {
+                    return false;
+                }
+                if usage != WildcardMatcherUsage::Exclude && name[end] == '.' {
+                    // The * and ? wildcards should not match directories or files that start with . if they
+                    // appear first in a component. Dotted directories and files can be included explicitly
+                    // like so: **/.*/.*
+                    if p == 0 && end == 0 {
+                        return false;
+                    }
+                    // The * wildcard should not match a trailing `.min.js`, so that `*.js` skips minified files
+                    if usage == WildcardMatcherUsage::Files && isLastComponent && name[end + 1..].iter().collect::<String>() == "min.js" {
+                        return false;
+                    }
+                }
+                end += 1;
+            }
+        }
+        '?' => n < name.len() && !(usage != WildcardMatcherUsage::Exclude && p == 0 && name[n] == '.') && matchesWildcardCharacters(component, name, p + 1, n + 1, usage, isLastComponent),
+        c => n < name.len() && name[n] == c && matchesWildcardCharacters(component, name, p + 1, n + 1, usage, isLastComponent),
+    }
+}
+
+fn matchesAnyWildcardPattern(patterns: &[WildcardPattern], path: &str, usage: WildcardMatcherUsage, useCaseSensitiveFileNames: bool) -> bool { patterns.iter().any(|pattern| matchesWildcardPattern(pattern, path, usage, useCaseSensitiveFileNames)) }
+
+/**
+ * @param path directory of the tsconfig.json
+ *
+ * @internal
+ */
+pub fn getFileMatcherPatterns(path: &str, excludes: Option<&[String]>, includes: Option<&[String]>, useCaseSensitiveFileNames: bool, currentDirectory: &str) -> FileMatcherPatterns {
+    let path = normalizePath(path);
+    let currentDirectory = normalizePath(currentDirectory);
+    let absolutePath = combinePaths(&currentDirectory, &[Some(&path)]);
+
+    FileMatcherPatterns {
+        includeFilePatterns: getRegularExpressionsForWildcards(includes, &absolutePath, WildcardMatcherUsage::Files),
+        includeFilePattern: getRegularExpressionForWildcard(includes, &absolutePath, WildcardMatcherUsage::Files),
+        includeDirectoryPattern: getRegularExpressionForWildcard(includes, &absolutePath, WildcardMatcherUsage::Directories),
+        excludePattern: getRegularExpressionForWildcard(excludes, &absolutePath, WildcardMatcherUsage::Exclude),
+        basePaths: getBasePaths(&path, includes, useCaseSensitiveFileNames),
+    }
+}
+
+/**
+ * @param path directory of the tsconfig.json
+ *
+ * @internal
+ */
+pub fn matchFiles(
+    path: &str, extensions: Option<&[&str]>, excludes: Option<&[String]>, includes: Option<&[String]>, useCaseSensitiveFileNames: bool, currentDirectory: &str, depth: Option<usize>, getFileSystemEntries: &dyn Fn(&str) -> FileSystemEntries, realpath: &dyn Fn(&str) -> String,
+) -> Vec<String> {
+    let path = normalizePath(path);
+    let currentDirectory = normalizePath(currentDirectory);
+
+    let patterns = getFileMatcherPatterns(&path, excludes, includes, useCaseSensitiveFileNames, &currentDirectory);
+
+    // Associate an array of results with each include pattern. This keeps results in order of the "include" order.
+    // If there are no "includes", then just put everything in results[0].
+    let mut results: Vec<Vec<String>> = match &patterns.includeFilePatterns {
+        Some(includeFilePatterns) => includeFilePatterns.iter().map(|_| vec![]).collect(),
+        None => vec![vec![]],
+    };
+    let mut visited = HashSet::new();
+    let toCanonical = |path: &str| if useCaseSensitiveFileNames { path.to_string() } else { path.to_lowercase() };
+
+    let mut visitor = MatchFilesVisitor { extensions, patterns: &patterns, useCaseSensitiveFileNames, getFileSystemEntries, realpath, toCanonical: &toCanonical, results: &mut results, visited: &mut visited };
+    for basePath in &patterns.basePaths {
+        visitor.visitDirectory(basePath, &combinePaths(&currentDirectory, &[Some(basePath)]), depth);
+    }
+
+    results.into_iter().flatten().collect()
+}
+
+struct MatchFilesVisitor<'v> {
+    extensions: Option<&'v [&'v str]>,
+    patterns: &'v FileMatcherPatterns,
+    useCaseSensitiveFileNames: bool,
+    getFileSystemEntries: &'v dyn Fn(&str) -> FileSystemEntries,
+    realpath: &'v dyn Fn(&str) -> String,
+    toCanonical: &'v dyn Fn(&str) -> String,
+    results: &'v mut Vec<Vec<String>>,
+    visited: &'v mut HashSet<String>,
+}
+
+impl MatchFilesVisitor<'_> {
+    fn visitDirectory(&mut self, path: &str, absolutePath: &str, mut depth: Option<usize>) {
+        let canonicalPath = (self.toCanonical)(&(self.realpath)(absolutePath));
+        if !self.visited.insert(canonicalPath) {
+            return;
+        }
+        let FileSystemEntries { mut files, mut directories } = (self.getFileSystemEntries)(path);
+        files.sort();
+        directories.sort();
+
+        for current in &files {
+            let name = combinePaths(path, &[Some(current)]);
+            let absoluteName = combinePaths(absolutePath, &[Some(current)]);
+            if self.extensions.is_some_and(|extensions| !fileExtensionIsOneOf(&name, extensions.to_vec())) {
+                continue;
+            }
+            if self.patterns.excludePattern.as_ref().is_some_and(|exclude| matchesAnyWildcardPattern(exclude, &absoluteName, WildcardMatcherUsage::Exclude, self.useCaseSensitiveFileNames)) {
+                continue;
+            }
+            match &self.patterns.includeFilePatterns {
+                None => self.results[0].push(name),
+                Some(includeFilePatterns) => {
+                    if let Some(includeIndex) = includeFilePatterns.iter().position(|pattern| matchesWildcardPattern(pattern, &absoluteName, WildcardMatcherUsage::Files, self.useCaseSensitiveFileNames)) {
+                        self.results[includeIndex].push(name);
+                    }
+                }
+            }
+        }
+
+        if let Some(remaining) = depth {
+            if remaining <= 1 {
+                return;
+            }
+            depth = Some(remaining - 1);
+        }
+
+        for current in &directories {
+            let name = combinePaths(path, &[Some(current)]);
+            let absoluteName = combinePaths(absolutePath, &[Some(current)]);
+            if self.patterns.includeDirectoryPattern.as_ref().map_or(true, |include| matchesAnyWildcardPattern(include, &absoluteName, WildcardMatcherUsage::Directories, self.useCaseSensitiveFileNames))
+                && self.patterns.excludePattern.as_ref().map_or(true, |exclude| !matchesAnyWildcardPattern(exclude, &absoluteName, WildcardMatcherUsage::Exclude, self.useCaseSensitiveFileNames))
+            {
+                self.visitDirectory(&name, &absoluteName, depth);
+            }
+        }
+    }
+}
+
+/**
+ * Computes the unique non-wildcard base paths amongst the provided include patterns.
+ */
+fn getBasePaths(path: &str, includes: Option<&[String]>, useCaseSensitiveFileNames: bool) -> Vec<String> {
+    // Storage for our results in the form of literal paths (e.g. the paths as written by the user).
+    let mut basePaths = vec![path.to_string()];
+
+    if let Some(includes) = includes {
+        // Storage for literal base paths amongst the include patterns.
+        let mut includeBasePaths = vec![];
+        for include in includes {
+            // We also need to check the relative paths by converting them to absolute and normalizing
+            // in case they escape the base path (e.g "..\somedirectory")
+            let absolute = if isRootedDiskPath(include) { include.clone() } else { normalizePath(&combinePaths(path, &[Some(include)])) };
+            // Append the literal and canonical candidate base paths.
+            includeBasePaths.push(getIncludeBasePath(&absolute));
+        }
+
+        // Sort the offsets array using either the literal or canonical path representations.
+        if useCaseSensitiveFileNames {
+            includeBasePaths.sort();
+        } else {
+            includeBasePaths.sort_by_key(|basePath| basePath.to_uppercase());
+        }
+
+        // Iterate over each include base path and include unique base paths that are not a
+        // subpath of an existing base path
+        for includeBasePath in includeBasePaths {
+            if basePaths.iter().all(|basePath| !containsPath(basePath, &includeBasePath, Some(path), !useCaseSensitiveFileNames)) {
+                basePaths.push(includeBasePath);
+            }
+        }
+    }
+
+    basePaths
+}
+
+fn getIncludeBasePath(absolute: &str) -> String {
+    match absolute.find(['*', '?']) {
+        // No "*" or "?" in the path
+        None => {
+            if !hasExtension(absolute) {
+                absolute.to_string()
+            } else {
+                removeTrailingDirectorySeparator(&getDirectoryPath(absolute))
+            }
+        }
+        Some(wildcardOffset) => absolute[..absolute[..wildcardOffset].rfind(directorySeparator).unwrap_or(0)].to_string(),
+    }
+}
+// endregion: 9421
+
+// region: 9428
+/** @internal */
+pub fn ensureScriptKind(fileName: &str, scriptKind: Option<ScriptKind>) -> ScriptKind {
+    // Using scriptKind as a condition handles both:
+    // - 'scriptKind' is unspecified and thus it is `undefined`
+    // - 'scriptKind' is set and it is `Unknown` (0)
+    // If the 'scriptKind' is 'undefined' or 'Unknown' then we attempt
+    // to get the ScriptKind from the file name. If it cannot be resolved
+    // from the file name then the default 'TS' script kind is returned.
+    match scriptKind {
+        Some(scriptKind) if scriptKind != ScriptKind::Unknown => scriptKind,
+        _ => match getScriptKindFromFileName(fileName) {
+            ScriptKind::Unknown => ScriptKind::TS,
+            scriptKind => scriptKind,
+        },
+    }
+}
+
+/** @internal */
+pub fn getScriptKindFromFileName(fileName: &str) -> ScriptKind {
+    let ext = fileName.rfind('.').map_or("", |index| &fileName[index..]);
+    match ext.to_lowercase().as_str() {
+        ".js" | ".cjs" | ".mjs" => ScriptKind::JS,
+        ".jsx" => ScriptKind::JSX,
+        ".ts" | ".cts" | ".mts" => ScriptKind::TS,
+        ".tsx" => ScriptKind::TSX,
+        ".json" => ScriptKind::JSON,
+        _ => ScriptKind::Unknown,
+    }
+}
+// endregion: 9466
+
+// region: 9528
+/**
+ *  Groups of supported extensions in order of file resolution precedence. (eg, TS > TSX > DTS and seperately, CTS > DCTS)
+ *
+ * @internal
+ */
+pub static supportedTSExtensions: [&[Extension]; 3] = [&[Extension::Ts, Extension::Tsx, Extension::Dts], &[Extension::Cts, Extension::Dcts], &[Extension::Mts, Extension::Dmts]];
+/** @internal */
+pub static supportedTSExtensionsFlat: [Extension; 7] = [Extension::Ts, Extension::Tsx, Extension::Dts, Extension::Cts, Extension::Dcts, Extension::Mts, Extension::Dmts];
+/** @internal */
+pub static supportedTSExtensionsWithJson: [&[Extension]; 4] = [&[Extension::Ts, Extension::Tsx, Extension::Dts], &[Extension::Cts, Extension::Dcts], &[Extension::Mts, Extension::Dmts], &[Extension::Json]];
+/** @internal */
+pub static supportedTSExtensionsForExtractExtension: [Extension; 7] = [Extension::Dts, Extension::Dcts, Extension::Dmts, Extension::Cts, Extension::Mts, Extension::Ts, Extension::Tsx];
+/** @internal */
+pub static supportedJSExtensions: [&[Extension]; 3] = [&[Extension::Js, Extension::Jsx], &[Extension::Mjs], &[Extension::Cjs]];
+/** @internal */
+pub static supportedJSExtensionsFlat: [Extension; 4] = [Extension::Js, Extension::Jsx, Extension::Mjs, Extension::Cjs];
+static allSupportedExtensions: [&[Extension]; 3] = [&[Extension::Ts, Extension::Tsx, Extension::Dts, Extension::Js, Extension::Jsx], &[Extension::Cts, Extension::Dcts, Extension::Cjs], &[Extension::Mts, Extension::Dmts, Extension::Mjs]];
+static allSupportedExtensionsWithJson: [&[Extension]; 4] = [&[Extension::Ts, Extension::Tsx, Extension::Dts, Extension::Js, Extension::Jsx], &[Extension::Cts, Extension::Dcts, Extension::Cjs], &[Extension::Mts, Extension::Dmts, Extension::Mjs], &[Extension::Json]];
+/** @internal */
+pub static supportedDeclarationExtensions: [Extension; 3] = [Extension::Dts, Extension::Dcts, Extension::Dmts];
+/** @internal */
+pub static supportedTSImplementationExtensions: [Extension; 4] = [Extension::Ts, Extension::Cts, Extension::Mts, Extension::Tsx];
+// endregion: 9534
+
+// region: 9555
+/** @internal */
+pub fn getSupportedExtensions(options: Option<&CompilerOptions>) -> &'static [&'static [Extension]] {
+    let needJsExtensions = options.is_some_and(getAllowJSCompilerOption);
+    if needJsExtensions {
+        &allSupportedExtensions
+    } else {
+        &supportedTSExtensions
+    }
+}
+
+/** @internal */
+pub fn getSupportedExtensionsWithJsonIfResolveJsonModule(options: Option<&CompilerOptions>, supportedExtensions: &'static [&'static [Extension]]) -> &'static [&'static [Extension]] {
+    if !options.is_some_and(getResolveJsonModule) {
+        return supportedExtensions;
+    }
+    if std::ptr::eq(supportedExtensions, &allSupportedExtensions[..]) {
+        return &allSupportedExtensionsWithJson;
+    }
+    &supportedTSExtensionsWithJson
+}
+// endregion: 9598
+
+// region: 9633
+/** @internal */
+pub fn isExternalModuleNameRelative(moduleName: &str) -> bool {
+    // TypeScript 1.0 spec (April 2014): 11.2.1
+    // An external module name is "relative" if the first term is "." or "..".
+    // Update: We also consider a path like `C:\foo.ts` "relative" because we do not search for it in `node_modules` or treat it as an ambient module.
+    pathIsRelative(moduleName) || isRootedDiskPath(moduleName)
+}
+// endregion: 9639
+
+// region: 9716
+const extensionsToRemove: [Extension; 12] = [Extension::Dts, Extension::Dmts, Extension::Dcts, Extension::Mjs, Extension::Mts, Extension::Cjs, Extension::Cts, Extension::Ts, Extension::Js, Extension::Tsx, Extension::Jsx, Extension::Json];
+/** @internal */
+pub fn removeFileExtension(path: &str) -> String {
+    for ext in extensionsToRemove {
+        if let Some(extensionless) = tryRemoveExtension(path, ext.as_str()) {
+            return extensionless;
+        }
+    }
+    path.to_string()
+}
+
+/** @internal */
+pub fn tryRemoveExtension(path: &str, extension: &str) -> Option<String> {
+    if fileExtensionIs(path, extension) {
+        Some(removeExtension(path, extension))
+    } else {
+        None
+    }
+}
+
+/** @internal */
+pub fn removeExtension(path: &str, extension: &str) -> String { path[..path.len() - extension.len()].to_string() }
+
+/** @internal */
+pub fn changeExtension(path: &str, newExtension: &str) -> String {
+    let extensions: Vec<&str> = extensionsToRemove.iter().map(|e| e.as_str()).collect();
+    changeAnyExtension(path, newExtension, Some(&extensions), /*ignoreCase*/ false)
+}
+// endregion: 9743
+
+// region: 9779
+/** @internal */
+pub fn extensionIsTS(ext: &str) -> bool {
+    ext == Extension::Ts.as_str() || ext == Extension::Tsx.as_str() || ext == Extension::Dts.as_str() || ext == Extension::Cts.as_str() || ext == Extension::Mts.as_str() || ext == Extension::Dmts.as_str() || ext == Extension::Dcts.as_str() || (ext.starts_with(".d.") && ext.ends_with(".ts"))
+}
+
+/** @internal */
+pub fn resolutionExtensionIsTSOrJson(ext: &str) -> bool { extensionIsTS(ext) || ext == Extension::Json.as_str() }
+
+/** @internal */
+pub fn tryGetExtensionFromPath(path: &str) -> Option<Extension> { extensionsToRemove.iter().find(|e| fileExtensionIs(path, e.as_str())).copied() }
+// endregion: 9790
+
 // region: 9974
 /** @internal */
 pub fn positionIsSynthesized(pos: u32) -> bool {
@@ -1395,6 +2513,66 @@ pub fn positionIsSynthesized(pos: u32) -> bool {
 }
 // endregion: 9979
 
+// region: 9985
+/**
+ * Returns the input if there are no stars, a pattern if there is exactly one,
+ * and None if there are more.
+ *
+ * @internal
+ */
+pub fn tryParsePattern(pattern: &str) -> Option<StringOrPattern> {
+    let indexOfStar = pattern.find('*');
+    match indexOfStar {
+        None => Some(StringOrPattern::String(pattern.to_string())),
+        Some(indexOfStar) => pattern[indexOfStar + 1..].find('*').is_none().then(|| StringOrPattern::Pattern(Pattern { prefix: pattern[..indexOfStar].to_string(), suffix: pattern[indexOfStar + 1..].to_string() })),
+    }
+}
+
+/** @internal */
+#[derive(Debug, Clone, Default)]
+pub struct ParsedPatterns {
+    pub matchableStringSet: Option<HashSet<String>>,
+    pub patterns: Option<Vec<Pattern>>,
+}
+
+/** @internal */
+pub fn tryParsePatterns(pathMappings: &HashMap<String, Vec<String>>) -> ParsedPatterns {
+    let mut matchableStringSet: Option<HashSet<String>> = None;
+    let mut patterns: Option<Vec<Pattern>> = None;
+
+    for path in pathMappings.keys() {
+        match tryParsePattern(path) {
+            None => continue,
+            Some(StringOrPattern::String(patternOrStr)) => {
+                matchableStringSet.get_or_insert_with(HashSet::new).insert(patternOrStr);
+            }
+            Some(StringOrPattern::Pattern(patternOrStr)) => patterns.get_or_insert_with(Vec::new).push(patternOrStr),
+        }
+    }
+
+    ParsedPatterns { matchableStringSet, patterns }
+}
+
+/**
+ * patternOrStrings contains both patterns (containing "*") and regular strings.
+ * Return an exact match if possible, or a pattern match, or None.
+ * (These are verified by verifyCompilerOptions to have 0 or 1 "*" characters.)
+ *
+ * @internal
+ */
+pub fn matchPatternOrExact(parsedPatterns: &ParsedPatterns, candidate: &str) -> Option<StringOrPattern> {
+    let ParsedPatterns { matchableStringSet, patterns } = parsedPatterns;
+
+    if matchableStringSet.as_ref().is_some_and(|set| set.contains(candidate)) {
+        return Some(StringOrPattern::String(candidate.to_string()));
+    }
+
+    let patterns = patterns.as_ref()?;
+
+    findBestPatternMatch(patterns, |pattern| pattern, candidate).cloned().map(StringOrPattern::Pattern)
+}
+// endregion: 10040
+
 // region: 10058
 /** @internal */
 pub fn addRelatedInfo<'a, 'b>(diagnostic: &'a mut Diagnostic<'b>, relatedInformation: Vec<DiagnosticRelatedInformation<'b>>) {
@@ -1415,6 +2593,28 @@ pub fn addRelatedInfo<'a, 'b>(diagnostic: &'a mut Diagnostic<'b>, relatedInforma
 }
 // endregion: 10071
 
+// region: 10075
+/** @internal */
+pub fn tryExtractTSExtension(fileName: &str) -> Option<Extension> { supportedTSExtensionsForExtractExtension.iter().find(|extension| fileExtensionIs(fileName, extension.as_str())).copied() }
+// endregion: 10079
+
+// region: 10311
+/** @internal */
+pub fn isPlainJsFile(file: Option<&SourceFile>, checkJs: Option<bool>) -> bool {
+    // ! rb `// @ts-check` directives are not recorded on the source file, so only checkJs is consulted
+    file.is_some_and(|file| matches!(file.scriptKind(), ScriptKind::JS | ScriptKind::JSX)) /* && !file.checkJsDirective */ && checkJs.is_none()
+}
+// endregion: 10314
+
+// region: 10836
+/** @internal */
+pub fn getPathsBasePath(options: &CompilerOptions, host: &dyn ModuleResolutionHost) -> Option<String> {
+    // If no explicit paths were specified, there's no base path
+    options.paths.as_ref()?;
+    options.baseUrl.clone().or_else(|| options.pathsBasePath.clone().filter(|pathsBasePath| !pathsBasePath.is_empty())).or_else(|| host.getCurrentDirectory())
+}
+// endregion: 10843
+
 // region: 10851
 /** @internal */
 pub fn getEscapedTextOfJsxNamespacedName(node: &JSXNamespacedName) -> __String { format!("{}:{}", node.namespace.escaped_text(), idText(MemberName::from_ast_kind(&node.property.to_ast_kind()).unwrap())) }
@@ -1422,3 +2622,100 @@ pub fn getEscapedTextOfJsxNamespacedName(node: &JSXNamespacedName) -> __String {
 /** @internal */
 pub fn getTextOfJsxNamespacedName(node: &JSXNamespacedName) -> String { format!("{}:{}", idText(MemberName::from_ast_kind(&node.namespace.to_ast_kind()).unwrap()), idText(MemberName::from_ast_kind(&node.property.to_ast_kind()).unwrap())) }
 // endregion: 10861
+
+// region: 11253
+/** @internal */
+pub fn isDeclarationFileName(fileName: &str) -> bool { getDeclarationFileExtension(fileName).is_some() }
+
+/** @internal */
+pub fn getDeclarationFileExtension(fileName: &str) -> Option<String> {
+    let extensions: Vec<&str> = supportedDeclarationExtensions.iter().map(|e| e.as_str()).collect();
+    let standardExtension = getAnyExtensionFromPath(fileName, Some(&extensions), /*ignoreCase*/ false);
+    if !standardExtension.is_empty() {
+        return Some(standardExtension);
+    }
+    if fileExtensionIs(fileName, Extension::Ts.as_str()) {
+        let baseName = getBaseFileName(fileName);
+        if let Some(index) = baseName.rfind(".d.") {
+            return Some(baseName[index..].to_string());
+        }
+    }
+    None
+}
+// endregion: 11272
+
+#[cfg(test)]
+mod tests {
+    use super::{getSubPatternFromSpec, isImplicitGlob, matchFiles, matchesWildcardPattern, FileSystemEntries, WildcardMatcherUsage};
+
+    fn matches(spec: &str, path: &str, usage: WildcardMatcherUsage) -> bool {
+        let pattern = getSubPatternFromSpec(spec, "/proj", usage).expect("the spec should yield a pattern");
+        matchesWildcardPattern(&pattern, path, usage, true)
+    }
+
+    // lists the files below /proj that match, with the directories of `files` as the only ones on disk
+    fn read_directory(files: &[&str], includes: &[&str], excludes: &[&str]) -> Vec<String> {
+        let get_file_system_entries = |path: &str| {
+            let mut entries = FileSystemEntries::default();
+            for file in files {
+                let Some(rest) = file.strip_prefix(path).and_then(|rest| rest.strip_prefix('/')) else { continue };
+                match rest.split_once('/') {
+                    Some((directory, _)) if !entries.directories.iter().any(|entry| entry == directory) => entries.directories.push(directory.to_string()),
+                    Some(_) => {}
+                    None => entries.files.push(rest.to_string()),
+                }
+            }
+            entries
+        };
+        let includes: Vec<String> = includes.iter().map(|include| include.to_string()).collect();
+        let excludes: Vec<String> = excludes.iter().map(|exclude| exclude.to_string()).collect();
+        matchFiles("/proj", Some(&[".ts"][..]), Some(&excludes), Some(&includes), true, "/proj", None, &get_file_system_entries, &|path: &str| path.to_string())
+    }
+
+    #[test]
+    fn matches_double_asterisk_and_asterisk_components() {
+        use WildcardMatcherUsage::Files;
+        assert!(matches("src/**/*.ts", "/proj/src/a.ts", Files));
+        assert!(matches("src/**/*.ts", "/proj/src/deep/er/a.ts", Files));
+        assert!(!matches("src/**/*.ts", "/proj/src/a.js", Files));
+        assert!(!matches("src/**/*.ts", "/proj/other/a.ts", Files));
+        // neither wildcard matches a leading dot, and `**` does not descend into package folders
+        assert!(!matches("src/**/*.ts", "/proj/src/.a.ts", Files));
+        assert!(!matches("src/**/*.ts", "/proj/src/.hidden/a.ts", Files));
+        assert!(!matches("src/**/*.ts", "/proj/src/node_modules/a.ts", Files));
+        // `*` does not match the `.min` of a minified file
+        assert!(matches("*.js", "/proj/a.js", Files));
+        assert!(!matches("*.js", "/proj/a.min.js", Files));
+
+        let pattern = getSubPatternFromSpec("src/*.ts", "/proj", Files).unwrap();
+        assert!(matchesWildcardPattern(&pattern, "/PROJ/SRC/A.TS", Files, false));
+        assert!(!matchesWildcardPattern(&pattern, "/PROJ/SRC/A.TS", Files, true));
+    }
+
+    #[test]
+    fn matches_everything_below_implicit_glob_directories() {
+        use WildcardMatcherUsage::{Directories, Exclude, Files};
+        assert!(isImplicitGlob("src"));
+        assert!(!isImplicitGlob("index.ts"));
+        assert!(!isImplicitGlob("src*"));
+
+        assert!(matches("src", "/proj/src/a/b.ts", Files));
+        assert!(!matches("src", "/proj/srcx/a.ts", Files));
+        // directory patterns also match the ancestors that lead to the directory
+        assert!(matches("src", "/proj", Directories));
+        assert!(matches("src", "/proj/src/a", Directories));
+        assert!(!matches("src", "/proj/lib", Directories));
+
+        // a trailing `**` matches nothing to include, but excludes everything below it
+        assert!(getSubPatternFromSpec("src/**", "/proj", Files).is_none());
+        assert!(matches("src/**", "/proj/src/a/b.ts", Exclude));
+    }
+
+    #[test]
+    fn excludes_take_precedence_over_includes() {
+        let files = ["/proj/src/a.ts", "/proj/src/a.spec.ts", "/proj/src/gen/b.ts", "/proj/src/gen/keep.ts", "/proj/other/c.ts"];
+        assert_eq!(read_directory(&files, &["src"], &[]), vec!["/proj/src/a.spec.ts", "/proj/src/a.ts", "/proj/src/gen/b.ts", "/proj/src/gen/keep.ts"]);
+        // an explicitly included file is still excluded
+        assert_eq!(read_directory(&files, &["src", "src/gen/keep.ts"], &["src/gen", "**/*.spec.ts"]), vec!["/proj/src/a.ts"]);
+    }
+}
@@ -1,7 +1,8 @@
 use oxc_ast::{
-    ast::{CallExpression, ElementAccessExpression},
+    ast::{CallExpression, ElementAccessExpression, Expression, LogicalOperator, TSNonNullExpression},
     AstKind, GetChildren,
 };
+use oxc_span::GetSpan;
 
 use crate::{compiler::types::BinaryExpression, define_subset_enum};
 
@@ -9,17 +10,69 @@ use super::{
     factory::nodeTests::*, rb_extra::AstKindExt, rb_unions::StrName, types::*, utilities::*
 };
 
+// region: 366
+pub fn textSpanEnd(span: &TextSpan) -> u32 { span.start + span.length }
+// endregion: 368
+
 // region: 430
 pub fn createTextSpan(start: u32, length: u32) -> TextSpan { TextSpan { start, length } }
 
 pub fn createTextSpanFromBounds(start: u32, end: u32) -> TextSpan { createTextSpan(start, end - start) }
 
+pub fn textChangeRangeNewSpan(range: &TextChangeRange) -> TextSpan { createTextSpan(range.span.start, range.newLength) }
+
+pub fn textChangeRangeIsUnchanged(range: &TextChangeRange) -> bool { range.span.length == 0 && range.newLength == 0 }
+
+pub fn createTextChangeRange(span: TextSpan, newLength: u32) -> TextChangeRange { TextChangeRange { span, newLength } }
+
+pub const unchangedTextChangeRange: TextChangeRange = TextChangeRange { span: TextSpan { start: 0, length: 0 }, newLength: 0 };
 // endregion: 444
 
 // region: 643
 pub fn isBinaryExpression(node: &AstKind) -> bool { BinaryExpression::from_ast_kind(node).is_some() }
 // endregion: 647
 
+// region: 704
+/** @internal */
+pub fn walkUpBindingElementsAndPatterns<'a>(binding: &AstKind<'a>) -> AstKind<'a> {
+    let mut node = *binding;
+    while let Some(parent) = node.parent() {
+        if !isBindingElement(&parent) && !matches!(parent, AstKind::ObjectPattern(_) | AstKind::ArrayPattern(_) | AstKind::AssignmentPattern(_)) {
+            return parent;
+        }
+        node = parent;
+    }
+    node
+}
+
+fn getCombinedFlags<T: std::ops::BitOrAssign>(node: &AstKind, getFlags: impl Fn(&AstKind) -> T) -> T {
+    let mut node = Some(*node);
+    if isBindingElement(node.as_ref().unwrap()) {
+        node = Some(walkUpBindingElementsAndPatterns(node.as_ref().unwrap()));
+    }
+    let mut flags = getFlags(node.as_ref().unwrap());
+    if let Some(AstKind::VariableDeclarator(_)) = node {
+        node = node.unwrap().parent();
+    }
+    // VariableDeclarationList doubles as the VariableStatement
+    if let Some(list @ AstKind::VariableDeclarationList(_)) = node {
+        flags |= getFlags(&list);
+    }
+    flags
+}
+
+/**
+ * Gets the effective NodeFlags for the provided node, including on its parent VariableDeclarationList and VariableStatement.
+ * `let`, `const` and `using` flags live on the list, so this is how to ask whether a single declaration is block scoped.
+ */
+/**
+ * Gets the effective ModifierFlags for the provided node, including on its parent VariableDeclarationList and VariableStatement.
+ */
+pub fn getCombinedModifierFlags(node: &AstKind) -> ModifierFlags { getCombinedFlags(node, getEffectiveModifierFlags) }
+
+pub fn getCombinedNodeFlags(node: &AstKind) -> NodeFlags { getCombinedFlags(node, |node| node.flags()) }
+// endregion: 745
+
 // region: 764
 /**
  * Iterates through the parent chain of a node and performs the callback on each parent until the callback
@@ -219,21 +272,29 @@ pub fn getAssignedName<'a>(node: AstKind<'a>) -> Option<DeclarationName<'a>> {
 
 // region: 1187
 /** Gets the JSDoc type tag for the node if present and valid */
-pub fn getJSDocTypeTag<'a>(node: &'a AstKind) -> Option<()> {
-    // ! rb skipping jsdoc
-    // // We should have already issued an error if there were multiple type jsdocs, so just use the first one.
-    // let tag = getFirstJSDocTag(node, isJSDocTypeTag);
-    // if let Some(tag) = tag {
-    //     if let Some(type_expr) = &tag.type_expression {
-    //         if type_expr.type_.is_some() {
-    //             return Some(tag);
-    //         }
-    //     }
-    // }
-    None
+pub fn getJSDocTypeTag(node: &AstKind) -> Option<JSDocTag> {
+    // We should have already issued an error if there were multiple type jsdocs, so just use the first one.
+    let tag = getFirstJSDocTag(node, isJSDocTypeTag);
+    tag.filter(|tag| tag.typeExpression().is_some_and(|typeExpression| !typeExpression.r#type.is_empty()))
 }
 // endregion: 1197
 
+// region: 1265
+// ! rb the tags are not cached separately: the JSDoc they come from is cached on each node
+fn getJSDocTagsWorker(node: &AstKind) -> Vec<JSDocTag> {
+    if !canHaveJSDoc(node) {
+        return Vec::new();
+    }
+    getJSDocCommentsAndTags(node)
+}
+
+/** Get all JSDoc tags related to a node, including those on parent nodes. */
+pub fn getJSDocTags(node: &AstKind) -> Vec<JSDocTag> { getJSDocTagsWorker(node) }
+
+/** Get the first JSDoc tag of a specified kind, or undefined if not present. */
+fn getFirstJSDocTag(node: &AstKind, predicate: fn(&JSDocTag) -> bool) -> Option<JSDocTag> { getJSDocTagsWorker(node).into_iter().find(|tag| predicate(tag)) }
+// endregion: 1300
+
 // region: 1343
 pub fn isMemberName(node: &AstKind) -> bool {
     Identifier::from_ast_kind(node).is_some() || matches!(node, AstKind::PrivateIdentifier(_))
@@ -245,21 +306,100 @@ define_subset_enum!(isOptionalChainResult from AstKind {
     Sub(PropertyAccessExpression),
     ElementAccessExpression,
     CallExpression,
+    TSNonNullExpression,
 });
+impl<'a> isOptionalChainResult<'a> {
+    pub fn expression(&self) -> &'a Expression<'a> {
+        match self {
+            isOptionalChainResult::PropertyAccessExpression(n) => n.object(),
+            isOptionalChainResult::ElementAccessExpression(n) => &n.object,
+            isOptionalChainResult::CallExpression(n) => &n.callee,
+            isOptionalChainResult::TSNonNullExpression(n) => &n.expression,
+        }
+    }
+}
 pub fn isOptionalChain<'a>(node: AstKind<'a>) -> Option<isOptionalChainResult<'a>> {
-    // ! rb typescript also covers NonNullExpression, but I don't see how that's valid
-    match node {
+    // ! rb oxc only marks the links with a question dot as `optional`, where typescript flags every link of the chain.
+    // A link is part of a chain when it, or an access it continues without parentheses in between, has a question dot
+    let chain = match node {
         // PropertyAccessExpression
-        AstKind::StaticMemberExpression(n) if n.optional => Some(isOptionalChainResult::PropertyAccessExpression(PropertyAccessExpression::StaticMemberExpression(n))),
-        AstKind::PrivateFieldExpression(n) if n.optional => Some(isOptionalChainResult::PropertyAccessExpression(PropertyAccessExpression::PrivateFieldExpression(n))),
+        AstKind::StaticMemberExpression(n) => isOptionalChainResult::PropertyAccessExpression(PropertyAccessExpression::StaticMemberExpression(n)),
+        AstKind::PrivateFieldExpression(n) => isOptionalChainResult::PropertyAccessExpression(PropertyAccessExpression::PrivateFieldExpression(n)),
         // end PropertyAccessExpression
-        AstKind::ElementAccessExpression(n) if n.optional => Some(isOptionalChainResult::ElementAccessExpression(n)),
-        AstKind::CallExpression(n) if n.optional => Some(isOptionalChainResult::CallExpression(n)),
-        _ => None,
+        AstKind::ElementAccessExpression(n) => isOptionalChainResult::ElementAccessExpression(n),
+        AstKind::CallExpression(n) => isOptionalChainResult::CallExpression(n),
+        AstKind::TSNonNullExpression(n) => isOptionalChainResult::TSNonNullExpression(n),
+        _ => return None,
+    };
+    let mut link = node;
+    loop {
+        let (optional, expression) = match link {
+            AstKind::StaticMemberExpression(n) => (n.optional, &n.object),
+            AstKind::PrivateFieldExpression(n) => (n.optional, &n.object),
+            AstKind::ElementAccessExpression(n) => (n.optional, &n.object),
+            AstKind::CallExpression(n) => (n.optional, &n.callee),
+            AstKind::TSNonNullExpression(n) => (false, &n.expression),
+            _ => return None,
+        };
+        if optional {
+            return Some(chain);
+        }
+        link = expression.to_ast_kind();
     }
 }
 // endregion: 1373
 
+// region: 1381
+/** @internal */
+pub fn isOptionalChainRoot(node: &AstKind) -> bool {
+    // ! rb `optional` is set exactly when there is a question dot token
+    match node {
+        AstKind::StaticMemberExpression(n) => n.optional,
+        AstKind::PrivateFieldExpression(n) => n.optional,
+        AstKind::ElementAccessExpression(n) => n.optional,
+        AstKind::CallExpression(n) => n.optional,
+        _ => false,
+    }
+}
+
+/**
+ * Determines whether a node is the expression preceding an optional chain (i.e. `a` in `a?.b`).
+ *
+ * @internal
+ */
+pub fn isExpressionOfOptionalChainRoot(node: &AstKind) -> bool {
+    let Some(parent) = node.parent() else { return false };
+    isOptionalChainRoot(&parent) && isOptionalChain(parent).unwrap().expression().span() == node.span()
+}
+
+/**
+ * Determines whether a node is the outermost `OptionalChain` in an ECMAScript `OptionalExpression`:
+ *
+ * 1. For `a?.b.c`, the outermost chain is `a?.b.c` (`c` is the end of the chain starting at `a?.`)
+ * 2. For `a?.b!`, the outermost chain is `a?.b` (`b` is the end of the chain starting at `a?.`)
+ * 3. For `(a?.b.c).d`, the outermost chain is `a?.b.c` (`c` is the end of the chain starting at `a?.` since parens end the chain)
+ * 4. For `a?.b.c?.d`, both `a?.b.c` and `a?.b.c?.d` are outermost (`c` is the end of the chain starting at `a?.`, and `d` is
+ *   the end of the chain starting at `c?.`)
+ * 5. For `a?.(b?.c).d`, both `b?.c` and `a?.(b?.c)d` are outermost (`c` is the end of the chain starting at `b`, and `d` is
+ *   the end of the chain starting at `a?.`)
+ *
+ * @internal
+ */
+pub fn isOutermostOptionalChain(node: &AstKind) -> bool {
+    let Some(parent) = node.parent() else { return true };
+    match isOptionalChain(parent) {
+        None => true, // cases 1, 2, and 3
+        Some(chain) => isOptionalChainRoot(&parent) // case 4
+            || chain.expression().span() != node.span(), // case 5
+    }
+}
+// endregion: 1401
+
+// region: 1402
+/** @internal */
+pub fn isNullishCoalesce(node: &AstKind) -> bool { matches!(node, AstKind::LogicalExpression(expr) if expr.operator == LogicalOperator::Coalesce) }
+// endregion: 1404
+
 // region: 1415
 pub fn skipPartiallyEmittedExpressions<'a>(node: &'a AstKind) -> &'a AstKind<'a> {
     // ! rb skipping PartiallyEmittedExpressions
@@ -288,6 +428,21 @@ pub fn isBooleanLiteral(node: &AstKind) -> bool { matches!(node, AstKind::Boolea
 pub fn isClassLike(node: &AstKind) -> bool { matches!(node, AstKind::Class(_) ) }
 // endregion: 1727
 
+// region: 1738
+pub fn isClassElement(node: &AstKind) -> bool {
+    matches!(
+        node,
+        // Constructor, MethodDeclaration, GetAccessor, SetAccessor
+        AstKind::MethodDefinition(_) |
+        AstKind::PropertyDefinition(_) |
+        AstKind::AccessorProperty(_) |
+        AstKind::TSIndexSignature(_) |
+        AstKind::StaticBlock(_)
+        // case SyntaxKind.SemicolonClassElement:
+    )
+}
+// endregion: 1750
+
 // region: 1970
 pub fn isLeftHandSideExpression(node: &AstKind) -> bool { isLeftHandSideExpressionKind(skipPartiallyEmittedExpressions(node)) }
 
@@ -422,6 +577,38 @@ pub fn canHaveSymbol(node: &AstKind) -> bool {
 pub fn canHaveLocals(node: &AstKind) -> bool { HasLocals::from_ast_kind(node).is_some() }
 // endregion: 2296
 
+// region: 2300
+fn isStatementKindButNotDeclarationKind(node: &AstKind) -> bool {
+    matches!(
+        node,
+        AstKind::BreakStatement(_) |
+        AstKind::ContinueStatement(_) |
+        AstKind::DebuggerStatement(_) |
+        AstKind::DoWhileStatement(_) | // DoStatement
+        AstKind::ExpressionStatement(_) |
+        AstKind::EmptyStatement(_) |
+        AstKind::ForInStatement(_) |
+        AstKind::ForOfStatement(_) |
+        AstKind::ForStatement(_) |
+        AstKind::IfStatement(_) |
+        AstKind::LabeledStatement(_) |
+        AstKind::ReturnStatement(_) |
+        AstKind::SwitchStatement(_) |
+        AstKind::ThrowStatement(_) |
+        AstKind::TryStatement(_) |
+        AstKind::VariableDeclarationList(_) | // VariableStatement
+        AstKind::WhileStatement(_) |
+        AstKind::WithStatement(_)
+        // case SyntaxKind.NotEmittedStatement:
+    )
+}
+// endregion: 2323
+
+// region: 2332
+/** @internal */
+pub fn isStatementButNotDeclaration(node: &AstKind) -> bool { isStatementKindButNotDeclarationKind(node) }
+// endregion: 2335
+
 // region: 2593
 pub fn isStringLiteralLike(node: &AstKind) -> bool {
     match node {